@@ -0,0 +1,372 @@
+//! Derive and procedural macros for [Decorum](https://crates.io/crates/decorum).
+//!
+//! These macros are re-exported by Decorum when its `derive` feature is enabled and should not be
+//! used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse_macro_input, parse_quote, BinOp, Data, DeriveInput, Expr, Field, Fields, Index, LitInt,
+    LitStr, UnOp, WherePredicate,
+};
+
+/// Derives `CanonicalDigest` by feeding each field into the digest in declaration order.
+///
+/// The variants of enumerations are identified by their index in declaration order, which is fed
+/// into the digest as a little-endian `u32` before the fields of the variant.
+#[proc_macro_derive(CanonicalDigest)]
+pub fn derive_canonical_digest(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    for parameter in input.generics.type_params_mut() {
+        parameter
+            .bounds
+            .push(parse_quote!(::decorum::hash::CanonicalDigest));
+    }
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, fields) = destructure(&data.fields);
+            quote! {
+                let Self #pattern = self;
+                #(#fields)*
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let ident = &variant.ident;
+                let index = index as u32;
+                let (pattern, fields) = destructure(&variant.fields);
+                quote! {
+                    Self::#ident #pattern => {
+                        ::decorum::hash::CanonicalDigest::digest_canonical(&#index, state);
+                        #(#fields)*
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new(Span::call_site(), "unions cannot derive `CanonicalDigest`")
+                .to_compile_error()
+                .into();
+        }
+    };
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::decorum::hash::CanonicalDigest for #ident #type_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn digest_canonical<D>(&self, state: &mut D)
+            where
+                D: ::decorum::hash::Update,
+            {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Derives `PartialEq`, `Eq`, and `Hash` such that values can be used as keys.
+///
+/// By default, each field is compared and hashed via its own `PartialEq` and `Hash`
+/// implementations. The comparison of a field can be configured with the `canonical` attribute:
+///
+/// - `#[canonical(eq = "canonical")]` compares and hashes the field via `CanonicalEq` and
+///   `CanonicalHash`, which is useful for primitive floating-point fields.
+/// - `#[canonical(eq = "ulps(N)")]` compares the field via `Approx` with a tolerance of `N` ULPs.
+///   Such fields are not hashed, so hashing remains consistent with equality.
+///
+/// Like `Approx`, tolerant comparisons are not transitive, so a type with such fields does not
+/// strictly satisfy the requirements of `Eq`. This is typically acceptable for cache keys over
+/// measured data, where nearly equal values are expected to identify the same entry.
+///
+/// The variants of enumerations are compared and hashed by their index in declaration order.
+#[proc_macro_derive(CanonicalKey, attributes(canonical))]
+pub fn derive_canonical_key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match canonical_key(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn canonical_key(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut predicates = Vec::new();
+    let (eq, hash) = match &input.data {
+        Data::Struct(data) => {
+            let (left, eqs, hashes) = compare(&data.fields, &mut predicates)?;
+            let right = pattern(&data.fields, "right");
+            (
+                quote! {
+                    let Self #left = self;
+                    let Self #right = other;
+                    true #(&& #eqs)*
+                },
+                quote! {
+                    let Self #left = self;
+                    #(#hashes)*
+                },
+            )
+        }
+        Data::Enum(data) => {
+            let mut eq_arms = Vec::new();
+            let mut hash_arms = Vec::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                let ident = &variant.ident;
+                let index = index as u32;
+                let (left, eqs, hashes) = compare(&variant.fields, &mut predicates)?;
+                let right = pattern(&variant.fields, "right");
+                eq_arms.push(quote! {
+                    (Self::#ident #left, Self::#ident #right) => true #(&& #eqs)*,
+                });
+                hash_arms.push(quote! {
+                    Self::#ident #left => {
+                        ::core::hash::Hash::hash(&#index, state);
+                        #(#hashes)*
+                    }
+                });
+            }
+            (
+                quote! {
+                    #[allow(unreachable_patterns)]
+                    match (self, other) {
+                        #(#eq_arms)*
+                        _ => false,
+                    }
+                },
+                quote! {
+                    match self {
+                        #(#hash_arms)*
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "unions cannot derive `CanonicalKey`",
+            ));
+        }
+    };
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.extend(predicates);
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::core::cmp::PartialEq for #ident #type_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn eq(&self, other: &Self) -> bool {
+                #eq
+            }
+        }
+
+        impl #impl_generics ::core::cmp::Eq for #ident #type_generics
+        #where_clause
+        {}
+
+        impl #impl_generics ::core::hash::Hash for #ident #type_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: ::core::hash::Hasher,
+            {
+                #hash
+            }
+        }
+    })
+}
+
+// This macro is documented by its re-export in `decorum::expression`. Documentation here would
+// follow the examples there.
+#[proc_macro]
+pub fn expression(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as Expr);
+    propagate(&mut input);
+    input.into_token_stream().into()
+}
+
+// Rewrites an expression such that it and each of its operations are tried.
+fn propagate(expression: &mut Expr) {
+    let is_tried = match expression {
+        Expr::Binary(binary) if is_arithmetic(&binary.op) => {
+            propagate(&mut binary.left);
+            propagate(&mut binary.right);
+            true
+        }
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+            propagate(&mut unary.expr);
+            true
+        }
+        Expr::MethodCall(call) => {
+            propagate(&mut call.receiver);
+            call.args.iter_mut().for_each(propagate);
+            true
+        }
+        Expr::Call(call) => {
+            call.args.iter_mut().for_each(propagate);
+            true
+        }
+        Expr::Paren(paren) => {
+            propagate(&mut paren.expr);
+            false
+        }
+        _ => false,
+    };
+    if is_tried {
+        let inner = &*expression;
+        *expression = parse_quote! {
+            match ::decorum::expression::TryExpression::try_expression(#inner) {
+                ::decorum::expression::Expression::Defined(defined) => defined,
+                ::decorum::expression::Expression::Undefined(undefined) => {
+                    return ::decorum::expression::Expression::Undefined(undefined);
+                }
+            }
+        };
+    }
+}
+
+fn is_arithmetic(operator: &BinOp) -> bool {
+    matches!(
+        operator,
+        BinOp::Add(_) | BinOp::Sub(_) | BinOp::Mul(_) | BinOp::Div(_) | BinOp::Rem(_)
+    )
+}
+
+// Comparison of a field in a derived `CanonicalKey` implementation.
+enum Comparison {
+    Default,
+    Canonical,
+    Ulps(LitInt),
+}
+
+impl Comparison {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut comparison = Comparison::Default;
+        for attribute in &field.attrs {
+            if !attribute.path().is_ident("canonical") {
+                continue;
+            }
+            attribute.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("eq") {
+                    return Err(meta.error("expected `eq`"));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                let text = value.value();
+                comparison = if text == "canonical" {
+                    Comparison::Canonical
+                }
+                else if let Some(ulps) = text
+                    .strip_prefix("ulps(")
+                    .and_then(|text| text.strip_suffix(')'))
+                {
+                    Comparison::Ulps(LitInt::new(ulps.trim(), value.span()))
+                }
+                else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "expected `\"canonical\"` or `\"ulps(N)\"`",
+                    ));
+                };
+                Ok(())
+            })?;
+        }
+        if let Comparison::Ulps(ulps) = &comparison {
+            ulps.base10_parse::<u32>()?;
+        }
+        Ok(comparison)
+    }
+}
+
+// Gets a pattern that binds each field of `self` and the expressions and statements that compare
+// and hash the bindings. Bounds on the types of fields are pushed into `predicates`.
+fn compare(
+    fields: &Fields,
+    predicates: &mut Vec<WherePredicate>,
+) -> syn::Result<(TokenStream2, Vec<TokenStream2>, Vec<TokenStream2>)> {
+    let mut eqs = Vec::new();
+    let mut hashes = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let left = format_ident!("left{}", index);
+        let right = format_ident!("right{}", index);
+        let ty = &field.ty;
+        match Comparison::parse(field)? {
+            Comparison::Default => {
+                predicates.push(parse_quote!(#ty: ::core::cmp::Eq + ::core::hash::Hash));
+                eqs.push(quote!(::core::cmp::PartialEq::eq(#left, #right)));
+                hashes.push(quote!(::core::hash::Hash::hash(#left, state);));
+            }
+            Comparison::Canonical => {
+                predicates.push(parse_quote!(
+                    #ty: ::decorum::cmp::CanonicalEq + ::decorum::hash::CanonicalHash
+                ));
+                eqs.push(quote!(::decorum::cmp::CanonicalEq::eq_canonical(#left, #right)));
+                hashes.push(quote!(::decorum::hash::CanonicalHash::hash_canonical(#left, state);));
+            }
+            Comparison::Ulps(ulps) => {
+                predicates.push(parse_quote!(#ty: ::core::marker::Copy));
+                predicates.push(parse_quote!(
+                    ::decorum::cmp::Approx<#ty, #ulps>: ::core::cmp::PartialEq
+                ));
+                eqs.push(quote! {
+                    ::decorum::cmp::Approx::<#ty, #ulps>(*#left)
+                        == ::decorum::cmp::Approx::<#ty, #ulps>(*#right)
+                });
+            }
+        }
+    }
+    Ok((pattern(fields, "left"), eqs, hashes))
+}
+
+// Gets a pattern that binds each field to an identifier with the given prefix.
+fn pattern(fields: &Fields, prefix: &str) -> TokenStream2 {
+    let bindings = (0..fields.len()).map(|index| format_ident!("{}{}", prefix, index));
+    match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..fields.len()).map(Index::from);
+            quote!({ #(#indices: #bindings),* })
+        }
+        Fields::Unit => quote!(),
+    }
+}
+
+// Gets a pattern that binds each field and the statements that feed the bindings into the digest.
+fn destructure(fields: &Fields) -> (TokenStream2, Vec<TokenStream2>) {
+    let bindings: Vec<_> = (0..fields.len())
+        .map(|index| format_ident!("field{}", index))
+        .collect();
+    let pattern = match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => {
+            let indices = (0..fields.len()).map(Index::from);
+            quote!({ #(#indices: #bindings),* })
+        }
+        Fields::Unit => quote!(),
+    };
+    let statements = bindings
+        .iter()
+        .map(|binding| {
+            quote! {
+                ::decorum::hash::CanonicalDigest::digest_canonical(#binding, state);
+            }
+        })
+        .collect();
+    (pattern, statements)
+}
@@ -0,0 +1,497 @@
+//! Dynamically typed real numbers.
+//!
+//! [`AnyReal`] is a real number that is represented by either an `f32` or `f64` [`Real`] proxy
+//! and is determined at runtime. It implements the real function traits by dispatching to its
+//! representation, and so can be used by code that cannot be generic over [`Primitive`] types,
+//! such as plugin and scripting layers.
+//!
+//! Binary operations on real numbers with different representations promote the `f32`
+//! representation to `f64`, so the output is `f32` only if both operands are `f32`. Comparisons
+//! also promote representations and so compare the values of real numbers regardless of their
+//! representation.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::any::AnyReal;
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::{R32, R64};
+//!
+//! let x = AnyReal::from(<R32>::assert(2.0));
+//! let y = AnyReal::from(<R64>::assert(0.5));
+//! assert!(x.is_f32());
+//! assert!((x * y).is_f64());
+//! assert_eq!(x.sqrt().into_f64(), 2.0f32.sqrt() as f64);
+//! ```
+//!
+//! [`Primitive`]: crate::Primitive
+//! [`Real`]: crate::Real
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use crate::constraint::{Constraint, IsReal, NotRealError};
+use crate::divergence::{self, Divergence, OrPanic};
+use crate::proxy::Constrained;
+use crate::real::{BinaryRealFunction, Function, Sign, UnaryRealFunction};
+use crate::{Primitive, Real};
+
+type OutputFor<D> = divergence::OutputFor<D, AnyReal<D>, ErrorFor<D>>;
+#[cfg(not(feature = "trace"))]
+type ErrorFor<D> = NotRealError;
+#[cfg(feature = "trace")]
+type ErrorFor<D> = <D as Divergence>::Error<NotRealError>;
+
+/// Real number represented by either an `f32` or `f64` [`Real`] proxy.
+///
+/// See the [module documentation][`any`].
+///
+/// [`any`]: crate::any
+/// [`Real`]: crate::Real
+pub enum AnyReal<D = OrPanic> {
+    F32(Real<f32, D>),
+    F64(Real<f64, D>),
+}
+
+impl<D> AnyReal<D>
+where
+    D: Divergence,
+{
+    pub fn is_f32(&self) -> bool {
+        matches!(self, AnyReal::F32(_))
+    }
+
+    pub fn is_f64(&self) -> bool {
+        matches!(self, AnyReal::F64(_))
+    }
+
+    /// Converts the real number into an `f64`, which is lossless for both representations.
+    pub fn into_f64(self) -> f64 {
+        match self {
+            AnyReal::F32(x) => x.into_inner().into(),
+            AnyReal::F64(x) => x.into_inner(),
+        }
+    }
+
+    /// Converts the real number into its `f64` representation.
+    pub fn into_f64_real(self) -> Self {
+        AnyReal::F64(Constrained::unchecked(self.into_f64()))
+    }
+
+    // Real numbers are checked here rather than by proxies, because the outputs of proxies cannot
+    // be mapped into `AnyReal` for arbitrary divergences.
+    fn checked<T, F>(inner: T, f: F) -> OutputFor<D>
+    where
+        T: Primitive,
+        F: FnOnce(Real<T, D>) -> Self,
+    {
+        D::diverge(IsReal::<D>::check(inner).map(|_| f(Constrained::unchecked(inner))))
+    }
+}
+
+// Maps each representation with the same expression. Outputs are checked if given `checked`.
+macro_rules! map {
+    ($x:expr, |$inner:ident| $f:expr $(,)?) => {
+        match $x {
+            AnyReal::F32($inner) => AnyReal::F32($f),
+            AnyReal::F64($inner) => AnyReal::F64($f),
+        }
+    };
+    (checked $x:expr, |$inner:ident| $f:expr $(,)?) => {
+        match $x {
+            AnyReal::F32(x) => {
+                let $inner = x.into_inner();
+                AnyReal::checked($f, AnyReal::F32)
+            }
+            AnyReal::F64(x) => {
+                let $inner = x.into_inner();
+                AnyReal::checked($f, AnyReal::F64)
+            }
+        }
+    };
+}
+
+// Maps the representations of each operand with the same expression, promoting `f32` to `f64` if
+// the representations differ. Outputs are always checked.
+macro_rules! zip_map {
+    ($x:expr, $y:expr, |$a:ident, $b:ident| $f:expr $(,)?) => {
+        match ($x, $y) {
+            (AnyReal::F32(x), AnyReal::F32(y)) => {
+                let ($a, $b) = (x.into_inner(), y.into_inner());
+                AnyReal::checked($f, AnyReal::F32)
+            }
+            (x, y) => {
+                let ($a, $b) = (x.into_f64(), y.into_f64());
+                AnyReal::checked($f, AnyReal::F64)
+            }
+        }
+    };
+}
+
+impl<D> BinaryRealFunction for AnyReal<D>
+where
+    D: Divergence,
+{
+    #[cfg(feature = "std")]
+    fn div_euclid(self, n: Self) -> Self::Codomain {
+        zip_map!(self, n, |x, n| BinaryRealFunction::div_euclid(x, n))
+    }
+
+    #[cfg(feature = "std")]
+    fn rem_euclid(self, n: Self) -> Self::Codomain {
+        zip_map!(self, n, |x, n| BinaryRealFunction::rem_euclid(x, n))
+    }
+
+    #[cfg(feature = "std")]
+    fn pow(self, n: Self) -> Self::Codomain {
+        zip_map!(self, n, |x, n| BinaryRealFunction::pow(x, n))
+    }
+
+    #[cfg(feature = "std")]
+    fn log(self, base: Self) -> Self::Codomain {
+        zip_map!(self, base, |x, base| BinaryRealFunction::log(x, base))
+    }
+
+    #[cfg(feature = "std")]
+    fn hypot(self, other: Self) -> Self::Codomain {
+        zip_map!(self, other, |x, y| BinaryRealFunction::hypot(x, y))
+    }
+
+    #[cfg(feature = "std")]
+    fn atan2(self, other: Self) -> Self::Codomain {
+        zip_map!(self, other, |x, y| BinaryRealFunction::atan2(x, y))
+    }
+}
+
+impl<D> Clone for AnyReal<D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<D> Copy for AnyReal<D> {}
+
+impl<D> Debug for AnyReal<D>
+where
+    Real<f32, D>: Debug,
+    Real<f64, D>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyReal::F32(x) => formatter.debug_tuple("F32").field(x).finish(),
+            AnyReal::F64(x) => formatter.debug_tuple("F64").field(x).finish(),
+        }
+    }
+}
+
+impl<D> Display for AnyReal<D>
+where
+    D: Divergence,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyReal::F32(x) => Display::fmt(x.as_ref(), formatter),
+            AnyReal::F64(x) => Display::fmt(x.as_ref(), formatter),
+        }
+    }
+}
+
+impl<D> From<Real<f32, D>> for AnyReal<D> {
+    fn from(x: Real<f32, D>) -> Self {
+        AnyReal::F32(x)
+    }
+}
+
+impl<D> From<Real<f64, D>> for AnyReal<D> {
+    fn from(x: Real<f64, D>) -> Self {
+        AnyReal::F64(x)
+    }
+}
+
+impl<D> Function for AnyReal<D>
+where
+    D: Divergence,
+{
+    type Codomain = OutputFor<D>;
+}
+
+impl<D> Neg for AnyReal<D>
+where
+    D: Divergence,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        map!(self, |x| -x)
+    }
+}
+
+impl<D> PartialEq for AnyReal<D>
+where
+    D: Divergence,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.into_f64() == other.into_f64()
+    }
+}
+
+impl<D> PartialOrd for AnyReal<D>
+where
+    D: Divergence,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.into_f64().partial_cmp(&other.into_f64())
+    }
+}
+
+impl<D> UnaryRealFunction for AnyReal<D>
+where
+    D: Divergence,
+{
+    const ZERO: Self = AnyReal::F64(UnaryRealFunction::ZERO);
+    const ONE: Self = AnyReal::F64(UnaryRealFunction::ONE);
+    const E: Self = AnyReal::F64(UnaryRealFunction::E);
+    const PI: Self = AnyReal::F64(UnaryRealFunction::PI);
+    const FRAC_1_PI: Self = AnyReal::F64(UnaryRealFunction::FRAC_1_PI);
+    const FRAC_2_PI: Self = AnyReal::F64(UnaryRealFunction::FRAC_2_PI);
+    const FRAC_2_SQRT_PI: Self = AnyReal::F64(UnaryRealFunction::FRAC_2_SQRT_PI);
+    const FRAC_PI_2: Self = AnyReal::F64(UnaryRealFunction::FRAC_PI_2);
+    const FRAC_PI_3: Self = AnyReal::F64(UnaryRealFunction::FRAC_PI_3);
+    const FRAC_PI_4: Self = AnyReal::F64(UnaryRealFunction::FRAC_PI_4);
+    const FRAC_PI_6: Self = AnyReal::F64(UnaryRealFunction::FRAC_PI_6);
+    const FRAC_PI_8: Self = AnyReal::F64(UnaryRealFunction::FRAC_PI_8);
+    const SQRT_2: Self = AnyReal::F64(UnaryRealFunction::SQRT_2);
+    const FRAC_1_SQRT_2: Self = AnyReal::F64(UnaryRealFunction::FRAC_1_SQRT_2);
+    const LN_2: Self = AnyReal::F64(UnaryRealFunction::LN_2);
+    const LN_10: Self = AnyReal::F64(UnaryRealFunction::LN_10);
+    const LOG2_E: Self = AnyReal::F64(UnaryRealFunction::LOG2_E);
+    const LOG10_E: Self = AnyReal::F64(UnaryRealFunction::LOG10_E);
+
+    fn is_zero(self) -> bool {
+        self.into_f64().is_zero()
+    }
+
+    fn is_one(self) -> bool {
+        self.into_f64().is_one()
+    }
+
+    fn sign(self) -> Sign {
+        self.into_f64().sign()
+    }
+
+    #[cfg(feature = "std")]
+    fn abs(self) -> Self {
+        map!(self, |x| x.abs())
+    }
+
+    #[cfg(feature = "std")]
+    fn floor(self) -> Self {
+        map!(self, |x| x.floor())
+    }
+
+    #[cfg(feature = "std")]
+    fn ceil(self) -> Self {
+        map!(self, |x| x.ceil())
+    }
+
+    #[cfg(feature = "std")]
+    fn round(self) -> Self {
+        map!(self, |x| x.round())
+    }
+
+    #[cfg(feature = "std")]
+    fn trunc(self) -> Self {
+        map!(self, |x| x.trunc())
+    }
+
+    #[cfg(feature = "std")]
+    fn fract(self) -> Self {
+        map!(self, |x| x.fract())
+    }
+
+    fn recip(self) -> Self::Codomain {
+        map!(checked self, |x| x.recip())
+    }
+
+    #[cfg(feature = "std")]
+    fn powi(self, n: i32) -> Self::Codomain {
+        map!(checked self, |x| x.powi(n))
+    }
+
+    #[cfg(feature = "std")]
+    fn sqrt(self) -> Self::Codomain {
+        map!(checked self, |x| x.sqrt())
+    }
+
+    #[cfg(feature = "std")]
+    fn cbrt(self) -> Self {
+        map!(self, |x| x.cbrt())
+    }
+
+    #[cfg(feature = "std")]
+    fn exp(self) -> Self::Codomain {
+        map!(checked self, |x| x.exp())
+    }
+
+    #[cfg(feature = "std")]
+    fn exp2(self) -> Self::Codomain {
+        map!(checked self, |x| x.exp2())
+    }
+
+    #[cfg(feature = "std")]
+    fn exp_m1(self) -> Self::Codomain {
+        map!(checked self, |x| x.exp_m1())
+    }
+
+    #[cfg(feature = "std")]
+    fn ln(self) -> Self::Codomain {
+        map!(checked self, |x| x.ln())
+    }
+
+    #[cfg(feature = "std")]
+    fn log2(self) -> Self::Codomain {
+        map!(checked self, |x| x.log2())
+    }
+
+    #[cfg(feature = "std")]
+    fn log10(self) -> Self::Codomain {
+        map!(checked self, |x| x.log10())
+    }
+
+    #[cfg(feature = "std")]
+    fn ln_1p(self) -> Self::Codomain {
+        map!(checked self, |x| x.ln_1p())
+    }
+
+    #[cfg(feature = "std")]
+    fn to_degrees(self) -> Self::Codomain {
+        map!(checked self, |x| x.to_degrees())
+    }
+
+    #[cfg(feature = "std")]
+    fn to_radians(self) -> Self {
+        map!(self, |x| x.to_radians())
+    }
+
+    #[cfg(feature = "std")]
+    fn sin(self) -> Self {
+        map!(self, |x| x.sin())
+    }
+
+    #[cfg(feature = "std")]
+    fn cos(self) -> Self {
+        map!(self, |x| x.cos())
+    }
+
+    #[cfg(feature = "std")]
+    fn tan(self) -> Self::Codomain {
+        map!(checked self, |x| x.tan())
+    }
+
+    #[cfg(feature = "std")]
+    fn asin(self) -> Self::Codomain {
+        map!(checked self, |x| x.asin())
+    }
+
+    #[cfg(feature = "std")]
+    fn acos(self) -> Self::Codomain {
+        map!(checked self, |x| x.acos())
+    }
+
+    #[cfg(feature = "std")]
+    fn atan(self) -> Self {
+        map!(self, |x| x.atan())
+    }
+
+    #[cfg(feature = "std")]
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    #[cfg(feature = "std")]
+    fn sinh(self) -> Self {
+        map!(self, |x| x.sinh())
+    }
+
+    #[cfg(feature = "std")]
+    fn cosh(self) -> Self {
+        map!(self, |x| x.cosh())
+    }
+
+    #[cfg(feature = "std")]
+    fn tanh(self) -> Self {
+        map!(self, |x| x.tanh())
+    }
+
+    #[cfg(feature = "std")]
+    fn asinh(self) -> Self::Codomain {
+        map!(checked self, |x| x.asinh())
+    }
+
+    #[cfg(feature = "std")]
+    fn acosh(self) -> Self::Codomain {
+        map!(checked self, |x| x.acosh())
+    }
+
+    #[cfg(feature = "std")]
+    fn atanh(self) -> Self::Codomain {
+        map!(checked self, |x| x.atanh())
+    }
+}
+
+macro_rules! impl_binary_operation {
+    ($($trait:ident :: $method:ident),* $(,)?) => {$(
+        impl<D> $trait for AnyReal<D>
+        where
+            D: Divergence,
+        {
+            type Output = OutputFor<D>;
+
+            fn $method(self, other: Self) -> Self::Output {
+                zip_map!(self, other, |x, y| $trait::$method(x, y))
+            }
+        }
+    )*};
+}
+impl_binary_operation!(Add::add, Div::div, Mul::mul, Rem::rem, Sub::sub);
+
+#[cfg(test)]
+mod tests {
+    use crate::any::AnyReal;
+    use crate::divergence::OrError;
+    use crate::real::{BinaryRealFunction, UnaryRealFunction};
+    use crate::{R32, R64};
+
+    #[test]
+    fn promote() {
+        let x = AnyReal::from(<R32>::assert(1.5));
+        let y = AnyReal::from(<R64>::assert(0.25));
+        assert!((x + x).is_f32());
+        assert!((x + y).is_f64());
+        assert_eq!((x - y).into_f64(), 1.25);
+        assert!(x > y);
+        assert_eq!(AnyReal::from(<R32>::assert(0.25)), y);
+        assert!(x.hypot(y).is_f64());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn dispatch() {
+        let x = AnyReal::from(<R32>::assert(-4.0));
+        assert_eq!(x.abs().into_f64(), 4.0);
+        assert!(x.abs().sqrt().is_f32());
+        assert_eq!(<AnyReal>::PI.into_f64(), core::f64::consts::PI);
+
+        let x = AnyReal::<OrError>::from(R64::<OrError>::assert(-1.0));
+        assert!(x.sqrt().is_undefined());
+        assert!((x / AnyReal::ZERO).is_undefined());
+        assert_eq!(x.abs().sqrt().unwrap().into_f64(), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_zero_panic() {
+        let _ = AnyReal::from(<R32>::assert(1.0)) / AnyReal::from(<R32>::assert(0.0));
+    }
+}
@@ -0,0 +1,136 @@
+//! Detection and dispatch of hardware floating-point features.
+//!
+//! Fused multiply-add (FMA) computes $ab+c$ with a single rounding. Most modern targets provide
+//! FMA instructions, but on targets that do not, fused operations are emulated in software and are
+//! much slower than separate multiplication and addition. This module detects FMA at runtime and
+//! provides operations that exploit it:
+//!
+//! - Error-free products in the compensated algorithms of the [`geometry`] and [`roots`] modules
+//!   use FMA when it is available and otherwise split their operands. These products are exact
+//!   either way (barring underflow), so this only affects performance.
+//! - [`hypot`] computes a scaled square root with a fused multiply-add. This is an alternative to
+//!   the `hypot` function of the target (`libm`) that is typically faster when FMA is available but
+//!   is not correctly rounded.
+//!
+//! The `mul_add` functions of primitive and [`Constrained`] types are always fused and do not
+//! depend on the hardware. Only their performance does.
+//!
+//! [`set_consistent`] forces the software paths regardless of the hardware, so that results are
+//! reproducible across targets even when products underflow.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::arch;
+//!
+//! arch::set_consistent(true);
+//! assert!(!arch::uses_fma());
+//! # arch::set_consistent(false);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`geometry`]: crate::geometry
+//! [`hypot`]: crate::arch::hypot
+//! [`roots`]: crate::roots
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::Primitive;
+
+static CONSISTENT: AtomicBool = AtomicBool::new(false);
+
+/// Returns `true` if the hardware supports fused multiply-add instructions.
+///
+/// All `aarch64` targets support FMA. On `x86` and `x86_64` targets, support is detected at
+/// runtime. On other targets, this function conservatively returns `false`.
+pub fn has_fma() -> bool {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        std::is_x86_feature_detected!("fma")
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        true
+    }
+    #[cfg(not(any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")))]
+    {
+        false
+    }
+}
+
+/// Sets whether or not operations use the software paths regardless of the hardware.
+///
+/// This setting is global and is not enabled by default.
+pub fn set_consistent(consistent: bool) {
+    CONSISTENT.store(consistent, Ordering::Relaxed);
+}
+
+/// Returns `true` if operations use the software paths regardless of the hardware.
+pub fn is_consistent() -> bool {
+    CONSISTENT.load(Ordering::Relaxed)
+}
+
+/// Returns `true` if operations dispatch to their FMA paths.
+///
+/// This is `true` if the hardware supports FMA and [consistent results][`set_consistent`] are
+/// not enabled.
+pub fn uses_fma() -> bool {
+    !is_consistent() && has_fma()
+}
+
+/// Computes the length of the hypotenuse of a right triangle with legs `x` and `y`.
+///
+/// This function scales its operands to avoid intermediate overflow and underflow and computes
+/// $\max(|x|,|y|)\sqrt{1+r^2}$ with a fused multiply-add, where $r$ is the ratio of the lesser to
+/// the greater magnitude. It is typically faster than the `hypot` functions of primitive types when
+/// the hardware [supports FMA][`has_fma`], but its error is at most a few ULPs rather than being
+/// nearly correctly rounded. The output is the same on all targets.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::arch;
+///
+/// assert_eq!(arch::hypot(3.0f64, -4.0), 5.0);
+/// assert_eq!(arch::hypot(1.0e300f64, 0.0), 1.0e300);
+/// ```
+pub fn hypot<T>(x: T, y: T) -> T
+where
+    T: Primitive,
+{
+    let (x, y) = (x.abs(), y.abs());
+    if x.is_infinite() || y.is_infinite() {
+        T::INFINITY
+    }
+    else if x.is_nan() || y.is_nan() {
+        T::NAN.into_inner()
+    }
+    else {
+        let (max, min) = if x >= y { (x, y) } else { (y, x) };
+        if max == T::ZERO {
+            T::ZERO
+        }
+        else {
+            let ratio = min / max;
+            max * ratio.fused_mul_add(ratio, T::ONE).sqrt()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::arch;
+
+    #[test]
+    fn hypot() {
+        assert_eq!(arch::hypot(3.0f64, -4.0), 5.0);
+        assert_eq!(arch::hypot(0.0f64, -0.0), 0.0);
+        assert_eq!(arch::hypot(f64::NAN, f64::INFINITY), f64::INFINITY);
+        assert!(arch::hypot(f64::NAN, 1.0).is_nan());
+        assert_eq!(arch::hypot(f64::MAX, 0.0), f64::MAX);
+        for (x, y) in [(1.0e300f64, 1.0e300), (1.0e-300, 3.0e-300), (0.1, 0.7)] {
+            let error = (arch::hypot(x, y) / f64::hypot(x, y)) - 1.0;
+            assert!(error.abs() <= 4.0 * f64::EPSILON);
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! Integration of [`Constrained`] types with [`ndarray`] arrays.
+//!
+//! [`Constrained`] types can be used as the elements of arrays. This module provides conversions
+//! between arrays of primitive floating-point types and arrays of [`Constrained`] types as well as
+//! reductions over arrays of [`Constrained`] types.
+//!
+//! Because [`Constrained`] types have the same representation as their primitive types, views of
+//! primitive arrays can be reinterpreted as views of [`Constrained`] arrays without copying.
+//! [`Total`] views are always valid, but views of other [`Constrained`] types are checked against
+//! their constraints.
+//!
+//! # Examples
+//!
+//! Viewing a primitive array as [`Real`]s:
+//!
+//! ```rust
+//! use decorum::array;
+//! use decorum::R64;
+//! use ndarray::{array, ArrayView2};
+//!
+//! let xs = array![[1.0f64, 2.0], [3.0, 4.0]];
+//! let ys: ArrayView2<R64> = array::try_view(xs.view()).unwrap();
+//! let sum: R64 = array::sum(&ys);
+//! assert_eq!(sum, 10.0);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`ndarray`]: https://crates.io/crates/ndarray
+//! [`Real`]: crate::Real
+//! [`Total`]: crate::Total
+
+use ::ndarray::{Array, ArrayBase, ArrayView, ArrayViewMut, Data, Dimension, ScalarOperand};
+
+use crate::constraint::{Constraint, IsFloat};
+use crate::proxy::{Constrained, OutputFor};
+use crate::{Primitive, Total};
+
+impl<T, C> ScalarOperand for Constrained<T, C>
+where
+    T: 'static + Primitive,
+    C: 'static + Constraint,
+{
+}
+
+/// Reinterprets a view of a primitive array as a view of a [`Total`] array.
+///
+/// [`Total`]: crate::Total
+pub fn view_total<T, D>(view: ArrayView<'_, T, D>) -> ArrayView<'_, Total<T>, D>
+where
+    T: Primitive,
+    D: Dimension,
+{
+    // SAFETY: `Constrained` is `repr(transparent)`, so it has the same layout as its primitive
+    //         type. `Total` is constrained by `IsFloat`, which accepts all values.
+    unsafe { view.raw_view().cast::<Total<T>>().deref_into_view() }
+}
+
+/// Reinterprets a mutable view of a primitive array as a mutable view of a [`Total`] array.
+///
+/// [`Total`]: crate::Total
+pub fn view_total_mut<T, D>(mut view: ArrayViewMut<'_, T, D>) -> ArrayViewMut<'_, Total<T>, D>
+where
+    T: Primitive,
+    D: Dimension,
+{
+    // SAFETY: `Constrained` is `repr(transparent)`, so it has the same layout as its primitive
+    //         type. `Total` is constrained by `IsFloat`, which accepts all values, so any value
+    //         written through the view is also a valid primitive value.
+    unsafe { view.raw_view_mut().cast::<Total<T>>().deref_into_view_mut() }
+}
+
+/// Reinterprets a view of a primitive array as a view of a [`Constrained`] array.
+///
+/// # Errors
+///
+/// Returns an error if any element of the array does not satisfy the constraint.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn try_view<T, C, D>(
+    view: ArrayView<'_, T, D>,
+) -> Result<ArrayView<'_, Constrained<T, C>, D>, C::Error>
+where
+    T: Primitive,
+    C: Constraint,
+    D: Dimension,
+{
+    for inner in view.iter() {
+        C::check(*inner)?;
+    }
+    // SAFETY: `Constrained` is `repr(transparent)`, so it has the same layout as its primitive
+    //         type. All elements satisfy the constraint and the view is immutable.
+    Ok(unsafe {
+        view.raw_view()
+            .cast::<Constrained<T, C>>()
+            .deref_into_view()
+    })
+}
+
+/// Converts an array of primitive values into an array of [`Constrained`] values.
+///
+/// # Errors
+///
+/// Returns an error if any element of the array does not satisfy the constraint.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn try_from_array<T, C, D>(array: Array<T, D>) -> Result<Array<Constrained<T, C>, D>, C::Error>
+where
+    T: Primitive,
+    C: Constraint,
+    D: Dimension,
+{
+    for inner in array.iter() {
+        C::check(*inner)?;
+    }
+    Ok(array.mapv(Constrained::unchecked))
+}
+
+/// Converts an array of primitive values into an array of [`Total`] values.
+///
+/// [`Total`]: crate::Total
+pub fn from_array_total<T, D>(array: Array<T, D>) -> Array<Total<T>, D>
+where
+    T: Primitive,
+    D: Dimension,
+{
+    array.mapv(Constrained::<T, IsFloat>::unchecked)
+}
+
+/// Converts an array of [`Constrained`] values into an array of primitive values.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn into_primitive_array<T, C, D>(array: Array<Constrained<T, C>, D>) -> Array<T, D>
+where
+    T: Primitive,
+    D: Dimension,
+{
+    array.mapv(Constrained::into_inner)
+}
+
+/// Sums the elements of an array.
+///
+/// The sum is computed using the primitive floating-point type and is checked against the
+/// constraint once. The output type is determined by the [divergence][`divergence`]. The sum of an
+/// empty array is zero.
+///
+/// [`divergence`]: crate::divergence
+pub fn sum<S, T, C, D>(array: &ArrayBase<S, D>) -> OutputFor<Constrained<T, C>>
+where
+    S: Data<Elem = Constrained<T, C>>,
+    T: Primitive,
+    C: Constraint,
+    D: Dimension,
+{
+    Constrained::<T, C>::new(array.iter().fold(T::ZERO, |sum, x| sum + x.into_inner()))
+}
+
+/// Gets the minimum element of an array with respect to the [total ordering][`cmp`].
+///
+/// Returns `None` if the array is empty.
+///
+/// [`cmp`]: crate::cmp
+pub fn min_total<S, T, C, D>(array: &ArrayBase<S, D>) -> Option<Constrained<T, C>>
+where
+    S: Data<Elem = Constrained<T, C>>,
+    T: Primitive,
+    D: Dimension,
+{
+    array.iter().copied().min()
+}
+
+/// Gets the maximum element of an array with respect to the [total ordering][`cmp`].
+///
+/// Returns `None` if the array is empty.
+///
+/// [`cmp`]: crate::cmp
+pub fn max_total<S, T, C, D>(array: &ArrayBase<S, D>) -> Option<Constrained<T, C>>
+where
+    S: Data<Elem = Constrained<T, C>>,
+    T: Primitive,
+    D: Dimension,
+{
+    array.iter().copied().max()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec;
+
+    use ::ndarray::{array, Array1, ArrayView1, ArrayView2};
+
+    use crate::array;
+    use crate::{Total, R64};
+
+    #[test]
+    fn view_total() {
+        let mut xs = array![1.0f64, f64::NAN, -0.0];
+        {
+            let ys = array::view_total(xs.view());
+            assert_eq!(array::max_total(&ys).unwrap(), Total::from(f64::NAN));
+            assert_eq!(array::min_total(&ys).unwrap(), Total::from(0.0));
+        }
+        array::view_total_mut(xs.view_mut())[0] = Total::from(2.0);
+        assert_eq!(xs[0], 2.0);
+    }
+
+    #[test]
+    fn try_view() {
+        let xs = array![[1.0f64, 2.0], [3.0, 4.0]];
+        let ys: ArrayView2<R64> = array::try_view(xs.view()).unwrap();
+        let sum: R64 = array::sum(&ys);
+        assert_eq!(sum, 10.0);
+
+        let xs = array![1.0f64, f64::INFINITY];
+        assert!(array::try_view(xs.view())
+            .map(|ys: ArrayView1<R64>| ys.len())
+            .is_err());
+    }
+
+    #[test]
+    fn try_from_array() {
+        let xs: Array1<R64> = array::try_from_array(array![1.0, 2.0]).unwrap();
+        assert_eq!(
+            xs * R64::assert(2.0),
+            array![R64::assert(2.0), R64::assert(4.0)]
+        );
+        assert!(array::try_from_array(array![f64::NAN])
+            .map(|xs: Array1<R64>| xs.len())
+            .is_err());
+        let xs = array::from_array_total(array![1.0f64, f64::NAN]);
+        assert!(array::into_primitive_array(xs)[1].is_nan());
+    }
+
+    #[test]
+    fn sum_empty() {
+        let xs: Array1<R64> = Array1::from_elem(0, R64::assert(1.0));
+        let sum: R64 = array::sum(&xs);
+        assert_eq!(sum, 0.0);
+        assert!(array::min_total(&xs).is_none());
+    }
+}
@@ -0,0 +1,232 @@
+//! Binades and grouping of values by magnitude.
+//!
+//! A _binade_ is the set of floating-point values with the same exponent, i.e., the values with a
+//! magnitude in the interval $[2^e,2^{e+1})$ for some integer $e$. All values in a binade have
+//! the same spacing (ULP), so binades are a natural unit of scale when analyzing the accuracy of
+//! floating-point computations or histogramming values by magnitude. Subnormal values are assigned
+//! to binades by their magnitude as if they were normal.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::binade;
+//! use decorum::R64;
+//!
+//! let x: R64 = R64::assert(10.0);
+//! assert_eq!(x.binade(), Some(3));
+//! assert!(binade::same_binade(x, <R64>::assert(-12.0)));
+//! ```
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+{
+    /// Gets the binade of the proxy, which is the floor of the base-2 logarithm of its magnitude.
+    ///
+    /// Returns `None` if the proxy is zero, infinite, or `NaN`.
+    pub fn binade(self) -> Option<i32> {
+        binade(self.into_inner())
+    }
+
+    /// Gets the bucket of the proxy where each bucket spans `width` adjacent binades.
+    ///
+    /// Bucket `k` contains the binades from `k * width` to `(k + 1) * width` (exclusive). Returns
+    /// `None` if the proxy is zero, infinite, or `NaN`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    pub fn binade_bucket(self, width: u32) -> Option<i32> {
+        assert!(width != 0, "bucket width must be non-zero");
+        self.binade()
+            .map(|binade| (i64::from(binade).div_euclid(i64::from(width))) as i32)
+    }
+}
+
+/// Returns `true` if the magnitudes of the proxies are in the same binade.
+///
+/// Zeros, infinities, and `NaN`s are not in any binade, so this function returns `false` if
+/// either proxy is such a value.
+pub fn same_binade<T, C>(a: Constrained<T, C>, b: Constrained<T, C>) -> bool
+where
+    T: Primitive,
+{
+    match (a.binade(), b.binade()) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Histogram of values by binade.
+///
+/// Zeros and values that are not real numbers (infinities and `NaN`s) are counted separately.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::binade::Histogram;
+/// use decorum::Total;
+///
+/// let histogram: Histogram = [0.0, 1.0, 1.5, 4.0, f64::NAN]
+///     .into_iter()
+///     .map(Total::from)
+///     .collect();
+/// assert_eq!(histogram.count(0), 2);
+/// assert_eq!(histogram.count(2), 1);
+/// assert_eq!(histogram.zeros(), 1);
+/// assert_eq!(histogram.not_real(), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Histogram {
+    binades: BTreeMap<i32, usize>,
+    zeros: usize,
+    not_real: usize,
+}
+
+#[cfg(feature = "std")]
+impl Histogram {
+    pub fn new() -> Self {
+        Histogram::default()
+    }
+
+    pub fn insert<T, C>(&mut self, x: Constrained<T, C>)
+    where
+        T: Primitive,
+    {
+        let inner = x.into_inner();
+        if let Some(binade) = binade(inner) {
+            *self.binades.entry(binade).or_insert(0) += 1;
+        }
+        else if inner.is_finite() {
+            self.zeros += 1;
+        }
+        else {
+            self.not_real += 1;
+        }
+    }
+
+    /// Gets the number of values in the given binade.
+    pub fn count(&self, binade: i32) -> usize {
+        self.binades.get(&binade).copied().unwrap_or(0)
+    }
+
+    pub fn zeros(&self) -> usize {
+        self.zeros
+    }
+
+    pub fn not_real(&self) -> usize {
+        self.not_real
+    }
+
+    /// Gets an iterator over the non-empty binades and their counts in increasing order.
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (i32, usize)> {
+        self.binades.iter().map(|(binade, count)| (*binade, *count))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, C> Extend<Constrained<T, C>> for Histogram
+where
+    T: Primitive,
+{
+    fn extend<I>(&mut self, input: I)
+    where
+        I: IntoIterator<Item = Constrained<T, C>>,
+    {
+        for x in input {
+            self.insert(x);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, C> FromIterator<Constrained<T, C>> for Histogram
+where
+    T: Primitive,
+{
+    fn from_iter<I>(input: I) -> Self
+    where
+        I: IntoIterator<Item = Constrained<T, C>>,
+    {
+        let mut histogram = Histogram::new();
+        histogram.extend(input);
+        histogram
+    }
+}
+
+pub(crate) fn binade<T>(inner: T) -> Option<i32>
+where
+    T: Primitive,
+{
+    if !inner.is_finite() {
+        return None;
+    }
+    let (mantissa, exponent, _) = inner.integer_decode();
+    if mantissa == 0 {
+        None
+    }
+    else {
+        Some(i32::from(exponent) + (63 - mantissa.leading_zeros() as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binade;
+    use crate::{Total, R32, R64};
+
+    #[test]
+    fn binade() {
+        assert_eq!(<R64>::assert(1.0).binade(), Some(0));
+        assert_eq!(<R64>::assert(1.99).binade(), Some(0));
+        assert_eq!(<R64>::assert(2.0).binade(), Some(1));
+        assert_eq!(<R64>::assert(-0.75).binade(), Some(-1));
+        assert_eq!(<R64>::assert(f64::MAX).binade(), Some(1023));
+        assert_eq!(<R64>::assert(f64::MIN_POSITIVE).binade(), Some(-1022));
+        assert_eq!(<R64>::assert(5.0e-324).binade(), Some(-1074));
+        assert_eq!(<R32>::assert(f32::from_bits(1)).binade(), Some(-149));
+        assert_eq!(<R32>::assert(3.0).binade(), Some(1));
+        assert_eq!(<R64>::assert(0.0).binade(), None);
+        assert_eq!(Total::from(f64::INFINITY).binade(), None);
+        assert_eq!(Total::from(f64::NAN).binade(), None);
+    }
+
+    #[test]
+    fn binade_bucket() {
+        assert_eq!(<R64>::assert(1.0).binade_bucket(4), Some(0));
+        assert_eq!(<R64>::assert(16.0).binade_bucket(4), Some(1));
+        assert_eq!(<R64>::assert(0.5).binade_bucket(4), Some(-1));
+    }
+
+    #[test]
+    fn same_binade() {
+        assert!(binade::same_binade(<R64>::assert(4.0), <R64>::assert(7.5)));
+        assert!(!binade::same_binade(<R64>::assert(4.0), <R64>::assert(8.0)));
+        assert!(!binade::same_binade(
+            Total::from(f64::NAN),
+            Total::from(f64::NAN)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn histogram() {
+        let histogram: binade::Histogram = [1.0, 3.0, 2.5, -0.0, f64::INFINITY]
+            .into_iter()
+            .map(Total::from)
+            .collect();
+        assert_eq!(
+            histogram.iter().collect::<std::vec::Vec<_>>(),
+            [(0, 1), (1, 2)]
+        );
+        assert_eq!(histogram.zeros(), 1);
+        assert_eq!(histogram.not_real(), 1);
+    }
+}
@@ -0,0 +1,220 @@
+//! Explicit arithmetic on the bit patterns of IEEE 754 floating-point values.
+//!
+//! IEEE 754 binary formats encode a value as three fields: a sign bit, a biased exponent, and a
+//! mantissa (trailing significand). The [`Bits`] trait exposes these fields for primitive
+//! floating-point types, composes values from fields with validation, and steps values by units in
+//! the last place (ULPs) directly on their encodings.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::bits::Bits;
+//!
+//! let x = 1.5f64;
+//! assert!(!x.sign_field());
+//! assert_eq!(x.exponent_field(), 1023);
+//! assert_eq!(x.mantissa_field(), 1 << 51);
+//! assert_eq!(f64::from_fields(false, 1023, 1 << 51), Ok(x));
+//!
+//! assert_eq!(0.0f64.step_ulps(1), f64::from_bits(1));
+//! assert_eq!(1.0f64.step_ulps(-1).step_ulps(1), 1.0);
+//! ```
+
+use thiserror::Error;
+
+use crate::{with_primitives, Primitive};
+
+#[derive(Clone, Copy, Debug, Eq, Error, PartialEq)]
+pub enum FieldError {
+    #[error("{}", "exponent field out of range")]
+    Exponent,
+    #[error("{}", "mantissa field out of range")]
+    Mantissa,
+}
+
+/// A primitive IEEE 754 floating-point type with an accessible binary encoding.
+pub trait Bits: Primitive {
+    /// The width of the exponent field in bits.
+    const EXPONENT_WIDTH: u32;
+    /// The width of the mantissa field in bits.
+    const MANTISSA_WIDTH: u32;
+    /// The bias of the exponent field.
+    const EXPONENT_BIAS: i32;
+    /// The value of the exponent field of infinities and `NaN`s.
+    const MAX_EXPONENT_FIELD: u32 = (1 << Self::EXPONENT_WIDTH) - 1;
+
+    /// Gets the sign bit. This is `true` if the sign is negative, including for `-0` and `NaN`s.
+    fn sign_field(self) -> bool;
+
+    /// Gets the biased exponent field.
+    ///
+    /// This is zero for zeros and subnormals and [`MAX_EXPONENT_FIELD`] for infinities and `NaN`s.
+    ///
+    /// [`MAX_EXPONENT_FIELD`]: crate::bits::Bits::MAX_EXPONENT_FIELD
+    fn exponent_field(self) -> u32;
+
+    /// Gets the mantissa field, which excludes the implicit leading bit of the significand.
+    fn mantissa_field(self) -> u64;
+
+    /// Composes a value from its sign, biased exponent, and mantissa fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exponent or mantissa does not fit in its field.
+    fn from_fields(sign: bool, exponent: u32, mantissa: u64) -> Result<Self, FieldError>;
+
+    /// Steps the value by the given number of ULPs.
+    ///
+    /// Positive steps move toward `+INF` and negative steps move toward `-INF` through adjacent
+    /// representable values. Both zeros are treated as the same value and stepping onto zero
+    /// yields `+0`. The output saturates at the infinities and `NaN`s are returned as-is.
+    fn step_ulps(self, n: i64) -> Self;
+
+    /// Gets the number of ULPs between two values.
+    ///
+    /// This is the number of steps (see [`step_ulps`]) from one value to the other, where both
+    /// zeros are treated as the same value. Returns `None` if either value is `NaN`.
+    ///
+    /// [`step_ulps`]: crate::bits::Bits::step_ulps
+    fn ulps_between(self, other: Self) -> Option<u64> {
+        fn key<T>(x: T) -> i64
+        where
+            T: Bits,
+        {
+            let magnitude =
+                (i64::from(x.exponent_field()) << T::MANTISSA_WIDTH) | x.mantissa_field() as i64;
+            if x.sign_field() {
+                -magnitude
+            }
+            else {
+                magnitude
+            }
+        }
+
+        (!self.is_nan() && !other.is_nan()).then(|| key(self).abs_diff(key(other)))
+    }
+}
+
+macro_rules! impl_bits_for_primitive {
+    () => {
+        with_primitives!(impl_bits_for_primitive);
+    };
+    (primitive => f32) => {
+        impl_bits_for_primitive!(primitive => f32, bits => u32, exponent => 8, mantissa => 23);
+    };
+    (primitive => f64) => {
+        impl_bits_for_primitive!(primitive => f64, bits => u64, exponent => 11, mantissa => 52);
+    };
+    (
+        primitive => $t:ident,
+        bits => $u:ident,
+        exponent => $exponent:literal,
+        mantissa => $mantissa:literal $(,)?
+    ) => {
+        impl Bits for $t {
+            const EXPONENT_WIDTH: u32 = $exponent;
+            const MANTISSA_WIDTH: u32 = $mantissa;
+            const EXPONENT_BIAS: i32 = (1 << ($exponent - 1)) - 1;
+
+            fn sign_field(self) -> bool {
+                (self.to_bits() >> ($exponent + $mantissa)) != 0
+            }
+
+            fn exponent_field(self) -> u32 {
+                ((self.to_bits() >> $mantissa) as u32) & Self::MAX_EXPONENT_FIELD
+            }
+
+            fn mantissa_field(self) -> u64 {
+                u64::from(self.to_bits() & ((1 << $mantissa) - 1))
+            }
+
+            fn from_fields(sign: bool, exponent: u32, mantissa: u64) -> Result<Self, FieldError> {
+                if exponent > Self::MAX_EXPONENT_FIELD {
+                    Err(FieldError::Exponent)
+                }
+                else if mantissa >> $mantissa != 0 {
+                    Err(FieldError::Mantissa)
+                }
+                else {
+                    Ok($t::from_bits(
+                        ($u::from(sign) << ($exponent + $mantissa))
+                            | ($u::from(exponent) << $mantissa)
+                            | mantissa as $u,
+                    ))
+                }
+            }
+
+            fn step_ulps(self, n: i64) -> Self {
+                if self.is_nan() {
+                    return self;
+                }
+                // Map values onto integers such that adjacent values are adjacent integers.
+                let magnitude = (self.to_bits() & !(1 << ($exponent + $mantissa))) as i64;
+                let key = if self.sign_field() {
+                    -magnitude
+                }
+                else {
+                    magnitude
+                };
+                let limit = $t::INFINITY.to_bits() as i64;
+                let key = key.saturating_add(n).clamp(-limit, limit);
+                let bits = key.unsigned_abs() as $u;
+                if key < 0 {
+                    $t::from_bits(bits | (1 << ($exponent + $mantissa)))
+                }
+                else {
+                    $t::from_bits(bits)
+                }
+            }
+        }
+    };
+}
+impl_bits_for_primitive!();
+
+#[cfg(test)]
+mod tests {
+    use crate::bits::{Bits, FieldError};
+
+    #[test]
+    fn fields() {
+        assert!((-2.0f32).sign_field());
+        assert!((-0.0f64).sign_field());
+        assert_eq!(2.0f32.exponent_field(), 128);
+        assert_eq!(f64::INFINITY.exponent_field(), f64::MAX_EXPONENT_FIELD);
+        assert_eq!(f64::from_bits(1).exponent_field(), 0);
+        assert_eq!(f64::from_bits(1).mantissa_field(), 1);
+        for x in [0.0f64, -1.5, 1.0e-310, f64::MAX, f64::NEG_INFINITY] {
+            let y = f64::from_fields(x.sign_field(), x.exponent_field(), x.mantissa_field());
+            assert_eq!(y.map(f64::to_bits), Ok(x.to_bits()));
+        }
+        assert_eq!(f32::from_fields(true, 127, 0), Ok(-1.0));
+        assert_eq!(f32::from_fields(false, 256, 0), Err(FieldError::Exponent));
+        assert_eq!(
+            f32::from_fields(false, 0, 1 << 23),
+            Err(FieldError::Mantissa)
+        );
+    }
+
+    #[test]
+    fn step_ulps() {
+        assert_eq!(1.0f64.step_ulps(1), 1.0 + f64::EPSILON);
+        assert_eq!(1.0f32.step_ulps(-1), 1.0 - (f32::EPSILON / 2.0));
+        assert_eq!((-0.0f64).step_ulps(1), f64::from_bits(1));
+        assert_eq!(f64::from_bits(1).step_ulps(-2), -f64::from_bits(1));
+        assert_eq!(f64::from_bits(1).step_ulps(-1).to_bits(), 0);
+        assert_eq!(f64::MAX.step_ulps(1), f64::INFINITY);
+        assert_eq!(f64::MAX.step_ulps(i64::MAX), f64::INFINITY);
+        assert_eq!(f32::MIN.step_ulps(i64::MIN), f32::NEG_INFINITY);
+        assert_eq!(f64::NEG_INFINITY.step_ulps(1), f64::MIN);
+        assert!(f64::NAN.step_ulps(1).is_nan());
+    }
+
+    #[test]
+    fn ulps_between() {
+        assert_eq!(1.0f64.ulps_between(1.0f64.step_ulps(3)), Some(3));
+        assert_eq!((-0.0f32).ulps_between(0.0), Some(0));
+        assert_eq!(f64::from_bits(1).ulps_between(-f64::from_bits(1)), Some(2));
+        assert_eq!(f32::MIN.ulps_between(f32::NEG_INFINITY), Some(1));
+        assert_eq!(f64::NAN.ulps_between(1.0), None);
+    }
+}
@@ -64,11 +64,28 @@
 //! let min = cmp::min_or_empty(x, y);
 //! ```
 //!
+//! # Tolerance
+//!
+//! Approximate equality is not transitive: if `a` is close to `b` and `b` is close to `c`, then
+//! `a` is not necessarily close to `c`. [`Approx`] compares pairs of values, while
+//! [`group_by_ulps`] and [`group_by_abs`] partition sorted slices into _chains_ of values where
+//! each value is within tolerance of its neighbor. Every value belongs to exactly one chain, so
+//! this grouping is well-defined where pairwise comparisons are not.
+//!
 //! [`Total`]: crate::Total
 
 use core::cmp::Ordering;
 use core::convert::Infallible;
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
+
+#[cfg(feature = "derive")]
+pub use decorum_derive::CanonicalKey;
 
+use crate::bits::Bits;
+use crate::expression::Expression;
+use crate::hash::CanonicalHash;
+use crate::proxy::Constrained;
 use crate::{with_primitives, Primitive, ToCanonical};
 
 /// Total equivalence relation of IEEE 754 floating-point encoded types.
@@ -175,6 +192,15 @@ where
     }
 }
 
+impl<T, C> CanonicalOrd for Constrained<T, C>
+where
+    T: Primitive,
+{
+    fn cmp_canonical(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp_canonical(other.as_ref())
+    }
+}
+
 impl<T> CanonicalOrd for [T]
 where
     T: CanonicalOrd,
@@ -365,6 +391,549 @@ macro_rules! impl_empty_inhabitant_for_float_primitive {
 }
 impl_empty_inhabitant_for_float_primitive!();
 
+/// Approximate equivalence of IEEE 754 floating-point encoded types within `ULPS` units in the last
+/// place.
+///
+/// `Approx` wraps a primitive floating-point type or [`Constrained`] type and implements
+/// [`PartialEq`] such that values are equal if there are no more than `ULPS` representable values
+/// between them. Positive and negative zero are equal and are one unit in the last place from the
+/// smallest subnormals, so values of opposite sign that are within `ULPS` of zero may be equal.
+/// Infinities are only equal to themselves and `NaN`s are never equal. This is useful for deduplicating nearly equal values, such as vertex
+/// positions in meshes that have accumulated rounding error.
+///
+/// **This relation is not transitive and so `Approx` does not implement [`Eq`].** The [`Hash`]
+/// implementation partitions values into buckets of `ULPS + 1` consecutive representable values.
+/// Values that are equal per [`PartialEq`] but lie on either side of a bucket boundary have
+/// different hashes, so hash-based deduplication is a best effort that may retain some nearly
+/// equal values. When `ULPS` is zero, `Approx` is an exact equivalence (except for `NaN`s) and
+/// hashing is consistent.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp::Approx;
+///
+/// let x = 0.1f64 + 0.2;
+/// assert_ne!(x, 0.3);
+/// assert_eq!(Approx::<_, 4>(x), Approx(0.3));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct Approx<T, const ULPS: u32>(pub T);
+
+impl<T, const ULPS: u32> Approx<T, ULPS> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+// Maps floating-point values to integers such that adjacent representable values map to adjacent
+// integers. The output is `None` for `NaN`s.
+pub(crate) trait UlpKey: Copy {
+    fn ulp_key(self) -> Option<i64>;
+}
+
+impl UlpKey for f32 {
+    fn ulp_key(self) -> Option<i64> {
+        (!self.is_nan()).then(|| {
+            let bits = i64::from(self.to_bits() as i32);
+            if bits < 0 {
+                -(bits & i64::from(i32::MAX))
+            }
+            else {
+                bits
+            }
+        })
+    }
+}
+
+impl UlpKey for f64 {
+    fn ulp_key(self) -> Option<i64> {
+        (!self.is_nan()).then(|| {
+            let bits = self.to_bits() as i64;
+            if bits < 0 {
+                -(bits & i64::MAX)
+            }
+            else {
+                bits
+            }
+        })
+    }
+}
+
+pub(crate) fn eq_ulps<T>(a: T, b: T, ulps: u32) -> bool
+where
+    T: Primitive + UlpKey,
+{
+    match (a.ulp_key(), b.ulp_key()) {
+        (Some(a), Some(b)) => a.abs_diff(b) <= u64::from(ulps),
+        _ => false,
+    }
+}
+
+fn hash_ulps<T, H>(x: T, ulps: u32, state: &mut H)
+where
+    T: Primitive + UlpKey,
+    H: Hasher,
+{
+    x.ulp_key()
+        .map(|key| {
+            if x.is_infinite() {
+                key
+            }
+            else {
+                key.div_euclid(i64::from(ulps) + 1)
+            }
+        })
+        .hash(state)
+}
+
+macro_rules! impl_approx_for_primitive {
+    () => {
+        with_primitives!(impl_approx_for_primitive);
+    };
+    (primitive => $t:ty) => {
+        impl<const ULPS: u32> Hash for Approx<$t, ULPS> {
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                hash_ulps(self.0, ULPS, state)
+            }
+        }
+
+        impl<C, const ULPS: u32> Hash for Approx<Constrained<$t, C>, ULPS> {
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                hash_ulps(self.0.into_inner(), ULPS, state)
+            }
+        }
+
+        impl<const ULPS: u32> PartialEq for Approx<$t, ULPS> {
+            fn eq(&self, other: &Self) -> bool {
+                if self.0.is_infinite() || other.0.is_infinite() {
+                    self.0 == other.0
+                }
+                else {
+                    eq_ulps(self.0, other.0, ULPS)
+                }
+            }
+        }
+
+        impl<C, const ULPS: u32> PartialEq for Approx<Constrained<$t, C>, ULPS> {
+            fn eq(&self, other: &Self) -> bool {
+                Approx::<$t, ULPS>(self.0.into_inner()) == Approx(other.0.into_inner())
+            }
+        }
+    };
+}
+impl_approx_for_primitive!();
+
+/// Equivalence and total ordering of IEEE 754 floating-point encoded types that coalesces all
+/// non-finite values.
+///
+/// `CoalesceNonFinite` wraps a primitive floating-point type or [`Constrained`] type and
+/// implements [`Eq`], [`Ord`], and [`Hash`] such that infinities and `NaN`s are members of a single
+/// equivalence class that is greater than all finite values. Finite values are compared per
+/// [`CanonicalOrd`]. Given the set of non-finite values $F$, this ordering expresses:
+///
+/// $$
+/// \begin{aligned}
+/// a=b&\mid a\in{F},~b\in{F}\cr\[1em\]
+/// f>x&\mid f\in{F},~x\notin{F}
+/// \end{aligned}
+/// $$
+///
+/// This is useful for deduplication when any non-finite value represents missing data, such as
+/// with [`Total`] values: unlike the [`Eq`] implementation of [`Total`], `+inf`, `-inf`, and `NaN`
+/// are all equal.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp::CoalesceNonFinite;
+/// use decorum::Total;
+///
+/// let x = Total::from(f64::INFINITY);
+/// let y = Total::from(f64::NEG_INFINITY);
+/// assert_ne!(x, y);
+/// assert_eq!(CoalesceNonFinite(x), CoalesceNonFinite(y));
+/// assert!(CoalesceNonFinite(x) > CoalesceNonFinite(Total::from(f64::MAX)));
+/// ```
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`Total`]: crate::Total
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct CoalesceNonFinite<T>(pub T);
+
+impl<T> CoalesceNonFinite<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+// Maps non-finite values to `None`.
+fn finite<T>(x: T) -> Option<T>
+where
+    T: Primitive,
+{
+    x.is_finite().then_some(x)
+}
+
+fn cmp_non_finite<T>(a: T, b: T) -> Ordering
+where
+    T: Primitive,
+{
+    match (finite(a), finite(b)) {
+        (Some(a), Some(b)) => a.cmp_canonical(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn hash_non_finite<T, H>(x: T, state: &mut H)
+where
+    T: Primitive,
+    H: Hasher,
+{
+    // All non-finite values are hashed as positive infinity.
+    finite(x).unwrap_or(T::INFINITY).hash_canonical(state)
+}
+
+macro_rules! impl_coalesce_non_finite_for_primitive {
+    () => {
+        with_primitives!(impl_coalesce_non_finite_for_primitive);
+    };
+    (primitive => $t:ty) => {
+        impl Eq for CoalesceNonFinite<$t> {}
+
+        impl<C> Eq for CoalesceNonFinite<Constrained<$t, C>> {}
+
+        impl Hash for CoalesceNonFinite<$t> {
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                hash_non_finite(self.0, state)
+            }
+        }
+
+        impl<C> Hash for CoalesceNonFinite<Constrained<$t, C>> {
+            fn hash<H>(&self, state: &mut H)
+            where
+                H: Hasher,
+            {
+                hash_non_finite(self.0.into_inner(), state)
+            }
+        }
+
+        impl Ord for CoalesceNonFinite<$t> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                cmp_non_finite(self.0, other.0)
+            }
+        }
+
+        impl<C> Ord for CoalesceNonFinite<Constrained<$t, C>> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                cmp_non_finite(self.0.into_inner(), other.0.into_inner())
+            }
+        }
+
+        impl PartialEq for CoalesceNonFinite<$t> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl<C> PartialEq for CoalesceNonFinite<Constrained<$t, C>> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl PartialOrd for CoalesceNonFinite<$t> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<C> PartialOrd for CoalesceNonFinite<Constrained<$t, C>> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+    };
+}
+impl_coalesce_non_finite_for_primitive!();
+
+/// Outcome of a comparison that may be undefined.
+///
+/// `PartialDecision` is like `Option<Ordering>`, but flattens the undefined case into a variant,
+/// so that decisions can be matched exhaustively. See [`Expression::cmp_defined`].
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp::PartialDecision;
+/// use decorum::divergence::OrError;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::R64;
+///
+/// let x = R64::<OrError>::ONE / R64::ZERO;
+/// let y = R64::<OrError>::ONE + R64::ONE;
+/// match x.cmp_defined(&y) {
+///     PartialDecision::Less | PartialDecision::Equal => {}
+///     PartialDecision::Greater => {}
+///     PartialDecision::Undefined => {}
+/// }
+/// ```
+///
+/// [`Expression::cmp_defined`]: crate::expression::Expression::cmp_defined
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PartialDecision {
+    Less,
+    Equal,
+    Greater,
+    Undefined,
+}
+
+impl PartialDecision {
+    pub fn ordering(self) -> Option<Ordering> {
+        match self {
+            PartialDecision::Less => Some(Ordering::Less),
+            PartialDecision::Equal => Some(Ordering::Equal),
+            PartialDecision::Greater => Some(Ordering::Greater),
+            PartialDecision::Undefined => None,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            PartialDecision::Less => PartialDecision::Greater,
+            PartialDecision::Greater => PartialDecision::Less,
+            decision => decision,
+        }
+    }
+
+    pub fn is_undefined(self) -> bool {
+        matches!(self, PartialDecision::Undefined)
+    }
+}
+
+impl From<Ordering> for PartialDecision {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Less => PartialDecision::Less,
+            Ordering::Equal => PartialDecision::Equal,
+            Ordering::Greater => PartialDecision::Greater,
+        }
+    }
+}
+
+impl From<Option<Ordering>> for PartialDecision {
+    fn from(ordering: Option<Ordering>) -> Self {
+        ordering.map_or(PartialDecision::Undefined, PartialDecision::from)
+    }
+}
+
+impl From<PartialDecision> for Option<Ordering> {
+    fn from(decision: PartialDecision) -> Self {
+        decision.ordering()
+    }
+}
+
+/// Total ordering of [`Expression`]s in which undefined expressions are greatest.
+///
+/// The comparison of [`Expression`]s is partial: like `NaN`s, undefined expressions are not
+/// equal to nor ordered with any other expression, including themselves. `UndefinedGreatest`
+/// implements [`Eq`], [`Ord`], and [`Hash`] for expressions of totally ordered types, such that
+/// all undefined expressions are equal (regardless of their errors) and greater than any defined
+/// expression. This allows expressions to be sorted and used as keys in maps and sets.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp::UndefinedGreatest;
+/// use decorum::divergence::OrError;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::R64;
+///
+/// let x = R64::<OrError>::ONE / R64::ZERO;
+/// let y = R64::<OrError>::ONE + R64::ONE;
+/// assert!(x != x);
+/// assert_eq!(UndefinedGreatest(x), UndefinedGreatest(x));
+/// assert!(UndefinedGreatest(x) > UndefinedGreatest(y));
+/// ```
+///
+/// [`Expression`]: crate::expression::Expression
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct UndefinedGreatest<T>(pub T);
+
+impl<T> UndefinedGreatest<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, E> Eq for UndefinedGreatest<Expression<T, E>> where T: Ord {}
+
+impl<T, E> Hash for UndefinedGreatest<Expression<T, E>>
+where
+    T: Hash + Ord,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        // Undefined expressions are hashed by their variant only. Defined values are hashed like
+        // the values themselves, such as the canonical hashing of `Constrained` values.
+        match self.0 {
+            Expression::Defined(ref defined) => {
+                0u8.hash(state);
+                defined.hash(state);
+            }
+            Expression::Undefined(_) => 1u8.hash(state),
+        }
+    }
+}
+
+impl<T, E> Ord for UndefinedGreatest<Expression<T, E>>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0.as_ref().defined(), other.0.as_ref().defined()) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl<T, E> PartialEq for UndefinedGreatest<Expression<T, E>>
+where
+    T: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, E> PartialOrd for UndefinedGreatest<Expression<T, E>>
+where
+    T: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Iterator over the ranges of chains in a slice of [`Constrained`] values.
+///
+/// See [`group_by_ulps`] and [`group_by_abs`].
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub struct Chains<'a, T, C, F> {
+    values: &'a [Constrained<T, C>],
+    start: usize,
+    f: F,
+}
+
+impl<T, C, F> Iterator for Chains<'_, T, C, F>
+where
+    T: Primitive,
+    F: FnMut(T, T) -> bool,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start;
+        if start >= self.values.len() {
+            return None;
+        }
+        let mut end = start + 1;
+        while end < self.values.len()
+            && (self.f)(
+                self.values[end - 1].into_inner(),
+                self.values[end].into_inner(),
+            )
+        {
+            end += 1;
+        }
+        self.start = end;
+        Some(start..end)
+    }
+}
+
+/// Groups a sorted slice into chains of values where adjacent values are within the given number
+/// of ULPs.
+///
+/// The output is an iterator over the ranges of consecutive chains, which together cover the
+/// slice. Values in a chain may be more than `ulps` apart from each other, but each is within
+/// `ulps` of its neighbor. `NaN`s are never within tolerance and so form chains of their own.
+///
+/// The slice should be sorted, such as with [`Ord`]. Otherwise, chains are still formed from
+/// adjacent values but may not group all values that are within tolerance.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp;
+/// use decorum::R64;
+///
+/// let xs = [1.0, 1.0 + f64::EPSILON, 1.0 + (2.0 * f64::EPSILON), 2.0].map(<R64>::assert);
+/// let chains: Vec<_> = cmp::group_by_ulps(&xs, 1).collect();
+/// assert_eq!(chains, [0..3, 3..4]);
+/// ```
+pub fn group_by_ulps<T, C>(
+    values: &[Constrained<T, C>],
+    ulps: u64,
+) -> Chains<'_, T, C, impl FnMut(T, T) -> bool>
+where
+    T: Bits,
+{
+    Chains {
+        values,
+        start: 0,
+        f: move |a: T, b: T| a.ulps_between(b).is_some_and(|n| n <= ulps),
+    }
+}
+
+/// Groups a sorted slice into chains of values where adjacent values differ by no more than the
+/// given absolute tolerance.
+///
+/// See [`group_by_ulps`]. Infinities are within tolerance of other infinities with the same sign.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp;
+/// use decorum::R64;
+///
+/// let xs = [0.0, 0.4, 0.8, 2.0, 2.3].map(<R64>::assert);
+/// let chains: Vec<_> = cmp::group_by_abs(&xs, 0.5).collect();
+/// assert_eq!(chains, [0..3, 3..5]);
+/// ```
+pub fn group_by_abs<T, C>(
+    values: &[Constrained<T, C>],
+    epsilon: T,
+) -> Chains<'_, T, C, impl FnMut(T, T) -> bool>
+where
+    T: Primitive,
+{
+    Chains {
+        values,
+        start: 0,
+        f: move |a: T, b: T| a == b || (b - a <= epsilon && a - b <= epsilon),
+    }
+}
+
 /// Pairwise maximum for types that may have an empty inhabitant that is incomparable.
 ///
 /// See the [`EmptyOrd`] trait.
@@ -410,11 +979,50 @@ where
     }
 }
 
+/// Sorts a slice of floating-point values.
+///
+/// This function provides the unstable `sort_floats` function of slices in the standard library on
+/// stable Rust. Values are sorted by the IEEE 754 `totalOrder` predicate, except that all `NaN`s
+/// are sorted last regardless of their sign:
+///
+/// $$-\infin<\cdots<-0<+0<\cdots<\infin<\text{NaN}$$
+///
+/// Unlike [`CanonicalOrd`], this ordering distinguishes `-0` and `+0`. Like `sort_floats`, this
+/// sort is not stable and does not allocate.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::cmp;
+///
+/// let mut xs = [2.0, f64::NAN, 0.0, -0.0, f64::NEG_INFINITY];
+/// cmp::sort_floats(&mut xs);
+/// assert_eq!(xs[..4], [f64::NEG_INFINITY, -0.0, 0.0, 2.0]);
+/// assert!(xs[1].is_sign_negative());
+/// assert!(xs[4].is_nan());
+/// ```
+pub fn sort_floats<T>(xs: &mut [T])
+where
+    T: Primitive,
+{
+    xs.sort_unstable_by(|a, b| {
+        a.cmp_canonical(b).then_with(|| {
+            // Only zeros and `NaN`s are equal in the canonical ordering but may differ in sign.
+            if a.is_nan() {
+                Ordering::Equal
+            }
+            else {
+                b.is_sign_negative().cmp(&a.is_sign_negative())
+            }
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use num_traits::{One, Zero};
 
-    use crate::cmp::{self, CanonicalEq, EmptyOrd};
+    use crate::cmp::{self, CanonicalEq, EmptyOrd, PartialDecision};
     use crate::{NanEncoding, Total};
 
     #[test]
@@ -469,4 +1077,205 @@ mod tests {
         assert_eq!(nan, cmp::min_or_empty(nan, nan));
         assert_eq!(nan, cmp::max_or_empty(nan, nan));
     }
+
+    #[test]
+    fn approx_eq() {
+        use crate::cmp::Approx;
+        use crate::R64;
+
+        let x = 1.0f64;
+        let y = f64::from_bits(x.to_bits() + 2);
+        assert_eq!(Approx::<_, 2>(x), Approx(y));
+        assert_ne!(Approx::<_, 1>(x), Approx(y));
+        assert_eq!(Approx::<_, 1>(-0.0f64), Approx(0.0));
+        assert_eq!(
+            Approx::<_, 2>(-f64::from_bits(1)),
+            Approx(f64::from_bits(1))
+        );
+        assert_ne!(Approx::<_, 8>(f64::MAX), Approx(f64::INFINITY));
+        assert_ne!(Approx::<_, 8>(f64::NAN), Approx(f64::NAN));
+        assert_eq!(Approx::<_, 0>(1.0f32), Approx(1.0));
+        assert_eq!(Approx::<R64, 2>(R64::assert(x)), Approx(R64::assert(y)));
+    }
+
+    #[cfg(all(feature = "derive", feature = "std"))]
+    #[test]
+    fn derive_canonical_key() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+        use std::string::String;
+
+        use crate::cmp::CanonicalKey;
+        use crate::R64;
+
+        #[derive(CanonicalKey)]
+        struct Key {
+            name: String,
+            #[canonical(eq = "canonical")]
+            scale: f64,
+            #[canonical(eq = "ulps(4)")]
+            measured: R64,
+        }
+
+        let hash = |key: &Key| {
+            let mut state = DefaultHasher::new();
+            key.hash(&mut state);
+            state.finish()
+        };
+        let key = |measured: f64| Key {
+            name: String::from("a"),
+            scale: f64::NAN,
+            measured: R64::assert(measured),
+        };
+        let x = key(1.0);
+        let y = key(f64::from_bits(1.0f64.to_bits() + 4));
+        assert!(x == y);
+        assert_eq!(hash(&x), hash(&y));
+        assert!(x != key(f64::from_bits(1.0f64.to_bits() + 5)));
+        assert!(
+            x != Key {
+                name: String::from("b"),
+                ..key(1.0)
+            }
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn coalesce_non_finite() {
+        use core::hash::{Hash, Hasher};
+        use std::collections::hash_map::DefaultHasher;
+
+        use crate::cmp::CoalesceNonFinite;
+        use crate::InfinityEncoding;
+
+        let hash = |x: Total<f64>| {
+            let mut state = DefaultHasher::new();
+            CoalesceNonFinite(x).hash(&mut state);
+            state.finish()
+        };
+        let inf = Total::<f64>::INFINITY;
+        let neg_inf = Total::<f64>::NEG_INFINITY;
+        let nan = Total::<f64>::NAN;
+
+        assert_eq!(CoalesceNonFinite(inf), CoalesceNonFinite(neg_inf));
+        assert_eq!(CoalesceNonFinite(inf), CoalesceNonFinite(nan));
+        assert_eq!(hash(neg_inf), hash(nan));
+        assert_eq!(CoalesceNonFinite(-0.0f64), CoalesceNonFinite(0.0));
+        assert_ne!(CoalesceNonFinite(1.0f64), CoalesceNonFinite(f64::INFINITY));
+        assert!(CoalesceNonFinite(neg_inf) > CoalesceNonFinite(Total::from(f64::MAX)));
+        assert!(CoalesceNonFinite(f64::MIN) < CoalesceNonFinite(0.0));
+    }
+
+    #[test]
+    fn sort_floats() {
+        let mut xs = [
+            f64::NAN,
+            1.0,
+            0.0,
+            -f64::NAN,
+            -0.0,
+            f64::INFINITY,
+            -1.0,
+            0.0,
+            -0.0,
+        ];
+        cmp::sort_floats(&mut xs);
+        let bits = xs[..7].iter().map(|x| x.to_bits());
+        assert!(bits.eq([
+            (-1.0f64).to_bits(),
+            (-0.0f64).to_bits(),
+            (-0.0f64).to_bits(),
+            0.0f64.to_bits(),
+            0.0f64.to_bits(),
+            1.0f64.to_bits(),
+            f64::INFINITY.to_bits(),
+        ]));
+        assert!(xs[7..].iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn partial_decision() {
+        use crate::divergence::OrError;
+        use crate::real::UnaryRealFunction;
+        use crate::R64;
+
+        let undefined = R64::<OrError>::ONE / R64::ZERO;
+        let one = R64::<OrError>::ONE.into_expression();
+        let two = R64::<OrError>::ONE + R64::ONE;
+        assert_eq!(one.cmp_defined(&two), PartialDecision::Less);
+        assert_eq!(two.cmp_defined(&one), PartialDecision::Greater);
+        assert_eq!(one.cmp_defined(&undefined), PartialDecision::Undefined);
+        assert_eq!(
+            R64::<OrError>::ONE.cmp_defined(&one),
+            PartialDecision::Equal
+        );
+        assert_eq!(
+            R64::<OrError>::ONE.cmp_defined(&undefined),
+            PartialDecision::Undefined
+        );
+        assert_eq!(PartialDecision::from(None), PartialDecision::Undefined);
+        assert_eq!(PartialDecision::Less.reverse(), PartialDecision::Greater);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn group_by() {
+        use std::vec::Vec;
+
+        use crate::{Total, R64};
+
+        let xs = [0.0f64, 0.1, 0.2, 0.3, 1.0, 1.05].map(<R64>::assert);
+        let chains: Vec<_> = cmp::group_by_abs(&xs, 0.1 + 1.0e-9).collect();
+        assert_eq!(chains, [0..4, 4..6]);
+        assert_eq!(cmp::group_by_abs(&xs[..0], 1.0).count(), 0);
+
+        let xs = [
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NAN,
+            f64::NAN,
+        ]
+        .map(Total::from);
+        let chains: Vec<_> = cmp::group_by_ulps(&xs, 1).collect();
+        assert_eq!(chains, [0..1, 1..3, 3..4, 4..5]);
+        let chains: Vec<_> = cmp::group_by_abs(&xs, 1.0).collect();
+        assert_eq!(chains, [0..1, 1..3, 3..4, 4..5]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn undefined_greatest() {
+        use std::collections::BTreeMap;
+        use std::vec::Vec;
+
+        use crate::cmp::UndefinedGreatest;
+        use crate::divergence::OrError;
+        use crate::real::UnaryRealFunction;
+        use crate::R64;
+
+        let undefined = R64::<OrError>::ONE / R64::ZERO;
+        let mut xs: Vec<_> = [
+            undefined,
+            R64::<OrError>::ONE + R64::ONE,
+            R64::<OrError>::ZERO.into_expression(),
+            R64::<OrError>::ZERO / R64::ZERO,
+        ]
+        .into_iter()
+        .map(UndefinedGreatest)
+        .collect();
+        xs.sort();
+        assert_eq!(xs[0].0, R64::<OrError>::ZERO);
+        assert_eq!(xs[1].0, 2.0);
+        assert!(xs[2].0.is_undefined() && xs[3].0.is_undefined());
+        assert_eq!(xs[2], xs[3]);
+
+        let mut counts = BTreeMap::new();
+        for x in xs {
+            *counts.entry(x).or_insert(0usize) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&UndefinedGreatest(undefined)], 2);
+    }
 }
@@ -0,0 +1,150 @@
+//! Mathematical constants beyond those of [`UnaryRealFunction`].
+//!
+//! The [`Constants`] trait provides correctly rounded constants that are not provided by the
+//! standard library (at time of writing) and so are often written by hand as approximations.
+//! These constants are real numbers, so they are available for any [`Constrained`] type.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::constants::Constants;
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::R64;
+//!
+//! let radius: R64 = R64::assert(2.0);
+//! let circumference = R64::TAU * radius;
+//! assert_eq!(circumference, <R64>::PI * 4.0);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`UnaryRealFunction`]: crate::real::UnaryRealFunction
+
+use crate::expression::Expression::{self, Defined};
+use crate::proxy::Constrained;
+use crate::{with_primitives, Primitive};
+
+/// Correctly rounded mathematical constants.
+pub trait Constants: Sized {
+    /// The full circle constant $\tau=2\pi$.
+    const TAU: Self;
+    /// $\sqrt{3}$.
+    const SQRT_3: Self;
+    /// $1/\sqrt{3}$.
+    const FRAC_1_SQRT_3: Self;
+    /// $\sqrt{5}$.
+    const SQRT_5: Self;
+    /// The golden ratio $\varphi=(1+\sqrt{5})/2$.
+    const PHI: Self;
+    /// The Euler-Mascheroni constant $\gamma$.
+    const EGAMMA: Self;
+    /// $\ln(3)$.
+    const LN_3: Self;
+    /// $\ln(\pi)$.
+    const LN_PI: Self;
+    /// $\sqrt{\pi}$.
+    const SQRT_PI: Self;
+    /// $1/\sqrt{\pi}$.
+    const FRAC_1_SQRT_PI: Self;
+    /// $1/\sqrt{2\pi}$, the maximum of the probability density function of the standard normal
+    /// distribution.
+    const FRAC_1_SQRT_2PI: Self;
+}
+
+impl<T, C> Constants for Constrained<T, C>
+where
+    T: Constants + Primitive,
+{
+    // These constants are real numbers, which satisfy any constraint.
+    const TAU: Self = Constrained::unchecked(Constants::TAU);
+    const SQRT_3: Self = Constrained::unchecked(Constants::SQRT_3);
+    const FRAC_1_SQRT_3: Self = Constrained::unchecked(Constants::FRAC_1_SQRT_3);
+    const SQRT_5: Self = Constrained::unchecked(Constants::SQRT_5);
+    const PHI: Self = Constrained::unchecked(Constants::PHI);
+    const EGAMMA: Self = Constrained::unchecked(Constants::EGAMMA);
+    const LN_3: Self = Constrained::unchecked(Constants::LN_3);
+    const LN_PI: Self = Constrained::unchecked(Constants::LN_PI);
+    const SQRT_PI: Self = Constrained::unchecked(Constants::SQRT_PI);
+    const FRAC_1_SQRT_PI: Self = Constrained::unchecked(Constants::FRAC_1_SQRT_PI);
+    const FRAC_1_SQRT_2PI: Self = Constrained::unchecked(Constants::FRAC_1_SQRT_2PI);
+}
+
+impl<T, E> Constants for Expression<T, E>
+where
+    T: Constants,
+{
+    const TAU: Self = Defined(Constants::TAU);
+    const SQRT_3: Self = Defined(Constants::SQRT_3);
+    const FRAC_1_SQRT_3: Self = Defined(Constants::FRAC_1_SQRT_3);
+    const SQRT_5: Self = Defined(Constants::SQRT_5);
+    const PHI: Self = Defined(Constants::PHI);
+    const EGAMMA: Self = Defined(Constants::EGAMMA);
+    const LN_3: Self = Defined(Constants::LN_3);
+    const LN_PI: Self = Defined(Constants::LN_PI);
+    const SQRT_PI: Self = Defined(Constants::SQRT_PI);
+    const FRAC_1_SQRT_PI: Self = Defined(Constants::FRAC_1_SQRT_PI);
+    const FRAC_1_SQRT_2PI: Self = Defined(Constants::FRAC_1_SQRT_2PI);
+}
+
+macro_rules! impl_constants_for_primitive {
+    () => {
+        with_primitives!(impl_constants_for_primitive);
+    };
+    (primitive => $t:ident) => {
+        // These literals have more significant digits than necessary for any primitive type and
+        // so are correctly rounded by the compiler.
+        impl Constants for $t {
+            const TAU: Self = core::$t::consts::TAU;
+            const SQRT_3: Self = 1.732_050_807_568_877_293_527_446_341_505_872_366_942_81;
+            const FRAC_1_SQRT_3: Self = 0.577_350_269_189_625_764_509_148_780_501_957_455_647_60;
+            const SQRT_5: Self = 2.236_067_977_499_789_696_409_173_668_731_276_235_440_62;
+            const PHI: Self = 1.618_033_988_749_894_848_204_586_834_365_638_117_720_31;
+            const EGAMMA: Self = 0.577_215_664_901_532_860_606_512_090_082_402_431_042_16;
+            const LN_3: Self = 1.098_612_288_668_109_691_395_245_236_922_525_704_647_49;
+            const LN_PI: Self = 1.144_729_885_849_400_174_143_427_351_353_058_711_647_29;
+            const SQRT_PI: Self = 1.772_453_850_905_516_027_298_167_483_341_145_182_797_55;
+            const FRAC_1_SQRT_PI: Self = 0.564_189_583_547_756_286_948_079_451_560_772_585_844_05;
+            const FRAC_1_SQRT_2PI: Self = 0.398_942_280_401_432_677_939_946_059_934_381_868_475_86;
+        }
+    };
+}
+impl_constants_for_primitive!();
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::constants::Constants;
+    use crate::real::UnaryRealFunction;
+    use crate::{Total, R32, R64};
+
+    #[test]
+    fn constants_f64() {
+        assert_eq!(f64::TAU, core::f64::consts::TAU);
+        assert_eq!(f64::SQRT_3, 3.0f64.sqrt());
+        assert_eq!(f64::SQRT_5, 5.0f64.sqrt());
+        assert_eq!(f64::SQRT_PI.to_bits(), 0x3ffc_5bf8_91b4_ef6b);
+        assert_eq!(f64::LN_3, 3.0f64.ln());
+        assert_eq!(f64::PHI, (1.0 + 5.0f64.sqrt()) / 2.0);
+        assert_eq!(f64::FRAC_1_SQRT_PI * 2.0, core::f64::consts::FRAC_2_SQRT_PI);
+        assert_eq!(f64::EGAMMA.to_bits(), 0x3fe2_788c_fc6f_b619);
+        assert_eq!(f64::LN_PI.to_bits(), 0x3ff2_50d0_48e7_a1bd);
+        assert!(
+            (f64::FRAC_1_SQRT_2PI - 1.0 / (2.0 * core::f64::consts::PI).sqrt()).abs()
+                <= f64::EPSILON
+        );
+        assert!((f64::FRAC_1_SQRT_3 - 1.0 / 3.0f64.sqrt()).abs() <= f64::EPSILON);
+    }
+
+    #[test]
+    fn constants_f32() {
+        assert_eq!(f32::TAU, core::f32::consts::TAU);
+        assert_eq!(f32::SQRT_3, 3.0f32.sqrt());
+        assert_eq!(f32::PHI, f64::PHI as f32);
+        assert_eq!(f32::EGAMMA, f64::EGAMMA as f32);
+    }
+
+    #[test]
+    fn constants_proxy() {
+        assert_eq!(<R64>::TAU, <R64>::PI * 2.0);
+        assert_eq!(<R32>::SQRT_3, f32::SQRT_3);
+        assert_eq!(Total::<f64>::LN_PI, f64::LN_PI);
+    }
+}
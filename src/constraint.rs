@@ -31,12 +31,16 @@
 use core::convert::Infallible;
 use core::fmt::{self, Debug, Display, Formatter};
 use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::cmp::EmptyInhabitant;
 use crate::divergence::{Divergence, OrPanic, OutputFor};
 use crate::proxy::{Constrained, ConstrainedProxy};
 use crate::sealed::{Sealed, StaticDebug};
+#[cfg(feature = "trace")]
+use crate::trace::Trace;
 use crate::{NanEncoding, Primitive};
 
 pub(crate) mod sealed {
@@ -68,6 +72,7 @@ pub(crate) mod sealed {
 }
 use sealed::FromEmpty;
 
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Error)]
 pub enum ConstraintError {
     #[error(transparent)]
@@ -88,6 +93,7 @@ impl From<NotRealError> for ConstraintError {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Error)]
 #[error("{}", "floating-point value must be an extended real")]
 pub struct NotExtendedRealError;
@@ -98,6 +104,7 @@ impl EmptyInhabitant for NotExtendedRealError {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug, Error)]
 #[error("{}", "floating-point value must be a real")]
 pub struct NotRealError;
@@ -147,7 +154,10 @@ where
 pub trait Constraint: FromEmpty + Member<RealSet> + StaticDebug {
     type Divergence: Divergence;
     // TODO: Bound this on `core::Error` once it is stabilized.
+    #[cfg(not(feature = "trace"))]
     type Error: Debug + Display;
+    #[cfg(feature = "trace")]
+    type Error: Debug + Display + Trace;
 
     // It is not possible for constraints to map accepted values because of reference conversions,
     // so the successful output is the unit type and primitive values must be used as-is. That is,
@@ -249,14 +259,19 @@ where
     D: Divergence,
 {
     type Divergence = D;
+    #[cfg(not(feature = "trace"))]
     type Error = NotExtendedRealError;
+    #[cfg(feature = "trace")]
+    type Error = D::Error<NotExtendedRealError>;
 
+    // The error is converted into a traced error only with the `trace` feature.
+    #[cfg_attr(not(feature = "trace"), allow(clippy::useless_conversion))]
     fn check<T>(inner: T) -> Result<(), Self::Error>
     where
         T: Primitive,
     {
-        if inner.is_nan() {
-            Err(NotExtendedRealError)
+        if D::is_injected() || inner.is_nan() {
+            Err(NotExtendedRealError.into())
         }
         else {
             Ok(())
@@ -320,14 +335,19 @@ where
     D: Divergence,
 {
     type Divergence = D;
+    #[cfg(not(feature = "trace"))]
     type Error = NotRealError;
+    #[cfg(feature = "trace")]
+    type Error = D::Error<NotRealError>;
 
+    // The error is converted into a traced error only with the `trace` feature.
+    #[cfg_attr(not(feature = "trace"), allow(clippy::useless_conversion))]
     fn check<T>(inner: T) -> Result<(), Self::Error>
     where
         T: Primitive,
     {
-        if inner.is_nan() || inner.is_infinite() {
-            Err(NotRealError)
+        if D::is_injected() || inner.is_nan() || inner.is_infinite() {
+            Err(NotRealError.into())
         }
         else {
             Ok(())
@@ -378,3 +398,31 @@ where
         write!(formatter, ">")
     }
 }
+
+/// Constraints with a divergence that can be replaced without changing their set of members.
+///
+/// See [`Constrained::with_divergence`].
+///
+/// [`Constrained::with_divergence`]: crate::proxy::Constrained::with_divergence
+pub trait WithDivergence<D>: Constraint
+where
+    D: Divergence,
+{
+    type Constraint: Constraint<Divergence = D>;
+}
+
+impl<D1, D2> WithDivergence<D2> for IsExtendedReal<D1>
+where
+    D1: Divergence,
+    D2: Divergence,
+{
+    type Constraint = IsExtendedReal<D2>;
+}
+
+impl<D1, D2> WithDivergence<D2> for IsReal<D1>
+where
+    D1: Divergence,
+    D2: Divergence,
+{
+    type Constraint = IsReal<D2>;
+}
@@ -0,0 +1,406 @@
+//! Explicit exact and lossy numeric conversions.
+//!
+//! Conversions between floating-point and integer types with `as` silently round, truncate, and
+//! saturate. This module provides traits that make the behavior of a conversion explicit at the
+//! call site: [`ExactFrom`] fails if a conversion would change the value in any way and
+//! [`LossyFrom`] documents how values are rounded. These traits are implemented between
+//! primitive floating-point types, primitive integer types, and [`Constrained`] types.
+//!
+//! | Source           | Target           | Lossy Rounding                                     |
+//! |------------------|------------------|----------------------------------------------------|
+//! | float            | float            | To nearest, ties to even; overflows to $\pm\infin$ |
+//! | integer          | float            | To nearest, ties to even                           |
+//! | float            | integer          | Toward zero; saturates; `NaN` is zero              |
+//! | [`Constrained`]  | float or integer | As with the primitive type of the proxy            |
+//! | integer          | [`Constrained`]  | As with the primitive type of the proxy            |
+//! | [`Constrained`]  | [`Constrained`]  | As with the primitive type of the proxy            |
+//!
+//! Lossy conversions into [`Constrained`] types never diverge: if rounding overflows to an
+//! infinity that is not supported by the constraint, then the output saturates to the largest
+//! finite value with the same sign instead. Lossy conversions between [`Constrained`] types
+//! require the same constraint, so `NaN` is never encountered unless it is supported.
+//!
+//! [`Constrained`] types also provide software conversions between `f32` and `f64` and the bits of
+//! IEEE 754 binary16 (half precision) and binary128 (quadruple precision) interchange formats,
+//! such as `from_binary16_bits` and `to_binary16_bits`. These conversions round to nearest, ties
+//! to even, and check the constraints of the proxy.
+//!
+//! [`Constrained`] types can also be converted into and from signed Q`M`.`N` fixed-point integers
+//! with `to_fixed` and `from_fixed`. Conversions into fixed-point integers use an explicit
+//! [`Rounding`] and fail if the value is out of the range of the fixed-point format.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::convert::{ExactFrom, ExactInto, LossyInto};
+//! use decorum::R32;
+//!
+//! assert!(f32::exact_from(0.5f64).is_ok());
+//! assert!(f32::exact_from(0.1f64).is_err());
+//! assert!(i32::exact_from(2.5f64).is_err());
+//!
+//! let x: Result<R32, _> = 16_777_217u32.exact_into();
+//! assert!(x.is_err());
+//!
+//! let n: u8 = 300.7f64.lossy_into();
+//! assert_eq!(n, 255);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+
+mod fixed;
+mod interchange;
+
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+pub use crate::convert::fixed::{FixedError, Rounding};
+
+#[derive(Clone, Copy, Debug, Error)]
+#[error("{}", "conversion is not exact")]
+pub struct InexactError;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum ConversionError<E> {
+    #[error("{0}")]
+    Inexact(InexactError),
+    #[error("{0}")]
+    Constraint(E),
+}
+
+impl<E> From<InexactError> for ConversionError<E> {
+    fn from(error: InexactError) -> Self {
+        ConversionError::Inexact(error)
+    }
+}
+
+/// Conversion that fails if the value cannot be represented exactly.
+///
+/// Conversions between floating-point types consider all `NaN`s exact, though their payloads may
+/// not be preserved.
+pub trait ExactFrom<T>: Sized {
+    type Error;
+
+    fn exact_from(value: T) -> Result<Self, Self::Error>;
+}
+
+pub trait ExactInto<T> {
+    type Error;
+
+    fn exact_into(self) -> Result<T, Self::Error>;
+}
+
+impl<T, U> ExactInto<U> for T
+where
+    U: ExactFrom<T>,
+{
+    type Error = U::Error;
+
+    fn exact_into(self) -> Result<U, Self::Error> {
+        U::exact_from(self)
+    }
+}
+
+/// Conversion that rounds if the value cannot be represented exactly.
+///
+/// See the [module documentation][`convert`] for the rounding behavior of each conversion.
+///
+/// [`convert`]: crate::convert
+pub trait LossyFrom<T> {
+    fn lossy_from(value: T) -> Self;
+}
+
+pub trait LossyInto<T> {
+    fn lossy_into(self) -> T;
+}
+
+impl<T, U> LossyInto<U> for T
+where
+    U: LossyFrom<T>,
+{
+    fn lossy_into(self) -> U {
+        U::lossy_from(self)
+    }
+}
+
+impl<T, C, U> ExactFrom<U> for Constrained<T, C>
+where
+    T: ExactFrom<U, Error = InexactError> + Primitive,
+    C: Constraint,
+{
+    type Error = ConversionError<C::Error>;
+
+    fn exact_from(value: U) -> Result<Self, Self::Error> {
+        let inner = T::exact_from(value)?;
+        Constrained::try_new(inner).map_err(ConversionError::Constraint)
+    }
+}
+
+impl<T, U, C> LossyFrom<Constrained<U, C>> for Constrained<T, C>
+where
+    T: LossyFrom<U> + Primitive,
+    U: Primitive,
+    C: Constraint,
+{
+    fn lossy_from(value: Constrained<U, C>) -> Self {
+        saturate(T::lossy_from(value.into_inner()))
+    }
+}
+
+// The input is either the output of a lossy conversion from a value that satisfies the constraint
+// `C` or a finite value, so only infinities from overflow can fail the constraint.
+fn saturate<T, C>(inner: T) -> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    Constrained::unchecked(match C::check(inner) {
+        Ok(_) => inner,
+        _ => {
+            if inner.is_sign_negative() {
+                T::MIN_FINITE
+            }
+            else {
+                T::MAX_FINITE
+            }
+        }
+    })
+}
+
+// Gets `true` if an integer of the given magnitude can be represented exactly by a floating-point
+// type with the given number of significand digits. This assumes that the exponent range of the
+// floating-point type is sufficient, which is true of all primitive integers and `f32` or `f64`.
+fn is_representable(magnitude: u128, digits: u32) -> bool {
+    magnitude == 0
+        || (u128::BITS - magnitude.leading_zeros() - magnitude.trailing_zeros()) <= digits
+}
+
+macro_rules! impl_conversions_for_primitive_float {
+    (float => $f:ident) => {
+        impl_conversions_for_primitive_float!(float => $f, from => f32);
+        impl_conversions_for_primitive_float!(float => $f, from => f64);
+        impl_conversions_for_primitive_float!(float => $f, signed => i8);
+        impl_conversions_for_primitive_float!(float => $f, signed => i16);
+        impl_conversions_for_primitive_float!(float => $f, signed => i32);
+        impl_conversions_for_primitive_float!(float => $f, signed => i64);
+        impl_conversions_for_primitive_float!(float => $f, signed => i128);
+        impl_conversions_for_primitive_float!(float => $f, signed => isize);
+        impl_conversions_for_primitive_float!(float => $f, unsigned => u8);
+        impl_conversions_for_primitive_float!(float => $f, unsigned => u16);
+        impl_conversions_for_primitive_float!(float => $f, unsigned => u32);
+        impl_conversions_for_primitive_float!(float => $f, unsigned => u64);
+        impl_conversions_for_primitive_float!(float => $f, unsigned => u128);
+        impl_conversions_for_primitive_float!(float => $f, unsigned => usize);
+    };
+    (float => $f:ident, from => $g:ident) => {
+        impl ExactFrom<$g> for $f {
+            type Error = InexactError;
+
+            #[allow(clippy::float_cmp)]
+            fn exact_from(value: $g) -> Result<Self, Self::Error> {
+                let output = value as $f;
+                if value.is_nan() || (output as $g) == value {
+                    Ok(output)
+                }
+                else {
+                    Err(InexactError)
+                }
+            }
+        }
+
+        impl<C> ExactFrom<Constrained<$g, C>> for $f {
+            type Error = InexactError;
+
+            fn exact_from(value: Constrained<$g, C>) -> Result<Self, Self::Error> {
+                $f::exact_from(value.into_inner())
+            }
+        }
+
+        impl LossyFrom<$g> for $f {
+            fn lossy_from(value: $g) -> Self {
+                value as $f
+            }
+        }
+
+        impl<C> LossyFrom<Constrained<$g, C>> for $f {
+            fn lossy_from(value: Constrained<$g, C>) -> Self {
+                value.into_inner() as $f
+            }
+        }
+    };
+    (float => $f:ident, signed => $i:ident) => {
+        impl_conversions_for_primitive_float!(
+            float => $f,
+            integer => $i,
+            magnitude => |value: $i| value.unsigned_abs() as u128,
+            min => $i::MIN as $f,
+            max => -($i::MIN as $f),
+        );
+    };
+    (float => $f:ident, unsigned => $u:ident) => {
+        impl_conversions_for_primitive_float!(
+            float => $f,
+            integer => $u,
+            magnitude => |value: $u| value as u128,
+            min => -1.0,
+            max => 2.0 * ((1 as $u) << ($u::BITS - 1)) as $f,
+        );
+    };
+    // The bounds `min` and `max` are exclusive, except that `min` is inclusive for signed types.
+    (
+        float => $f:ident,
+        integer => $i:ident,
+        magnitude => $magnitude:expr,
+        min => $min:expr,
+        max => $max:expr $(,)?
+    ) => {
+        impl ExactFrom<$i> for $f {
+            type Error = InexactError;
+
+            fn exact_from(value: $i) -> Result<Self, Self::Error> {
+                if is_representable(($magnitude)(value), $f::MANTISSA_DIGITS) {
+                    Ok(value as $f)
+                }
+                else {
+                    Err(InexactError)
+                }
+            }
+        }
+
+        impl ExactFrom<$f> for $i {
+            type Error = InexactError;
+
+            #[allow(clippy::float_cmp)]
+            fn exact_from(value: $f) -> Result<Self, Self::Error> {
+                let (min, max): ($f, $f) = ($min, $max);
+                // Unsigned minimums are exclusive, but `-1.0` is never an exact unsigned integer.
+                if value >= min && value < max && ((value as $i) as $f) == value {
+                    Ok(value as $i)
+                }
+                else {
+                    Err(InexactError)
+                }
+            }
+        }
+
+        impl<C> ExactFrom<Constrained<$f, C>> for $i {
+            type Error = InexactError;
+
+            fn exact_from(value: Constrained<$f, C>) -> Result<Self, Self::Error> {
+                $i::exact_from(value.into_inner())
+            }
+        }
+
+        impl LossyFrom<$i> for $f {
+            fn lossy_from(value: $i) -> Self {
+                value as $f
+            }
+        }
+
+        impl LossyFrom<$f> for $i {
+            fn lossy_from(value: $f) -> Self {
+                value as $i
+            }
+        }
+
+        impl<C> LossyFrom<Constrained<$f, C>> for $i {
+            fn lossy_from(value: Constrained<$f, C>) -> Self {
+                value.into_inner() as $i
+            }
+        }
+
+        impl<C> LossyFrom<$i> for Constrained<$f, C>
+        where
+            C: Constraint,
+        {
+            fn lossy_from(value: $i) -> Self {
+                saturate(value as $f)
+            }
+        }
+    };
+}
+impl_conversions_for_primitive_float!(float => f32);
+impl_conversions_for_primitive_float!(float => f64);
+
+#[cfg(test)]
+mod tests {
+    use crate::convert::{ConversionError, ExactFrom, ExactInto, LossyFrom, LossyInto};
+    use crate::{ExtendedReal, Real, Total, E32, R32, R64};
+
+    #[test]
+    fn exact_float() {
+        assert_eq!(f32::exact_from(0.5f64).unwrap(), 0.5);
+        assert!(f32::exact_from(0.1f64).is_err());
+        assert!(f32::exact_from(f64::MAX).is_err());
+        assert!(f32::exact_from(f64::NAN).unwrap().is_nan());
+        assert_eq!(f32::exact_from(f64::INFINITY).unwrap(), f32::INFINITY);
+        assert_eq!(f64::exact_from(0.1f32).unwrap(), f64::from(0.1f32));
+    }
+
+    #[test]
+    fn exact_integer() {
+        assert_eq!(f32::exact_from(16_777_216u32).unwrap(), 16_777_216.0);
+        assert!(f32::exact_from(16_777_217u32).is_err());
+        assert_eq!(
+            f64::exact_from(i64::MIN).unwrap(),
+            -9_223_372_036_854_775_808.0
+        );
+        assert!(f64::exact_from(i64::MAX).is_err());
+        assert!(f32::exact_from(u128::MAX).is_err());
+
+        assert_eq!(i8::exact_from(-128.0f32).unwrap(), i8::MIN);
+        assert!(i8::exact_from(128.0f32).is_err());
+        assert_eq!(u8::exact_from(255.0f64).unwrap(), u8::MAX);
+        assert!(u8::exact_from(256.0f64).is_err());
+        assert!(u8::exact_from(-0.5f64).is_err());
+        assert_eq!(u8::exact_from(-0.0f64).unwrap(), 0);
+        assert!(i64::exact_from(9_223_372_036_854_775_808.0f64).is_err());
+        assert!(u128::exact_from(f32::MAX).is_ok());
+        assert!(i32::exact_from(2.5f64).is_err());
+        assert!(i32::exact_from(f64::NAN).is_err());
+        assert!(i32::exact_from(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn exact_proxy() {
+        let x: R32 = <R32>::exact_from(0.5f64).unwrap();
+        assert_eq!(x, 0.5);
+        assert!(matches!(
+            <R32>::exact_from(0.1f64),
+            Err(ConversionError::Inexact(_))
+        ));
+        assert!(matches!(
+            <R32>::exact_from(f64::INFINITY),
+            Err(ConversionError::Constraint(_))
+        ));
+        let x: Result<E32, _> = <R64>::assert(1.0).exact_into();
+        assert_eq!(x.unwrap(), 1.0);
+        assert_eq!(i32::exact_from(<R64>::assert(3.0)).unwrap(), 3);
+        let x: Total<f64> = 7u64.exact_into().unwrap();
+        assert_eq!(x, 7.0);
+    }
+
+    #[test]
+    fn lossy() {
+        assert_eq!(f32::lossy_from(0.1f64), 0.1f32);
+        assert_eq!(f32::lossy_from(16_777_217u32), 16_777_216.0);
+        assert_eq!(i32::lossy_from(-2.9f64), -2);
+        assert_eq!(u8::lossy_from(300.0f32), u8::MAX);
+        assert_eq!(u8::lossy_from(-1.0f32), 0);
+        assert_eq!(i32::lossy_from(f64::NAN), 0);
+
+        let x: R32 = Real::<f64>::assert(f64::MAX).lossy_into();
+        assert_eq!(x, f32::MAX);
+        let x: R32 = Real::<f64>::assert(-1e300).lossy_into();
+        assert_eq!(x, f32::MIN);
+        let x: ExtendedReal<f32> = ExtendedReal::<f64>::assert(f64::MAX).lossy_into();
+        assert_eq!(x, f32::INFINITY);
+        let x: R32 = u128::MAX.lossy_into();
+        assert_eq!(x, f32::MAX);
+        let x: Total<f32> = Total::<f64>::from(f64::NAN).lossy_into();
+        assert!(x.into_inner().is_nan());
+    }
+}
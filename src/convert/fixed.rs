@@ -0,0 +1,225 @@
+use thiserror::Error;
+
+use crate::constraint::{Constraint, NotRealError};
+use crate::convert::LossyFrom;
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum FixedError {
+    #[error("{}", "value is out of the range of the fixed-point format")]
+    Range,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for FixedError {
+    fn from(error: NotRealError) -> Self {
+        FixedError::NotReal(error)
+    }
+}
+
+/// Rounding of conversions into fixed-point formats.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Rounding {
+    /// Round to the nearest representable value, with ties to even.
+    ToNearest,
+    /// Round toward negative infinity.
+    Downward,
+    /// Round toward positive infinity.
+    Upward,
+    /// Round toward zero (truncation).
+    TowardZero,
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Converts the proxy into a signed Q`M`.`N` fixed-point integer.
+    ///
+    /// A Q`M`.`N` fixed-point integer has a sign bit, `M` integer bits, and `N` fractional bits and
+    /// represents the value of the integer divided by $2^N$. The output is rounded to the nearest
+    /// multiple of $2^{-N}$ per `rounding`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FixedError::NotReal`] if the proxy is not a real number and [`FixedError::Range`]
+    /// if the rounded value cannot be represented by the fixed-point format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `M + N` is greater than 63.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::convert::Rounding;
+    /// use decorum::R64;
+    ///
+    /// let x: R64 = R64::assert(1.5);
+    /// assert_eq!(
+    ///     x.to_fixed::<16, 16>(Rounding::ToNearest).unwrap(),
+    ///     0x0001_8000
+    /// );
+    /// assert!(<R64>::assert(65536.0)
+    ///     .to_fixed::<16, 16>(Rounding::ToNearest)
+    ///     .is_err());
+    /// ```
+    pub fn to_fixed<const M: u32, const N: u32>(
+        self,
+        rounding: Rounding,
+    ) -> Result<i64, FixedError> {
+        assert!(M + N <= 63, "fixed-point format exceeds 64 bits");
+        let inner = self.into_inner();
+        if !inner.is_finite() {
+            return Err(NotRealError.into());
+        }
+        let (mantissa, exponent, sign) = inner.integer_decode();
+        let negative = sign < 0;
+        // The value is `mantissa * 2^shift` in units of `2^-N`.
+        let shift = i32::from(exponent) + N as i32;
+        let magnitude = if shift >= 0 {
+            let mantissa = u128::from(mantissa);
+            if mantissa != 0 && (shift as u32) >= mantissa.leading_zeros() {
+                return Err(FixedError::Range);
+            }
+            mantissa << shift
+        }
+        else {
+            let shift = shift.unsigned_abs();
+            let (quotient, remainder, half) = if shift >= 64 {
+                // The value is less than one half in units of `2^-N` (`mantissa` has less than 64
+                // significant bits).
+                (0, u128::from(mantissa), 1u128 << 64)
+            }
+            else {
+                (
+                    u128::from(mantissa >> shift),
+                    u128::from(mantissa & ((1 << shift) - 1)),
+                    1u128 << (shift - 1),
+                )
+            };
+            let increment = remainder != 0
+                && match rounding {
+                    Rounding::ToNearest => {
+                        remainder > half || (remainder == half && quotient & 1 == 1)
+                    }
+                    Rounding::Downward => negative,
+                    Rounding::Upward => !negative,
+                    Rounding::TowardZero => false,
+                };
+            quotient + u128::from(increment)
+        };
+        let limit = 1u128 << (M + N);
+        if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+            Err(FixedError::Range)
+        }
+        else {
+            let magnitude = magnitude as i64;
+            Ok(if negative {
+                magnitude.wrapping_neg()
+            }
+            else {
+                magnitude
+            })
+        }
+    }
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: LossyFrom<i64> + LossyFrom<u64> + Primitive,
+    C: Constraint,
+{
+    /// Converts a signed Q`M`.`N` fixed-point integer into a proxy.
+    ///
+    /// See [`Constrained::to_fixed`]. If the fixed-point value cannot be represented exactly, then
+    /// it is rounded to the nearest representable value with ties to even.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FixedError::Range`] if `fixed` is not in the range of the fixed-point format.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `M + N` is greater than 63.
+    pub fn from_fixed<const M: u32, const N: u32>(fixed: i64) -> Result<Self, FixedError> {
+        assert!(M + N <= 63, "fixed-point format exceeds 64 bits");
+        let limit = 1i128 << (M + N);
+        if !(-limit..limit).contains(&i128::from(fixed)) {
+            return Err(FixedError::Range);
+        }
+        // Scaling by a power of two is exact, so the output is rounded only once.
+        let inner = T::lossy_from(fixed) / T::lossy_from(1u64 << N);
+        // Fixed-point values are real numbers, which satisfy any constraint.
+        Ok(Constrained::unchecked(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::convert::{FixedError, Rounding};
+    use crate::{Total, R32, R64};
+
+    #[test]
+    fn to_fixed_rounding() {
+        let fixed = |x: f64, rounding| <R64>::assert(x).to_fixed::<7, 8>(rounding).unwrap();
+        // `1/512` is half of a unit in the last place.
+        let x = 1.0 + (1.0 / 512.0);
+        assert_eq!(fixed(x, Rounding::ToNearest), 256);
+        assert_eq!(fixed(x + (1.0 / 256.0), Rounding::ToNearest), 258);
+        assert_eq!(fixed(x, Rounding::Upward), 257);
+        assert_eq!(fixed(x, Rounding::Downward), 256);
+        assert_eq!(fixed(x, Rounding::TowardZero), 256);
+        assert_eq!(fixed(-x, Rounding::Upward), -256);
+        assert_eq!(fixed(-x, Rounding::Downward), -257);
+        assert_eq!(fixed(-x, Rounding::TowardZero), -256);
+        assert_eq!(fixed(1.0e-30, Rounding::Upward), 1);
+        assert_eq!(fixed(1.0e-30, Rounding::ToNearest), 0);
+        assert_eq!(fixed(-0.0, Rounding::ToNearest), 0);
+    }
+
+    #[test]
+    fn to_fixed_range() {
+        let fixed = |x: f64| <R64>::assert(x).to_fixed::<7, 8>(Rounding::ToNearest);
+        assert_eq!(fixed(-128.0).unwrap(), -(1 << 15));
+        assert_eq!(fixed(127.99609375).unwrap(), (1 << 15) - 1);
+        assert!(matches!(fixed(128.0), Err(FixedError::Range)));
+        assert!(matches!(fixed(-128.00390625), Err(FixedError::Range)));
+        assert!(matches!(fixed(1.0e300), Err(FixedError::Range)));
+        assert!(matches!(
+            Total::from(f64::NAN).to_fixed::<7, 8>(Rounding::ToNearest),
+            Err(FixedError::NotReal(_))
+        ));
+        assert_eq!(
+            <R64>::assert(-1.0)
+                .to_fixed::<0, 63>(Rounding::ToNearest)
+                .unwrap(),
+            i64::MIN
+        );
+    }
+
+    #[test]
+    fn from_fixed() {
+        assert_eq!(<R64>::from_fixed::<16, 16>(0x0001_8000).unwrap(), 1.5);
+        assert_eq!(<R64>::from_fixed::<16, 16>(-0x0001_8000).unwrap(), -1.5);
+        assert_eq!(<R64>::from_fixed::<0, 63>(i64::MIN).unwrap(), -1.0);
+        assert!(matches!(
+            <R64>::from_fixed::<7, 8>(1 << 15),
+            Err(FixedError::Range)
+        ));
+        // Rounds to nearest.
+        assert_eq!(
+            <R32>::from_fixed::<31, 0>((1 << 24) + 1).unwrap(),
+            16_777_216.0
+        );
+        let x = <R64>::assert(-3.25);
+        assert_eq!(
+            <R64>::from_fixed::<16, 16>(x.to_fixed::<16, 16>(Rounding::ToNearest).unwrap())
+                .unwrap(),
+            x
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use core::mem;
+
+use crate::constraint::Constraint;
+use crate::proxy::{Constrained, OutputFor};
+use crate::with_primitives;
+
+// An IEEE 754 binary interchange format.
+#[derive(Clone, Copy)]
+struct Format {
+    exponent: u32,
+    fraction: u32,
+}
+
+impl Format {
+    const BINARY16: Self = Format::new(5, 10);
+    const BINARY32: Self = Format::new(8, 23);
+    const BINARY64: Self = Format::new(11, 52);
+    const BINARY128: Self = Format::new(15, 112);
+
+    const fn new(exponent: u32, fraction: u32) -> Self {
+        Format { exponent, fraction }
+    }
+
+    const fn max_biased_exponent(&self) -> u32 {
+        (1 << self.exponent) - 1
+    }
+
+    const fn bias(&self) -> i32 {
+        (1 << (self.exponent - 1)) - 1
+    }
+
+    const fn precision(&self) -> u32 {
+        self.fraction + 1
+    }
+
+    fn encode(&self, sign: bool, biased: u32, fraction: u128) -> u128 {
+        (u128::from(sign) << (self.exponent + self.fraction))
+            | (u128::from(biased) << self.fraction)
+            | fraction
+    }
+
+    fn infinity(&self, sign: bool) -> u128 {
+        self.encode(sign, self.max_biased_exponent(), 0)
+    }
+
+    fn nan(&self, sign: bool) -> u128 {
+        self.encode(sign, self.max_biased_exponent(), 1 << (self.fraction - 1))
+    }
+
+    // Encodes `(-1)^sign * significand * 2^exponent` with rounding to nearest, ties to even.
+    fn round(&self, sign: bool, significand: u128, exponent: i32) -> u128 {
+        if significand == 0 {
+            return self.encode(sign, 0, 0);
+        }
+        let precision = self.precision() as i32;
+        let emin = 1 - self.bias();
+        // The exponent of the leading bit of the significand.
+        let leading = exponent + (127 - significand.leading_zeros() as i32);
+        // The exponent of a unit in the last place of the output.
+        let ulp = leading.max(emin) - (precision - 1);
+        let mut significand = if ulp >= exponent {
+            shift_right_even(significand, (ulp - exponent) as u32)
+        }
+        else {
+            significand << (exponent - ulp)
+        };
+        let mut ulp = ulp;
+        if significand >> precision != 0 {
+            // Rounding carried into the next binade.
+            significand >>= 1;
+            ulp += 1;
+        }
+        if significand >> (precision - 1) == 0 {
+            // Subnormal or zero.
+            self.encode(sign, 0, significand)
+        }
+        else {
+            let biased = ulp + (precision - 1) + self.bias();
+            if biased >= self.max_biased_exponent() as i32 {
+                self.infinity(sign)
+            }
+            else {
+                let fraction = significand & ((1 << self.fraction) - 1);
+                self.encode(sign, biased as u32, fraction)
+            }
+        }
+    }
+
+    // Converts the bits of a value in this format into the bits of the nearest value in the
+    // target format. `NaN`s are converted into quiet `NaN`s with the same sign.
+    fn convert(&self, bits: u128, target: &Format) -> u128 {
+        let sign = (bits >> (self.exponent + self.fraction)) & 1 == 1;
+        let biased = ((bits >> self.fraction) as u32) & self.max_biased_exponent();
+        let fraction = bits & ((1 << self.fraction) - 1);
+        let shift = self.bias() + self.fraction as i32;
+        if biased == self.max_biased_exponent() {
+            if fraction == 0 {
+                target.infinity(sign)
+            }
+            else {
+                target.nan(sign)
+            }
+        }
+        else if biased == 0 {
+            target.round(sign, fraction, 1 - shift)
+        }
+        else {
+            target.round(sign, fraction | (1 << self.fraction), biased as i32 - shift)
+        }
+    }
+}
+
+// Divides by `2^shift` with rounding to nearest, ties to even.
+fn shift_right_even(n: u128, shift: u32) -> u128 {
+    match shift {
+        0 => n,
+        1..=127 => {
+            let quotient = n >> shift;
+            let remainder = n & ((1 << shift) - 1);
+            let half = 1 << (shift - 1);
+            if remainder > half || (remainder == half && quotient & 1 == 1) {
+                quotient + 1
+            }
+            else {
+                quotient
+            }
+        }
+        128 => u128::from(n > 1 << 127),
+        _ => 0,
+    }
+}
+
+macro_rules! impl_interchange_for_proxy {
+    () => {
+        with_primitives!(impl_interchange_for_proxy);
+    };
+    (primitive => f32) => {
+        impl_interchange_for_proxy!(primitive => f32, bits => u32, format => BINARY32);
+    };
+    (primitive => f64) => {
+        impl_interchange_for_proxy!(primitive => f64, bits => u64, format => BINARY64);
+    };
+    (primitive => $t:ident, bits => $u:ident, format => $format:ident) => {
+        impl<C> Constrained<$t, C>
+        where
+            C: Constraint,
+        {
+            /// Constructs a proxy from the bits of its IEEE 754 binary interchange encoding.
+            ///
+            /// The decoded value is checked against the constraints of the proxy and the output
+            /// type is determined by its [divergence][`divergence`].
+            ///
+            /// [`divergence`]: crate::divergence
+            pub fn from_bits(bits: $u) -> OutputFor<Self> {
+                Self::new($t::from_bits(bits))
+            }
+
+            /// Constructs a proxy from the bits of an IEEE 754 binary16 (half precision) value.
+            ///
+            /// This conversion is exact: all binary16 values are representable. The decoded value
+            /// is checked against the constraints of the proxy and the output type is determined
+            /// by its [divergence][`divergence`].
+            ///
+            /// [`divergence`]: crate::divergence
+            pub fn from_binary16_bits(bits: u16) -> OutputFor<Self> {
+                Self::new($t::from_bits(
+                    Format::BINARY16.convert(u128::from(bits), &Format::$format) as $u,
+                ))
+            }
+
+            /// Constructs a proxy from the bits of an IEEE 754 binary128 (quadruple precision)
+            /// value.
+            ///
+            /// The value is rounded to nearest, ties to even, and may overflow to an infinity. The
+            /// rounded value is checked against the constraints of the proxy and the output type
+            /// is determined by its [divergence][`divergence`].
+            ///
+            /// [`divergence`]: crate::divergence
+            pub fn from_binary128_bits(bits: u128) -> OutputFor<Self> {
+                Self::new($t::from_bits(
+                    Format::BINARY128.convert(bits, &Format::$format) as $u,
+                ))
+            }
+
+            /// Gets the bits of the IEEE 754 binary interchange encoding of the proxy.
+            pub fn to_bits(self) -> $u {
+                self.into_inner().to_bits()
+            }
+
+            /// Gets the bits of the nearest IEEE 754 binary16 (half precision) value.
+            ///
+            /// The value is rounded to nearest, ties to even. `NaN`s are encoded as quiet `NaN`s.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the rounded value does not satisfy the constraints of the
+            /// proxy, such as when a real value overflows to an infinity.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use decorum::R32;
+            ///
+            /// let x: R32 = R32::assert(1.0 / 3.0);
+            /// assert_eq!(x.to_binary16_bits().unwrap(), 0x3555);
+            /// assert!(<R32>::assert(1.0e6).to_binary16_bits().is_err());
+            /// ```
+            pub fn to_binary16_bits(self) -> Result<u16, C::Error> {
+                let bits =
+                    Format::$format.convert(u128::from(self.to_bits()), &Format::BINARY16) as u16;
+                // Binary16 values are always representable, so this checks the rounded value.
+                C::check($t::from_bits(
+                    Format::BINARY16.convert(u128::from(bits), &Format::$format) as $u,
+                ))
+                .map(|_| bits)
+            }
+
+            /// Gets the bits of the IEEE 754 binary128 (quadruple precision) encoding of the
+            /// proxy.
+            ///
+            /// This conversion is exact.
+            pub fn to_binary128_bits(self) -> u128 {
+                Format::$format.convert(u128::from(self.to_bits()), &Format::BINARY128)
+            }
+        }
+
+        const _: () = assert!(mem::size_of::<$u>() == mem::size_of::<$t>());
+    };
+}
+impl_interchange_for_proxy!();
+
+#[cfg(test)]
+mod tests {
+    use crate::divergence::{AsResult, OrError};
+    use crate::{Real, Total, E32, R64};
+
+    #[test]
+    fn binary16() {
+        let decode = |bits| Total::<f32>::from_binary16_bits(bits).into_inner();
+        assert_eq!(decode(0x3c00), 1.0);
+        assert_eq!(decode(0xc000), -2.0);
+        assert_eq!(decode(0x7bff), 65504.0);
+        assert_eq!(decode(0x0001), 2.0f32.powi(-24));
+        assert_eq!(decode(0x7c00), f32::INFINITY);
+        assert!(decode(0x7e01).is_nan());
+        assert!(decode(0x8000).is_sign_negative());
+
+        let encode = |x: f64| Total::<f64>::from(x).to_binary16_bits().unwrap();
+        assert_eq!(encode(1.0), 0x3c00);
+        assert_eq!(encode(65504.0), 0x7bff);
+        // Ties to even.
+        assert_eq!(encode(65520.0), 0x7c00);
+        assert_eq!(encode(1.0 + 2.0f64.powi(-11)), 0x3c00);
+        assert_eq!(encode(1.0 + 3.0 * 2.0f64.powi(-11)), 0x3c02);
+        // Subnormal and underflow.
+        assert_eq!(encode(2.0f64.powi(-24)), 0x0001);
+        assert_eq!(encode(2.0f64.powi(-25)), 0x0000);
+        assert_eq!(encode(3.0 * 2.0f64.powi(-26)), 0x0001);
+        assert_eq!(encode(2.0f64.powi(-14) - 2.0f64.powi(-30)), 0x0400);
+        assert_eq!(encode(f64::NAN) & 0x7e00, 0x7e00);
+
+        assert!(<R64>::assert(1.0e5).to_binary16_bits().is_err());
+        assert!(<E32>::assert(1.0e5).to_binary16_bits().is_ok());
+        assert!(Real::<f32, OrError<AsResult>>::from_binary16_bits(0x7c00).is_err());
+    }
+
+    #[test]
+    fn binary128() {
+        let x = Total::<f64>::from(core::f64::consts::PI);
+        assert_eq!(Total::<f64>::from_binary128_bits(x.to_binary128_bits()), x);
+        assert_eq!(
+            Total::<f64>::from(1.0).to_binary128_bits(),
+            0x3fff_0000_0000_0000_0000_0000_0000_0000
+        );
+        assert_eq!(
+            Total::<f32>::from(-f32::MIN_POSITIVE / 2.0).to_binary128_bits(),
+            0xbf80_0000_0000_0000_0000_0000_0000_0000
+        );
+        // Rounds directly to `f32` rather than through `f64`.
+        let bits = 0x3fff_0000_0100_0000_0000_0000_0000_0001;
+        assert_eq!(
+            Total::<f32>::from_binary128_bits(bits).into_inner(),
+            1.0 + f32::EPSILON
+        );
+        assert!(Total::<f64>::from_binary128_bits(0x7ffe << 112)
+            .into_inner()
+            .is_infinite());
+    }
+}
@@ -18,6 +18,9 @@
 //! an error value, it can be used with output types that cannot respresent errors. This differs
 //! from [`OrError`], which requires an error representation.
 //!
+//! When the `trace` feature is enabled, the `OrTrace` divergence behaves like [`OrError`], but its
+//! errors also record the operation from which they originated. See the [`trace`] module.
+//!
 //! # Output Types
 //!
 //! Output types are determined by an [output kind][`Continue`]. An output kind is type constructor
@@ -81,14 +84,19 @@
 //! [`Constrained`]: crate::proxy::Constrained
 //! [`constraint`]: crate::constraint
 //! [`Constraint::Error`]: crate::constraint::Constraint::Error
+//! [`trace`]: crate::trace
 
 use core::convert::Infallible;
+#[cfg(feature = "trace")]
+use core::fmt::Display;
 use core::fmt::{self, Debug, Formatter};
 use core::marker::PhantomData;
 
 use crate::constraint::ExpectConstrained as _;
 use crate::expression::{Defined, Expression, Undefined};
 use crate::sealed::{Sealed, StaticDebug};
+#[cfg(feature = "trace")]
+use crate::trace::{Trace, Traced};
 
 /// An output kind that can continue with an output.
 pub trait Continue: Sealed + StaticDebug {
@@ -211,10 +219,28 @@ impl StaticDebug for AsSelf {
 /// [`divergence`]: crate::divergence
 pub trait Divergence: Sealed + StaticDebug {
     type Continue: Continue;
+    /// The error type of constraints with this divergence, where `E` is the error type of the
+    /// constraint itself.
+    ///
+    /// This type is only available with the `trace` feature. See the [`trace`] module.
+    ///
+    /// [`trace`]: crate::trace
+    #[cfg(feature = "trace")]
+    type Error<E>: Debug + Display + From<E> + Trace
+    where
+        E: Debug + Display + Trace;
 
     fn diverge<T, E>(result: Result<T, E>) -> <Self::Continue as Continue>::As<T, E>
     where
         E: Debug;
+
+    // Returns `true` if constraint checks must fail regardless of their inputs. See the `inject`
+    // module.
+    #[doc(hidden)]
+    #[inline(always)]
+    fn is_injected() -> bool {
+        false
+    }
 }
 
 pub type ContinueFor<D> = <D as Divergence>::Continue;
@@ -237,6 +263,11 @@ where
     K: Continue,
 {
     type Continue = K;
+    #[cfg(feature = "trace")]
+    type Error<E>
+        = E
+    where
+        E: Debug + Display + Trace;
 
     fn diverge<T, E>(result: Result<T, E>) -> K::As<T, E>
     where
@@ -272,6 +303,11 @@ where
     K: Break,
 {
     type Continue = K;
+    #[cfg(feature = "trace")]
+    type Error<E>
+        = E
+    where
+        E: Debug + Display + Trace;
 
     fn diverge<T, E>(result: Result<T, E>) -> K::As<T, E>
     where
@@ -296,3 +332,47 @@ where
         write!(formatter, ">")
     }
 }
+
+/// Divergence that breaks on errors like [`OrError`], but with [`Traced`] errors that record the
+/// operation from which an error originated.
+///
+/// By default, `OrTrace` uses the [`AsExpression`] kind and therefore has an [`Expression`] output
+/// type. See the [`trace`] module.
+///
+/// [`trace`]: crate::trace
+#[cfg(feature = "trace")]
+pub struct OrTrace<K = AsExpression>(PhantomData<fn() -> K>, Infallible);
+
+#[cfg(feature = "trace")]
+impl<K> Divergence for OrTrace<K>
+where
+    K: Break,
+{
+    type Continue = K;
+    type Error<E>
+        = Traced<E>
+    where
+        E: Debug + Display + Trace;
+
+    fn diverge<T, E>(result: Result<T, E>) -> K::As<T, E>
+    where
+        E: Debug,
+    {
+        OrError::<K>::diverge(result)
+    }
+}
+
+#[cfg(feature = "trace")]
+impl<K> Sealed for OrTrace<K> {}
+
+#[cfg(feature = "trace")]
+impl<K> StaticDebug for OrTrace<K>
+where
+    K: StaticDebug,
+{
+    fn fmt(formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "OrTrace<")?;
+        K::fmt(formatter)?;
+        write!(formatter, ">")
+    }
+}
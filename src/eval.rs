@@ -0,0 +1,479 @@
+//! Evaluation of arithmetic expressions in text.
+//!
+//! This module evaluates arithmetic expressions like `2 * (x + 1)` with [`Constrained`] types, so
+//! that formulas read from configuration or provided by scripts have the same guarantees as
+//! arithmetic written in Rust. Every intermediate value is checked against the constraints of the
+//! proxy, and the first violation is returned as an `Undefined` [`Expression`] instead of
+//! diverging.
+//!
+//! The grammar supports the following:
+//!
+//! - Numbers in decimal and scientific notation (e.g., `1.5` and `1e-3`).
+//! - Variables, which are looked up in a [`Variables`] implementation.
+//! - The constants `pi` and `e` when they are not shadowed by a variable.
+//! - The binary operators `+`, `-`, `*`, `/`, `%`, and `^` (exponentiation) with the usual
+//!   precedence. Exponentiation is right associative and binds more tightly than negation.
+//! - Negation, parentheses, and function calls like `sqrt(x)` and `atan2(y, x)`.
+//!
+//! Negations, parentheses, function calls, and exponents may be nested at most 256 deep. Deeper
+//! nesting is an error rather than overflowing the stack.
+//!
+//! The supported unary functions are `abs`, `acos`, `asin`, `atan`, `cbrt`, `ceil`, `cos`,
+//! `cosh`, `exp`, `floor`, `ln`, `log10`, `log2`, `round`, `sin`, `sinh`, `sqrt`, `tan`, `tanh`,
+//! and `trunc`. The supported binary functions are `atan2`, `hypot`, `log`, `max`, `min`, and
+//! `pow`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::eval;
+//! use decorum::R64;
+//!
+//! let x = eval::eval::<R64>("2 * (x + 1)", &[("x", <R64>::assert(3.0))]);
+//! assert_eq!(x.unwrap(), 8.0);
+//!
+//! let x = eval::eval::<R64>("1 / (x - 3)", &[("x", <R64>::assert(3.0))]);
+//! assert!(x.is_undefined());
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Expression`]: crate::expression::Expression
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use core::str::FromStr;
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::expression::Expression;
+use crate::proxy::{Constrained, ConstrainedProxy};
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum EvalError<E> {
+    #[error("unexpected input at {0}")]
+    Syntax(usize),
+    #[error("expression nested too deeply at {0}")]
+    Depth(usize),
+    #[error("unknown variable at {0}")]
+    UnknownVariable(usize),
+    #[error("unknown function at {0}")]
+    UnknownFunction(usize),
+    #[error("{0}")]
+    Constraint(E),
+}
+
+/// Named values that can be referenced by variables in evaluated text.
+pub trait Variables<P> {
+    fn get(&self, name: &str) -> Option<P>;
+}
+
+impl<P> Variables<P> for () {
+    fn get(&self, _: &str) -> Option<P> {
+        None
+    }
+}
+
+impl<P> Variables<P> for [(&str, P)]
+where
+    P: Copy,
+{
+    fn get(&self, name: &str) -> Option<P> {
+        self.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl<P, const N: usize> Variables<P> for [(&str, P); N]
+where
+    P: Copy,
+{
+    fn get(&self, name: &str) -> Option<P> {
+        Variables::get(self.as_slice(), name)
+    }
+}
+
+impl<K, P> Variables<P> for BTreeMap<K, P>
+where
+    K: Borrow<str> + Ord,
+    P: Copy,
+{
+    fn get(&self, name: &str) -> Option<P> {
+        BTreeMap::get(self, name).copied()
+    }
+}
+
+impl<K, P, S> Variables<P> for HashMap<K, P, S>
+where
+    K: Borrow<str> + Eq + Hash,
+    P: Copy,
+    S: BuildHasher,
+{
+    fn get(&self, name: &str) -> Option<P> {
+        HashMap::get(self, name).copied()
+    }
+}
+
+/// Evaluates an arithmetic expression in text.
+///
+/// See the [module documentation][`eval`] for the supported grammar.
+///
+/// # Errors
+///
+/// Returns `Undefined` with an [`EvalError`] if the text cannot be parsed, refers to an unknown
+/// variable or function, or if any intermediate value does not satisfy the constraints of the
+/// proxy.
+///
+/// [`eval`]: crate::eval
+pub fn eval<P>(
+    text: &str,
+    variables: &(impl Variables<P> + ?Sized),
+) -> Expression<P, EvalError<<P::Constraint as Constraint>::Error>>
+where
+    P: ConstrainedProxy + Into<Constrained<P::Primitive, P::Constraint>>,
+    P::Primitive: FromStr,
+    Constrained<P::Primitive, P::Constraint>: Into<P>,
+{
+    let lookup = |name: &str| variables.get(name).map(Into::into);
+    let mut parser = Parser {
+        text,
+        position: 0,
+        depth: 0,
+        variables: &lookup,
+    };
+    parser.parse().map(|value| value.into()).into()
+}
+
+// The maximum nesting depth of sub-expressions, which bounds the recursion of the parser.
+const MAX_DEPTH: usize = 256;
+
+type Lookup<'v, T, C> = dyn 'v + Fn(&str) -> Option<Constrained<T, C>>;
+
+struct Parser<'t, 'v, T, C> {
+    text: &'t str,
+    position: usize,
+    depth: usize,
+    variables: &'v Lookup<'v, T, C>,
+}
+
+type ParseResult<T, C> = Result<Constrained<T, C>, EvalError<<C as Constraint>::Error>>;
+
+impl<'t, 'v, T, C> Parser<'t, 'v, T, C>
+where
+    T: FromStr + Primitive,
+    C: Constraint,
+{
+    fn parse(&mut self) -> ParseResult<T, C> {
+        let value = self.sum()?;
+        self.skip_whitespace();
+        if self.position == self.text.len() {
+            Ok(value)
+        }
+        else {
+            Err(EvalError::Syntax(self.position))
+        }
+    }
+
+    fn sum(&mut self) -> ParseResult<T, C> {
+        let mut value = self.product()?;
+        loop {
+            if self.eat('+') {
+                let other = self.product()?;
+                value = check(value.into_inner() + other.into_inner())?;
+            }
+            else if self.eat('-') {
+                let other = self.product()?;
+                value = check(value.into_inner() - other.into_inner())?;
+            }
+            else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn product(&mut self) -> ParseResult<T, C> {
+        let mut value = self.negation()?;
+        loop {
+            if self.eat('*') {
+                let other = self.negation()?;
+                value = check(value.into_inner() * other.into_inner())?;
+            }
+            else if self.eat('/') {
+                let other = self.negation()?;
+                value = check(value.into_inner() / other.into_inner())?;
+            }
+            else if self.eat('%') {
+                let other = self.negation()?;
+                value = check(value.into_inner() % other.into_inner())?;
+            }
+            else {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn negation(&mut self) -> ParseResult<T, C> {
+        // Every recursive production passes through negation, so the depth is tracked here.
+        if self.depth == MAX_DEPTH {
+            return Err(EvalError::Depth(self.position));
+        }
+        self.depth += 1;
+        let value = if self.eat('-') {
+            let value = self.negation()?;
+            check(-value.into_inner())
+        }
+        else {
+            self.power()
+        };
+        self.depth -= 1;
+        value
+    }
+
+    fn power(&mut self) -> ParseResult<T, C> {
+        let value = self.atom()?;
+        if self.eat('^') {
+            let exponent = self.negation()?;
+            check(value.into_inner().pow(exponent.into_inner()))
+        }
+        else {
+            Ok(value)
+        }
+    }
+
+    fn atom(&mut self) -> ParseResult<T, C> {
+        self.skip_whitespace();
+        let start = self.position;
+        if self.eat('(') {
+            let value = self.sum()?;
+            self.expect(')')?;
+            Ok(value)
+        }
+        else if let Some(name) = self.identifier() {
+            if self.eat('(') {
+                self.call(name, start)
+            }
+            else {
+                match (self.variables)(name) {
+                    Some(value) => Ok(value),
+                    None => match name {
+                        "pi" => check(T::PI),
+                        "e" => check(T::E),
+                        _ => Err(EvalError::UnknownVariable(start)),
+                    },
+                }
+            }
+        }
+        else {
+            self.number()
+        }
+    }
+
+    fn call(&mut self, name: &str, start: usize) -> ParseResult<T, C> {
+        let x = self.sum()?.into_inner();
+        let y = if self.eat(',') {
+            Some(self.sum()?.into_inner())
+        }
+        else {
+            None
+        };
+        self.expect(')')?;
+        let output = match (name, y) {
+            ("abs", None) => x.abs(),
+            ("acos", None) => x.acos(),
+            ("asin", None) => x.asin(),
+            ("atan", None) => x.atan(),
+            ("cbrt", None) => x.cbrt(),
+            ("ceil", None) => x.ceil(),
+            ("cos", None) => x.cos(),
+            ("cosh", None) => x.cosh(),
+            ("exp", None) => x.exp(),
+            ("floor", None) => x.floor(),
+            ("ln", None) => x.ln(),
+            ("log10", None) => x.log10(),
+            ("log2", None) => x.log2(),
+            ("round", None) => x.round(),
+            ("sin", None) => x.sin(),
+            ("sinh", None) => x.sinh(),
+            ("sqrt", None) => x.sqrt(),
+            ("tan", None) => x.tan(),
+            ("tanh", None) => x.tanh(),
+            ("trunc", None) => x.trunc(),
+            ("atan2", Some(y)) => x.atan2(y),
+            ("hypot", Some(y)) => x.hypot(y),
+            ("log", Some(y)) => x.log(y),
+            ("max", Some(y)) => {
+                if x >= y {
+                    x
+                }
+                else {
+                    y
+                }
+            }
+            ("min", Some(y)) => {
+                if x <= y {
+                    x
+                }
+                else {
+                    y
+                }
+            }
+            ("pow", Some(y)) => x.pow(y),
+            _ => return Err(EvalError::UnknownFunction(start)),
+        };
+        check(output)
+    }
+
+    fn number(&mut self) -> ParseResult<T, C> {
+        let start = self.position;
+        let rest = &self.text[start..];
+        let mut end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if let Some(exponent) = rest[end..].strip_prefix(['e', 'E']) {
+            let sign = usize::from(exponent.starts_with(['+', '-']));
+            let digits = exponent[sign..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(exponent.len() - sign);
+            if digits > 0 {
+                end += 1 + sign + digits;
+            }
+        }
+        let inner = rest[..end].parse().map_err(|_| EvalError::Syntax(start))?;
+        self.position += end;
+        check(inner)
+    }
+
+    fn identifier(&mut self) -> Option<&'t str> {
+        let rest = &self.text[self.position..];
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            return None;
+        }
+        let end = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        self.position += end;
+        Some(&rest[..end])
+    }
+
+    fn expect(&mut self, token: char) -> Result<(), EvalError<C::Error>> {
+        if self.eat(token) {
+            Ok(())
+        }
+        else {
+            Err(EvalError::Syntax(self.position))
+        }
+    }
+
+    fn eat(&mut self, token: char) -> bool {
+        self.skip_whitespace();
+        if self.text[self.position..].starts_with(token) {
+            self.position += token.len_utf8();
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+}
+
+fn check<T, C>(inner: T) -> ParseResult<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    Constrained::try_new(inner).map_err(EvalError::Constraint)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::format;
+
+    use crate::eval::{self, EvalError};
+    use crate::expression::Expression;
+    use crate::{Total, R64};
+
+    fn eval(text: &str) -> f64 {
+        eval::eval::<R64>(text, &()).unwrap().into_inner()
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(eval("-2 ^ 2"), -4.0);
+        assert_eq!(eval("2 ^ -1"), 0.5);
+        assert_eq!(eval("7 % 4 - 1"), 2.0);
+        assert_eq!(eval("1.5e1 / .5"), 30.0);
+        assert_eq!(eval("max(1, min(3, 2)) + sqrt(16)"), 6.0);
+        assert_eq!(eval("cos(pi)"), -1.0);
+        assert_eq!(
+            eval(&format!("{}1{}", "(".repeat(255), ")".repeat(255))),
+            1.0
+        );
+        assert_eq!(eval(&format!("{}1", "-".repeat(254))), 1.0);
+    }
+
+    #[test]
+    fn variables() {
+        let mut variables = HashMap::new();
+        variables.insert("x", <R64>::assert(2.0));
+        variables.insert("pi", <R64>::assert(3.0));
+        assert_eq!(
+            eval::eval::<R64>("2 * (x + 1) - pi", &variables).unwrap(),
+            3.0
+        );
+        assert!(matches!(
+            eval::eval::<R64>("y", &variables),
+            Expression::Undefined(EvalError::UnknownVariable(0))
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            eval::eval::<R64>("1 / 0", &()).undefined(),
+            Some(EvalError::Constraint(_))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>("sqrt(-1) * 0", &()).undefined(),
+            Some(EvalError::Constraint(_))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>("1 +", &()).undefined(),
+            Some(EvalError::Syntax(3))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>("(1", &()).undefined(),
+            Some(EvalError::Syntax(2))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>("f(1)", &()).undefined(),
+            Some(EvalError::UnknownFunction(0))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>(&"(".repeat(10_000), &()).undefined(),
+            Some(EvalError::Depth(_))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>(&"-".repeat(10_000), &()).undefined(),
+            Some(EvalError::Depth(_))
+        ));
+        assert!(matches!(
+            eval::eval::<R64>(&"1^".repeat(10_000), &()).undefined(),
+            Some(EvalError::Depth(_))
+        ));
+        assert!(eval::eval::<Total<f64>>("1 / 0", &())
+            .unwrap()
+            .into_inner()
+            .is_infinite());
+    }
+}
@@ -1,16 +1,34 @@
+#[cfg(feature = "futures")]
+pub mod future;
+
 use core::cmp::Ordering;
 use core::convert::Infallible;
-use core::fmt::Debug;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::Hash;
 use core::hint;
+use core::iter::{Product, Sum};
+use core::mem;
 #[cfg(all(nightly, feature = "unstable"))]
-use core::ops::{self, ControlFlow, FromResidual};
-use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
-
-use crate::cmp::{self, EmptyOrd};
+use core::ops::{self, FromResidual};
+use core::ops::{
+    Add, AddAssign, ControlFlow, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+    SubAssign,
+};
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::process::{ExitCode, Termination};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+use thiserror::Error;
+
+use crate::cmp::{self, EmptyOrd, PartialDecision};
 use crate::constraint::{Constraint, Member, NanSet};
 use crate::divergence::{AsExpression, Divergence, OrError};
 use crate::proxy::{Constrained, ErrorFor, ExpressionFor};
+use crate::range::FiniteRange;
 use crate::real::{BinaryRealFunction, Function, Sign, UnaryRealFunction};
+use crate::trace::{self, Operand, Operation, Origin};
 use crate::{with_binary_operations, with_primitives, InfinityEncoding, NanEncoding, Primitive};
 
 pub use Expression::Defined;
@@ -22,6 +40,37 @@ pub use Expression::Undefined;
 /// [`Result`]s. If the given [`Expression`] is the `Defined` variant, then the expression (of the
 /// macro) is the accompanying value. Otherwise, the error in the `Undefined` variant is converted
 /// via [`From`] and returned in the constructed [`Expression`].
+///
+/// A function can be given as a second argument to map the error before it is converted and
+/// returned. This bridges expressions with different error types, such as those of different
+/// constraints, without intermediate calls to [`Expression::map_undefined`].
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::constraint::NotRealError;
+/// use decorum::divergence::OrError;
+/// use decorum::expression::{Defined, Expression};
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::{try_expression, Real};
+///
+/// type R64 = Real<f64, OrError>;
+///
+/// #[derive(Debug)]
+/// enum Error {
+///     Domain(NotRealError),
+/// }
+///
+/// fn f(x: R64) -> Expression<R64, Error> {
+///     let y = try_expression!(R64::ONE / x, Error::Domain);
+///     Defined(y)
+/// }
+///
+/// assert!(matches!(
+///     f(R64::ZERO),
+///     Expression::Undefined(Error::Domain(_))
+/// ));
+/// ```
 #[macro_export]
 macro_rules! try_expression {
     ($x:expr $(,)?) => {{
@@ -33,6 +82,17 @@ macro_rules! try_expression {
             }
         }
     }};
+    ($x:expr, $f:expr $(,)?) => {{
+        let expression: $crate::expression::Expression<_, _> = $x;
+        match expression {
+            $crate::expression::Expression::Defined(inner) => inner,
+            $crate::expression::Expression::Undefined(error) => {
+                return $crate::expression::Expression::Undefined(core::convert::From::from(($f)(
+                    error,
+                )));
+            }
+        }
+    }};
     ($x:block $(,)?) => {
         let expression: $crate::expression::Expression<_, _> = $x;
         try_expression!(expression);
@@ -40,6 +100,38 @@ macro_rules! try_expression {
 }
 pub use try_expression;
 
+/// Evaluates an arithmetic expression and propagates undefined sub-expressions.
+///
+/// Each arithmetic operation, negation, method call, and function call in the expression is tried
+/// as if by [`try_expression!`], such that its defined value is used as the operand of the
+/// enclosing operation and its error is returned from the enclosing function. The output is the
+/// defined value of the entire expression. The output of each tried sub-expression must implement
+/// [`TryExpression`]. Parenthesized sub-expressions are rewritten, but blocks, closures, and the
+/// operands of comparisons are not.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::divergence::OrError;
+/// use decorum::expression::{expression, Defined};
+/// use decorum::proxy::ExpressionFor;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::Real;
+///
+/// type R64 = Real<f64, OrError>;
+///
+/// fn f(x: R64, y: R64) -> ExpressionFor<R64> {
+///     // Each operation is tried, so its operands are always `R64`.
+///     Defined(expression!(x / (x / y - R64::ONE)))
+/// }
+///
+/// assert_eq!(f(R64::assert(4.0), R64::assert(2.0)).unwrap(), 4.0);
+/// assert!(f(R64::ONE, R64::ZERO).is_undefined());
+/// assert!(f(R64::ONE, R64::ONE).is_undefined());
+/// ```
+#[cfg(feature = "derive")]
+pub use decorum_derive::expression;
+
 /// The result of an arithmetic expression that may or may not be defined.
 ///
 /// `Expression` is a fallible output of arithmetic expressions over [`Constrained`] types. It
@@ -50,6 +142,10 @@ pub use try_expression;
 /// When the `unstable` Cargo feature is enabled with a nightly Rust toolchain, [`Expression`] also
 /// implements the unstable (at time of writing) [`Try`] trait and supports the try operator `?`.
 ///
+/// Like `NaN`s, undefined expressions are not equal to any expression (including themselves), so
+/// `Expression` implements neither [`Eq`] nor [`Hash`]. Wrap expressions in [`UndefinedGreatest`]
+/// to use them as keys in maps and sets.
+///
 /// # Examples
 ///
 /// The following two examples contrast deferred matching and trying of `Expression`s versus
@@ -131,6 +227,8 @@ pub use try_expression;
 /// ```
 ///
 /// [`Try`]: core::ops::Try
+/// [`UndefinedGreatest`]: crate::cmp::UndefinedGreatest
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Clone, Copy, Debug)]
 pub enum Expression<T, E = ()> {
     Defined(T),
@@ -138,10 +236,33 @@ pub enum Expression<T, E = ()> {
 }
 
 impl<T, E> Expression<T, E> {
-    pub fn unwrap(self) -> T {
+    /// Gets the defined value or the given value if the expression is undefined.
+    pub fn unwrap_or(self, default: T) -> T {
+        match self {
+            Defined(defined) => defined,
+            Undefined(_) => default,
+        }
+    }
+
+    /// Gets the defined value or computes a value from the error if the expression is undefined.
+    pub fn unwrap_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce(E) -> T,
+    {
+        match self {
+            Defined(defined) => defined,
+            Undefined(undefined) => f(undefined),
+        }
+    }
+
+    /// Gets the defined value or the default value if the expression is undefined.
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
         match self {
             Defined(defined) => defined,
-            _ => panic!(),
+            Undefined(_) => T::default(),
         }
     }
 
@@ -162,6 +283,41 @@ impl<T, E> Expression<T, E> {
         }
     }
 
+    /// Calls a function with a reference to the defined value and gets the expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = (R64::<OrError>::ONE + R64::ONE)
+    ///     .inspect(|x| eprintln!("x = {}", x))
+    ///     .inspect_undefined(|error| eprintln!("undefined: {}", error));
+    /// ```
+    pub fn inspect<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T),
+    {
+        if let Defined(ref defined) = self {
+            f(defined);
+        }
+        self
+    }
+
+    /// Calls a function with a reference to the error of an undefined expression and gets the
+    /// expression.
+    pub fn inspect_undefined<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&E),
+    {
+        if let Undefined(ref undefined) = self {
+            f(undefined);
+        }
+        self
+    }
+
     pub fn and_then<U, F>(self, f: F) -> Expression<U, E>
     where
         F: FnOnce(T) -> Expression<U, E>,
@@ -172,6 +328,122 @@ impl<T, E> Expression<T, E> {
         }
     }
 
+    /// Zips the defined values of expressions into a pair. If either expression is undefined, then
+    /// the output is undefined with the error of the first undefined expression.
+    pub fn zip<U>(self, other: Expression<U, E>) -> Expression<(T, U), E> {
+        self.zip_with(other, |left, right| (left, right))
+    }
+
+    /// Zips the defined values of expressions with a function. If either expression is undefined,
+    /// then the output is undefined with the error of the first undefined expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::{BinaryRealFunction, UnaryRealFunction};
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE + R64::ONE;
+    /// let y = R64::<OrError>::ONE - R64::ONE;
+    /// let z = x.zip_with(y, |x, y| x.max(y));
+    /// assert_eq!(z.unwrap(), 2.0);
+    /// ```
+    pub fn zip_with<U, V, F>(self, other: Expression<U, E>, f: F) -> Expression<V, E>
+    where
+        F: FnOnce(T, U) -> V,
+    {
+        match (self, other) {
+            (Defined(left), Defined(right)) => Defined(f(left, right)),
+            (Undefined(undefined), _) | (_, Undefined(undefined)) => Undefined(undefined),
+        }
+    }
+
+    /// Converts the error of an undefined expression into [`Accumulated`] errors.
+    ///
+    /// See [`Expression::zip_accumulate`].
+    #[cfg(feature = "std")]
+    pub fn accumulate(self) -> Expression<T, Accumulated<E>> {
+        self.map_undefined(Accumulated::from)
+    }
+
+    /// Gets `other` if the expression is defined. Otherwise, gets the undefined expression.
+    pub fn and<U>(self, other: Expression<U, E>) -> Expression<U, E> {
+        match self {
+            Defined(_) => other,
+            Undefined(undefined) => Undefined(undefined),
+        }
+    }
+
+    /// Gets the expression if it is defined. Otherwise, gets `other`.
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Defined(defined) => Defined(defined),
+            Undefined(_) => other,
+        }
+    }
+
+    /// Gets the defined expression or the output of `f` with the error if the expression is
+    /// undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE;
+    /// let y = (x / R64::ZERO).or_else(|_| x / R64::ONE);
+    /// assert_eq!(y.unwrap(), 1.0);
+    /// ```
+    pub fn or_else<R, F>(self, f: F) -> Expression<T, R>
+    where
+        F: FnOnce(E) -> Expression<T, R>,
+    {
+        match self {
+            Defined(defined) => Defined(defined),
+            Undefined(undefined) => f(undefined),
+        }
+    }
+
+    /// Maps the error of an undefined expression with a function. If the expression is defined,
+    /// then the output is the defined value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = (R64::<OrError>::ONE / R64::ZERO).map_undefined(|error| error.to_string());
+    /// assert_eq!(
+    ///     x.undefined().unwrap(),
+    ///     "floating-point value must be a real"
+    /// );
+    /// ```
+    pub fn map_undefined<R, F>(self, f: F) -> Expression<T, R>
+    where
+        F: FnOnce(E) -> R,
+    {
+        match self {
+            Defined(defined) => Defined(defined),
+            Undefined(undefined) => Undefined(f(undefined)),
+        }
+    }
+
+    /// Compares the defined values of expressions.
+    ///
+    /// The output is [`PartialDecision::Undefined`] if either expression is undefined or if the
+    /// defined values are incomparable.
+    pub fn cmp_defined(&self, other: &Self) -> PartialDecision
+    where
+        T: PartialOrd,
+    {
+        self.partial_cmp(other).into()
+    }
+
     pub fn defined(self) -> Option<T> {
         match self {
             Defined(defined) => Some(defined),
@@ -179,6 +451,13 @@ impl<T, E> Expression<T, E> {
         }
     }
 
+    /// Converts the expression into an [`Option`] of its defined value.
+    ///
+    /// This is an alias of [`Expression::defined`] that matches the naming of [`Result::ok`].
+    pub fn ok(self) -> Option<T> {
+        self.defined()
+    }
+
     pub fn undefined(self) -> Option<E> {
         match self {
             Undefined(undefined) => Some(undefined),
@@ -193,6 +472,223 @@ impl<T, E> Expression<T, E> {
     pub fn is_undefined(&self) -> bool {
         matches!(self, Undefined(_))
     }
+
+    /// Converts the expression into a `Result`.
+    ///
+    /// This allows the `?` operator to be used with expressions in functions that return a
+    /// `Result` on the stable channel, where `Expression` does not implement `Try`. The error of
+    /// an undefined expression is converted via `From` as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::constraint::NotRealError;
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::Real;
+    ///
+    /// type R64 = Real<f64, OrError>;
+    ///
+    /// fn f(x: R64, y: R64) -> Result<R64, NotRealError> {
+    ///     let z = (x / y).try_defined()?;
+    ///     (z * z).try_defined()
+    /// }
+    ///
+    /// assert!(f(R64::assert(4.0), R64::ONE).is_ok());
+    /// assert!(f(R64::ONE, R64::ZERO).is_err());
+    /// ```
+    pub fn try_defined(self) -> Result<T, E> {
+        self.into()
+    }
+
+    /// Gets the defined value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression is undefined. Use [`Expression::expect`] to include the error of
+    /// the expression in the panic message.
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        match self {
+            Defined(defined) => defined,
+            Undefined(_) => panic!("called `Expression::unwrap` on an `Undefined` value"),
+        }
+    }
+}
+
+impl<T, E> Expression<T, E>
+where
+    E: Debug,
+{
+    /// Gets the defined value.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the given message and the error of the expression if it is undefined.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,should_panic
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE / R64::ZERO;
+    /// let x = x.expect("division by zero");
+    /// ```
+    #[track_caller]
+    pub fn expect(self, message: &str) -> T {
+        match self {
+            Defined(defined) => defined,
+            Undefined(undefined) => panic!("{}: {:?}", message, undefined),
+        }
+    }
+}
+
+impl<T, E> Expression<T, E>
+where
+    E: cmp::EmptyInhabitant,
+{
+    /// Gets the expression if it is defined and `f` returns `true` for its value. Otherwise, the
+    /// output is undefined.
+    ///
+    /// If the expression is defined and `f` returns `false`, then the error of the output is the
+    /// empty inhabitant of `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE + R64::ONE;
+    /// assert!(x.filter(|x| *x > R64::ONE).is_defined());
+    /// assert!(x.filter(|x| *x > R64::PI).is_undefined());
+    /// ```
+    pub fn filter<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        match self {
+            Defined(defined) => {
+                if f(&defined) {
+                    Defined(defined)
+                }
+                else {
+                    Undefined(E::empty())
+                }
+            }
+            Undefined(undefined) => Undefined(undefined),
+        }
+    }
+
+    /// Gets the defined expression if exactly one of the expressions is defined. Otherwise, the
+    /// output is undefined.
+    ///
+    /// If both expressions are defined, then the error of the output is the empty inhabitant of
+    /// `E`. If both expressions are undefined, then the output is the undefined expression `self`.
+    pub fn xor(self, other: Self) -> Self {
+        match (self, other) {
+            (Defined(defined), Undefined(_)) | (Undefined(_), Defined(defined)) => Defined(defined),
+            (Defined(_), Defined(_)) => Undefined(E::empty()),
+            (Undefined(undefined), Undefined(_)) => Undefined(undefined),
+        }
+    }
+}
+
+impl<T, C, E> Expression<Constrained<T, C>, E>
+where
+    T: Primitive,
+{
+    /// Restricts a defined expression to a range.
+    ///
+    /// See [`FiniteRange::clamp`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn clamp_to(self, range: &FiniteRange<T>) -> Self {
+        self.map(|defined| range.clamp(defined))
+    }
+
+    /// Gets the expression if it is defined and its value is in a range. Otherwise, the output is
+    /// undefined.
+    ///
+    /// If the expression is defined and its value is not in the range, then the error of the
+    /// output is the empty inhabitant of `E`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::range::FiniteRange;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let unit = FiniteRange::new(R64::<OrError>::ZERO, R64::ONE);
+    /// let x = R64::<OrError>::ONE + R64::ONE;
+    /// assert!(x.restrict_to(&unit).is_undefined());
+    /// assert_eq!(x.clamp_to(&unit).unwrap(), R64::<OrError>::ONE);
+    /// ```
+    pub fn restrict_to(self, range: &FiniteRange<T>) -> Self
+    where
+        E: cmp::EmptyInhabitant,
+    {
+        self.filter(|defined| range.contains(defined))
+    }
+}
+
+impl<T, E> Expression<T, E>
+where
+    T: Display,
+{
+    /// Gets a [`Display`] implementation that writes the given token for undefined expressions.
+    ///
+    /// The [`Display`] implementation of `Expression` writes the error (or `NaN` with the alternate
+    /// flag `#`) for the [`Undefined`] variant. This function writes an arbitrary token instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE / R64::ZERO;
+    /// assert_eq!(format!("{}", x.display_undefined_as("-")), "-");
+    /// ```
+    pub const fn display_undefined_as<'a>(&'a self, token: &'a str) -> impl 'a + Copy + Display {
+        struct Formatted<'a, T, E> {
+            expression: &'a Expression<T, E>,
+            token: &'a str,
+        }
+
+        impl<T, E> Clone for Formatted<'_, T, E> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T, E> Copy for Formatted<'_, T, E> {}
+
+        impl<T, E> Display for Formatted<'_, T, E>
+        where
+            T: Display,
+        {
+            fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                match self.expression {
+                    Defined(ref defined) => Display::fmt(defined, formatter),
+                    Undefined(_) => formatter.pad(self.token),
+                }
+            }
+        }
+
+        Formatted {
+            expression: self,
+            token,
+        }
+    }
 }
 
 impl<T, E> Expression<&'_ T, E> {
@@ -239,6 +735,143 @@ impl<T, E> Expression<&'_ mut T, E> {
     }
 }
 
+impl<T, E> Expression<Option<T>, E> {
+    /// Transposes an expression of an [`Option`] into an [`Option`] of an expression.
+    ///
+    /// `Defined(None)` is mapped to `None`, `Defined(Some(_))` is mapped to `Some(Defined(_))`,
+    /// and `Undefined(_)` is mapped to `Some(Undefined(_))`. See [`TransposeOption`] for the
+    /// inverse.
+    pub fn transpose(self) -> Option<Expression<T, E>> {
+        match self {
+            Defined(Some(defined)) => Some(Defined(defined)),
+            Defined(None) => None,
+            Undefined(undefined) => Some(Undefined(undefined)),
+        }
+    }
+
+    /// Converts an expression of an [`Option`] into an expression, where `Defined(None)` is
+    /// mapped to `Undefined(error)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::constraint::NotRealError;
+    /// use decorum::expression::{Defined, Expression};
+    /// use decorum::R64;
+    ///
+    /// let x: Expression<Option<R64>, NotRealError> = Defined(None);
+    /// assert!(x.ok_or_undefined(NotRealError).is_undefined());
+    /// ```
+    pub fn ok_or_undefined(self, error: E) -> Expression<T, E> {
+        match self {
+            Defined(Some(defined)) => Defined(defined),
+            Defined(None) => Undefined(error),
+            Undefined(undefined) => Undefined(undefined),
+        }
+    }
+}
+
+impl<T, E> Expression<Expression<T, E>, E> {
+    /// Converts a nested expression into an expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE + R64::ONE;
+    /// let y = x.map(|x| x / R64::ZERO).flatten();
+    /// assert!(y.is_undefined());
+    /// ```
+    pub fn flatten(self) -> Expression<T, E> {
+        match self {
+            Defined(defined) => defined,
+            Undefined(undefined) => Undefined(undefined),
+        }
+    }
+}
+
+/// Transposes an [`Option`] of an [`Expression`] into an expression of an [`Option`].
+///
+/// This is the inverse of [`Expression::transpose`].
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::divergence::OrError;
+/// use decorum::expression::TransposeOption;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::R64;
+///
+/// let x = Some(R64::<OrError>::ONE / R64::ONE);
+/// assert_eq!(x.transpose().transpose(), x);
+/// ```
+pub trait TransposeOption<T, E> {
+    fn transpose(self) -> Expression<Option<T>, E>;
+}
+
+impl<T, E> TransposeOption<T, E> for Option<Expression<T, E>> {
+    fn transpose(self) -> Expression<Option<T>, E> {
+        match self {
+            Some(Defined(defined)) => Defined(Some(defined)),
+            Some(Undefined(undefined)) => Undefined(undefined),
+            None => Defined(None),
+        }
+    }
+}
+
+/// Conversion into an [`Expression`] that can be tried.
+///
+/// This trait is implemented by [`Expression`]s, [`Constrained`] types, and primitive
+/// floating-point types, where the latter are always defined. It is used by the `expression`
+/// macro (provided by the `derive` feature) to try sub-expressions regardless of whether or not
+/// they are fallible. The error type `R` is the error type of the enclosing function and errors
+/// are converted via [`From`].
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub trait TryExpression<R> {
+    type Output;
+
+    fn try_expression(self) -> Expression<Self::Output, R>;
+}
+
+impl<T, E, R> TryExpression<R> for Expression<T, E>
+where
+    R: From<E>,
+{
+    type Output = T;
+
+    fn try_expression(self) -> Expression<Self::Output, R> {
+        self.map_undefined(From::from)
+    }
+}
+
+impl<T, C, R> TryExpression<R> for Constrained<T, C> {
+    type Output = Self;
+
+    fn try_expression(self) -> Expression<Self::Output, R> {
+        Defined(self)
+    }
+}
+
+macro_rules! impl_try_expression_for_primitive {
+    () => {
+        with_primitives!(impl_try_expression_for_primitive);
+    };
+    (primitive => $t:ty) => {
+        impl<R> TryExpression<R> for $t {
+            type Output = Self;
+
+            fn try_expression(self) -> Expression<Self::Output, R> {
+                Defined(self)
+            }
+        }
+    };
+}
+impl_try_expression_for_primitive!();
+
 impl<T> Expression<T, Infallible> {
     pub fn into_defined(self) -> T {
         #[allow(unreachable_patterns)]
@@ -273,6 +906,174 @@ impl<E> Expression<Infallible, E> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T, E> Expression<T, Accumulated<E>> {
+    /// Zips the defined values of expressions into a pair and accumulates the errors of undefined
+    /// expressions.
+    ///
+    /// Unlike [`Expression::zip`], this does not short-circuit: if both expressions are
+    /// undefined, then the output is undefined with the errors of both expressions. This is useful
+    /// for validation, where all causes of undefined outputs should be reported rather than only
+    /// the first. The error of `other` can be a single error or [`Accumulated`] errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE / R64::ZERO;
+    /// let y = (-R64::<OrError>::ONE).sqrt();
+    /// let z = R64::<OrError>::ONE.ln();
+    ///
+    /// let errors = x
+    ///     .accumulate()
+    ///     .zip_accumulate(y)
+    ///     .zip_accumulate(z)
+    ///     .undefined()
+    ///     .unwrap();
+    /// assert_eq!(errors.errors().len(), 2);
+    /// ```
+    pub fn zip_accumulate<U, R>(self, other: Expression<U, R>) -> Expression<(T, U), Accumulated<E>>
+    where
+        Accumulated<E>: From<R>,
+    {
+        match (self, other) {
+            (Defined(left), Defined(right)) => Defined((left, right)),
+            (Undefined(undefined), Defined(_)) => Undefined(undefined),
+            (Defined(_), Undefined(undefined)) => Undefined(undefined.into()),
+            (Undefined(mut left), Undefined(right)) => {
+                left.errors.extend(Accumulated::from(right).errors);
+                Undefined(left)
+            }
+        }
+    }
+}
+
+/// Error of indexing that is out of bounds.
+#[derive(Clone, Copy, Debug, Eq, Error, Hash, PartialEq)]
+#[error("index {index} is out of bounds for length {len}")]
+pub struct IndexError {
+    pub index: usize,
+    pub len: usize,
+}
+
+/// Errors accumulated from undefined expressions.
+///
+/// See [`Expression::accumulate`] and [`Expression::zip_accumulate`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Accumulated<E> {
+    errors: Vec<E>,
+}
+
+#[cfg(feature = "std")]
+impl<E> Accumulated<E> {
+    /// Gets the errors in the order in which they were accumulated.
+    pub fn errors(&self) -> &[E] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<E> {
+        self.errors
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> Display for Accumulated<E>
+where
+    E: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                write!(formatter, "; ")?;
+            }
+            Display::fmt(error, formatter)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> std::error::Error for Accumulated<E> where E: Debug + Display {}
+
+#[cfg(feature = "std")]
+impl<E> From<E> for Accumulated<E> {
+    fn from(error: E) -> Self {
+        Accumulated {
+            errors: std::vec![error],
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> IntoIterator for Accumulated<E> {
+    type Item = E;
+    type IntoIter = std::vec::IntoIter<E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+/// Indexing of slices of [`Constrained`] types into [`Expression`]s.
+///
+/// Indexing with these functions does not panic when an index is out of bounds. Instead, the
+/// output is `Undefined` with an [`IndexError`], so table-driven code can be written in terms of
+/// [`Expression`]s. The error can be converted into other error types via [`try_expression`].
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::constraint::IsReal;
+/// use decorum::divergence::OrError;
+/// use decorum::expression::{Expression, IndexError, SliceExpression};
+/// use decorum::proxy::Constrained;
+/// use decorum::try_expression;
+///
+/// pub type Real = Constrained<f64, IsReal<OrError>>;
+///
+/// fn lerp(table: &[Real], index: usize, t: Real) -> Expression<f64, IndexError> {
+///     let a = try_expression!(table.get_expr(index));
+///     let b = try_expression!(table.get_expr(index + 1));
+///     Expression::Defined((a + (b - a) * t).unwrap().into_inner())
+/// }
+///
+/// let table = [0.0, 1.0, 4.0].map(Real::assert);
+/// assert_eq!(lerp(&table, 1, Real::assert(0.5)).unwrap(), 2.5);
+/// assert!(lerp(&table, 2, Real::assert(0.5)).is_undefined());
+/// ```
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`try_expression`]: crate::try_expression
+pub trait SliceExpression<T, C> {
+    /// Gets the proxy at the given index.
+    fn get_expr(&self, index: usize) -> Expression<Constrained<T, C>, IndexError>;
+
+    /// Gets a mutable reference to the proxy at the given index.
+    fn get_expr_mut(&mut self, index: usize) -> Expression<&mut Constrained<T, C>, IndexError>;
+}
+
+impl<T, C> SliceExpression<T, C> for [Constrained<T, C>]
+where
+    T: Primitive,
+{
+    fn get_expr(&self, index: usize) -> Expression<Constrained<T, C>, IndexError> {
+        let len = self.len();
+        self.get(index)
+            .copied()
+            .ok_or(IndexError { index, len })
+            .into()
+    }
+
+    fn get_expr_mut(&mut self, index: usize) -> Expression<&mut Constrained<T, C>, IndexError> {
+        let len = self.len();
+        self.get_mut(index).ok_or(IndexError { index, len }).into()
+    }
+}
+
 impl<T, C> BinaryRealFunction for ExpressionFor<Constrained<T, C>>
 where
     ErrorFor<Constrained<T, C>>: Clone + cmp::EmptyInhabitant,
@@ -485,6 +1286,39 @@ where
     }
 }
 
+/// Collects the defined values of expressions or the error of the first undefined expression.
+///
+/// Like the [`FromIterator`] implementation for [`Result`], no further items are consumed after an
+/// undefined expression.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::divergence::OrError;
+/// use decorum::expression::Expression;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::R64;
+///
+/// let xs = [4.0, 1.0, 2.0].map(R64::<OrError>::assert);
+/// let recips: Expression<Vec<_>, _> = xs.iter().map(|x| R64::ONE / *x).collect();
+/// assert_eq!(recips.unwrap(), [0.25, 1.0, 0.5]);
+/// ```
+impl<T, E, V> FromIterator<Expression<T, E>> for Expression<V, E>
+where
+    V: FromIterator<T>,
+{
+    fn from_iter<I>(expressions: I) -> Self
+    where
+        I: IntoIterator<Item = Expression<T, E>>,
+    {
+        expressions
+            .into_iter()
+            .map(Result::from)
+            .collect::<Result<V, E>>()
+            .into()
+    }
+}
+
 impl<T, E> From<Result<T, E>> for Expression<T, E> {
     fn from(result: Result<T, E>) -> Self {
         match result {
@@ -494,6 +1328,15 @@ impl<T, E> From<Result<T, E>> for Expression<T, E> {
     }
 }
 
+impl<T, E> From<Expression<T, E>> for ControlFlow<E, T> {
+    fn from(expression: Expression<T, E>) -> Self {
+        match expression {
+            Defined(defined) => ControlFlow::Continue(defined),
+            Undefined(undefined) => ControlFlow::Break(undefined),
+        }
+    }
+}
+
 impl<T, E> From<Expression<T, E>> for Result<T, E> {
     fn from(result: Expression<T, E>) -> Self {
         match result {
@@ -503,6 +1346,52 @@ impl<T, E> From<Expression<T, E>> for Result<T, E> {
     }
 }
 
+/// Constructs a defined expression of the default value of `T`.
+impl<T, E> Default for Expression<T, E>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Defined(T::default())
+    }
+}
+
+/// Writes the defined value or the error of an undefined expression.
+///
+/// With the alternate flag `#`, undefined expressions are written as `NaN` instead of their error,
+/// which is useful when writing numeric tables.
+impl<T, E> Display for Expression<T, E>
+where
+    T: Display,
+    E: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Defined(ref defined) => Display::fmt(defined, formatter),
+            Undefined(_) if formatter.alternate() => formatter.pad("NaN"),
+            Undefined(ref undefined) => Display::fmt(undefined, formatter),
+        }
+    }
+}
+
+impl<T, E> From<Option<T>> for Expression<T, E>
+where
+    E: cmp::EmptyInhabitant,
+{
+    fn from(option: Option<T>) -> Self {
+        match option {
+            Some(output) => Defined(output),
+            None => Undefined(E::empty()),
+        }
+    }
+}
+
+impl<T, E> From<Expression<T, E>> for Option<T> {
+    fn from(expression: Expression<T, E>) -> Self {
+        expression.defined()
+    }
+}
+
 #[cfg(all(nightly, feature = "unstable"))]
 impl<T, E> FromResidual for Expression<T, E> {
     fn from_residual(residual: Expression<Infallible, E>) -> Self {
@@ -565,6 +1454,21 @@ where
     }
 }
 
+// Propagates an undefined operand of an operation and records the origin of its error.
+macro_rules! try_operand {
+    ($x:expr, $operation:ident, $operand:ident $(,)?) => {
+        match $x {
+            Defined(inner) => inner,
+            Undefined(error) => {
+                return Undefined(trace::trace(
+                    error,
+                    Origin::new(Operation::$operation, Operand::$operand),
+                ));
+            }
+        }
+    };
+}
+
 impl<T, C> Neg for ExpressionFor<Constrained<T, C>>
 where
     T: Primitive,
@@ -574,10 +1478,67 @@ where
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        self.map(|defined| -defined)
+        Defined(-try_operand!(self, Neg, Left))
     }
 }
 
+// Reductions stop at the first undefined partial output, like the `Sum` and `Product`
+// implementations for `Result`.
+macro_rules! impl_reduction_for_expression {
+    (reduction => $trait:ident :: $method:ident, operation => $operation:ident :: $operation_method:ident, identity => $identity:ident) => {
+        impl<T, C> $trait for ExpressionFor<Constrained<T, C>>
+        where
+            T: Primitive,
+            C: Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+        {
+            fn $method<I>(input: I) -> Self
+            where
+                I: Iterator<Item = Self>,
+            {
+                let mut output = Defined(UnaryRealFunction::$identity);
+                for x in input {
+                    output = $operation::$operation_method(output, x);
+                    if output.is_undefined() {
+                        break;
+                    }
+                }
+                output
+            }
+        }
+
+        impl<T, C> $trait<Constrained<T, C>> for ExpressionFor<Constrained<T, C>>
+        where
+            T: Primitive,
+            C: Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+        {
+            fn $method<I>(input: I) -> Self
+            where
+                I: Iterator<Item = Constrained<T, C>>,
+            {
+                input.map(Defined).$method()
+            }
+        }
+
+        impl<'a, T, C> $trait<&'a Constrained<T, C>> for ExpressionFor<Constrained<T, C>>
+        where
+            T: 'a + Primitive,
+            C: 'a + Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+        {
+            fn $method<I>(input: I) -> Self
+            where
+                I: Iterator<Item = &'a Constrained<T, C>>,
+            {
+                input.copied().map(Defined).$method()
+            }
+        }
+    };
+}
+impl_reduction_for_expression!(reduction => Sum::sum, operation => Add::add, identity => ZERO);
+impl_reduction_for_expression!(reduction => Product::product, operation => Mul::mul, identity => ONE);
+
 impl<T, E> PartialEq for Expression<T, E>
 where
     T: PartialEq,
@@ -602,6 +1563,94 @@ where
     }
 }
 
+// Undefined expressions are incomparable with proxies and primitive values, consistent with the
+// comparison of expressions.
+impl<T, C, E> PartialEq<Constrained<T, C>> for Expression<Constrained<T, C>, E>
+where
+    T: Primitive,
+{
+    fn eq(&self, other: &Constrained<T, C>) -> bool {
+        self.as_ref()
+            .defined()
+            .is_some_and(|defined| defined.eq(other))
+    }
+}
+
+impl<T, C, E> PartialEq<T> for Expression<Constrained<T, C>, E>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.as_ref()
+            .defined()
+            .is_some_and(|defined| defined.eq(other))
+    }
+}
+
+impl<T, C, E> PartialEq<Expression<Constrained<T, C>, E>> for Constrained<T, C>
+where
+    T: Primitive,
+{
+    fn eq(&self, other: &Expression<Constrained<T, C>, E>) -> bool {
+        other.eq(self)
+    }
+}
+
+impl<T, C, E> PartialOrd<Constrained<T, C>> for Expression<Constrained<T, C>, E>
+where
+    T: Primitive,
+{
+    fn partial_cmp(&self, other: &Constrained<T, C>) -> Option<Ordering> {
+        self.as_ref()
+            .defined()
+            .and_then(|defined| defined.partial_cmp(other))
+    }
+}
+
+impl<T, C, E> PartialOrd<T> for Expression<Constrained<T, C>, E>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        self.as_ref()
+            .defined()
+            .and_then(|defined| defined.partial_cmp(other))
+    }
+}
+
+impl<T, C, E> PartialOrd<Expression<Constrained<T, C>, E>> for Constrained<T, C>
+where
+    T: Primitive,
+{
+    fn partial_cmp(&self, other: &Expression<Constrained<T, C>, E>) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+/// Reports the termination of a process or test from an expression.
+///
+/// This mirrors the implementation for [`Result`]: defined values report their own termination and
+/// undefined expressions write their error to `stderr` and report failure. This allows
+/// expressions to be returned from `main` and `#[test]` functions.
+#[cfg(feature = "std")]
+impl<T, E> Termination for Expression<T, E>
+where
+    T: Termination,
+    E: Debug,
+{
+    fn report(self) -> ExitCode {
+        match self {
+            Defined(defined) => defined.report(),
+            Undefined(undefined) => {
+                std::eprintln!("Error: {:?}", undefined);
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
 #[cfg(all(nightly, feature = "unstable"))]
 impl<T, E> ops::Try for Expression<T, E> {
     type Output = T;
@@ -688,17 +1737,17 @@ where
     }
 
     fn recip(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::recip)
+        UnaryRealFunction::recip(try_operand!(self, Recip, Left))
     }
 
     #[cfg(feature = "std")]
     fn powi(self, n: i32) -> Self::Codomain {
-        self.and_then(|defined| UnaryRealFunction::powi(defined, n))
+        UnaryRealFunction::powi(try_operand!(self, Powi, Left), n)
     }
 
     #[cfg(feature = "std")]
     fn sqrt(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::sqrt)
+        UnaryRealFunction::sqrt(try_operand!(self, Sqrt, Left))
     }
 
     #[cfg(feature = "std")]
@@ -708,42 +1757,42 @@ where
 
     #[cfg(feature = "std")]
     fn exp(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::exp)
+        UnaryRealFunction::exp(try_operand!(self, Exp, Left))
     }
 
     #[cfg(feature = "std")]
     fn exp2(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::exp2)
+        UnaryRealFunction::exp2(try_operand!(self, Exp2, Left))
     }
 
     #[cfg(feature = "std")]
     fn exp_m1(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::exp_m1)
+        UnaryRealFunction::exp_m1(try_operand!(self, ExpM1, Left))
     }
 
     #[cfg(feature = "std")]
     fn ln(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::ln)
+        UnaryRealFunction::ln(try_operand!(self, Ln, Left))
     }
 
     #[cfg(feature = "std")]
     fn log2(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::log2)
+        UnaryRealFunction::log2(try_operand!(self, Log2, Left))
     }
 
     #[cfg(feature = "std")]
     fn log10(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::log10)
+        UnaryRealFunction::log10(try_operand!(self, Log10, Left))
     }
 
     #[cfg(feature = "std")]
     fn ln_1p(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::ln_1p)
+        UnaryRealFunction::ln_1p(try_operand!(self, Ln1p, Left))
     }
 
     #[cfg(feature = "std")]
     fn to_degrees(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::to_degrees)
+        UnaryRealFunction::to_degrees(try_operand!(self, ToDegrees, Left))
     }
 
     #[cfg(feature = "std")]
@@ -763,17 +1812,17 @@ where
 
     #[cfg(feature = "std")]
     fn tan(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::tan)
+        UnaryRealFunction::tan(try_operand!(self, Tan, Left))
     }
 
     #[cfg(feature = "std")]
     fn asin(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::asin)
+        UnaryRealFunction::asin(try_operand!(self, Asin, Left))
     }
 
     #[cfg(feature = "std")]
     fn acos(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::acos)
+        UnaryRealFunction::acos(try_operand!(self, Acos, Left))
     }
 
     #[cfg(feature = "std")]
@@ -809,17 +1858,17 @@ where
 
     #[cfg(feature = "std")]
     fn asinh(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::asinh)
+        UnaryRealFunction::asinh(try_operand!(self, Asinh, Left))
     }
 
     #[cfg(feature = "std")]
     fn acosh(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::acosh)
+        UnaryRealFunction::acosh(try_operand!(self, Acosh, Left))
     }
 
     #[cfg(feature = "std")]
     fn atanh(self) -> Self::Codomain {
-        self.and_then(UnaryRealFunction::atanh)
+        UnaryRealFunction::atanh(try_operand!(self, Atanh, Left))
     }
 }
 
@@ -839,7 +1888,7 @@ macro_rules! impl_binary_operation_for_expression {
     };
     (operation => $trait:ident :: $method:ident) => {
         impl_binary_operation_for_expression!(operation => $trait :: $method, |left, right| {
-            left.zip_map(right, $trait::$method)
+            left.zip_map_traced(right, Operation::$trait, $trait::$method)
         });
     };
     (operation => $trait:ident :: $method:ident, |$left:ident, $right:ident| $f:block) => {
@@ -856,8 +1905,8 @@ macro_rules! impl_binary_operation_for_expression {
                     type Output = ExpressionFor<Constrained<$t, C>>;
 
                     fn $method(self, other: ExpressionFor<Constrained<$t, C>>) -> Self::Output {
-                        let $left = try_expression!(Constrained::<_, C>::new(self));
-                        let $right = try_expression!(other);
+                        let $left = try_operand!(Constrained::<_, C>::new(self), $trait, Left);
+                        let $right = try_operand!(other, $trait, Right);
                         $f
                     }
                 }
@@ -875,7 +1924,7 @@ macro_rules! impl_binary_operation_for_expression {
 
             fn $method(self, other: ExpressionFor<Self>) -> Self::Output {
                 let $left = self;
-                let $right = try_expression!(other);
+                let $right = try_operand!(other, $trait, Right);
                 $f
             }
         }
@@ -889,7 +1938,7 @@ macro_rules! impl_binary_operation_for_expression {
             type Output = Self;
 
             fn $method(self, other: Constrained<T, C>) -> Self::Output {
-                let $left = try_expression!(self);
+                let $left = try_operand!(self, $trait, Left);
                 let $right = other;
                 $f
             }
@@ -904,8 +1953,8 @@ macro_rules! impl_binary_operation_for_expression {
             type Output = Self;
 
             fn $method(self, other: Self) -> Self::Output {
-                let $left = try_expression!(self);
-                let $right = try_expression!(other);
+                let $left = try_operand!(self, $trait, Left);
+                let $right = try_operand!(other, $trait, Right);
                 $f
             }
         }
@@ -919,8 +1968,8 @@ macro_rules! impl_binary_operation_for_expression {
             type Output = Self;
 
             fn $method(self, other: T) -> Self::Output {
-                let $left = try_expression!(self);
-                let $right = try_expression!(Constrained::<_, C>::new(other));
+                let $left = try_operand!(self, $trait, Left);
+                let $right = try_operand!(Constrained::<_, C>::new(other), $trait, Right);
                 $f
             }
         }
@@ -928,6 +1977,103 @@ macro_rules! impl_binary_operation_for_expression {
 }
 impl_binary_operation_for_expression!();
 
+// Implements binary operations on references by cloning operands and forwarding to the
+// implementations on values.
+macro_rules! impl_reference_binary_operation_for_expression {
+    () => {
+        with_binary_operations!(impl_reference_binary_operation_for_expression);
+    };
+    (operation => $trait:ident :: $method:ident) => {
+        impl_reference_binary_operation_for_expression!(
+            operation => $trait::$method,
+            (Constrained<T, C>, ExpressionFor<Constrained<T, C>>)
+        );
+        impl_reference_binary_operation_for_expression!(
+            operation => $trait::$method,
+            (ExpressionFor<Constrained<T, C>>, Constrained<T, C>)
+        );
+        impl_reference_binary_operation_for_expression!(
+            operation => $trait::$method,
+            (ExpressionFor<Constrained<T, C>>, ExpressionFor<Constrained<T, C>>)
+        );
+        impl_reference_binary_operation_for_expression!(
+            operation => $trait::$method,
+            (ExpressionFor<Constrained<T, C>>, T)
+        );
+    };
+    (operation => $trait:ident :: $method:ident, ($left:ty, $right:ty $(,)?)) => {
+        impl<T, C> $trait<&'_ $right> for $left
+        where
+            T: Primitive,
+            C: Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+            $right: Clone,
+        {
+            type Output = ExpressionFor<Constrained<T, C>>;
+
+            fn $method(self, other: &$right) -> Self::Output {
+                $trait::$method(self, Clone::clone(other))
+            }
+        }
+
+        impl<T, C> $trait<$right> for &'_ $left
+        where
+            T: Primitive,
+            C: Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+            $left: Clone,
+        {
+            type Output = ExpressionFor<Constrained<T, C>>;
+
+            fn $method(self, other: $right) -> Self::Output {
+                $trait::$method(Clone::clone(self), other)
+            }
+        }
+
+        impl<T, C> $trait<&'_ $right> for &'_ $left
+        where
+            T: Primitive,
+            C: Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+            $left: Clone,
+            $right: Clone,
+        {
+            type Output = ExpressionFor<Constrained<T, C>>;
+
+            fn $method(self, other: &$right) -> Self::Output {
+                $trait::$method(Clone::clone(self), Clone::clone(other))
+            }
+        }
+    };
+}
+impl_reference_binary_operation_for_expression!();
+
+macro_rules! impl_binary_assign_operation_for_expression {
+    () => {
+        impl_binary_assign_operation_for_expression!(operation => AddAssign::add_assign, Add::add);
+        impl_binary_assign_operation_for_expression!(operation => DivAssign::div_assign, Div::div);
+        impl_binary_assign_operation_for_expression!(operation => MulAssign::mul_assign, Mul::mul);
+        impl_binary_assign_operation_for_expression!(operation => RemAssign::rem_assign, Rem::rem);
+        impl_binary_assign_operation_for_expression!(operation => SubAssign::sub_assign, Sub::sub);
+    };
+    (operation => $trait:ident :: $method:ident, $operation:ident :: $f:ident) => {
+        impl<T, C, R> $trait<R> for ExpressionFor<Constrained<T, C>>
+        where
+            Self: $operation<R, Output = Self>,
+            T: Primitive,
+            C: Constraint,
+            C::Divergence: Divergence<Continue = AsExpression>,
+        {
+            fn $method(&mut self, other: R) {
+                // The placeholder is never observed: it is immediately replaced by the output.
+                let expression = mem::replace(self, Defined(Constrained::unchecked(T::ZERO)));
+                *self = $operation::$f(expression, other);
+            }
+        }
+    };
+}
+impl_binary_assign_operation_for_expression!();
+
 macro_rules! impl_try_from_for_expression {
     () => {
         with_primitives!(impl_try_from_for_expression);
@@ -967,3 +2113,447 @@ macro_rules! impl_try_from_for_expression {
     };
 }
 impl_try_from_for_expression!();
+
+#[cfg(test)]
+mod tests {
+    use crate::constraint::NotRealError;
+    use crate::expression::{Defined, Expression, Undefined};
+    use crate::real::UnaryRealFunction;
+    use crate::R64;
+
+    #[test]
+    fn option_conversion() {
+        let x: Expression<f64, NotRealError> = Some(1.0).into();
+        assert!(matches!(x, Defined(_)));
+        let x: Expression<f64, NotRealError> = None.into();
+        assert!(matches!(x, Undefined(NotRealError)));
+
+        assert_eq!(
+            Option::<f64>::from(Defined::<_, NotRealError>(1.0)),
+            Some(1.0)
+        );
+        assert_eq!(Option::<f64>::from(Undefined::<f64, _>(NotRealError)), None);
+
+        assert_eq!(Defined::<_, NotRealError>(1.0).ok(), Some(1.0));
+        assert_eq!(Undefined::<f64, _>(NotRealError).ok(), None);
+
+        let x: Expression<Option<f64>, NotRealError> = Defined(Some(1.0));
+        assert!(matches!(x.ok_or_undefined(NotRealError), Defined(_)));
+        let x: Expression<Option<f64>, NotRealError> = Defined(None);
+        assert!(matches!(x.ok_or_undefined(NotRealError), Undefined(_)));
+    }
+
+    #[test]
+    fn combinators() {
+        let defined = || Defined::<f64, Option<NotRealError>>(1.0);
+        let undefined = || Undefined::<f64, _>(Some(NotRealError));
+
+        assert_eq!(defined().and(Defined(2.0)).defined(), Some(2.0));
+        assert!(undefined().and(Defined(2.0)).is_undefined());
+        assert_eq!(defined().or(Defined(2.0)).defined(), Some(1.0));
+        assert_eq!(undefined().or(Defined(2.0)).defined(), Some(2.0));
+
+        assert_eq!(defined().xor(undefined()).defined(), Some(1.0));
+        assert_eq!(undefined().xor(defined()).defined(), Some(1.0));
+        assert!(matches!(defined().xor(defined()), Undefined(None)));
+        assert!(matches!(undefined().xor(undefined()), Undefined(Some(_))));
+
+        assert_eq!(defined().filter(|x| *x > 0.0).defined(), Some(1.0));
+        assert!(matches!(defined().filter(|x| *x < 0.0), Undefined(None)));
+        assert!(matches!(
+            undefined().filter(|x| *x > 0.0),
+            Undefined(Some(_))
+        ));
+    }
+
+    #[test]
+    fn transpose() {
+        assert!(matches!(
+            Defined::<_, NotRealError>(Some(1.0)).transpose(),
+            Some(Defined(_))
+        ));
+        assert!(Defined::<Option<f64>, NotRealError>(None)
+            .transpose()
+            .is_none());
+        assert!(matches!(
+            Undefined::<Option<f64>, _>(NotRealError).transpose(),
+            Some(Undefined(NotRealError))
+        ));
+    }
+
+    #[test]
+    fn transpose_option() {
+        use crate::expression::TransposeOption;
+
+        assert!(matches!(
+            Some(Defined::<_, NotRealError>(1.0)).transpose(),
+            Defined(Some(_))
+        ));
+        assert!(matches!(
+            Some(Undefined::<f64, _>(NotRealError)).transpose(),
+            Undefined(NotRealError)
+        ));
+        assert!(matches!(
+            None::<Expression<f64, NotRealError>>.transpose(),
+            Defined(None)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        use std::format;
+
+        let x: Expression<R64, NotRealError> = Defined(R64::ONE);
+        assert_eq!(format!("{}", x), "1");
+        assert_eq!(format!("{:.2}", x), "1.00");
+
+        let x: Expression<R64, NotRealError> = Undefined(NotRealError);
+        assert_eq!(format!("{}", x), "floating-point value must be a real");
+        assert_eq!(format!("{:#}", x), "NaN");
+        assert_eq!(format!("{:>4}", x.display_undefined_as("-")), "   -");
+    }
+
+    #[test]
+    fn get_expr() {
+        use crate::expression::{IndexError, SliceExpression};
+
+        let mut xs = [<R64>::ZERO, R64::ONE];
+        assert_eq!(xs.get_expr(1), Defined(R64::ONE));
+        assert!(matches!(
+            xs.get_expr(2),
+            Undefined(IndexError { index: 2, len: 2 })
+        ));
+        if let Defined(x) = xs.get_expr_mut(0) {
+            *x = R64::ONE;
+        }
+        assert_eq!(xs, [R64::ONE, R64::ONE]);
+        assert!(xs[..0].get_expr_mut(0).is_undefined());
+    }
+
+    #[test]
+    fn unwrap_or() {
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::ONE / R64::ZERO;
+        assert_eq!(x.unwrap_or(R64::ONE), R64::ONE);
+        assert_eq!(x.unwrap_or_else(|_| R64::ONE), R64::ONE);
+        assert_eq!(x.unwrap_or_default(), R64::ZERO);
+        assert_eq!((R64::<OrError>::ONE + R64::ONE).unwrap_or(R64::ZERO), 2.0);
+    }
+
+    #[test]
+    fn or_else_map_undefined() {
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::ONE / R64::ZERO;
+        assert_eq!(x.map_undefined(|_| 1u8).undefined(), Some(1u8));
+        assert_eq!(
+            x.or_else(|_| Expression::<_, ()>::Defined(R64::ONE))
+                .unwrap(),
+            1.0
+        );
+        assert!(x
+            .or_else(|_| Expression::<R64<OrError>, _>::Undefined(()))
+            .is_undefined());
+        assert_eq!(
+            (R64::<OrError>::ONE + R64::ONE)
+                .map_undefined(|_| ())
+                .unwrap(),
+            2.0
+        );
+    }
+
+    #[test]
+    fn inspect() {
+        use crate::divergence::OrError;
+
+        let mut defined = None;
+        let mut undefined = false;
+        let x = (R64::<OrError>::ONE + R64::ONE)
+            .inspect(|x| defined = Some(*x))
+            .inspect_undefined(|_| undefined = true);
+        assert_eq!(defined, Some(x.unwrap()));
+        assert!(!undefined);
+        let _ = (R64::<OrError>::ONE / R64::ZERO).inspect_undefined(|_| undefined = true);
+        assert!(undefined);
+    }
+
+    #[test]
+    fn flatten() {
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::ONE + R64::ONE;
+        assert_eq!(x.map(|x| x / R64::ONE).flatten().unwrap(), 2.0);
+        assert!(x.map(|x| x / R64::ZERO).flatten().is_undefined());
+        assert!((R64::<OrError>::ONE / R64::ZERO)
+            .map(|x| x / R64::ONE)
+            .flatten()
+            .is_undefined());
+    }
+
+    #[test]
+    fn cmp_proxy() {
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::ONE + R64::ONE;
+        assert!(x > R64::ONE);
+        assert!(x == 2.0);
+        assert!(x < 3.0);
+        assert!(R64::ONE < x);
+        assert!(R64::assert(2.0) == x);
+
+        let y = R64::<OrError>::ONE / R64::ZERO;
+        assert!(y != R64::ONE);
+        assert!(R64::ONE != y);
+        assert_eq!(y.partial_cmp(&R64::ZERO), None);
+        assert_eq!(R64::ONE.partial_cmp(&y), None);
+    }
+
+    #[test]
+    fn zip() {
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::ONE + R64::ONE;
+        let y = R64::<OrError>::ONE / R64::ZERO;
+        assert_eq!(x.zip(x).unwrap(), (R64::assert(2.0), R64::assert(2.0)));
+        assert!(x.zip(y).is_undefined());
+        assert!(y.zip(x).is_undefined());
+        assert_eq!(x.zip_with(x, |x, y| x * y).unwrap(), 4.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_iter() {
+        use std::vec::Vec;
+
+        use crate::divergence::OrError;
+
+        let xs = [1.0, 2.0, 0.0, 4.0].map(R64::<OrError>::assert);
+        let ys: Expression<Vec<_>, _> = xs[..2].iter().map(|x| R64::ONE / *x).collect();
+        assert_eq!(ys.unwrap(), [1.0, 0.5]);
+        let mut count = 0;
+        let ys: Expression<Vec<_>, _> = xs
+            .iter()
+            .inspect(|_| count += 1)
+            .map(|x| R64::ONE / *x)
+            .collect();
+        assert!(ys.is_undefined());
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn sum_product() {
+        use crate::divergence::OrError;
+        use crate::proxy::ExpressionFor;
+
+        let xs = [1.0, 2.0, 3.0].map(R64::<OrError>::assert);
+        assert_eq!(xs.iter().sum::<ExpressionFor<R64<OrError>>>().unwrap(), 6.0);
+        assert_eq!(
+            xs.into_iter()
+                .product::<ExpressionFor<R64<OrError>>>()
+                .unwrap(),
+            6.0
+        );
+
+        let xs = [f64::MAX, f64::MAX].map(R64::<OrError>::assert);
+        assert!(xs
+            .iter()
+            .sum::<ExpressionFor<R64<OrError>>>()
+            .is_undefined());
+        let ys = [
+            R64::<OrError>::ONE / R64::ZERO,
+            R64::<OrError>::ONE + R64::ONE,
+        ];
+        assert!(ys
+            .into_iter()
+            .product::<ExpressionFor<R64<OrError>>>()
+            .is_undefined());
+    }
+
+    #[test]
+    fn default() {
+        use crate::divergence::OrError;
+        use crate::proxy::ExpressionFor;
+
+        let x = ExpressionFor::<R64<OrError>>::default();
+        assert_eq!(x, Defined(R64::ZERO));
+    }
+
+    #[test]
+    fn result_interop() {
+        use core::ops::ControlFlow;
+
+        use crate::divergence::OrError;
+
+        fn f(x: R64<OrError>) -> Result<R64<OrError>, NotRealError> {
+            let y = (R64::ONE / x).try_defined()?;
+            Ok(-y)
+        }
+
+        assert_eq!(f(R64::assert(2.0)).ok(), Some(R64::assert(-0.5)));
+        assert!(f(R64::ZERO).is_err());
+
+        let x: ControlFlow<_, _> = (R64::<OrError>::ONE / R64::ZERO).into();
+        assert!(x.is_break());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn try_expression_map() {
+        use crate::divergence::OrError;
+
+        fn f(x: R64<OrError>) -> Expression<R64<OrError>, Option<NotRealError>> {
+            let y = crate::try_expression!(R64::ONE / x, |_| None);
+            let z = crate::try_expression!(y.sqrt(), Some);
+            Defined(z)
+        }
+
+        assert_eq!(f(R64::assert(0.25)).defined(), Some(R64::assert(2.0)));
+        assert!(matches!(f(R64::ZERO), Undefined(None)));
+        assert!(matches!(f(-R64::ONE), Undefined(Some(_))));
+    }
+
+    #[cfg(all(feature = "derive", feature = "std"))]
+    #[test]
+    fn expression_macro() {
+        use crate::divergence::OrError;
+        use crate::expression::expression;
+
+        fn f(x: R64<OrError>, y: f64) -> Expression<f64, Option<NotRealError>> {
+            Defined(expression!(-(R64::ONE / x).ln().into_inner() + (y % 2.0)))
+        }
+
+        assert_eq!(f(R64::ONE, 3.0).defined(), Some(1.0));
+        assert!(matches!(f(R64::ZERO, 3.0), Undefined(Some(_))));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn zip_accumulate() {
+        use std::string::ToString;
+
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::ONE / R64::ZERO;
+        let y = R64::<OrError>::assert(4.0).sqrt();
+        let z = (-R64::<OrError>::ONE).sqrt();
+
+        let xyz = x.accumulate().zip_accumulate(y).zip_accumulate(z);
+        let errors = xyz.clone().undefined().unwrap();
+        assert_eq!(errors.errors().len(), 2);
+        assert_eq!(
+            errors.to_string(),
+            "floating-point value must be a real; floating-point value must be a real"
+        );
+
+        let xy = x.accumulate().zip_accumulate(y);
+        assert_eq!(
+            xy.zip_accumulate(xyz)
+                .undefined()
+                .unwrap()
+                .into_iter()
+                .count(),
+            3
+        );
+        assert_eq!(
+            y.accumulate().zip_accumulate(y).defined(),
+            Some((R64::assert(2.0), R64::assert(2.0)))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn termination() -> Expression<(), NotRealError> {
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::assert(2.0);
+        let _ = crate::try_expression!(x / x);
+        Defined(())
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_key() {
+        use std::collections::HashMap;
+
+        use crate::cmp::UndefinedGreatest;
+        use crate::divergence::OrError;
+
+        let x = R64::<OrError>::assert(0.0);
+        let mut counts = HashMap::new();
+        for expression in [
+            Defined(x),
+            Defined(-x),
+            x / x,
+            R64::ONE / x,
+            Defined(R64::ONE),
+        ] {
+            *counts
+                .entry(UndefinedGreatest(expression))
+                .or_insert(0usize) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&UndefinedGreatest(Defined(x))], 2);
+        assert_eq!(counts[&UndefinedGreatest(x / x)], 2);
+        assert_eq!(counts[&UndefinedGreatest(Defined(R64::ONE))], 1);
+    }
+
+    #[test]
+    fn reference_operations() {
+        use crate::divergence::OrError;
+        use crate::proxy::ExpressionFor;
+
+        let xs: [ExpressionFor<R64<OrError>>; 2] =
+            [Defined(R64::assert(2.0)), Defined(R64::assert(3.0))];
+        let ys = [4.0, 2.0].map(R64::<OrError>::assert);
+        let mut zs = xs.iter().zip(xs.iter().rev()).map(|(a, b)| a * b);
+        assert_eq!(zs.next().unwrap().unwrap(), 6.0);
+        let mut zs = xs.iter().zip(ys.iter()).map(|(x, y)| y / x);
+        assert_eq!(zs.nth(1).unwrap().unwrap(), 2.0 / 3.0);
+        let mut zs = xs.iter().zip(ys.iter()).map(|(x, y)| x % y);
+        assert_eq!(zs.nth(1).unwrap().unwrap(), 1.0);
+        assert_eq!(
+            xs.iter()
+                .map(|x| x - 1.0)
+                .sum::<ExpressionFor<R64<OrError>>>()
+                .unwrap(),
+            3.0
+        );
+        assert_eq!(
+            xs.iter()
+                .map(|x| x + ys[0])
+                .sum::<ExpressionFor<R64<OrError>>>()
+                .unwrap(),
+            13.0
+        );
+    }
+
+    #[test]
+    fn assign() {
+        use crate::divergence::OrError;
+        use crate::proxy::ExpressionFor;
+
+        let mut x: ExpressionFor<R64<OrError>> = Defined(R64::assert(1.0));
+        x += R64::assert(2.0);
+        x *= Defined(R64::assert(3.0));
+        x -= 1.0;
+        x /= R64::assert(4.0);
+        x %= 2.0;
+        assert_eq!(x.unwrap(), 0.0);
+
+        x += R64::ONE;
+        x /= R64::ZERO;
+        assert!(x.is_undefined());
+        x += R64::ONE;
+        assert!(x.is_undefined());
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero: NotRealError")]
+    fn expect_undefined() {
+        use crate::divergence::OrError;
+
+        let _ = (R64::<OrError>::ONE / R64::ZERO).expect("division by zero");
+    }
+}
@@ -0,0 +1,225 @@
+//! Adapters for futures of [`Expression`]s.
+//!
+//! [`ExpressionFuture`] is implemented by any [`Future`] that outputs an [`Expression`] and
+//! provides combinators that operate on the defined output of the future, much like the
+//! combinators of [`Expression`] itself. This allows asynchronous computations over
+//! [`Constrained`] types to be chained without matching and rewrapping [`Expression`]s after each
+//! `.await`.
+//!
+//! These adapters only depend on [`core::future`] and are compatible with any executor.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::constraint::IsReal;
+//! use decorum::divergence::OrError;
+//! use decorum::expression::future::ExpressionFuture;
+//! use decorum::proxy::{Constrained, OutputFor};
+//! use decorum::real::UnaryRealFunction;
+//!
+//! pub type Real = Constrained<f64, IsReal<OrError>>;
+//! pub type Expr = OutputFor<Real>;
+//!
+//! async fn load() -> Expr {
+//!     Real::ONE + Real::ONE
+//! }
+//!
+//! async fn reciprocal(x: Real) -> Expr {
+//!     Real::ONE / x
+//! }
+//!
+//! // The output of `future` is an `Expression<f64, _>`.
+//! let future = load().and_then(reciprocal).map_defined(Real::into_inner);
+//! # let _ = future;
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Expression`]: crate::expression::Expression
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::expression::Expression::{self, Defined, Undefined};
+
+/// Extension trait for futures that output an [`Expression`].
+///
+/// [`Expression`]: crate::expression::Expression
+pub trait ExpressionFuture<T, E>: Future<Output = Expression<T, E>> {
+    /// Maps the defined output of the future with the given function.
+    ///
+    /// The function is not called if the output is undefined.
+    fn map_defined<U, F>(self, f: F) -> MapDefined<Self, F>
+    where
+        Self: Sized,
+        F: FnOnce(T) -> U,
+    {
+        MapDefined {
+            future: self,
+            f: Some(f),
+        }
+    }
+
+    /// Chains the defined output of the future into another future of an [`Expression`].
+    ///
+    /// The function is not called if the output is undefined, in which case the output of the
+    /// returned future is the undefined output.
+    ///
+    /// [`Expression`]: crate::expression::Expression
+    fn and_then<U, F, R>(self, f: F) -> AndThen<Self, F, R>
+    where
+        Self: Sized,
+        F: FnOnce(T) -> R,
+        R: Future<Output = Expression<U, E>>,
+    {
+        AndThen {
+            state: AndThenState::First {
+                future: self,
+                f: Some(f),
+            },
+        }
+    }
+}
+
+impl<T, E, F> ExpressionFuture<T, E> for F where F: Future<Output = Expression<T, E>> {}
+
+/// Future returned by [`ExpressionFuture::map_defined`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct MapDefined<R, F> {
+    future: R,
+    f: Option<F>,
+}
+
+impl<T, U, E, R, F> Future for MapDefined<R, F>
+where
+    R: Future<Output = Expression<T, E>>,
+    F: FnOnce(T) -> U,
+{
+    type Output = Expression<U, E>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is structurally pinned and is never moved out of `self`. `f` is not
+        //         pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        future.poll(context).map(|output| {
+            let f = this.f.take().expect("`MapDefined` polled after completion");
+            output.map(f)
+        })
+    }
+}
+
+/// Future returned by [`ExpressionFuture::and_then`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct AndThen<R, F, S> {
+    state: AndThenState<R, F, S>,
+}
+
+#[derive(Debug)]
+enum AndThenState<R, F, S> {
+    First { future: R, f: Option<F> },
+    Second { future: S },
+    Complete,
+}
+
+impl<T, U, E, R, F, S> Future for AndThen<R, F, S>
+where
+    R: Future<Output = Expression<T, E>>,
+    F: FnOnce(T) -> S,
+    S: Future<Output = Expression<U, E>>,
+{
+    type Output = Expression<U, E>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: the futures in `state` are structurally pinned. They are only dropped in place
+        //         by assigning to `state` and are never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        loop {
+            match &mut this.state {
+                AndThenState::First { future, f } => {
+                    let output = match unsafe { Pin::new_unchecked(future) }.poll(context) {
+                        Poll::Ready(output) => output,
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    let f = f.take().expect("`AndThen` polled after completion");
+                    match output {
+                        Defined(defined) => {
+                            this.state = AndThenState::Second { future: f(defined) };
+                        }
+                        Undefined(undefined) => {
+                            this.state = AndThenState::Complete;
+                            return Poll::Ready(Undefined(undefined));
+                        }
+                    }
+                }
+                AndThenState::Second { future } => {
+                    let output = match unsafe { Pin::new_unchecked(future) }.poll(context) {
+                        Poll::Ready(output) => output,
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    this.state = AndThenState::Complete;
+                    return Poll::Ready(output);
+                }
+                AndThenState::Complete => panic!("`AndThen` polled after completion"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::{self, Future};
+    use core::pin::pin;
+    use core::ptr;
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    use crate::constraint::IsReal;
+    use crate::divergence::OrError;
+    use crate::expression::future::ExpressionFuture;
+    use crate::expression::Expression;
+    use crate::proxy::{Constrained, OutputFor};
+    use crate::real::UnaryRealFunction;
+
+    type Real = Constrained<f64, IsReal<OrError>>;
+    type Expr = OutputFor<Real>;
+
+    // Polls a future that is immediately ready.
+    fn ready<F>(future: F) -> F::Output
+    where
+        F: Future,
+    {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(ptr::null(), &VTABLE),
+            |_| {},
+            |_| {},
+            |_| {},
+        );
+        let waker = unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) };
+        let mut context = Context::from_waker(&waker);
+        match pin!(future).poll(&mut context) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future is not ready"),
+        }
+    }
+
+    #[test]
+    fn map_defined() {
+        let x = ready(future::ready(Expr::from(Real::ONE)).map_defined(Real::into_inner));
+        assert_eq!(x.unwrap(), 1.0);
+
+        let x = ready(future::ready(Real::ONE / Real::ZERO).map_defined(Real::into_inner));
+        assert!(x.is_undefined());
+    }
+
+    #[test]
+    fn and_then() {
+        let reciprocal = |x: Real| future::ready(Real::ONE / x);
+        let x: Expr = ready(future::ready(Expression::from(Real::ONE)).and_then(reciprocal));
+        assert_eq!(x.unwrap(), 1.0);
+
+        let x: Expr = ready(future::ready(Expression::from(Real::ZERO)).and_then(reciprocal));
+        assert!(x.is_undefined());
+    }
+}
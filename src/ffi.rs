@@ -0,0 +1,227 @@
+//! C foreign function interface.
+//!
+//! This module provides FFI-safe type definitions and `extern "C"` functions that construct and
+//! validate [`Constrained`] values, so that C and C++ callers can exchange these values with Rust
+//! code across the FFI boundary. [`Constrained`] types are `#[repr(transparent)]`, so each type
+//! definition has the same layout and ABI as its primitive floating-point type (e.g., `float` or
+//! `double`). Validating constructors return a [`Status`] code rather than diverging and never
+//! panic.
+//!
+//! The exported symbols are prefixed with `decorum_` and the types and functions in this module
+//! are compatible with [`cbindgen`]. To call these functions from C, link against a `staticlib` or
+//! `cdylib` crate that depends on `decorum` with the `ffi` feature.
+//!
+//! # Examples
+//!
+//! Constructing a real number from C:
+//!
+//! ```c
+//! double x = 1.0;
+//! Real64 y;
+//! if (decorum_real_f64_new(x, &y) == Status_Ok) {
+//!     // ...
+//! }
+//! ```
+//!
+//! [`cbindgen`]: https://crates.io/crates/cbindgen
+//! [`Constrained`]: crate::proxy::Constrained
+
+use crate::constraint::{Constraint, ConstraintError, NotExtendedRealError, NotRealError};
+use crate::proxy::Constrained;
+use crate::{ExtendedReal, Primitive, Real, Total};
+
+/// FFI-safe 32-bit floating-point representation with no constraints.
+pub type Total32 = Total<f32>;
+/// FFI-safe 64-bit floating-point representation with no constraints.
+pub type Total64 = Total<f64>;
+/// FFI-safe 32-bit floating-point representation that must be an extended real.
+pub type ExtendedReal32 = ExtendedReal<f32>;
+/// FFI-safe 64-bit floating-point representation that must be an extended real.
+pub type ExtendedReal64 = ExtendedReal<f64>;
+/// FFI-safe 32-bit floating-point representation that must be a real number.
+pub type Real32 = Real<f32>;
+/// FFI-safe 64-bit floating-point representation that must be a real number.
+pub type Real64 = Real<f64>;
+
+/// Status code of FFI functions.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub enum Status {
+    /// The operation succeeded.
+    Ok = 0,
+    /// A value is not an extended real (it is `NaN`).
+    NotExtendedReal = 1,
+    /// A value is not a real number (it is `NaN` or infinite).
+    NotReal = 2,
+    /// A pointer is null.
+    NullPointer = 3,
+}
+
+impl From<ConstraintError> for Status {
+    fn from(error: ConstraintError) -> Self {
+        match error {
+            ConstraintError::NotExtendedReal(error) => error.into(),
+            ConstraintError::NotReal(error) => error.into(),
+        }
+    }
+}
+
+impl From<NotExtendedRealError> for Status {
+    fn from(_: NotExtendedRealError) -> Self {
+        Status::NotExtendedReal
+    }
+}
+
+impl From<NotRealError> for Status {
+    fn from(_: NotRealError) -> Self {
+        Status::NotReal
+    }
+}
+
+// Writes a validated proxy to `output` if it is not null.
+//
+// # Safety
+//
+// `output` must be null or valid for writes.
+unsafe fn write<T, C>(inner: T, output: *mut Constrained<T, C>) -> Status
+where
+    T: Primitive,
+    C: Constraint,
+    C::Error: Into<Status>,
+{
+    if output.is_null() {
+        return Status::NullPointer;
+    }
+    match Constrained::<T, C>::try_new(inner) {
+        Ok(proxy) => {
+            output.write(proxy);
+            Status::Ok
+        }
+        Err(error) => error.into(),
+    }
+}
+
+macro_rules! impl_ffi_for_primitive {
+    (
+        primitive => $t:ty,
+        total => $total:ident,
+        extended_real => $extended_real:ident,
+        real => $real:ident,
+        new_total => $new_total:ident,
+        new_extended_real => $new_extended_real:ident,
+        new_real => $new_real:ident,
+        check_extended_real => $check_extended_real:ident,
+        check_real => $check_real:ident $(,)?
+    ) => {
+        /// Constructs a total floating-point value. This function is infallible.
+        #[no_mangle]
+        pub extern "C" fn $new_total(inner: $t) -> $total {
+            Total::from(inner)
+        }
+
+        /// Constructs an extended real and writes it to `output`.
+        ///
+        /// Returns [`Status::NotExtendedReal`] if `inner` is `NaN` and [`Status::NullPointer`] if
+        /// `output` is null. `output` is not written unless the status is [`Status::Ok`].
+        ///
+        /// # Safety
+        ///
+        /// `output` must be null or valid for writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $new_extended_real(
+            inner: $t,
+            output: *mut $extended_real,
+        ) -> Status {
+            write(inner, output)
+        }
+
+        /// Constructs a real number and writes it to `output`.
+        ///
+        /// Returns [`Status::NotReal`] if `inner` is `NaN` or infinite and
+        /// [`Status::NullPointer`] if `output` is null. `output` is not written unless the status
+        /// is [`Status::Ok`].
+        ///
+        /// # Safety
+        ///
+        /// `output` must be null or valid for writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $new_real(inner: $t, output: *mut $real) -> Status {
+            write(inner, output)
+        }
+
+        /// Checks that `inner` is an extended real.
+        #[no_mangle]
+        pub extern "C" fn $check_extended_real(inner: $t) -> Status {
+            match $extended_real::try_new(inner) {
+                Ok(_) => Status::Ok,
+                Err(error) => error.into(),
+            }
+        }
+
+        /// Checks that `inner` is a real number.
+        #[no_mangle]
+        pub extern "C" fn $check_real(inner: $t) -> Status {
+            match $real::try_new(inner) {
+                Ok(_) => Status::Ok,
+                Err(error) => error.into(),
+            }
+        }
+    };
+}
+impl_ffi_for_primitive!(
+    primitive => f32,
+    total => Total32,
+    extended_real => ExtendedReal32,
+    real => Real32,
+    new_total => decorum_total_f32_new,
+    new_extended_real => decorum_extended_real_f32_new,
+    new_real => decorum_real_f32_new,
+    check_extended_real => decorum_extended_real_f32_check,
+    check_real => decorum_real_f32_check,
+);
+impl_ffi_for_primitive!(
+    primitive => f64,
+    total => Total64,
+    extended_real => ExtendedReal64,
+    real => Real64,
+    new_total => decorum_total_f64_new,
+    new_extended_real => decorum_extended_real_f64_new,
+    new_real => decorum_real_f64_new,
+    check_extended_real => decorum_extended_real_f64_check,
+    check_real => decorum_real_f64_check,
+);
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+    use core::ptr;
+
+    use crate::ffi::{self, Real64, Status};
+
+    #[test]
+    fn new_real() {
+        let mut output = MaybeUninit::<Real64>::uninit();
+        let status = unsafe { ffi::decorum_real_f64_new(1.0, output.as_mut_ptr()) };
+        assert_eq!(status, Status::Ok);
+        assert_eq!(unsafe { output.assume_init() }, 1.0);
+
+        let status = unsafe { ffi::decorum_real_f64_new(f64::INFINITY, output.as_mut_ptr()) };
+        assert_eq!(status, Status::NotReal);
+        let status = unsafe { ffi::decorum_real_f64_new(1.0, ptr::null_mut()) };
+        assert_eq!(status, Status::NullPointer);
+    }
+
+    #[test]
+    fn check() {
+        assert_eq!(
+            ffi::decorum_extended_real_f32_check(f32::INFINITY),
+            Status::Ok
+        );
+        assert_eq!(
+            ffi::decorum_extended_real_f32_check(f32::NAN),
+            Status::NotExtendedReal
+        );
+        assert_eq!(ffi::decorum_real_f32_check(f32::INFINITY), Status::NotReal);
+        assert!(ffi::decorum_total_f32_new(f32::NAN).into_inner().is_nan());
+    }
+}
@@ -0,0 +1,317 @@
+//! Formatting utilities for debugging.
+//!
+//! This module provides [`Grid`], which formats buffers of [`Constrained`] values as aligned
+//! columns. Entries that are not finite or are subnormal are enclosed in brackets, which makes
+//! them easy to spot when debugging numeric kernels (e.g., with [`Total`] buffers).
+//!
+//! [`Constrained::debug_with`] formats a single value with a [`DebugPolicy`], which can show the
+//! bit pattern of the value and always shows the full constraint (including its divergence).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::fmt::Grid;
+//! use decorum::Total;
+//!
+//! let xs: [Total<f64>; 4] = [1.0, -10.5, f64::INFINITY, 2.25].map(Total::from);
+//! assert_eq!(
+//!     Grid::new(&xs).with_columns(2).to_string(),
+//!     "     1   -10.5\n  [inf]   2.25",
+//! );
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Constrained::debug_with`]: crate::proxy::Constrained::debug_with
+//! [`Total`]: crate::Total
+
+use core::fmt::{self, Debug, Display, Formatter, Write};
+use core::num::FpCategory;
+
+use crate::bits::Bits;
+use crate::constraint::Constraint;
+use crate::proxy::Constrained;
+use crate::sealed::StaticDebug;
+use crate::{BaseEncoding, Primitive};
+
+/// Determines how [`DebugWith`] formats the value of a [`Constrained`].
+///
+/// [`Constrained`]: crate::proxy::Constrained
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DebugPolicy {
+    /// Formats the value, such as `1.5`.
+    #[default]
+    Value,
+    /// Formats the bits of the IEEE 754 encoding of the value, such as `0x3ff8000000000000`.
+    Bits,
+    /// Formats both the value and its bits, such as `1.5 (0x3ff8000000000000)`.
+    Both,
+}
+
+/// Formats a [`Constrained`] with a [`DebugPolicy`] and the name of its constraint.
+///
+/// See [`Constrained::debug_with`].
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`Constrained::debug_with`]: crate::proxy::Constrained::debug_with
+pub struct DebugWith<'a, T, C> {
+    proxy: &'a Constrained<T, C>,
+    policy: DebugPolicy,
+}
+
+impl<T, C> Debug for DebugWith<'_, T, C>
+where
+    T: Bits + Debug,
+    C: Constraint,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let inner = self.proxy.into_inner();
+        let bits = (u64::from(inner.sign_field()) << (T::EXPONENT_WIDTH + T::MANTISSA_WIDTH))
+            | (u64::from(inner.exponent_field()) << T::MANTISSA_WIDTH)
+            | inner.mantissa_field();
+        let width = (1 + T::EXPONENT_WIDTH + T::MANTISSA_WIDTH) as usize / 4;
+        <C as StaticDebug>::fmt(formatter)?;
+        formatter.write_char('(')?;
+        match self.policy {
+            DebugPolicy::Value => write!(formatter, "{:?}", inner)?,
+            DebugPolicy::Bits => write!(formatter, "0x{:0width$x}", bits, width = width)?,
+            DebugPolicy::Both => write!(
+                formatter,
+                "{:?} (0x{:0width$x})",
+                inner,
+                bits,
+                width = width,
+            )?,
+        }
+        formatter.write_char(')')
+    }
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Bits + Debug,
+    C: Constraint,
+{
+    /// Formats the proxy with the given [`DebugPolicy`].
+    ///
+    /// Unlike the `Debug` implementation of `Constrained`, the output includes the full
+    /// constraint and divergence of the proxy and can include the bits of its encoding, which is
+    /// useful for distinguishing values like `-0.0` and `NaN` payloads in assertion failures.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::fmt::DebugPolicy;
+    /// use decorum::R64;
+    ///
+    /// let x: R64 = R64::assert(1.5);
+    /// assert_eq!(format!("{:?}", x), "Real(1.5)");
+    /// assert_eq!(
+    ///     format!("{:?}", x.debug_with(DebugPolicy::Both)),
+    ///     "IsReal<OrPanic<AsSelf>>(1.5 (0x3ff8000000000000))",
+    /// );
+    /// ```
+    pub fn debug_with(&self, policy: DebugPolicy) -> DebugWith<'_, T, C> {
+        DebugWith {
+            proxy: self,
+            policy,
+        }
+    }
+}
+
+/// Formats a slice of [`Constrained`] values as a grid with aligned columns.
+///
+/// By default, a grid has a single row. [`Grid::with_columns`] arranges the slice in rows of the
+/// given length in row-major order, so a grid can also format a 2D view of a buffer. The final row
+/// may be shorter than the others.
+///
+/// All entries are right-aligned to the width of the widest entry. Entries that are not finite
+/// (infinities and `NaN`s) or are subnormal are enclosed in brackets.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub struct Grid<'a, T, C> {
+    entries: &'a [Constrained<T, C>],
+    columns: usize,
+    precision: Option<usize>,
+}
+
+impl<'a, T, C> Grid<'a, T, C>
+where
+    T: Display + Primitive,
+    C: Constraint,
+{
+    pub fn new(entries: &'a [Constrained<T, C>]) -> Self {
+        Grid {
+            entries,
+            columns: entries.len().max(1),
+            precision: None,
+        }
+    }
+
+    /// Arranges the entries of the grid in rows with the given number of columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is zero.
+    pub fn with_columns(self, columns: usize) -> Self {
+        assert!(columns > 0, "grid must have at least one column");
+        Grid { columns, ..self }
+    }
+
+    /// Formats entries with the given number of digits after the decimal point.
+    pub fn with_precision(self, precision: usize) -> Self {
+        Grid {
+            precision: Some(precision),
+            ..self
+        }
+    }
+
+    fn write_entry<W>(&self, writer: &mut W, entry: T) -> fmt::Result
+    where
+        W: Write,
+    {
+        match self.precision {
+            Some(precision) => write!(writer, "{:.*}", precision, entry),
+            _ => write!(writer, "{}", entry),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let mut counter = Counter(0);
+                // Writing to a `Counter` never fails.
+                let _ = self.write_entry(&mut counter, entry.into_inner());
+                counter.0
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<T, C> Display for Grid<'_, T, C>
+where
+    T: Display + Primitive,
+    C: Constraint,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let width = self.width();
+        for (n, row) in self.entries.chunks(self.columns).enumerate() {
+            if n > 0 {
+                formatter.write_char('\n')?;
+            }
+            for (m, entry) in row.iter().enumerate() {
+                let entry = entry.into_inner();
+                let mut counter = Counter(0);
+                self.write_entry(&mut counter, entry)?;
+                let (open, close) = if is_highlighted(entry) {
+                    ('[', ']')
+                }
+                else {
+                    (' ', ' ')
+                };
+                if m > 0 {
+                    formatter.write_char(' ')?;
+                }
+                for _ in counter.0..width {
+                    formatter.write_char(' ')?;
+                }
+                formatter.write_char(open)?;
+                self.write_entry(formatter, entry)?;
+                // Omit trailing whitespace.
+                if close != ' ' || m + 1 < row.len() {
+                    formatter.write_char(close)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Counts the characters written to it.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.0 += text.chars().count();
+        Ok(())
+    }
+}
+
+fn is_highlighted<T>(x: T) -> bool
+where
+    T: Primitive,
+{
+    matches!(
+        BaseEncoding::classify(x),
+        FpCategory::Infinite | FpCategory::Nan | FpCategory::Subnormal
+    )
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::format;
+    use std::string::ToString;
+
+    use crate::fmt::{DebugPolicy, Grid};
+    use crate::{Total, R32, R64};
+
+    #[test]
+    fn debug_with() {
+        let x: R32 = R32::assert(-0.0);
+        assert_eq!(
+            format!("{:?}", x.debug_with(DebugPolicy::Value)),
+            "IsReal<OrPanic<AsSelf>>(-0.0)"
+        );
+        assert_eq!(
+            format!("{:?}", x.debug_with(DebugPolicy::Bits)),
+            "IsReal<OrPanic<AsSelf>>(0x80000000)"
+        );
+        let y: Total<f64> = Total::from(1.0);
+        assert_eq!(
+            format!("{:?}", y.debug_with(DebugPolicy::Both)),
+            "IsFloat(1.0 (0x3ff0000000000000))"
+        );
+    }
+
+    #[test]
+    fn grid_alignment() {
+        let xs: [Total<f64>; 6] = [1.0, -10.5, 100.0, 2.0, 0.5, -3.0].map(Total::from);
+        assert_eq!(
+            Grid::new(&xs).with_columns(3).to_string(),
+            "     1   -10.5     100\n     2     0.5      -3",
+        );
+        assert_eq!(
+            Grid::new(&xs).to_string(),
+            "     1   -10.5     100       2     0.5      -3"
+        );
+        assert_eq!(
+            Grid::new(&xs[..5]).with_columns(2).to_string(),
+            "     1   -10.5\n   100       2\n   0.5",
+        );
+    }
+
+    #[test]
+    fn grid_highlight() {
+        let xs: [Total<f64>; 4] = [1.0, f64::NAN, f64::NEG_INFINITY, 1.0e-310].map(Total::from);
+        assert_eq!(
+            Grid::new(&xs).with_columns(2).with_precision(1).to_string(),
+            "  1.0   [NaN]\n[-inf]  [0.0]",
+        );
+    }
+
+    #[test]
+    fn grid_precision() {
+        let xs: [R64; 2] = [1.0, 2.0 / 3.0].map(R64::assert);
+        assert_eq!(
+            Grid::new(&xs).with_precision(3).to_string(),
+            " 1.000   0.667"
+        );
+    }
+
+    #[test]
+    fn grid_empty() {
+        let xs: [R64; 0] = [];
+        assert_eq!(Grid::new(&xs).to_string(), "");
+    }
+}
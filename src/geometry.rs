@@ -0,0 +1,909 @@
+//! Robust geometric predicates and intersections over [`Constrained`] coordinates.
+//!
+//! This module provides predicates that determine the relative positions of points in the plane.
+//! Predicates are first computed with floating-point arithmetic and a forward error bound. If the
+//! error bound cannot guarantee the sign of the output, then the predicate falls back to exact
+//! arithmetic using floating-point expansions as described by Shewchuk in _Adaptive Precision
+//! Floating-Point Arithmetic and Fast Robust Geometric Predicates_. **The output of a predicate is
+//! always exact.**
+//!
+//! Predicates output an [`Expression`] of a [`Sign`]. Degenerate configurations (e.g., collinear
+//! points) are [`Sign::Zero`]. If any coordinate is not a real number or an intermediate
+//! computation overflows, then the output is [`Undefined`].
+//!
+//! This module also provides intersection queries between rays, segments, and axis-aligned
+//! bounding boxes. These queries output an [`Expression`] of the parameters of intersection and
+//! model disjoint and parallel geometry as [`Undefined`] with an [`IntersectionError`] rather than
+//! `NaN`s or sentinel values.
+//!
+//! # Examples
+//!
+//! Determining the orientation of a triangle:
+//!
+//! ```rust
+//! use decorum::geometry::{self, Point2};
+//! use decorum::real::Sign;
+//! use decorum::R64;
+//!
+//! let a: Point2<R64> = [R64::assert(0.0), R64::assert(0.0)];
+//! let b: Point2<R64> = [R64::assert(1.0), R64::assert(0.0)];
+//! let c: Point2<R64> = [R64::assert(0.0), R64::assert(1.0)];
+//!
+//! // The points `a`, `b`, and `c` are in counterclockwise order.
+//! assert_eq!(geometry::orient2d(a, b, c).unwrap(), Sign::Positive);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Undefined`]: crate::expression::Expression::Undefined
+
+use thiserror::Error;
+
+#[cfg(feature = "std")]
+use crate::arch;
+use crate::constraint::{Constraint, NotRealError};
+use crate::expression::{Defined, Expression, Undefined};
+use crate::proxy::Constrained;
+use crate::real::Sign;
+use crate::{try_expression, Primitive};
+
+/// A point in the plane.
+pub type Point2<P> = [P; 2];
+
+/// A pair of parameters that describes an intersection.
+pub type Parameters<P> = (P, P);
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum IntersectionError {
+    #[error("{}", "geometry does not intersect")]
+    Disjoint,
+    #[error("{}", "geometry is parallel")]
+    Parallel,
+    #[error("{}", "geometry overlaps")]
+    Overlapping,
+    #[error("{}", "geometry is degenerate")]
+    Degenerate,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for IntersectionError {
+    fn from(error: NotRealError) -> Self {
+        IntersectionError::NotReal(error)
+    }
+}
+
+/// Determines the orientation of the points `a`, `b`, and `c`.
+///
+/// The output is [`Sign::Positive`] if the points are in counterclockwise order,
+/// [`Sign::Negative`] if the points are in clockwise order, and [`Sign::Zero`] if the points are
+/// collinear. Equivalently, this is the sign of the determinant
+///
+/// $$
+/// \begin{vmatrix}
+/// a_x-c_x&a_y-c_y\cr
+/// b_x-c_x&b_y-c_y
+/// \end{vmatrix}
+/// $$
+///
+/// # Errors
+///
+/// Returns [`Undefined`] if any coordinate is not a real number or if the computation overflows.
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn orient2d<T, C>(
+    a: Point2<Constrained<T, C>>,
+    b: Point2<Constrained<T, C>>,
+    c: Point2<Constrained<T, C>>,
+) -> Expression<Sign, NotRealError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let [ax, ay] = try_expression!(into_primitives(a));
+    let [bx, by] = try_expression!(into_primitives(b));
+    let [cx, cy] = try_expression!(into_primitives(c));
+
+    let left = (ax - cx) * (by - cy);
+    let right = (ay - cy) * (bx - cx);
+    let determinant = left - right;
+    let bound = orient2d_error_bound::<T>() * (abs(left) + abs(right));
+    if abs(determinant) > bound {
+        return Defined(determinant.sign());
+    }
+
+    let splitter = splitter::<T>();
+    let acx = Expansion::<T, 2>::difference(ax, cx);
+    let acy = Expansion::<T, 2>::difference(ay, cy);
+    let bcx = Expansion::<T, 2>::difference(bx, cx);
+    let bcy = Expansion::<T, 2>::difference(by, cy);
+    let left = acx.product::<2, 8>(&bcy, splitter);
+    let right = acy.product::<2, 8>(&bcx, splitter);
+    left.sum::<8, 16>(&right.neg()).sign()
+}
+
+/// Determines the position of the point `d` with respect to the circle through the points `a`,
+/// `b`, and `c`.
+///
+/// The points `a`, `b`, and `c` must be in counterclockwise order (see [`orient2d`]), otherwise
+/// the sign of the output is reversed. The output is [`Sign::Positive`] if `d` lies inside of the
+/// circle, [`Sign::Negative`] if `d` lies outside of the circle, and [`Sign::Zero`] if the points
+/// are cocircular.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] if any coordinate is not a real number or if the computation overflows.
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn incircle<T, C>(
+    a: Point2<Constrained<T, C>>,
+    b: Point2<Constrained<T, C>>,
+    c: Point2<Constrained<T, C>>,
+    d: Point2<Constrained<T, C>>,
+) -> Expression<Sign, NotRealError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let [ax, ay] = try_expression!(into_primitives(a));
+    let [bx, by] = try_expression!(into_primitives(b));
+    let [cx, cy] = try_expression!(into_primitives(c));
+    let [dx, dy] = try_expression!(into_primitives(d));
+
+    let (adx, ady) = (ax - dx, ay - dy);
+    let (bdx, bdy) = (bx - dx, by - dy);
+    let (cdx, cdy) = (cx - dx, cy - dy);
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = (adx * adx) + (ady * ady);
+    let blift = (bdx * bdx) + (bdy * bdy);
+    let clift = (cdx * cdx) + (cdy * cdy);
+    let determinant =
+        (alift * (bdxcdy - cdxbdy)) + (blift * (cdxady - adxcdy)) + (clift * (adxbdy - bdxady));
+    let permanent = ((abs(bdxcdy) + abs(cdxbdy)) * alift)
+        + ((abs(cdxady) + abs(adxcdy)) * blift)
+        + ((abs(adxbdy) + abs(bdxady)) * clift);
+    let bound = incircle_error_bound::<T>() * permanent;
+    if abs(determinant) > bound {
+        return Defined(determinant.sign());
+    }
+
+    let splitter = splitter::<T>();
+    let adx = Expansion::<T, 2>::difference(ax, dx);
+    let ady = Expansion::<T, 2>::difference(ay, dy);
+    let bdx = Expansion::<T, 2>::difference(bx, dx);
+    let bdy = Expansion::<T, 2>::difference(by, dy);
+    let cdx = Expansion::<T, 2>::difference(cx, dx);
+    let cdy = Expansion::<T, 2>::difference(cy, dy);
+    let lift = |x: &Expansion<T, 2>, y: &Expansion<T, 2>| {
+        x.product::<2, 8>(x, splitter)
+            .sum::<8, 16>(&y.product::<2, 8>(y, splitter))
+    };
+    let cross =
+        |x0: &Expansion<T, 2>, y1: &Expansion<T, 2>, x1: &Expansion<T, 2>, y0: &Expansion<T, 2>| {
+            x0.product::<2, 8>(y1, splitter)
+                .sum::<8, 16>(&x1.product::<2, 8>(y0, splitter).neg())
+        };
+    let a = lift(&adx, &ady).product::<16, 512>(&cross(&bdx, &cdy, &cdx, &bdy), splitter);
+    let b = lift(&bdx, &bdy).product::<16, 512>(&cross(&cdx, &ady, &adx, &cdy), splitter);
+    let c = lift(&cdx, &cdy).product::<16, 512>(&cross(&adx, &bdy, &bdx, &ady), splitter);
+    a.sum::<512, 1024>(&b).sum::<512, 1536>(&c).sign()
+}
+
+/// Intersects a ray with an axis-aligned bounding box using the slab method.
+///
+/// The ray is given by its `origin` and `direction` (which need not be normalized) and the box is
+/// given by its `min` and `max` corners. The output is the pair of ray parameters at which the ray
+/// enters and exits the box, such that `origin + (t * direction)` is a point on the boundary of
+/// the box. The entering parameter is never negative, so if the origin is within the box, then the
+/// entering parameter is zero.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`IntersectionError::Disjoint`] if the ray does not intersect the
+/// box, [`IntersectionError::Degenerate`] if the direction is zero, and
+/// [`IntersectionError::NotReal`] if any coordinate or parameter is not a real number.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::geometry;
+/// use decorum::R64;
+///
+/// let r = |x: f64| -> R64 { R64::assert(x) };
+///
+/// let origin = [r(-1.0), r(0.5)];
+/// let direction = [r(1.0), r(0.0)];
+/// let (near, far) =
+///     geometry::ray_aabb(origin, direction, [r(0.0), r(0.0)], [r(1.0), r(1.0)]).unwrap();
+/// assert_eq!((near, far), (r(1.0), r(2.0)));
+/// ```
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn ray_aabb<T, C, const N: usize>(
+    origin: [Constrained<T, C>; N],
+    direction: [Constrained<T, C>; N],
+    min: [Constrained<T, C>; N],
+    max: [Constrained<T, C>; N],
+) -> Expression<Parameters<Constrained<T, C>>, IntersectionError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let origin = try_expression!(into_primitives(origin));
+    let direction = try_expression!(into_primitives(direction));
+    let min = try_expression!(into_primitives(min));
+    let max = try_expression!(into_primitives(max));
+
+    let mut near = T::NEG_INFINITY;
+    let mut far = T::INFINITY;
+    let mut is_degenerate = true;
+    for (((origin, direction), min), max) in origin.into_iter().zip(direction).zip(min).zip(max) {
+        if min > max {
+            return Undefined(IntersectionError::Disjoint);
+        }
+        if direction.is_zero() {
+            if origin < min || origin > max {
+                return Undefined(IntersectionError::Disjoint);
+            }
+        }
+        else {
+            is_degenerate = false;
+            // These parameters may overflow to infinity, in which case the slab is entered or
+            // exited beyond the range of `T`.
+            let t0 = (min - origin) / direction;
+            let t1 = (max - origin) / direction;
+            let (t0, t1) = if t0 > t1 { (t1, t0) } else { (t0, t1) };
+            if t0 > near {
+                near = t0;
+            }
+            if t1 < far {
+                far = t1;
+            }
+            if near > far {
+                return Undefined(IntersectionError::Disjoint);
+            }
+        }
+    }
+    if is_degenerate {
+        Undefined(IntersectionError::Degenerate)
+    }
+    else if far < T::ZERO {
+        Undefined(IntersectionError::Disjoint)
+    }
+    else {
+        let near = if near < T::ZERO { T::ZERO } else { near };
+        Defined((
+            try_expression!(into_parameter(near)),
+            try_expression!(into_parameter(far)),
+        ))
+    }
+}
+
+/// Intersects the line segments `p0p1` and `q0q1`.
+///
+/// The output is the pair of segment parameters `(s, t)` in the closed interval $\[0,1\]$ such that
+/// `p0 + s(p1 - p0)` and `q0 + t(q1 - q0)` are the point of intersection. Whether or not the
+/// segments intersect is determined exactly using [`orient2d`]. Collinear segments that touch at
+/// an endpoint intersect at that endpoint.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`IntersectionError::Disjoint`] if the segments do not intersect,
+/// [`IntersectionError::Overlapping`] if the segments are collinear and overlap (and so there is
+/// no unique point of intersection), [`IntersectionError::Parallel`] if the segments intersect
+/// but are too close to parallel to compute the point of intersection,
+/// [`IntersectionError::Degenerate`] if either segment has zero length, and
+/// [`IntersectionError::NotReal`] if any coordinate or parameter is not a real number.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::geometry;
+/// use decorum::R64;
+///
+/// let r = |x: f64| -> R64 { R64::assert(x) };
+///
+/// let (s, t) = geometry::segment_intersection(
+///     [r(0.0), r(0.0)],
+///     [r(2.0), r(2.0)],
+///     [r(0.0), r(2.0)],
+///     [r(2.0), r(0.0)],
+/// )
+/// .unwrap();
+/// assert_eq!((s, t), (r(0.5), r(0.5)));
+/// ```
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn segment_intersection<T, C>(
+    p0: Point2<Constrained<T, C>>,
+    p1: Point2<Constrained<T, C>>,
+    q0: Point2<Constrained<T, C>>,
+    q1: Point2<Constrained<T, C>>,
+) -> Expression<Parameters<Constrained<T, C>>, IntersectionError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    if p0 == p1 || q0 == q1 {
+        return Undefined(IntersectionError::Degenerate);
+    }
+    let p0q0 = try_expression!(orient2d(p0, p1, q0));
+    let p0q1 = try_expression!(orient2d(p0, p1, q1));
+    let q0p0 = try_expression!(orient2d(q0, q1, p0));
+    let q0p1 = try_expression!(orient2d(q0, q1, p1));
+    let is_collinear = [p0q0, p0q1, q0p0, q0p1].iter().all(Sign::is_zero);
+    if !is_collinear && (is_same_side(p0q0, p0q1) || is_same_side(q0p0, q0p1)) {
+        return Undefined(IntersectionError::Disjoint);
+    }
+
+    let [p0x, p0y] = try_expression!(into_primitives(p0));
+    let [p1x, p1y] = try_expression!(into_primitives(p1));
+    let [q0x, q0y] = try_expression!(into_primitives(q0));
+    let [q1x, q1y] = try_expression!(into_primitives(q1));
+    if is_collinear {
+        // Project the segments onto the axis along which `p0p1` has the greatest extent. The
+        // segments lie on the same line and neither has zero length, so their projections onto
+        // this axis also have non-zero length and points with the same projection are equal.
+        let [p0, p1, q0, q1] = if abs(p1x - p0x) >= abs(p1y - p0y) {
+            [p0x, p1x, q0x, q1x]
+        }
+        else {
+            [p0y, p1y, q0y, q1y]
+        };
+        let (pmin, pmax) = if p0 < p1 { (p0, p1) } else { (p1, p0) };
+        let (qmin, qmax) = if q0 < q1 { (q0, q1) } else { (q1, q0) };
+        let endpoint = if pmax == qmin {
+            pmax
+        }
+        else if qmax == pmin {
+            qmax
+        }
+        else if pmax < qmin || qmax < pmin {
+            return Undefined(IntersectionError::Disjoint);
+        }
+        else {
+            return Undefined(IntersectionError::Overlapping);
+        };
+        let s = if p0 == endpoint { T::ZERO } else { T::ONE };
+        let t = if q0 == endpoint { T::ZERO } else { T::ONE };
+        return Defined((
+            try_expression!(into_parameter(s)),
+            try_expression!(into_parameter(t)),
+        ));
+    }
+    let (px, py) = (p1x - p0x, p1y - p0y);
+    let (qx, qy) = (q1x - q0x, q1y - q0y);
+    let (wx, wy) = (q0x - p0x, q0y - p0y);
+    let denominator = (px * qy) - (py * qx);
+    if denominator.is_zero() {
+        return Undefined(IntersectionError::Parallel);
+    }
+    // The segments are known to intersect, so the parameters are clamped to correct for rounding.
+    let s = clamp_unit(((wx * qy) - (wy * qx)) / denominator);
+    let t = clamp_unit(((wx * py) - (wy * px)) / denominator);
+    Defined((
+        try_expression!(into_parameter(s)),
+        try_expression!(into_parameter(t)),
+    ))
+}
+
+/// A nonoverlapping floating-point expansion.
+///
+/// An expansion represents a value exactly as the sum of its components, which are sorted by
+/// increasing magnitude. Zero components are eliminated, but an expansion always has at least one
+/// component.
+#[derive(Clone, Copy)]
+struct Expansion<T, const N: usize> {
+    components: [T; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Expansion<T, N>
+where
+    T: Primitive,
+{
+    fn empty() -> Self {
+        Expansion {
+            components: [T::ZERO; N],
+            len: 0,
+        }
+    }
+
+    fn difference(a: T, b: T) -> Self {
+        let (high, low) = two_difference(a, b);
+        let mut expansion = Expansion::empty();
+        expansion.push(low);
+        expansion.push_last(high);
+        expansion
+    }
+
+    fn as_slice(&self) -> &[T] {
+        &self.components[..self.len]
+    }
+
+    fn push(&mut self, component: T) {
+        if !component.is_zero() {
+            self.components[self.len] = component;
+            self.len += 1;
+        }
+    }
+
+    fn push_last(&mut self, component: T) {
+        if !component.is_zero() || self.len == 0 {
+            self.components[self.len] = component;
+            self.len += 1;
+        }
+    }
+
+    fn neg(mut self) -> Self {
+        for component in &mut self.components[..self.len] {
+            *component = -*component;
+        }
+        self
+    }
+
+    fn sign(&self) -> Expression<Sign, NotRealError> {
+        if self
+            .as_slice()
+            .iter()
+            .all(|component| component.is_finite())
+        {
+            Defined(
+                self.as_slice()
+                    .last()
+                    .map_or(Sign::Zero, |last| last.sign()),
+            )
+        }
+        else {
+            Undefined(NotRealError)
+        }
+    }
+
+    fn sum<const M: usize, const K: usize>(&self, other: &Expansion<T, M>) -> Expansion<T, K> {
+        let (e, f) = (self.as_slice(), other.as_slice());
+        let (mut i, mut j) = (0, 0);
+        // Merge the components of both expansions in order of increasing magnitude.
+        let mut next = || match (e.get(i), f.get(j)) {
+            (Some(&a), Some(&b)) => {
+                if (b > a) == (b > -a) {
+                    i += 1;
+                    Some(a)
+                }
+                else {
+                    j += 1;
+                    Some(b)
+                }
+            }
+            (Some(&a), None) => {
+                i += 1;
+                Some(a)
+            }
+            (None, Some(&b)) => {
+                j += 1;
+                Some(b)
+            }
+            (None, None) => None,
+        };
+        let mut output = Expansion::empty();
+        let mut q = next().unwrap_or(T::ZERO);
+        while let Some(component) = next() {
+            let (sum, error) = two_sum(q, component);
+            output.push(error);
+            q = sum;
+        }
+        output.push_last(q);
+        output
+    }
+
+    fn scale<const K: usize>(&self, b: T, splitter: T) -> Expansion<T, K> {
+        let mut output = Expansion::empty();
+        let mut components = self.as_slice().iter().copied();
+        let mut q = if let Some(first) = components.next() {
+            let (product, error) = two_product(first, b, splitter);
+            output.push(error);
+            product
+        }
+        else {
+            T::ZERO
+        };
+        for component in components {
+            let (high, low) = two_product(component, b, splitter);
+            let (sum, error) = two_sum(q, low);
+            output.push(error);
+            let (sum, error) = fast_two_sum(high, sum);
+            output.push(error);
+            q = sum;
+        }
+        output.push_last(q);
+        output
+    }
+
+    fn product<const M: usize, const K: usize>(
+        &self,
+        other: &Expansion<T, M>,
+        splitter: T,
+    ) -> Expansion<T, K> {
+        let mut components = other.as_slice().iter().copied();
+        let mut output = self.scale::<K>(components.next().unwrap_or(T::ZERO), splitter);
+        for component in components {
+            output = output.sum::<K, K>(&self.scale::<K>(component, splitter));
+        }
+        output
+    }
+}
+
+fn into_primitives<T, C, const N: usize>(
+    point: [Constrained<T, C>; N],
+) -> Expression<[T; N], NotRealError>
+where
+    T: Primitive,
+{
+    let point = point.map(Constrained::into_inner);
+    if point.iter().all(|x| x.is_finite()) {
+        Defined(point)
+    }
+    else {
+        Undefined(NotRealError)
+    }
+}
+
+fn into_parameter<T, C>(t: T) -> Expression<Constrained<T, C>, IntersectionError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    Constrained::try_new(t)
+        .map_err(|_| NotRealError.into())
+        .into()
+}
+
+fn is_same_side(a: Sign, b: Sign) -> bool {
+    matches!(
+        (a, b),
+        (Sign::Positive, Sign::Positive) | (Sign::Negative, Sign::Negative)
+    )
+}
+
+fn clamp_unit<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    if x < T::ZERO {
+        T::ZERO
+    }
+    else if x > T::ONE {
+        T::ONE
+    }
+    else {
+        x
+    }
+}
+
+pub(crate) fn abs<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    if x < T::ZERO {
+        -x
+    }
+    else {
+        x
+    }
+}
+
+pub(crate) fn integer<T>(n: u8) -> T
+where
+    T: Primitive,
+{
+    (0..n).fold(T::ZERO, |x, _| x + T::ONE)
+}
+
+// This is the largest power of two such that `1 + epsilon` is not equal to one (half of
+// `BaseEncoding::EPSILON`).
+fn epsilon<T>() -> T
+where
+    T: Primitive,
+{
+    T::EPSILON / integer::<T>(2)
+}
+
+fn orient2d_error_bound<T>() -> T
+where
+    T: Primitive,
+{
+    let epsilon = epsilon::<T>();
+    (integer::<T>(3) + (integer::<T>(16) * epsilon)) * epsilon
+}
+
+fn incircle_error_bound<T>() -> T
+where
+    T: Primitive,
+{
+    let epsilon = epsilon::<T>();
+    (integer::<T>(10) + (integer::<T>(96) * epsilon)) * epsilon
+}
+
+// The splitter is $2^{\lceil p/2\rceil}+1$, where $p$ is the precision of `T`. `EPSILON` is
+// $2^{1-p}$, so the precision is determined by doubling half of `EPSILON` until it reaches one.
+pub(crate) fn splitter<T>() -> T
+where
+    T: Primitive,
+{
+    let two = integer::<T>(2);
+    let mut splitter = T::ONE;
+    let mut x = epsilon::<T>();
+    let mut is_doubling = true;
+    while x < T::ONE {
+        if is_doubling {
+            splitter = splitter * two;
+        }
+        is_doubling = !is_doubling;
+        x = x * two;
+    }
+    splitter + T::ONE
+}
+
+fn split<T>(a: T, splitter: T) -> (T, T)
+where
+    T: Primitive,
+{
+    let c = splitter * a;
+    let big = c - a;
+    let high = c - big;
+    (high, a - high)
+}
+
+fn fast_two_sum<T>(a: T, b: T) -> (T, T)
+where
+    T: Primitive,
+{
+    let x = a + b;
+    let virtual_b = x - a;
+    (x, b - virtual_b)
+}
+
+fn two_sum<T>(a: T, b: T) -> (T, T)
+where
+    T: Primitive,
+{
+    let x = a + b;
+    let virtual_b = x - a;
+    let virtual_a = x - virtual_b;
+    (x, (a - virtual_a) + (b - virtual_b))
+}
+
+fn two_difference<T>(a: T, b: T) -> (T, T)
+where
+    T: Primitive,
+{
+    let x = a - b;
+    let virtual_b = a - x;
+    let virtual_a = x + virtual_b;
+    (x, (a - virtual_a) + (virtual_b - b))
+}
+
+pub(crate) fn two_product<T>(a: T, b: T, splitter: T) -> (T, T)
+where
+    T: Primitive,
+{
+    let x = a * b;
+    #[cfg(feature = "std")]
+    if arch::uses_fma() {
+        return (x, a.fused_mul_add(b, -x));
+    }
+    let (a_high, a_low) = split(a, splitter);
+    let (b_high, b_low) = split(b, splitter);
+    let error = x - (a_high * b_high) - (a_low * b_high) - (a_high * b_low);
+    (x, (a_low * b_low) - error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::expression::Expression;
+    use crate::geometry::{self, IntersectionError, Point2};
+    use crate::real::Sign;
+    use crate::{Total, R32, R64};
+
+    fn point(x: f64, y: f64) -> Point2<R64> {
+        [R64::assert(x), R64::assert(y)]
+    }
+
+    #[test]
+    fn orient2d_sign() {
+        let a = point(0.0, 0.0);
+        let b = point(1.0, 0.0);
+        let c = point(0.0, 1.0);
+
+        assert_eq!(geometry::orient2d(a, b, c).unwrap(), Sign::Positive);
+        assert_eq!(geometry::orient2d(b, a, c).unwrap(), Sign::Negative);
+        assert_eq!(
+            geometry::orient2d(a, b, point(2.0, 0.0)).unwrap(),
+            Sign::Zero
+        );
+    }
+
+    #[test]
+    fn orient2d_near_degenerate() {
+        let b = point(12.0, 12.0);
+        let c = point(24.0, 24.0);
+
+        // These points are exactly collinear.
+        assert_eq!(
+            geometry::orient2d(point(0.5, 0.5), b, c).unwrap(),
+            Sign::Zero
+        );
+        // This point is below the line `y = x` by the smallest representable amount.
+        let a = point(0.5f64 + f64::EPSILON, 0.5);
+        assert_eq!(geometry::orient2d(a, b, c).unwrap(), Sign::Negative);
+        assert_eq!(geometry::orient2d(b, a, c).unwrap(), Sign::Positive);
+
+        let a: Point2<R32> = [R32::assert(0.5 + f32::EPSILON), R32::assert(0.5)];
+        let b: Point2<R32> = [R32::assert(12.0), R32::assert(12.0)];
+        let c: Point2<R32> = [R32::assert(24.0), R32::assert(24.0)];
+        assert_eq!(geometry::orient2d(a, b, c).unwrap(), Sign::Negative);
+    }
+
+    #[test]
+    fn orient2d_undefined() {
+        let a = [Total::from(0.0f64), Total::from(f64::INFINITY)];
+        let b = [Total::from(1.0f64), Total::from(0.0)];
+        let c = [Total::from(0.0f64), Total::from(1.0)];
+
+        assert!(geometry::orient2d(a, b, c).is_undefined());
+
+        let a = point(f64::MAX, f64::MAX);
+        let b = point(-f64::MAX, f64::MAX);
+        let c = point(f64::MAX, -f64::MAX);
+        assert!(matches!(
+            geometry::orient2d(a, b, c),
+            Expression::Undefined(_)
+        ));
+    }
+
+    #[test]
+    fn incircle_sign() {
+        let a = point(1.0, 0.0);
+        let b = point(0.0, 1.0);
+        let c = point(-1.0, 0.0);
+
+        assert_eq!(
+            geometry::incircle(a, b, c, point(0.0, 0.0)).unwrap(),
+            Sign::Positive
+        );
+        assert_eq!(
+            geometry::incircle(a, b, c, point(2.0, 0.0)).unwrap(),
+            Sign::Negative
+        );
+        assert_eq!(
+            geometry::incircle(a, b, c, point(0.0, -1.0)).unwrap(),
+            Sign::Zero
+        );
+    }
+
+    #[test]
+    fn incircle_near_degenerate() {
+        let a = point(1.0, 0.0);
+        let b = point(0.0, 1.0);
+        let c = point(-1.0, 0.0);
+
+        let d = point(0.0, -1.0 + f64::EPSILON);
+        assert_eq!(geometry::incircle(a, b, c, d).unwrap(), Sign::Positive);
+        let d = point(0.0, -1.0 - (2.0 * f64::EPSILON));
+        assert_eq!(geometry::incircle(a, b, c, d).unwrap(), Sign::Negative);
+        let d = point(f64::EPSILON, -1.0);
+        assert_eq!(geometry::incircle(a, b, c, d).unwrap(), Sign::Negative);
+    }
+
+    #[test]
+    fn ray_aabb_intersection() {
+        let (min, max) = (point(0.0, 0.0), point(1.0, 1.0));
+
+        let (near, far) = geometry::ray_aabb(point(-1.0, 0.5), point(2.0, 0.0), min, max).unwrap();
+        assert_eq!((near, far), (R64::assert(0.5), R64::assert(1.0)));
+        let (near, far) = geometry::ray_aabb(point(0.5, 0.5), point(1.0, 1.0), min, max).unwrap();
+        assert_eq!((near, far), (R64::assert(0.0), R64::assert(0.5)));
+
+        assert!(matches!(
+            geometry::ray_aabb(point(-1.0, 2.0), point(1.0, 0.0), min, max),
+            Expression::Undefined(IntersectionError::Disjoint),
+        ));
+        assert!(matches!(
+            geometry::ray_aabb(point(2.0, 0.5), point(1.0, 0.0), min, max),
+            Expression::Undefined(IntersectionError::Disjoint),
+        ));
+        assert!(matches!(
+            geometry::ray_aabb(point(0.5, 0.5), point(0.0, 0.0), min, max),
+            Expression::Undefined(IntersectionError::Degenerate),
+        ));
+    }
+
+    #[test]
+    fn ray_aabb_overflowing_entry() {
+        // The ray enters the slab along the `x` axis at a parameter that overflows to negative
+        // infinity, but the origin is within the box.
+        let (min, max) = (point(-f64::MAX, 0.0), point(1.0, 1.0));
+        let (near, far) = geometry::ray_aabb(point(0.5, 0.5), point(0.5, 0.0), min, max).unwrap();
+        assert_eq!((near, far), (R64::assert(0.0), R64::assert(1.0)));
+    }
+
+    #[test]
+    fn segment_intersection() {
+        let (s, t) = geometry::segment_intersection(
+            point(0.0, 0.0),
+            point(4.0, 0.0),
+            point(1.0, -1.0),
+            point(1.0, 1.0),
+        )
+        .unwrap();
+        assert_eq!((s, t), (R64::assert(0.25), R64::assert(0.5)));
+
+        // Segments that share an endpoint intersect.
+        assert!(geometry::segment_intersection(
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+        )
+        .is_defined());
+        assert!(matches!(
+            geometry::segment_intersection(
+                point(0.0, 0.0),
+                point(1.0, 0.0),
+                point(0.0, 1.0),
+                point(1.0, 1.0),
+            ),
+            Expression::Undefined(IntersectionError::Disjoint),
+        ));
+        assert!(matches!(
+            geometry::segment_intersection(
+                point(0.0, 0.0),
+                point(1.0, 0.0),
+                point(2.0, -1.0),
+                point(2.0, 1.0),
+            ),
+            Expression::Undefined(IntersectionError::Disjoint),
+        ));
+    }
+
+    #[test]
+    fn segment_intersection_collinear() {
+        // Collinear segments that do not touch are disjoint.
+        assert!(matches!(
+            geometry::segment_intersection(
+                point(0.0, 0.0),
+                point(1.0, 1.0),
+                point(2.0, 2.0),
+                point(3.0, 3.0),
+            ),
+            Expression::Undefined(IntersectionError::Disjoint),
+        ));
+        // Collinear segments that touch at an endpoint intersect at that endpoint.
+        let (s, t) = geometry::segment_intersection(
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(3.0, 0.0),
+            point(2.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!((s, t), (R64::assert(1.0), R64::assert(1.0)));
+        let (s, t) = geometry::segment_intersection(
+            point(0.0, 1.0),
+            point(0.0, 2.0),
+            point(0.0, 0.0),
+            point(0.0, 1.0),
+        )
+        .unwrap();
+        assert_eq!((s, t), (R64::assert(0.0), R64::assert(1.0)));
+        // Collinear segments that overlap have no unique point of intersection.
+        assert!(matches!(
+            geometry::segment_intersection(
+                point(0.0, 0.0),
+                point(2.0, 0.0),
+                point(1.0, 0.0),
+                point(3.0, 0.0),
+            ),
+            Expression::Undefined(IntersectionError::Overlapping),
+        ));
+    }
+}
@@ -14,13 +14,35 @@
 //! The [`CanonicalHash`] trait agrees with the ordering and equivalence relations of the
 //! [`CanonicalOrd`] and [`CanonicalEq`] traits.
 //!
+//! [`CanonicalHash`] feeds a [`Hasher`], so its output depends on the hashing algorithm and may
+//! change across versions of Decorum, Rust, or the platform. The versioned modules (e.g.,
+//! [`v1`]) instead provide hashing with a frozen and documented algorithm that never changes, so
+//! hashes can be persisted.
+//!
+//! With the `digest` feature, the `CanonicalDigest` trait feeds canonical representations of
+//! values into a cryptographic digest, such as SHA-256. This can be used to compute content
+//! hashes of structures that contain floating-point values, such as cache keys for the inputs of a
+//! simulation. `CanonicalDigest` can be derived with the `derive` feature.
+//!
 //! [`CanonicalEq`]: crate::cmp::CanonicalEq
 //! [`CanonicalOrd`]: crate::cmp::CanonicalOrd
+//! [`Hasher`]: core::hash::Hasher
+
+#[cfg(feature = "digest")]
+mod content;
 
 use core::hash::{Hash, Hasher};
 
 use crate::ToCanonical;
 
+#[cfg(feature = "derive")]
+pub use decorum_derive::CanonicalDigest;
+#[cfg(feature = "digest")]
+pub use digest::Update;
+
+#[cfg(feature = "digest")]
+pub use crate::hash::content::CanonicalDigest;
+
 pub trait CanonicalHash {
     fn hash_canonical<H>(&self, state: &mut H)
     where
@@ -56,3 +78,198 @@ where
         }
     }
 }
+
+/// Version 1 of stable canonical hashing.
+///
+/// **The algorithm in this module is frozen and its output never changes.** Any change to the
+/// algorithm is introduced in a new versioned module instead.
+///
+/// The algorithm is 64-bit [FNV-1a][fnv] with the standard offset basis and prime over a stream of
+/// bytes. Each floating-point value is first converted to `binary64` (`f64`), which is exact, and
+/// then canonicalized: zeroes are encoded as `+0` and `NaN`s are encoded as
+/// `0x7ff8_0000_0000_0000`. The canonicalized bits are written to the stream in little-endian
+/// byte order. Slices and arrays write each of their elements in order without a length. The stream
+/// always begins with a 64-bit seed in little-endian byte order, which is zero unless otherwise
+/// given.
+///
+/// Because values are converted to `binary64`, equal values of different floating-point types
+/// (e.g., `1.0f32` and `1.0f64`) and of different proxy types have the same hash.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::hash::v1;
+/// use decorum::R64;
+///
+/// assert_eq!(v1::canonical_hash(&0.0f64), v1::canonical_hash(&-0.0f64));
+/// assert_eq!(
+///     v1::canonical_hash(&[1.0f32, 2.0]),
+///     v1::canonical_hash(&[1.0f64, 2.0])
+/// );
+///
+/// let x: R64 = R64::assert(1.0);
+/// assert_eq!(v1::canonical_hash(&x), v1::canonical_hash(&1.0f64));
+/// ```
+///
+/// [fnv]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+pub mod v1 {
+    use crate::proxy::Constrained;
+
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    const CANONICAL_NAN_BITS: u64 = 0x7ff8_0000_0000_0000;
+
+    /// Types that can be hashed with the version 1 algorithm.
+    pub trait StableHash {
+        fn hash_stable(&self, state: &mut StableHasher);
+    }
+
+    impl StableHash for f32 {
+        fn hash_stable(&self, state: &mut StableHasher) {
+            f64::from(*self).hash_stable(state)
+        }
+    }
+
+    impl StableHash for f64 {
+        fn hash_stable(&self, state: &mut StableHasher) {
+            state.write_u64(canonical_bits(*self));
+        }
+    }
+
+    impl<T, C> StableHash for Constrained<T, C>
+    where
+        T: Copy + StableHash,
+    {
+        fn hash_stable(&self, state: &mut StableHasher) {
+            self.into_inner().hash_stable(state)
+        }
+    }
+
+    impl<T> StableHash for [T]
+    where
+        T: StableHash,
+    {
+        fn hash_stable(&self, state: &mut StableHasher) {
+            for item in self {
+                item.hash_stable(state);
+            }
+        }
+    }
+
+    impl<T, const N: usize> StableHash for [T; N]
+    where
+        T: StableHash,
+    {
+        fn hash_stable(&self, state: &mut StableHasher) {
+            self.as_slice().hash_stable(state)
+        }
+    }
+
+    /// The state of the version 1 algorithm.
+    #[derive(Clone, Debug)]
+    pub struct StableHasher {
+        state: u64,
+    }
+
+    impl StableHasher {
+        pub const fn new() -> Self {
+            StableHasher {
+                state: OFFSET_BASIS,
+            }
+        }
+
+        pub const fn with_seed(seed: u64) -> Self {
+            StableHasher {
+                state: mix(OFFSET_BASIS, seed),
+            }
+        }
+
+        pub const fn finish(&self) -> u64 {
+            self.state
+        }
+
+        pub fn write_u64(&mut self, n: u64) {
+            self.state = mix(self.state, n);
+        }
+    }
+
+    // Mutable references are not allowed in constant functions at the minimum supported Rust
+    // version, so the state is threaded through this function instead.
+    const fn mix(mut state: u64, n: u64) -> u64 {
+        let bytes = n.to_le_bytes();
+        let mut index = 0;
+        while index < bytes.len() {
+            state = (state ^ bytes[index] as u64).wrapping_mul(PRIME);
+            index += 1;
+        }
+        state
+    }
+
+    impl Default for StableHasher {
+        fn default() -> Self {
+            StableHasher::new()
+        }
+    }
+
+    pub(crate) fn canonical_bits(x: f64) -> u64 {
+        if x.is_nan() {
+            CANONICAL_NAN_BITS
+        }
+        else if x == 0.0 {
+            0
+        }
+        else {
+            x.to_bits()
+        }
+    }
+
+    /// Hashes a value with the version 1 algorithm.
+    pub fn canonical_hash<T>(value: &T) -> u64
+    where
+        T: StableHash + ?Sized,
+    {
+        canonical_hash_with_seed(0, value)
+    }
+
+    /// Hashes a value with the version 1 algorithm and the given seed.
+    ///
+    /// [`canonical_hash`] is equivalent to this function with a seed of zero.
+    pub fn canonical_hash_with_seed<T>(seed: u64, value: &T) -> u64
+    where
+        T: StableHash + ?Sized,
+    {
+        let mut state = StableHasher::with_seed(seed);
+        value.hash_stable(&mut state);
+        state.finish()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::hash::v1;
+
+        // These values are frozen. If this test fails, then the version 1 algorithm has changed,
+        // which is a breaking change for persisted hashes.
+        #[test]
+        fn frozen() {
+            assert_eq!(v1::canonical_hash(&1.0f64), 0x85c9_d0b9_5f02_e858);
+            assert_eq!(
+                v1::canonical_hash(&[0.0f64, f64::NAN]),
+                0x81ec_f0d7_0052_6480
+            );
+        }
+
+        #[test]
+        fn canonical() {
+            assert_eq!(v1::canonical_hash(&0.0f64), v1::canonical_hash(&-0.0f32));
+            assert_eq!(
+                v1::canonical_hash(&f64::NAN),
+                v1::canonical_hash(&-f32::NAN)
+            );
+            assert_ne!(v1::canonical_hash(&1.0f64), v1::canonical_hash(&-1.0f64));
+            assert_ne!(
+                v1::canonical_hash_with_seed(1, &1.0f64),
+                v1::canonical_hash(&1.0f64)
+            );
+        }
+    }
+}
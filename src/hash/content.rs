@@ -0,0 +1,309 @@
+#[cfg(feature = "std")]
+use std::boxed::Box;
+#[cfg(feature = "std")]
+use std::string::String;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use crate::hash::{v1, Update};
+use crate::proxy::Constrained;
+
+/// Content hashing of canonical representations with a [digest][`Update`].
+///
+/// Floating-point values are encoded in the same way as the [version 1][`v1`] stable hashing
+/// algorithm: values are converted to `binary64` and zeroes and `NaN`s are canonicalized. All
+/// integers are fed into the digest in little-endian byte order, `usize` and `isize` as 64-bit
+/// integers. Slices and strings are prefixed by their length as a `u64`, while arrays and tuples
+/// are not. `Option`s are prefixed by a byte that is zero for `None` and one for `Some`.
+///
+/// When derived, the fields of structures are fed into the digest in declaration order. The
+/// fields of an enumeration are preceded by the index of the variant as a `u32`.
+///
+/// # Examples
+#[cfg_attr(all(feature = "derive", feature = "std"), doc = "```rust")]
+#[cfg_attr(not(all(feature = "derive", feature = "std")), doc = "```rust,ignore")]
+/// use decorum::hash::CanonicalDigest;
+/// use decorum::R64;
+/// use sha2::{Digest, Sha256};
+///
+/// #[derive(CanonicalDigest)]
+/// struct Input {
+///     steps: u32,
+///     gravity: R64,
+///     masses: Vec<f64>,
+/// }
+///
+/// let input = Input {
+///     steps: 100,
+///     gravity: R64::assert(-9.81),
+///     masses: vec![1.0, 0.0, -0.0],
+/// };
+/// let mut sha = Sha256::new();
+/// input.digest_canonical(&mut sha);
+/// let key = sha.finalize();
+/// ```
+pub trait CanonicalDigest {
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update;
+}
+
+macro_rules! impl_canonical_digest_for_integer {
+    (integer => $t:ty, encoding => $e:ty) => {
+        impl CanonicalDigest for $t {
+            fn digest_canonical<D>(&self, state: &mut D)
+            where
+                D: Update,
+            {
+                state.update(&(*self as $e).to_le_bytes());
+            }
+        }
+    };
+    (integer => $t:ty) => {
+        impl_canonical_digest_for_integer!(integer => $t, encoding => $t);
+    };
+}
+impl_canonical_digest_for_integer!(integer => i8);
+impl_canonical_digest_for_integer!(integer => i16);
+impl_canonical_digest_for_integer!(integer => i32);
+impl_canonical_digest_for_integer!(integer => i64);
+impl_canonical_digest_for_integer!(integer => i128);
+impl_canonical_digest_for_integer!(integer => isize, encoding => i64);
+impl_canonical_digest_for_integer!(integer => u8);
+impl_canonical_digest_for_integer!(integer => u16);
+impl_canonical_digest_for_integer!(integer => u32);
+impl_canonical_digest_for_integer!(integer => u64);
+impl_canonical_digest_for_integer!(integer => u128);
+impl_canonical_digest_for_integer!(integer => usize, encoding => u64);
+
+impl CanonicalDigest for bool {
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        u8::from(*self).digest_canonical(state)
+    }
+}
+
+impl CanonicalDigest for f32 {
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        f64::from(*self).digest_canonical(state)
+    }
+}
+
+impl CanonicalDigest for f64 {
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        v1::canonical_bits(*self).digest_canonical(state)
+    }
+}
+
+impl<T, C> CanonicalDigest for Constrained<T, C>
+where
+    T: CanonicalDigest + Copy,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        self.into_inner().digest_canonical(state)
+    }
+}
+
+impl CanonicalDigest for str {
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        self.len().digest_canonical(state);
+        state.update(self.as_bytes());
+    }
+}
+
+#[cfg(feature = "std")]
+impl CanonicalDigest for String {
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        self.as_str().digest_canonical(state)
+    }
+}
+
+impl<T> CanonicalDigest for [T]
+where
+    T: CanonicalDigest,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        self.len().digest_canonical(state);
+        for item in self {
+            item.digest_canonical(state);
+        }
+    }
+}
+
+impl<T, const N: usize> CanonicalDigest for [T; N]
+where
+    T: CanonicalDigest,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        for item in self {
+            item.digest_canonical(state);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> CanonicalDigest for Vec<T>
+where
+    T: CanonicalDigest,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        self.as_slice().digest_canonical(state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> CanonicalDigest for Box<T>
+where
+    T: CanonicalDigest + ?Sized,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        self.as_ref().digest_canonical(state)
+    }
+}
+
+impl<T> CanonicalDigest for &'_ T
+where
+    T: CanonicalDigest + ?Sized,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        T::digest_canonical(self, state)
+    }
+}
+
+impl<T> CanonicalDigest for Option<T>
+where
+    T: CanonicalDigest,
+{
+    fn digest_canonical<D>(&self, state: &mut D)
+    where
+        D: Update,
+    {
+        match self {
+            Some(value) => {
+                1u8.digest_canonical(state);
+                value.digest_canonical(state);
+            }
+            None => 0u8.digest_canonical(state),
+        }
+    }
+}
+
+macro_rules! impl_canonical_digest_for_tuple {
+    ($($t:ident => $n:tt),*) => {
+        impl<$($t),*> CanonicalDigest for ($($t,)*)
+        where
+            $($t: CanonicalDigest,)*
+        {
+            fn digest_canonical<D>(&self, state: &mut D)
+            where
+                D: Update,
+            {
+                $(self.$n.digest_canonical(state);)*
+            }
+        }
+    };
+}
+impl_canonical_digest_for_tuple!(T0 => 0);
+impl_canonical_digest_for_tuple!(T0 => 0, T1 => 1);
+impl_canonical_digest_for_tuple!(T0 => 0, T1 => 1, T2 => 2);
+impl_canonical_digest_for_tuple!(T0 => 0, T1 => 1, T2 => 2, T3 => 3);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::hash::{CanonicalDigest, Update};
+    use crate::R64;
+
+    #[derive(Default)]
+    struct Bytes(Vec<u8>);
+
+    impl Update for Bytes {
+        fn update(&mut self, data: &[u8]) {
+            self.0.extend_from_slice(data);
+        }
+    }
+
+    fn digest<T>(value: &T) -> Vec<u8>
+    where
+        T: CanonicalDigest + ?Sized,
+    {
+        let mut state = Bytes::default();
+        value.digest_canonical(&mut state);
+        state.0
+    }
+
+    #[test]
+    fn canonical_encoding() {
+        assert_eq!(digest(&0.0f64), digest(&-0.0f32));
+        assert_eq!(digest(&f64::NAN), digest(&-f64::NAN));
+        assert_eq!(digest(&1.0f64), 1.0f64.to_bits().to_le_bytes());
+        let x: R64 = R64::assert(1.0);
+        assert_eq!(digest(&x), digest(&1.0f64));
+        assert_eq!(digest(&(1u8, true)), [1, 1]);
+    }
+
+    #[test]
+    fn unambiguous_nesting() {
+        let a: [&[f64]; 2] = [&[1.0], &[]];
+        let b: [&[f64]; 2] = [&[], &[1.0]];
+        assert_ne!(digest(&a), digest(&b));
+        assert_ne!(digest(&Some(0u8)), digest(&None::<u8>));
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive() {
+        #[derive(CanonicalDigest)]
+        struct Input<T> {
+            steps: u32,
+            values: Vec<T>,
+        }
+
+        #[derive(CanonicalDigest)]
+        enum Shape {
+            Point,
+            Circle(f64),
+        }
+
+        let input = Input {
+            steps: 2,
+            values: std::vec![0.0f64, -0.0],
+        };
+        assert_eq!(digest(&input), digest(&(2u32, [0.0f64, 0.0].as_slice())));
+        assert_eq!(digest(&Shape::Point), digest(&0u32));
+        assert_eq!(digest(&Shape::Circle(1.0)), digest(&(1u32, 1.0f64)));
+    }
+}
@@ -0,0 +1,347 @@
+//! Inspection of the IEEE 754 floating-point environment.
+//!
+//! IEEE 754 specifies status flags that are raised by floating-point operations when exceptional
+//! conditions occur, such as an invalid operation that produces a `NaN` or an operation that
+//! overflows. These flags are sticky: they remain raised until they are explicitly cleared. This
+//! module provides functions for reading and clearing these flags as well as reading the current
+//! rounding mode. This can be used to detect exceptional conditions in code that does not use
+//! [`Constrained`] types.
+//!
+//! This module is only available on `x86_64` and `aarch64` targets and requires the `std` feature.
+//!
+//! **Note that Rust and LLVM assume the default floating-point environment.** The compiler may
+//! evaluate or reorder floating-point operations (e.g., constant folding) such that the flags
+//! raised by the hardware do not reflect the operations as written. Inputs can be passed through
+//! [`black_box`] to discourage this, but the flags observed by this module should be considered
+//! diagnostic rather than exact.
+//!
+//! # Examples
+//!
+//! Detecting a division by zero within a closure:
+//!
+//! ```rust
+//! use core::hint;
+//! use decorum::ieee::{self, Flags};
+//!
+//! let (_, flags) = ieee::with_flags(|| hint::black_box(1.0f64) / hint::black_box(0.0));
+//! assert!(flags.contains(Flags::DIVIDE_BY_ZERO));
+//! ```
+//!
+//! [`black_box`]: core::hint::black_box
+//! [`Constrained`]: crate::proxy::Constrained
+
+use core::fmt::{self, Debug, Formatter};
+use core::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// A set of IEEE 754 floating-point exception flags.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Flags(u8);
+
+impl Flags {
+    /// An operation had no well-defined result, such as `0 / 0` or `sqrt(-1)`.
+    pub const INVALID: Self = Flags(1 << 0);
+    /// An operation on finite operands produced an exact infinity, such as `1 / 0`.
+    pub const DIVIDE_BY_ZERO: Self = Flags(1 << 1);
+    /// The result of an operation was too large in magnitude to be represented.
+    pub const OVERFLOW: Self = Flags(1 << 2);
+    /// The result of an operation was too small in magnitude to be represented as a normal.
+    pub const UNDERFLOW: Self = Flags(1 << 3);
+    /// The result of an operation was rounded.
+    pub const INEXACT: Self = Flags(1 << 4);
+
+    pub const fn empty() -> Self {
+        Flags(0)
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(&self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    pub const fn intersects(&self, other: Self) -> bool {
+        (self.0 & other.0) != 0
+    }
+
+    pub const fn is_invalid(&self) -> bool {
+        self.contains(Flags::INVALID)
+    }
+
+    pub const fn is_divide_by_zero(&self) -> bool {
+        self.contains(Flags::DIVIDE_BY_ZERO)
+    }
+
+    pub const fn is_overflow(&self) -> bool {
+        self.contains(Flags::OVERFLOW)
+    }
+
+    pub const fn is_underflow(&self) -> bool {
+        self.contains(Flags::UNDERFLOW)
+    }
+
+    pub const fn is_inexact(&self) -> bool {
+        self.contains(Flags::INEXACT)
+    }
+}
+
+impl BitAnd for Flags {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        Flags(self.0 & other.0)
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        Flags(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for Flags {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = *self | other;
+    }
+}
+
+impl Debug for Flags {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let mut list = formatter.debug_set();
+        for (flag, name) in [
+            (Flags::INVALID, "INVALID"),
+            (Flags::DIVIDE_BY_ZERO, "DIVIDE_BY_ZERO"),
+            (Flags::OVERFLOW, "OVERFLOW"),
+            (Flags::UNDERFLOW, "UNDERFLOW"),
+            (Flags::INEXACT, "INEXACT"),
+        ] {
+            if self.contains(flag) {
+                list.entry(&format_args!("{}", name));
+            }
+        }
+        list.finish()
+    }
+}
+
+/// IEEE 754 rounding mode.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Rounding {
+    /// Round to the nearest representable value, with ties to even. This is the default.
+    ToNearest,
+    /// Round toward negative infinity.
+    Downward,
+    /// Round toward positive infinity.
+    Upward,
+    /// Round toward zero (truncation).
+    TowardZero,
+}
+
+/// Gets the floating-point exception flags that are currently raised.
+pub fn flags() -> Flags {
+    arch::flags()
+}
+
+/// Clears all floating-point exception flags.
+pub fn clear_flags() {
+    arch::set_flags(Flags::empty())
+}
+
+/// Gets the current floating-point rounding mode.
+pub fn rounding() -> Rounding {
+    arch::rounding()
+}
+
+/// Executes a function and gets the floating-point exception flags that it raises.
+///
+/// The flags are cleared before the function is executed. After the function is executed, the
+/// flags that were raised beforehand are raised again, so `with_flags` can be nested and does not
+/// hide exceptions from enclosing code.
+pub fn with_flags<T, F>(f: F) -> (T, Flags)
+where
+    F: FnOnce() -> T,
+{
+    let previous = arch::flags();
+    arch::set_flags(Flags::empty());
+    let output = f();
+    let flags = arch::flags();
+    arch::set_flags(previous | flags);
+    (output, flags)
+}
+
+#[cfg(target_arch = "x86_64")]
+mod arch {
+    use core::arch::asm;
+
+    use crate::ieee::{Flags, Rounding};
+
+    const INVALID: u32 = 1 << 0;
+    const DIVIDE_BY_ZERO: u32 = 1 << 2;
+    const OVERFLOW: u32 = 1 << 3;
+    const UNDERFLOW: u32 = 1 << 4;
+    const INEXACT: u32 = 1 << 5;
+    const ROUNDING_SHIFT: u32 = 13;
+
+    const MAPPING: [(u32, Flags); 5] = [
+        (INVALID, Flags::INVALID),
+        (DIVIDE_BY_ZERO, Flags::DIVIDE_BY_ZERO),
+        (OVERFLOW, Flags::OVERFLOW),
+        (UNDERFLOW, Flags::UNDERFLOW),
+        (INEXACT, Flags::INEXACT),
+    ];
+
+    fn mxcsr() -> u32 {
+        let mut mxcsr = 0u32;
+        // SAFETY: `stmxcsr` only stores the `MXCSR` register into the given location.
+        unsafe {
+            asm!("stmxcsr [{}]", in(reg) &mut mxcsr, options(nostack, preserves_flags));
+        }
+        mxcsr
+    }
+
+    fn set_mxcsr(mxcsr: u32) {
+        // SAFETY: `ldmxcsr` only loads the `MXCSR` register. The control bits (masks and rounding)
+        //         are always preserved by the callers of this function.
+        unsafe {
+            asm!("ldmxcsr [{}]", in(reg) &mxcsr, options(nostack, readonly, preserves_flags));
+        }
+    }
+
+    pub fn flags() -> Flags {
+        let mxcsr = mxcsr();
+        MAPPING
+            .iter()
+            .filter(|(bit, _)| mxcsr & bit != 0)
+            .fold(Flags::empty(), |flags, (_, flag)| flags | *flag)
+    }
+
+    pub fn set_flags(flags: Flags) {
+        let mxcsr = MAPPING
+            .iter()
+            .filter(|(_, flag)| flags.contains(*flag))
+            .fold(mxcsr() & !0b11_1111, |mxcsr, (bit, _)| mxcsr | bit);
+        set_mxcsr(mxcsr);
+    }
+
+    pub fn rounding() -> Rounding {
+        match (mxcsr() >> ROUNDING_SHIFT) & 0b11 {
+            0b00 => Rounding::ToNearest,
+            0b01 => Rounding::Downward,
+            0b10 => Rounding::Upward,
+            _ => Rounding::TowardZero,
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+    use core::arch::asm;
+
+    use crate::ieee::{Flags, Rounding};
+
+    const INVALID: u64 = 1 << 0;
+    const DIVIDE_BY_ZERO: u64 = 1 << 1;
+    const OVERFLOW: u64 = 1 << 2;
+    const UNDERFLOW: u64 = 1 << 3;
+    const INEXACT: u64 = 1 << 4;
+    const ROUNDING_SHIFT: u64 = 22;
+
+    const MAPPING: [(u64, Flags); 5] = [
+        (INVALID, Flags::INVALID),
+        (DIVIDE_BY_ZERO, Flags::DIVIDE_BY_ZERO),
+        (OVERFLOW, Flags::OVERFLOW),
+        (UNDERFLOW, Flags::UNDERFLOW),
+        (INEXACT, Flags::INEXACT),
+    ];
+
+    fn fpsr() -> u64 {
+        let fpsr: u64;
+        // SAFETY: `mrs` only reads the `FPSR` register.
+        unsafe {
+            asm!("mrs {}, fpsr", out(reg) fpsr, options(nomem, nostack, preserves_flags));
+        }
+        fpsr
+    }
+
+    fn set_fpsr(fpsr: u64) {
+        // SAFETY: `msr` only writes the `FPSR` register, which contains status flags.
+        unsafe {
+            asm!("msr fpsr, {}", in(reg) fpsr, options(nomem, nostack, preserves_flags));
+        }
+    }
+
+    fn fpcr() -> u64 {
+        let fpcr: u64;
+        // SAFETY: `mrs` only reads the `FPCR` register.
+        unsafe {
+            asm!("mrs {}, fpcr", out(reg) fpcr, options(nomem, nostack, preserves_flags));
+        }
+        fpcr
+    }
+
+    pub fn flags() -> Flags {
+        let fpsr = fpsr();
+        MAPPING
+            .iter()
+            .filter(|(bit, _)| fpsr & bit != 0)
+            .fold(Flags::empty(), |flags, (_, flag)| flags | *flag)
+    }
+
+    pub fn set_flags(flags: Flags) {
+        let fpsr = MAPPING
+            .iter()
+            .filter(|(_, flag)| flags.contains(*flag))
+            .fold(fpsr() & !0b1001_1111, |fpsr, (bit, _)| fpsr | bit);
+        set_fpsr(fpsr);
+    }
+
+    pub fn rounding() -> Rounding {
+        match (fpcr() >> ROUNDING_SHIFT) & 0b11 {
+            0b00 => Rounding::ToNearest,
+            0b01 => Rounding::Upward,
+            0b10 => Rounding::Downward,
+            _ => Rounding::TowardZero,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::hint;
+
+    use crate::ieee::{self, Flags, Rounding};
+
+    #[test]
+    fn with_flags_raised() {
+        let (_, flags) = ieee::with_flags(|| hint::black_box(1.0f64) / hint::black_box(0.0));
+        assert!(flags.is_divide_by_zero());
+        assert!(!flags.is_invalid());
+
+        let (_, flags) = ieee::with_flags(|| hint::black_box(0.0f64) / hint::black_box(0.0));
+        assert!(flags.is_invalid());
+
+        let (_, flags) = ieee::with_flags(|| hint::black_box(f64::MAX) * hint::black_box(2.0));
+        assert!(flags.contains(Flags::OVERFLOW | Flags::INEXACT));
+
+        let (_, flags) = ieee::with_flags(|| hint::black_box(1.0f64) + hint::black_box(1.0));
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn with_flags_nested() {
+        ieee::clear_flags();
+        let (_, outer) = ieee::with_flags(|| {
+            let (_, inner) = ieee::with_flags(|| hint::black_box(0.0f64) / hint::black_box(0.0));
+            assert!(inner.is_invalid());
+            hint::black_box(1.0f64) + hint::black_box(1.0)
+        });
+        assert!(outer.is_invalid());
+    }
+
+    #[test]
+    fn default_rounding() {
+        assert_eq!(ieee::rounding(), Rounding::ToNearest);
+    }
+}
@@ -0,0 +1,348 @@
+//! Failure injection for testing divergence.
+//!
+//! Code that handles the divergence of [`Constrained`] types is difficult to test, because the
+//! inputs that cause divergence are often rare. This module provides two ways to provoke
+//! divergence deterministically:
+//!
+//! - [`Inject`] wraps a source of [`Constrained`] values and replaces some of them with
+//!   non-real values (`NaN` and infinities), either at given positions or with a given probability
+//!   from a seeded generator. The output is primitive, so it can be fed into constructors and
+//!   arithmetic to exercise the handling of non-real inputs.
+//! - [`InjectingDivergence`] behaves exactly like the divergence that it wraps, except that while
+//!   [injection is enabled][`with_injection`] on the current thread, **every** constraint check
+//!   fails. This provokes divergence in code that otherwise never sees non-real values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::constraint::IsReal;
+//! use decorum::divergence::OrError;
+//! use decorum::inject::{self, InjectingDivergence};
+//! use decorum::proxy::Constrained;
+//!
+//! type Real = Constrained<f64, IsReal<InjectingDivergence<OrError>>>;
+//!
+//! fn area(radius: Real) -> Option<f64> {
+//!     (radius * radius).defined().map(Real::into_inner)
+//! }
+//!
+//! let radius = Real::try_new(2.0).unwrap();
+//! assert_eq!(area(radius), Some(4.0));
+//! assert_eq!(inject::with_injection(|| area(radius)), None);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+
+use core::cell::Cell;
+use core::convert::Infallible;
+#[cfg(feature = "trace")]
+use core::fmt::Display;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use std::vec::Vec;
+
+use crate::divergence::{Continue, Divergence, OrError};
+use crate::proxy::Constrained;
+use crate::sealed::{Sealed, StaticDebug};
+#[cfg(feature = "trace")]
+use crate::trace::Trace;
+use crate::Primitive;
+
+std::thread_local! {
+    static INJECTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Enables or disables injection on the current thread.
+///
+/// Prefer [`with_injection`], which restores the previous state even if its function panics.
+pub fn set_injecting(injecting: bool) {
+    INJECTING.with(|cell| cell.set(injecting));
+}
+
+/// Returns `true` if injection is enabled on the current thread.
+pub fn is_injecting() -> bool {
+    INJECTING.with(Cell::get)
+}
+
+/// Calls a function with injection enabled on the current thread.
+///
+/// While the function executes, the constraint checks of [`Constrained`] types with an
+/// [`InjectingDivergence`] fail and so these types diverge.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn with_injection<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            set_injecting(self.0);
+        }
+    }
+
+    let _restore = Restore(is_injecting());
+    set_injecting(true);
+    f()
+}
+
+/// Divergence that behaves like `D`, but fails all constraint checks while injection is enabled.
+///
+/// See the [module documentation][`inject`].
+///
+/// [`inject`]: crate::inject
+pub struct InjectingDivergence<D = OrError>(PhantomData<fn() -> D>, Infallible);
+
+impl<D> Divergence for InjectingDivergence<D>
+where
+    D: Divergence,
+{
+    type Continue = D::Continue;
+    #[cfg(feature = "trace")]
+    type Error<E>
+        = D::Error<E>
+    where
+        E: Debug + Display + Trace;
+
+    fn diverge<T, E>(result: Result<T, E>) -> <Self::Continue as Continue>::As<T, E>
+    where
+        E: Debug,
+    {
+        D::diverge(result)
+    }
+
+    fn is_injected() -> bool {
+        is_injecting()
+    }
+}
+
+impl<D> Sealed for InjectingDivergence<D> {}
+
+impl<D> StaticDebug for InjectingDivergence<D>
+where
+    D: StaticDebug,
+{
+    fn fmt(formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "InjectingDivergence<")?;
+        D::fmt(formatter)?;
+        write!(formatter, ">")
+    }
+}
+
+/// Non-real value injected by [`Inject`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Injection {
+    Nan,
+    Infinity,
+    NegativeInfinity,
+}
+
+impl Injection {
+    pub fn into_primitive<T>(self) -> T
+    where
+        T: Primitive,
+    {
+        match self {
+            Injection::Nan => T::NAN.into_inner(),
+            Injection::Infinity => T::INFINITY,
+            Injection::NegativeInfinity => T::NEG_INFINITY,
+        }
+    }
+}
+
+/// Iterator that injects non-real values into a source of [`Constrained`] values.
+///
+/// Values are injected at explicit positions and with a probability. Both are disabled by default,
+/// in which case `Inject` yields the primitive values of its source. Injection is deterministic:
+/// the positions and values of injections depend only on the configuration and the seed.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::inject::{Inject, Injection};
+/// use decorum::R64;
+///
+/// let xs = [<R64>::assert(1.0), <R64>::assert(2.0), <R64>::assert(3.0)];
+/// let ys: Vec<f64> = Inject::new(xs, 0)
+///     .at([1])
+///     .with_injections([Injection::Infinity])
+///     .collect();
+/// assert_eq!(ys, [1.0, f64::INFINITY, 3.0]);
+/// ```
+///
+/// [`Constrained`]: crate::proxy::Constrained
+#[derive(Clone, Debug)]
+pub struct Inject<I> {
+    input: I,
+    index: usize,
+    positions: Vec<usize>,
+    probability: f64,
+    injections: Vec<Injection>,
+    state: u64,
+}
+
+impl<I> Inject<I> {
+    pub fn new<J>(input: J, seed: u64) -> Self
+    where
+        J: IntoIterator<IntoIter = I>,
+    {
+        Inject {
+            input: input.into_iter(),
+            index: 0,
+            positions: Vec::new(),
+            probability: 0.0,
+            injections: std::vec![
+                Injection::Nan,
+                Injection::Infinity,
+                Injection::NegativeInfinity,
+            ],
+            state: seed,
+        }
+    }
+
+    /// Injects values at the given (zero-based) positions.
+    pub fn at<P>(mut self, positions: P) -> Self
+    where
+        P: IntoIterator<Item = usize>,
+    {
+        self.positions.extend(positions);
+        self
+    }
+
+    /// Injects values with the given probability in the closed interval $[0, 1]$.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the probability is not in the closed interval $[0, 1]$.
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "probability not in unit interval"
+        );
+        self.probability = probability;
+        self
+    }
+
+    /// Sets the values to inject, which are chosen uniformly from the given set.
+    ///
+    /// By default, `NaN` and both infinities are injected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set of injections is empty.
+    pub fn with_injections<J>(mut self, injections: J) -> Self
+    where
+        J: IntoIterator<Item = Injection>,
+    {
+        self.injections = injections.into_iter().collect();
+        assert!(!self.injections.is_empty(), "no injections");
+        self
+    }
+
+    // SplitMix64.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+impl<I, T, C> Iterator for Inject<I>
+where
+    I: Iterator<Item = Constrained<T, C>>,
+    T: Primitive,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let inner = self.input.next()?.into_inner();
+        let index = self.index;
+        self.index += 1;
+        // The generator is advanced for every item, so that injections at a given position do
+        // not depend on whether or not previous positions are explicitly injected.
+        let sample = self.next_unit();
+        let choice = self.next_u64();
+        if self.positions.contains(&index) || sample < self.probability {
+            let n = self.injections.len() as u64;
+            Some(self.injections[(choice % n) as usize].into_primitive())
+        }
+        else {
+            Some(inner)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use crate::constraint::IsReal;
+    use crate::divergence::{OrError, OrPanic};
+    use crate::inject::{self, Inject, InjectingDivergence};
+    use crate::proxy::Constrained;
+    use crate::real::UnaryRealFunction;
+    use crate::R64;
+
+    type Real = Constrained<f64, IsReal<InjectingDivergence<OrError>>>;
+
+    #[test]
+    fn inject_at_positions() {
+        let xs: Vec<f64> = Inject::new([<R64>::ONE; 4], 7).at([0, 2]).collect();
+        assert!(!xs[0].is_finite());
+        assert_eq!(xs[1], 1.0);
+        assert!(!xs[2].is_finite());
+        assert_eq!(xs[3], 1.0);
+    }
+
+    #[test]
+    fn inject_with_probability() {
+        let sample = |seed| -> Vec<f64> {
+            Inject::new([<R64>::ONE; 1000], seed)
+                .with_probability(0.25)
+                .collect()
+        };
+        let xs = sample(42);
+        let n = xs.iter().filter(|x| !x.is_finite()).count();
+        assert!((150..350).contains(&n));
+        assert_eq!(
+            xs.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+            sample(42).iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+        );
+        assert!(Inject::new([<R64>::ONE; 100], 42)
+            .with_probability(0.0)
+            .all(|x| x == 1.0));
+    }
+
+    #[test]
+    fn injecting_divergence() {
+        let x = Real::try_new(2.0).unwrap();
+        assert!((x + x).is_defined());
+        inject::with_injection(|| {
+            assert!(inject::is_injecting());
+            assert!((x + x).is_undefined());
+            assert!(Real::try_new(1.0).is_err());
+        });
+        assert!(!inject::is_injecting());
+        assert!((x * x).is_defined());
+    }
+
+    #[test]
+    #[should_panic]
+    fn injecting_divergence_or_panic() {
+        type Real = Constrained<f64, IsReal<InjectingDivergence<OrPanic>>>;
+
+        let x = Real::assert(2.0);
+        inject::with_injection(|| x + x);
+    }
+}
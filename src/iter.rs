@@ -0,0 +1,1028 @@
+//! Iterator extensions for [`Constrained`] types.
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+
+use core::cmp::Ordering;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use thiserror::Error;
+
+use crate::cmp::{CanonicalOrd, EmptyInhabitant};
+use crate::constraint::Constraint;
+#[cfg(feature = "std")]
+use crate::divergence::{self, Divergence};
+use crate::expression::{Defined, Expression, Undefined};
+use crate::proxy::{Constrained, ExpressionFor, OutputFor};
+use crate::{try_expression, Primitive};
+
+/// Extrema of [`Expression`]s.
+///
+/// These functions reduce an iterator of [`Expression`]s to its minimum or maximum. The output is
+/// [`Undefined`] if any item is undefined, in which case the error of the first undefined item is
+/// forwarded, or if the iterator is empty, in which case the error is the [empty
+/// inhabitant][`EmptyInhabitant`] of the error type.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::divergence::OrError;
+/// use decorum::iter::ExpressionExtrema;
+/// use decorum::proxy::ExpressionFor;
+/// use decorum::R64;
+///
+/// let xs: [ExpressionFor<R64<OrError>>; 3] = [3.0.into(), 1.0.into(), 2.0.into()];
+/// assert_eq!(xs.into_iter().min_expression().unwrap(), 1.0);
+///
+/// let xs: [ExpressionFor<R64<OrError>>; 2] = [3.0.into(), f64::NAN.into()];
+/// assert!(xs.into_iter().max_expression().is_undefined());
+/// ```
+///
+/// [`EmptyInhabitant`]: crate::cmp::EmptyInhabitant
+/// [`Expression`]: crate::expression::Expression
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub trait ExpressionExtrema: Iterator {
+    type Output;
+
+    fn min_expression(self) -> Self::Output;
+
+    fn max_expression(self) -> Self::Output;
+}
+
+impl<I, T, C> ExpressionExtrema for I
+where
+    I: Iterator<Item = ExpressionFor<Constrained<T, C>>>,
+    T: Primitive,
+    C: Constraint,
+    C::Error: EmptyInhabitant,
+{
+    type Output = ExpressionFor<Constrained<T, C>>;
+
+    fn min_expression(self) -> Self::Output {
+        reduce_expression(self, Ord::min)
+    }
+
+    fn max_expression(self) -> Self::Output {
+        reduce_expression(self, Ord::max)
+    }
+}
+
+/// Extrema of items by totally ordered floating-point keys.
+///
+/// These functions are like [`Iterator::min_by_key`] and [`Iterator::max_by_key`], but accept
+/// keys that are primitive floating-point or [`Constrained`] types and compare them with the
+/// total ordering of [`CanonicalOrd`]. This replaces the common (and panicking) pattern
+/// `max_by(|a, b| a.partial_cmp(b).unwrap())`. As with [`Iterator::max_by_key`], the last of equal
+/// maxima and the first of equal minima are returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::iter::FloatKeyExtrema;
+///
+/// let points = [(0.0f64, 1.0f64), (1.0, f64::NAN), (2.0, 4.0)];
+/// let max = points.iter().max_by_float_key(|point| point.0).unwrap();
+/// assert_eq!(max.0, 2.0);
+///
+/// // `NaN` is the greatest value in the total ordering.
+/// let max = points.iter().max_by_float_key(|point| point.1).unwrap();
+/// assert_eq!(max.0, 1.0);
+/// ```
+///
+/// [`CanonicalOrd`]: crate::cmp::CanonicalOrd
+/// [`Constrained`]: crate::proxy::Constrained
+pub trait FloatKeyExtrema: Iterator + Sized {
+    fn min_by_float_key<K, F>(self, f: F) -> Option<Self::Item>
+    where
+        K: CanonicalOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        min_by_float_key(self, f)
+    }
+
+    fn max_by_float_key<K, F>(self, f: F) -> Option<Self::Item>
+    where
+        K: CanonicalOrd,
+        F: FnMut(&Self::Item) -> K,
+    {
+        max_by_float_key(self, f)
+    }
+}
+
+impl<I> FloatKeyExtrema for I where I: Iterator {}
+
+/// Gets the item with the minimum floating-point key.
+///
+/// See [`FloatKeyExtrema`].
+pub fn min_by_float_key<I, K, F>(items: I, mut f: F) -> Option<I::Item>
+where
+    I: IntoIterator,
+    K: CanonicalOrd,
+    F: FnMut(&I::Item) -> K,
+{
+    items
+        .into_iter()
+        .map(|item| (f(&item), item))
+        .min_by(|(a, _), (b, _)| a.cmp_canonical(b))
+        .map(|(_, item)| item)
+}
+
+/// Gets the item with the maximum floating-point key.
+///
+/// See [`FloatKeyExtrema`].
+pub fn max_by_float_key<I, K, F>(items: I, mut f: F) -> Option<I::Item>
+where
+    I: IntoIterator,
+    K: CanonicalOrd,
+    F: FnMut(&I::Item) -> K,
+{
+    items
+        .into_iter()
+        .map(|item| (f(&item), item))
+        .max_by(|(a, _), (b, _)| a.cmp_canonical(b))
+        .map(|(_, item)| item)
+}
+
+/// Iterator adapters for [`Expression`]s.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::divergence::OrError;
+/// use decorum::iter::ExpressionIterator;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::R64;
+///
+/// let xs = [4.0, 1.0, 0.0, 2.0].map(R64::<OrError>::assert);
+/// let recips: Vec<_> = xs
+///     .into_iter()
+///     .map(|x| R64::ONE / x)
+///     .take_while_defined()
+///     .collect();
+/// assert_eq!(recips, [0.25, 1.0]);
+///
+/// let recips: Vec<_> = xs
+///     .into_iter()
+///     .map(|x| R64::ONE / x)
+///     .skip_undefined()
+///     .collect();
+/// assert_eq!(recips, [0.25, 1.0, 0.5]);
+/// ```
+///
+/// [`Expression`]: crate::expression::Expression
+pub trait ExpressionIterator<T, E>: Iterator<Item = Expression<T, E>> + Sized {
+    /// Gets an iterator over the defined values of the items until the first undefined item.
+    fn take_while_defined(self) -> TakeWhileDefined<Self, E> {
+        TakeWhileDefined {
+            input: self,
+            undefined: None,
+        }
+    }
+
+    /// Gets an iterator over the defined values of the items, skipping undefined items.
+    fn skip_undefined(self) -> SkipUndefined<Self> {
+        SkipUndefined { input: self }
+    }
+
+    /// Partitions the items into their defined values and undefined errors.
+    #[cfg(feature = "std")]
+    fn partition_defined(self) -> (Vec<T>, Vec<E>) {
+        let mut defined = Vec::new();
+        let mut undefined = Vec::new();
+        for expression in self {
+            match expression {
+                Defined(value) => defined.push(value),
+                Undefined(error) => undefined.push(error),
+            }
+        }
+        (defined, undefined)
+    }
+}
+
+impl<I, T, E> ExpressionIterator<T, E> for I where I: Iterator<Item = Expression<T, E>> {}
+
+/// Iterator over the defined values of [`Expression`]s until the first undefined item.
+///
+/// See [`ExpressionIterator::take_while_defined`].
+///
+/// [`Expression`]: crate::expression::Expression
+#[derive(Clone, Debug)]
+pub struct TakeWhileDefined<I, E> {
+    input: I,
+    undefined: Option<E>,
+}
+
+impl<I, E> TakeWhileDefined<I, E> {
+    /// Gets the error of the undefined item that ended the iterator, if any.
+    pub fn undefined(&self) -> Option<&E> {
+        self.undefined.as_ref()
+    }
+}
+
+impl<I, T, E> Iterator for TakeWhileDefined<I, E>
+where
+    I: Iterator<Item = Expression<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.undefined.is_some() {
+            return None;
+        }
+        match self.input.next()? {
+            Defined(value) => Some(value),
+            Undefined(error) => {
+                self.undefined = Some(error);
+                None
+            }
+        }
+    }
+}
+
+/// Iterator over the defined values of [`Expression`]s.
+///
+/// See [`ExpressionIterator::skip_undefined`].
+///
+/// [`Expression`]: crate::expression::Expression
+#[derive(Clone, Debug)]
+pub struct SkipUndefined<I> {
+    input: I,
+}
+
+impl<I, T, E> Iterator for SkipUndefined<I>
+where
+    I: Iterator<Item = Expression<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.input.by_ref().find_map(Expression::defined)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.input.size_hint().1)
+    }
+}
+
+/// Summation in a wider floating-point type.
+///
+/// The [`Sum`] implementation of [`Constrained`] types checks the accumulated sum after each
+/// addition, so the sum of many large `f32` values may diverge when an intermediate sum overflows
+/// even if the total sum does not. This trait instead accumulates the sum in `f64` and rounds to
+/// the nearest `f32` at the end, which also reduces the rounding error of the sum. Only the
+/// rounded output is checked against the constraint of the proxy.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::iter::WideningSum;
+/// use decorum::R32;
+///
+/// let xs: [R32; 3] = [
+///     R32::assert(f32::MAX),
+///     R32::assert(f32::MAX),
+///     R32::assert(-f32::MAX),
+/// ];
+/// let sum: R32 = xs.into_iter().sum_widening();
+/// assert_eq!(sum, f32::MAX);
+/// ```
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`Sum`]: core::iter::Sum
+pub trait WideningSum: Iterator {
+    type Output;
+
+    fn sum_widening(self) -> Self::Output;
+}
+
+impl<I, C> WideningSum for I
+where
+    I: Iterator<Item = Constrained<f32, C>>,
+    C: Constraint,
+{
+    type Output = OutputFor<Constrained<f32, C>>;
+
+    fn sum_widening(self) -> Self::Output {
+        let sum: f64 = self.map(|x| f64::from(x.into_inner())).sum();
+        Constrained::<f32, C>::new(sum as f32)
+    }
+}
+
+/// Strategy of floating-point summation.
+///
+/// Each strategy trades performance for accuracy. For a sum of $n$ values, the worst-case error
+/// of naive summation grows with $O(n)$, pairwise summation with $O(\log n)$, and compensated
+/// summation is independent of $n$ (to first order).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Summation {
+    /// Adds values from left to right.
+    #[default]
+    Naive,
+    /// Recursively adds the sums of halves of the values (cascade summation).
+    Pairwise,
+    /// Adds values from left to right and accumulates the rounding error of each addition in a
+    /// separate compensation term (Kahan-Babuska or Neumaier summation).
+    Compensated,
+}
+
+/// Summation with a [`Summation`] strategy.
+///
+/// Summation is computed with the primitive floating-point type of the proxy and only the output
+/// is checked against the constraint of the proxy.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::iter::{StrategicSum, Summation};
+/// use decorum::R64;
+///
+/// let xs = [1.0, 1.0e100, 1.0, -1.0e100].map(<R64>::assert);
+/// assert_eq!(xs.into_iter().sum_with(Summation::Naive), 0.0);
+/// assert_eq!(xs.into_iter().sum_with(Summation::Compensated), 2.0);
+/// ```
+pub trait StrategicSum: Iterator {
+    type Output;
+
+    fn sum_with(self, summation: Summation) -> Self::Output;
+
+    /// Sums the items with [`Summation::Pairwise`].
+    fn sum_pairwise(self) -> Self::Output
+    where
+        Self: Sized,
+    {
+        self.sum_with(Summation::Pairwise)
+    }
+}
+
+impl<I, T, C> StrategicSum for I
+where
+    I: Iterator<Item = Constrained<T, C>>,
+    T: Primitive,
+    C: Constraint,
+{
+    type Output = OutputFor<Constrained<T, C>>;
+
+    fn sum_with(self, summation: Summation) -> Self::Output {
+        let xs = self.map(Constrained::into_inner);
+        Constrained::<T, C>::new(match summation {
+            Summation::Naive => xs.fold(T::ZERO, |sum, x| sum + x),
+            Summation::Pairwise => sum_pairwise(xs),
+            Summation::Compensated => sum_compensated(xs),
+        })
+    }
+}
+
+/// Summation of slices with a fixed order of operations.
+///
+/// Floating-point addition is not associative, so the sum of a sequence depends on the order in
+/// which its elements are added. In particular, parallel sums typically depend on the number of
+/// threads and how work is scheduled among them, so they are not reproducible from run to run.
+/// This trait sums a slice with a fixed tree of additions that depends only on the length of the
+/// slice: the slice is partitioned into blocks of contiguous elements that are summed from left
+/// to right and the sums of these blocks are then added pairwise. The output is bitwise identical
+/// regardless of the number of threads used to compute it.
+///
+/// Only the output is checked against the constraint of the proxy.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::iter::OrderedSum;
+/// use decorum::R64;
+///
+/// let xs: Vec<R64> = (0..100_000)
+///     .map(|i| R64::assert(1.0 / f64::from(i + 1)))
+///     .collect();
+/// let sum: R64 = xs.sum_ordered();
+/// ```
+#[cfg(feature = "std")]
+pub trait OrderedSum {
+    type Output;
+
+    /// Sums the slice on the current thread.
+    fn sum_ordered(&self) -> Self::Output;
+
+    /// Sums the slice on the current [`rayon`] thread pool.
+    ///
+    /// Subtrees of the fixed tree of additions are summed with [`rayon::join`], so the output is
+    /// bitwise identical to the output of [`OrderedSum::sum_ordered`] regardless of the number of
+    /// threads in the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::iter::OrderedSum;
+    /// use decorum::R64;
+    ///
+    /// let xs: Vec<R64> = (0..100_000)
+    ///     .map(|i| R64::assert(1.0 / f64::from(i + 1)))
+    ///     .collect();
+    /// let sum: R64 = xs.sum_ordered_parallel();
+    /// assert_eq!(
+    ///     sum.into_inner().to_bits(),
+    ///     xs.sum_ordered().into_inner().to_bits()
+    /// );
+    /// ```
+    ///
+    /// [`rayon`]: https://crates.io/crates/rayon
+    #[cfg(feature = "rayon")]
+    fn sum_ordered_parallel(&self) -> Self::Output;
+}
+
+#[cfg(feature = "std")]
+impl<T, C> OrderedSum for [Constrained<T, C>]
+where
+    T: Primitive + Send + Sync,
+    C: Constraint,
+{
+    type Output = OutputFor<Constrained<T, C>>;
+
+    fn sum_ordered(&self) -> Self::Output {
+        Constrained::<T, C>::new(sum_ordered(self))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn sum_ordered_parallel(&self) -> Self::Output {
+        Constrained::<T, C>::new(sum_ordered_parallel(self))
+    }
+}
+
+/// Error of a weighted reduction.
+///
+/// See [`weighted_sum`] and [`weighted_mean`].
+#[derive(Clone, Copy, Debug, Error)]
+pub enum WeightError<E> {
+    #[error("{}", "values and weights have different lengths")]
+    Length,
+    #[error("{}", "total weight is not positive")]
+    Weight,
+    #[error("{0}")]
+    Constraint(E),
+}
+
+/// Gets the sum of values scaled by weights.
+///
+/// Products are accumulated with [compensated summation][`Summation::Compensated`] and only the
+/// output is checked against the constraint of the proxy.
+///
+/// # Errors
+///
+/// Returns [`WeightError::Length`] if the slices have different lengths and
+/// [`WeightError::Constraint`] if the sum does not satisfy the constraint of the proxy (e.g., if it
+/// overflows or is not a real number).
+pub fn weighted_sum<T, C>(
+    values: &[Constrained<T, C>],
+    weights: &[Constrained<T, C>],
+) -> Expression<Constrained<T, C>, WeightError<C::Error>>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    if values.len() != weights.len() {
+        return Undefined(WeightError::Length);
+    }
+    let sum = sum_compensated(
+        values
+            .iter()
+            .zip(weights)
+            .map(|(x, w)| x.into_inner() * w.into_inner()),
+    );
+    Constrained::try_new(sum)
+        .map_err(WeightError::Constraint)
+        .into()
+}
+
+/// Gets the mean of values weighted by weights.
+///
+/// Both the weighted sum and the total weight are accumulated with [compensated
+/// summation][`Summation::Compensated`] and only the output is checked against the constraint of
+/// the proxy.
+///
+/// # Errors
+///
+/// Returns [`WeightError::Length`] if the slices have different lengths and
+/// [`WeightError::Weight`] if the total weight is not positive (including when the slices are
+/// empty). Returns [`WeightError::Constraint`] if the mean does not satisfy the constraint of the
+/// proxy (e.g., if the sums overflow).
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::iter::{self, WeightError};
+/// use decorum::R64;
+///
+/// let xs = [1.0, 2.0, 4.0].map(<R64>::assert);
+/// let ws = [1.0, 1.0, 2.0].map(<R64>::assert);
+/// assert_eq!(iter::weighted_mean(&xs, &ws).unwrap(), 2.75);
+///
+/// let ws = [1.0, 1.0, -2.0].map(<R64>::assert);
+/// assert!(matches!(
+///     iter::weighted_mean(&xs, &ws).undefined(),
+///     Some(WeightError::Weight),
+/// ));
+/// ```
+pub fn weighted_mean<T, C>(
+    values: &[Constrained<T, C>],
+    weights: &[Constrained<T, C>],
+) -> Expression<Constrained<T, C>, WeightError<C::Error>>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let sum = try_expression!(weighted_sum(values, weights));
+    let total = sum_compensated(weights.iter().map(|w| w.into_inner()));
+    // This also detects `NaN` total weights.
+    if total.partial_cmp(&T::ZERO) != Some(Ordering::Greater) {
+        return Undefined(WeightError::Weight);
+    }
+    Constrained::try_new(sum.into_inner() / total)
+        .map_err(WeightError::Constraint)
+        .into()
+}
+
+/// Error of a `Cumulative` operation with the index of the element at which it occurred.
+#[derive(Clone, Copy, Debug, Error)]
+#[error("element {index}: {error}")]
+pub struct IndexedError<E> {
+    pub index: usize,
+    pub error: E,
+}
+
+/// Cumulative operations over slices of [`Constrained`] values.
+///
+/// The output of each operation is a vector of validated values. The output diverges once if any
+/// of these values does not satisfy the constraint of the proxy, in which case the error is an
+/// [`IndexedError`] with the index of the first such value in the output.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::constraint::IsReal;
+/// use decorum::divergence::{AsResult, OrError};
+/// use decorum::iter::Cumulative;
+/// use decorum::proxy::Constrained;
+///
+/// type Real = Constrained<f64, IsReal<OrError<AsResult>>>;
+///
+/// let xs = [1.0, 2.0, 3.0].map(Real::assert);
+/// assert_eq!(xs.cumsum().unwrap(), [1.0, 3.0, 6.0]);
+/// assert_eq!(xs.cumprod().unwrap(), [1.0, 2.0, 6.0]);
+/// assert_eq!(xs.diff().unwrap(), [1.0, 1.0]);
+///
+/// let xs = [f64::MAX, f64::MAX, 1.0].map(Real::assert);
+/// assert_eq!(xs.cumsum().unwrap_err().index, 1);
+/// ```
+///
+/// [`Constrained`]: crate::proxy::Constrained
+#[cfg(feature = "std")]
+pub trait Cumulative {
+    type Output;
+
+    /// Gets the sums of the prefixes of the slice.
+    fn cumsum(&self) -> Self::Output;
+
+    /// Gets the sums of the prefixes of the slice with compensated summation.
+    ///
+    /// See [`Summation::Compensated`].
+    fn cumsum_compensated(&self) -> Self::Output;
+
+    /// Gets the products of the prefixes of the slice.
+    fn cumprod(&self) -> Self::Output;
+
+    /// Gets the differences of adjacent elements of the slice.
+    ///
+    /// The output has one fewer element than the slice (or none if the slice is empty).
+    fn diff(&self) -> Self::Output;
+}
+
+#[cfg(feature = "std")]
+impl<T, C> Cumulative for [Constrained<T, C>]
+where
+    T: Primitive,
+    C: Constraint,
+{
+    type Output =
+        divergence::OutputFor<C::Divergence, Vec<Constrained<T, C>>, IndexedError<C::Error>>;
+
+    fn cumsum(&self) -> Self::Output {
+        checked_scan::<_, T, C>(self.iter().scan(T::ZERO, |sum, x| {
+            *sum = *sum + x.into_inner();
+            Some(*sum)
+        }))
+    }
+
+    fn cumsum_compensated(&self) -> Self::Output {
+        checked_scan::<_, T, C>(
+            self.iter()
+                .scan((T::ZERO, T::ZERO), |(sum, compensation), x| {
+                    let x = x.into_inner();
+                    let next = *sum + x;
+                    *compensation = *compensation
+                        + if abs(*sum) >= abs(x) {
+                            (*sum - next) + x
+                        }
+                        else {
+                            (x - next) + *sum
+                        };
+                    *sum = next;
+                    Some(*sum + *compensation)
+                }),
+        )
+    }
+
+    fn cumprod(&self) -> Self::Output {
+        checked_scan::<_, T, C>(self.iter().scan(T::ONE, |product, x| {
+            *product = *product * x.into_inner();
+            Some(*product)
+        }))
+    }
+
+    fn diff(&self) -> Self::Output {
+        checked_scan::<_, T, C>(
+            self.windows(2)
+                .map(|pair| pair[1].into_inner() - pair[0].into_inner()),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+type CumulativeOutput<T, C> = divergence::OutputFor<
+    <C as Constraint>::Divergence,
+    Vec<Constrained<T, C>>,
+    IndexedError<<C as Constraint>::Error>,
+>;
+
+// Collects primitive values into proxies and diverges at the first value that does not satisfy
+// the constraint.
+#[cfg(feature = "std")]
+fn checked_scan<I, T, C>(xs: I) -> CumulativeOutput<T, C>
+where
+    I: Iterator<Item = T>,
+    T: Primitive,
+    C: Constraint,
+{
+    C::Divergence::diverge(
+        xs.enumerate()
+            .map(|(index, inner)| {
+                Constrained::try_new(inner).map_err(|error| IndexedError { index, error })
+            })
+            .collect(),
+    )
+}
+
+// The number of elements in the blocks of an ordered sum.
+#[cfg(feature = "std")]
+const SUM_BLOCK_LEN: usize = 1024;
+
+// Splits a slice into the subtrees of an ordered sum. The split is at a block boundary near the
+// middle of the slice, so the tree of additions depends only on the length of the slice. Returns
+// `None` if the slice is a single block.
+#[cfg(feature = "std")]
+fn split_ordered<T>(xs: &[T]) -> Option<(&[T], &[T])> {
+    (xs.len() > SUM_BLOCK_LEN).then(|| {
+        let blocks = (xs.len() + SUM_BLOCK_LEN - 1) / SUM_BLOCK_LEN;
+        xs.split_at((blocks / 2) * SUM_BLOCK_LEN)
+    })
+}
+
+// Sums a slice with a tree of additions that depends only on its length.
+#[cfg(feature = "std")]
+fn sum_ordered<T, C>(xs: &[Constrained<T, C>]) -> T
+where
+    T: Primitive,
+{
+    match split_ordered(xs) {
+        Some((left, right)) => sum_ordered(left) + sum_ordered(right),
+        None => xs.iter().fold(T::ZERO, |sum, x| sum + x.into_inner()),
+    }
+}
+
+// Sums a slice with the same tree of additions as `sum_ordered`, but sums subtrees on the current
+// `rayon` thread pool.
+#[cfg(feature = "rayon")]
+fn sum_ordered_parallel<T, C>(xs: &[Constrained<T, C>]) -> T
+where
+    T: Primitive + Send + Sync,
+{
+    match split_ordered(xs) {
+        Some((left, right)) => {
+            let (left, right) = rayon::join(
+                || sum_ordered_parallel(left),
+                || sum_ordered_parallel(right),
+            );
+            left + right
+        }
+        None => sum_ordered(xs),
+    }
+}
+
+// Sums values pairwise without allocating. Blocks of values are summed naively and the sums of
+// blocks are merged like the digits of a binary counter, such that each partial sum is the sum of
+// a power of two number of blocks.
+fn sum_pairwise<I, T>(xs: I) -> T
+where
+    I: Iterator<Item = T>,
+    T: Primitive,
+{
+    const BLOCK_LEN: usize = 8;
+
+    let mut xs = xs.peekable();
+    let mut partials = [T::ZERO; usize::BITS as usize];
+    let mut count: usize = 0;
+    while xs.peek().is_some() {
+        let mut sum = xs.by_ref().take(BLOCK_LEN).fold(T::ZERO, |sum, x| sum + x);
+        let mut level = 0;
+        while count & (1 << level) != 0 {
+            sum = partials[level] + sum;
+            level += 1;
+        }
+        partials[level] = sum;
+        count += 1;
+    }
+    (0..partials.len())
+        .filter(|level| count & (1 << level) != 0)
+        .fold(T::ZERO, |sum, level| partials[level] + sum)
+}
+
+fn sum_compensated<I, T>(xs: I) -> T
+where
+    I: Iterator<Item = T>,
+    T: Primitive,
+{
+    let (sum, compensation) = xs.fold((T::ZERO, T::ZERO), |(sum, compensation), x| {
+        let next = sum + x;
+        let error = if abs(sum) >= abs(x) {
+            (sum - next) + x
+        }
+        else {
+            (x - next) + sum
+        };
+        (next, compensation + error)
+    });
+    sum + compensation
+}
+
+fn abs<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    if x < T::ZERO {
+        -x
+    }
+    else {
+        x
+    }
+}
+
+fn reduce_expression<I, T, C, F>(expressions: I, mut f: F) -> ExpressionFor<Constrained<T, C>>
+where
+    I: Iterator<Item = ExpressionFor<Constrained<T, C>>>,
+    T: Primitive,
+    C: Constraint,
+    C::Error: EmptyInhabitant,
+    F: FnMut(Constrained<T, C>, Constrained<T, C>) -> Constrained<T, C>,
+{
+    let mut output = None;
+    for expression in expressions {
+        let x = try_expression!(expression);
+        output = Some(match output {
+            Some(y) => f(y, x),
+            _ => x,
+        });
+    }
+    match output {
+        Some(output) => Defined(output),
+        _ => Undefined(EmptyInhabitant::empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::divergence::{AsResult, OrError};
+    use crate::iter;
+    #[cfg(feature = "std")]
+    use crate::iter::OrderedSum;
+    use crate::iter::{
+        ExpressionExtrema, ExpressionIterator, FloatKeyExtrema, StrategicSum, Summation,
+        WideningSum,
+    };
+    use crate::proxy::ExpressionFor;
+    use crate::{Real, R32, R64};
+
+    type RealExpression = ExpressionFor<R64<OrError>>;
+
+    #[test]
+    fn extrema_expression() {
+        let xs: [RealExpression; 4] = [2.0, -1.0, 3.0, 0.0].map(RealExpression::from);
+        assert_eq!(xs.into_iter().min_expression().unwrap(), -1.0);
+        assert_eq!(xs.into_iter().max_expression().unwrap(), 3.0);
+
+        let xs: [RealExpression; 3] = [2.0, f64::NAN, 3.0].map(RealExpression::from);
+        assert!(xs.into_iter().min_expression().is_undefined());
+        assert!(xs.into_iter().max_expression().is_undefined());
+
+        let xs: [RealExpression; 0] = [];
+        assert!(xs.into_iter().min_expression().is_undefined());
+        assert!(core::iter::empty::<RealExpression>()
+            .max_expression()
+            .is_undefined());
+    }
+
+    #[test]
+    fn float_key_extrema() {
+        let xs = [(1.0f64, 'a'), (-0.0, 'b'), (0.0, 'c'), (f64::NAN, 'd')];
+        assert_eq!(xs.iter().min_by_float_key(|x| x.0).unwrap().1, 'b');
+        assert_eq!(xs.iter().max_by_float_key(|x| x.0).unwrap().1, 'd');
+        assert_eq!(xs[..3].iter().max_by_float_key(|x| x.0).unwrap().1, 'a');
+
+        let xs = [2.0, -1.0, 3.0].map(<R64>::assert);
+        assert_eq!(xs.into_iter().max_by_float_key(|x| *x), Some(xs[2]));
+        assert_eq!(
+            crate::iter::min_by_float_key(xs, |x| -*x),
+            Some(<R64>::assert(3.0))
+        );
+        assert_eq!(core::iter::empty::<f64>().min_by_float_key(|x| *x), None);
+    }
+
+    #[test]
+    fn sum_widening() {
+        let xs = [f32::MAX, f32::MAX, -f32::MAX, -f32::MAX, 1.0].map(<R32>::assert);
+        let sum: R32 = xs.into_iter().sum_widening();
+        assert_eq!(sum, 1.0);
+
+        let xs = [1.0e-8, 1.0, -1.0].map(<R32>::assert);
+        let sum: R32 = xs.into_iter().sum_widening();
+        assert_eq!(sum, 1.0e-8);
+    }
+
+    #[test]
+    fn sum_widening_overflow() {
+        type Checked = Real<f32, OrError<AsResult>>;
+
+        let xs = [f32::MAX, f32::MAX].map(Checked::assert);
+        assert!(xs.into_iter().sum_widening().is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sum_ordered() {
+        let xs: std::vec::Vec<R64> = (0..10_000)
+            .map(|i| R64::assert(f64::from(i).sin() * 1.0e10))
+            .collect();
+        let bits = xs.sum_ordered().into_inner().to_bits();
+        // Sum subtrees on separate threads and in either order, as a parallel sum would.
+        for depth in 0..4 {
+            assert_eq!(sum_ordered_threaded(&xs, depth, false).to_bits(), bits);
+            assert_eq!(sum_ordered_threaded(&xs, depth, true).to_bits(), bits);
+        }
+        assert_eq!(<[R64]>::sum_ordered(&[]), 0.0);
+    }
+
+    // Sums a slice with the tree of additions of an ordered sum, but sums the subtrees of the top
+    // `depth` levels of the tree on separate threads. If `reversed` is `true`, then right subtrees
+    // are spawned and joined before left subtrees.
+    #[cfg(feature = "std")]
+    fn sum_ordered_threaded(xs: &[R64], depth: usize, reversed: bool) -> f64 {
+        match iter::split_ordered(xs) {
+            Some((left, right)) if depth > 0 => std::thread::scope(|scope| {
+                let sum = |xs| move || sum_ordered_threaded(xs, depth - 1, reversed);
+                if reversed {
+                    let right = scope.spawn(sum(right));
+                    let left = scope.spawn(sum(left));
+                    let right = right.join().unwrap();
+                    left.join().unwrap() + right
+                }
+                else {
+                    let left = scope.spawn(sum(left));
+                    let right = scope.spawn(sum(right));
+                    left.join().unwrap() + right.join().unwrap()
+                }
+            }),
+            _ => xs.sum_ordered().into_inner(),
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn sum_ordered_parallel() {
+        let xs: std::vec::Vec<R64> = (0..10_000)
+            .map(|i| R64::assert(f64::from(i).sin() * 1.0e10))
+            .collect();
+        let bits = xs.sum_ordered().into_inner().to_bits();
+        for threads in 1..=8 {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let sum = pool.install(|| xs.sum_ordered_parallel());
+            assert_eq!(sum.into_inner().to_bits(), bits);
+        }
+        assert_eq!(<[R64]>::sum_ordered_parallel(&[]), 0.0);
+    }
+
+    #[test]
+    fn sum_pairwise() {
+        let xs = (0..1000).map(|i| <R64>::assert(f64::from(i)));
+        assert_eq!(xs.sum_pairwise(), 499_500.0);
+        assert_eq!(core::iter::empty::<R64>().sum_pairwise(), 0.0);
+
+        // The naive sum of many small values accumulates more rounding error than the pairwise
+        // sum.
+        let xs = || core::iter::repeat(<R32>::assert(0.1)).take(1 << 20);
+        let exact = 104_857.6f64;
+        let naive: R32 = xs().sum_with(Summation::Naive);
+        let pairwise: R32 = xs().sum_pairwise();
+        assert!(
+            (f64::from(pairwise.into_inner()) - exact).abs()
+                < (f64::from(naive.into_inner()) - exact).abs()
+        );
+    }
+
+    #[test]
+    fn sum_compensated() {
+        let xs = [1.0, 1.0e100, 1.0, -1.0e100].map(<R64>::assert);
+        assert_eq!(xs.into_iter().sum_with(Summation::Naive), 0.0);
+        assert_eq!(xs.into_iter().sum_with(Summation::Pairwise), 0.0);
+        assert_eq!(xs.into_iter().sum_with(Summation::Compensated), 2.0);
+    }
+
+    #[test]
+    fn weighted() {
+        use crate::constraint::IsFloat;
+        use crate::iter::WeightError;
+        use crate::Total;
+
+        let xs = [1.0, 1.0e100, 1.0, -1.0e100].map(<R64>::assert);
+        let ws = [1.0; 4].map(<R64>::assert);
+        assert_eq!(iter::weighted_sum(&xs, &ws).unwrap(), 2.0);
+        assert_eq!(iter::weighted_mean(&xs, &ws).unwrap(), 0.5);
+        assert!(matches!(
+            iter::weighted_sum(&xs, &ws[..3]).undefined(),
+            Some(WeightError::Length)
+        ));
+        assert!(matches!(
+            iter::weighted_mean::<f64, IsFloat>(&[], &[]).undefined(),
+            Some(WeightError::Weight)
+        ));
+
+        let xs = [f64::MAX, f64::MAX].map(<R64>::assert);
+        let ws = [1.0, 1.0].map(<R64>::assert);
+        assert!(matches!(
+            iter::weighted_sum(&xs, &ws).undefined(),
+            Some(WeightError::Constraint(_))
+        ));
+
+        let xs = [1.0, 2.0].map(Total::from);
+        let ws = [1.0, f64::NAN].map(Total::from);
+        assert!(matches!(
+            iter::weighted_mean(&xs, &ws).undefined(),
+            Some(WeightError::Weight)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn cumulative() {
+        use crate::iter::Cumulative;
+
+        let xs = [1.0, 1.0e100, 1.0, -1.0e100].map(<R64>::assert);
+        assert_eq!(xs.cumsum().last().unwrap(), &0.0);
+        assert_eq!(xs.cumsum_compensated().last().unwrap(), &2.0);
+        assert!(<[R64]>::diff(&[]).is_empty());
+
+        let xs = [2.0, f64::MAX, 1.0].map(<R64<OrError<AsResult>>>::assert);
+        let error = xs.cumprod().unwrap_err();
+        assert_eq!(error.index, 1);
+        assert!(xs.diff().is_ok());
+    }
+
+    #[test]
+    fn take_while_defined() {
+        let xs: [RealExpression; 4] = [1.0, 2.0, f64::NAN, 3.0].map(RealExpression::from);
+        let mut defined = xs.into_iter().take_while_defined();
+        assert_eq!(defined.next(), Some(<R64<OrError>>::assert(1.0)));
+        assert_eq!(defined.next(), Some(<R64<OrError>>::assert(2.0)));
+        assert!(defined.undefined().is_none());
+        assert_eq!(defined.next(), None);
+        assert!(defined.undefined().is_some());
+        assert_eq!(defined.next(), None);
+    }
+
+    #[test]
+    fn skip_undefined() {
+        let xs: [RealExpression; 4] = [f64::NAN, 1.0, f64::INFINITY, 3.0].map(RealExpression::from);
+        assert!(xs
+            .into_iter()
+            .skip_undefined()
+            .eq([1.0, 3.0].map(<R64<OrError>>::assert)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn partition_defined() {
+        let xs: [RealExpression; 3] = [f64::NAN, 1.0, 2.0].map(RealExpression::from);
+        let (defined, undefined) = xs.into_iter().partition_defined();
+        assert_eq!(defined, [1.0, 2.0]);
+        assert_eq!(undefined.len(), 1);
+    }
+}
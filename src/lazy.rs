@@ -0,0 +1,611 @@
+//! Lazily evaluated floating-point expression trees.
+//!
+//! [`LazyExpr`] records the operations applied to it as a tree rather than computing them
+//! eagerly. Nothing is checked against a constraint until the tree is evaluated, at which point a
+//! [constraint][`constraint`] and so a [divergence][`divergence`] is chosen. Because the tree is
+//! retained, a failed evaluation can report exactly which operation first produced a value that
+//! does not satisfy the constraint.
+//!
+//! Evaluation checks every intermediate value, not only the final value. This differs from
+//! primitive floating-point arithmetic, where an intermediate `NaN` or infinity may propagate
+//! silently or even disappear (e.g., `1 / +INF` is zero).
+//!
+//! Trees are evaluated, formatted, and dropped without recursion, so expressions may be
+//! accumulated in a loop regardless of their depth.
+//!
+//! # Examples
+//!
+//! Finding the operation that introduced a `NaN`:
+//!
+//! ```rust
+//! use decorum::constraint::IsReal;
+//! use decorum::divergence::OrPanic;
+//! use decorum::lazy::{LazyExpr, Operation, UnaryOperation};
+//!
+//! let x = LazyExpr::new(-1.0f64);
+//! let y = (x + 0.5).sqrt() * 2.0;
+//! assert_eq!(y.to_string(), "(sqrt((-1 + 0.5)) * 2)");
+//!
+//! let error = y.try_evaluate::<IsReal<OrPanic>>().unwrap_err();
+//! assert_eq!(
+//!     error.expression().operation(),
+//!     Operation::Unary(UnaryOperation::Sqrt)
+//! );
+//! assert_eq!(error.expression().to_string(), "sqrt((-1 + 0.5))");
+//! ```
+//!
+//! [`constraint`]: crate::constraint
+//! [`divergence`]: crate::divergence
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::sync::Arc;
+use std::vec;
+use std::vec::Vec;
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::proxy::{Constrained, OutputFor};
+use crate::Primitive;
+
+/// A unary operation in a [`LazyExpr`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UnaryOperation {
+    Neg,
+    Abs,
+    Recip,
+    Sqrt,
+    Exp,
+    Ln,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+}
+
+impl UnaryOperation {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            UnaryOperation::Neg => "neg",
+            UnaryOperation::Abs => "abs",
+            UnaryOperation::Recip => "recip",
+            UnaryOperation::Sqrt => "sqrt",
+            UnaryOperation::Exp => "exp",
+            UnaryOperation::Ln => "ln",
+            UnaryOperation::Sin => "sin",
+            UnaryOperation::Cos => "cos",
+            UnaryOperation::Tan => "tan",
+            UnaryOperation::Asin => "asin",
+            UnaryOperation::Acos => "acos",
+            UnaryOperation::Atan => "atan",
+        }
+    }
+
+    fn apply<T>(&self, x: T) -> T
+    where
+        T: Primitive,
+    {
+        match self {
+            UnaryOperation::Neg => -x,
+            UnaryOperation::Abs => x.abs(),
+            UnaryOperation::Recip => x.recip(),
+            UnaryOperation::Sqrt => x.sqrt(),
+            UnaryOperation::Exp => x.exp(),
+            UnaryOperation::Ln => x.ln(),
+            UnaryOperation::Sin => x.sin(),
+            UnaryOperation::Cos => x.cos(),
+            UnaryOperation::Tan => x.tan(),
+            UnaryOperation::Asin => x.asin(),
+            UnaryOperation::Acos => x.acos(),
+            UnaryOperation::Atan => x.atan(),
+        }
+    }
+}
+
+/// A binary operation in a [`LazyExpr`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BinaryOperation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Log,
+    Hypot,
+    Atan2,
+}
+
+impl BinaryOperation {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            BinaryOperation::Add => "add",
+            BinaryOperation::Sub => "sub",
+            BinaryOperation::Mul => "mul",
+            BinaryOperation::Div => "div",
+            BinaryOperation::Rem => "rem",
+            BinaryOperation::Pow => "pow",
+            BinaryOperation::Log => "log",
+            BinaryOperation::Hypot => "hypot",
+            BinaryOperation::Atan2 => "atan2",
+        }
+    }
+
+    // Gets the infix symbol of the operation, if any.
+    const fn symbol(&self) -> Option<&'static str> {
+        match self {
+            BinaryOperation::Add => Some("+"),
+            BinaryOperation::Sub => Some("-"),
+            BinaryOperation::Mul => Some("*"),
+            BinaryOperation::Div => Some("/"),
+            BinaryOperation::Rem => Some("%"),
+            _ => None,
+        }
+    }
+
+    fn apply<T>(&self, x: T, y: T) -> T
+    where
+        T: Primitive,
+    {
+        match self {
+            BinaryOperation::Add => x + y,
+            BinaryOperation::Sub => x - y,
+            BinaryOperation::Mul => x * y,
+            BinaryOperation::Div => x / y,
+            BinaryOperation::Rem => x % y,
+            BinaryOperation::Pow => x.pow(y),
+            BinaryOperation::Log => x.log(y),
+            BinaryOperation::Hypot => x.hypot(y),
+            BinaryOperation::Atan2 => x.atan2(y),
+        }
+    }
+}
+
+/// The operation at the root of a [`LazyExpr`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operation {
+    /// A value with no operation.
+    Leaf,
+    Unary(UnaryOperation),
+    Binary(BinaryOperation),
+}
+
+/// Error that describes the operation in a [`LazyExpr`] that first produced a value that does
+/// not satisfy a constraint.
+#[derive(Clone, Debug, Error)]
+#[error("{error}: `{expression}`")]
+pub struct LazyError<T, E>
+where
+    T: Debug + Display,
+    E: Debug + Display,
+{
+    expression: LazyExpr<T>,
+    value: T,
+    error: E,
+}
+
+impl<T, E> LazyError<T, E>
+where
+    T: Copy + Debug + Display,
+    E: Debug + Display,
+{
+    /// Gets the subexpression that produced the value that does not satisfy the constraint.
+    pub fn expression(&self) -> &LazyExpr<T> {
+        &self.expression
+    }
+
+    /// Gets the value that does not satisfy the constraint.
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+}
+
+// Operands are stored in a `Vec` rather than an array so that they can be unlinked from their
+// operation when dropped. See the `Drop` implementation for `LazyExpr`.
+#[derive(Debug)]
+enum Node<T> {
+    Leaf(T),
+    Unary(UnaryOperation, Vec<LazyExpr<T>>),
+    Binary(BinaryOperation, Vec<LazyExpr<T>>),
+}
+
+impl<T> Node<T> {
+    fn operands_mut(&mut self) -> Option<&mut Vec<LazyExpr<T>>> {
+        match self {
+            Node::Leaf(_) => None,
+            Node::Unary(_, operands) | Node::Binary(_, operands) => Some(operands),
+        }
+    }
+}
+
+/// A lazily evaluated floating-point expression tree.
+///
+/// `LazyExpr` is cheap to clone: subexpressions are shared rather than copied.
+#[derive(Debug)]
+pub struct LazyExpr<T> {
+    node: Arc<Node<T>>,
+}
+
+impl<T> LazyExpr<T> {
+    fn unary(operation: UnaryOperation, operand: Self) -> Self {
+        LazyExpr {
+            node: Arc::new(Node::Unary(operation, vec![operand])),
+        }
+    }
+
+    fn binary(operation: BinaryOperation, left: Self, right: Self) -> Self {
+        LazyExpr {
+            node: Arc::new(Node::Binary(operation, vec![left, right])),
+        }
+    }
+
+    pub fn operation(&self) -> Operation {
+        match self.node.as_ref() {
+            Node::Leaf(_) => Operation::Leaf,
+            Node::Unary(operation, _) => Operation::Unary(*operation),
+            Node::Binary(operation, _) => Operation::Binary(*operation),
+        }
+    }
+
+    /// Gets the operands of the root operation. Leaves have no operands.
+    pub fn operands(&self) -> &[Self] {
+        match self.node.as_ref() {
+            Node::Leaf(_) => &[],
+            Node::Unary(_, operands) | Node::Binary(_, operands) => operands,
+        }
+    }
+}
+
+impl<T> LazyExpr<T>
+where
+    T: Primitive,
+{
+    pub fn new(value: T) -> Self {
+        LazyExpr {
+            node: Arc::new(Node::Leaf(value)),
+        }
+    }
+
+    pub fn abs(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Abs, self)
+    }
+
+    pub fn recip(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Recip, self)
+    }
+
+    pub fn sqrt(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Sqrt, self)
+    }
+
+    pub fn exp(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Exp, self)
+    }
+
+    pub fn ln(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Ln, self)
+    }
+
+    pub fn sin(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Sin, self)
+    }
+
+    pub fn cos(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Cos, self)
+    }
+
+    pub fn tan(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Tan, self)
+    }
+
+    pub fn asin(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Asin, self)
+    }
+
+    pub fn acos(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Acos, self)
+    }
+
+    pub fn atan(self) -> Self {
+        LazyExpr::unary(UnaryOperation::Atan, self)
+    }
+
+    pub fn pow(self, n: impl Into<Self>) -> Self {
+        LazyExpr::binary(BinaryOperation::Pow, self, n.into())
+    }
+
+    pub fn log(self, base: impl Into<Self>) -> Self {
+        LazyExpr::binary(BinaryOperation::Log, self, base.into())
+    }
+
+    pub fn hypot(self, other: impl Into<Self>) -> Self {
+        LazyExpr::binary(BinaryOperation::Hypot, self, other.into())
+    }
+
+    pub fn atan2(self, other: impl Into<Self>) -> Self {
+        LazyExpr::binary(BinaryOperation::Atan2, self, other.into())
+    }
+
+    /// Evaluates the expression with the divergence of the given constraint.
+    ///
+    /// Every intermediate value is checked against the constraint `C`. If any value does not
+    /// satisfy the constraint, then the output diverges with that value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if an intermediate value does not satisfy the constraint **and** the
+    /// divergence of the constraint panics.
+    pub fn evaluate<C>(&self) -> OutputFor<Constrained<T, C>>
+    where
+        C: Constraint,
+    {
+        match self.evaluate_inner::<C>() {
+            Ok(inner) => Constrained::<T, C>::new(inner),
+            Err((_, inner, _)) => Constrained::<T, C>::new(inner),
+        }
+    }
+
+    /// Evaluates the expression and describes the operation that failed, if any.
+    ///
+    /// This function is independent of the divergence of the constraint `C`; it always outputs a
+    /// [`Result`] and never panics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any intermediate value does not satisfy the constraint `C`. The error
+    /// describes the first operation that produced such a value in evaluation order (left to
+    /// right, operands before operations).
+    pub fn try_evaluate<C>(&self) -> Result<Constrained<T, C>, LazyError<T, C::Error>>
+    where
+        T: Debug + Display,
+        C: Constraint,
+    {
+        self.evaluate_inner::<C>()
+            .map(Constrained::unchecked)
+            .map_err(|(expression, value, error)| LazyError {
+                expression,
+                value,
+                error,
+            })
+    }
+
+    fn evaluate_inner<C>(&self) -> Result<T, (Self, T, C::Error)>
+    where
+        C: Constraint,
+    {
+        // The tree is traversed in post-order with an explicit stack of pending subexpressions,
+        // each of which is paired with whether or not its operands have been evaluated. Operands
+        // are pushed in reverse so that they are evaluated from left to right.
+        let mut pending = vec![(self, false)];
+        let mut values = Vec::new();
+        while let Some((expr, is_evaluated)) = pending.pop() {
+            let value = match (expr.node.as_ref(), is_evaluated) {
+                (Node::Leaf(value), _) => *value,
+                (Node::Unary(_, operands) | Node::Binary(_, operands), false) => {
+                    pending.push((expr, true));
+                    pending.extend(operands.iter().rev().map(|operand| (operand, false)));
+                    continue;
+                }
+                (Node::Unary(operation, _), true) => {
+                    let operand = values.pop().expect("no operand for unary operation");
+                    operation.apply(operand)
+                }
+                (Node::Binary(operation, _), true) => {
+                    let right = values.pop().expect("no operand for binary operation");
+                    let left = values.pop().expect("no operand for binary operation");
+                    operation.apply(left, right)
+                }
+            };
+            if let Err(error) = C::check(value) {
+                return Err((expr.clone(), value, error));
+            }
+            values.push(value);
+        }
+        Ok(values.pop().expect("no value for expression"))
+    }
+}
+
+impl<T> Clone for LazyExpr<T> {
+    fn clone(&self) -> Self {
+        LazyExpr {
+            node: self.node.clone(),
+        }
+    }
+}
+
+impl<T> Display for LazyExpr<T>
+where
+    T: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        enum Token<'e, T> {
+            Expr(&'e LazyExpr<T>),
+            Text(&'static str),
+        }
+
+        // Subexpressions are formatted from an explicit stack of tokens, which are pushed in
+        // reverse.
+        let mut tokens = vec![Token::Expr(self)];
+        while let Some(token) = tokens.pop() {
+            let expr = match token {
+                Token::Expr(expr) => expr,
+                Token::Text(text) => {
+                    formatter.write_str(text)?;
+                    continue;
+                }
+            };
+            match expr.node.as_ref() {
+                Node::Leaf(value) => write!(formatter, "{}", value)?,
+                Node::Unary(UnaryOperation::Neg, operands) => {
+                    formatter.write_str("-")?;
+                    tokens.push(Token::Expr(&operands[0]));
+                }
+                Node::Unary(operation, operands) => {
+                    write!(formatter, "{}(", operation.name())?;
+                    tokens.extend([Token::Text(")"), Token::Expr(&operands[0])]);
+                }
+                Node::Binary(operation, operands) => {
+                    tokens.extend([
+                        Token::Text(")"),
+                        Token::Expr(&operands[1]),
+                        Token::Text(" "),
+                    ]);
+                    match operation.symbol() {
+                        Some(symbol) => {
+                            formatter.write_str("(")?;
+                            tokens.extend([Token::Text(symbol), Token::Text(" ")]);
+                        }
+                        _ => {
+                            write!(formatter, "{}(", operation.name())?;
+                            tokens.push(Token::Text(","));
+                        }
+                    }
+                    tokens.push(Token::Expr(&operands[0]));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Drop for LazyExpr<T> {
+    fn drop(&mut self) {
+        // Dropping the operands of a deep tree recursively overflows the stack. Instead, the
+        // operands of nodes that are not shared are unlinked and dropped from an explicit stack,
+        // so that each drop unlinks (rather than drops) its operands.
+        let mut operands = Vec::new();
+        if let Some(unlinked) = Arc::get_mut(&mut self.node).and_then(Node::operands_mut) {
+            operands.append(unlinked);
+        }
+        while let Some(mut expr) = operands.pop() {
+            if let Some(unlinked) = Arc::get_mut(&mut expr.node).and_then(Node::operands_mut) {
+                operands.append(unlinked);
+            }
+        }
+    }
+}
+
+impl<T> From<T> for LazyExpr<T>
+where
+    T: Primitive,
+{
+    fn from(value: T) -> Self {
+        LazyExpr::new(value)
+    }
+}
+
+impl<T, C> From<Constrained<T, C>> for LazyExpr<T>
+where
+    T: Primitive,
+{
+    fn from(value: Constrained<T, C>) -> Self {
+        LazyExpr::new(value.into_inner())
+    }
+}
+
+impl<T> Neg for LazyExpr<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        LazyExpr::unary(UnaryOperation::Neg, self)
+    }
+}
+
+macro_rules! impl_binary_operation_for_lazy_expr {
+    (operation => $trait:ident :: $method:ident) => {
+        impl<T> $trait for LazyExpr<T> {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self::Output {
+                LazyExpr::binary(BinaryOperation::$trait, self, other)
+            }
+        }
+
+        impl<T> $trait<T> for LazyExpr<T>
+        where
+            T: Primitive,
+        {
+            type Output = Self;
+
+            fn $method(self, other: T) -> Self::Output {
+                LazyExpr::binary(BinaryOperation::$trait, self, LazyExpr::new(other))
+            }
+        }
+    };
+}
+impl_binary_operation_for_lazy_expr!(operation => Add::add);
+impl_binary_operation_for_lazy_expr!(operation => Div::div);
+impl_binary_operation_for_lazy_expr!(operation => Mul::mul);
+impl_binary_operation_for_lazy_expr!(operation => Rem::rem);
+impl_binary_operation_for_lazy_expr!(operation => Sub::sub);
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+
+    use crate::constraint::{IsExtendedReal, IsFloat, IsReal};
+    use crate::divergence::{AsExpression, OrError, OrPanic};
+    use crate::lazy::{BinaryOperation, LazyExpr, Operation};
+
+    #[test]
+    fn evaluate() {
+        let x = LazyExpr::new(3.0f64);
+        let y = (x.clone() * x + 16.0).sqrt();
+        assert_eq!(y.evaluate::<IsReal<OrPanic>>(), 5.0);
+        assert_eq!(y.to_string(), "sqrt(((3 * 3) + 16))");
+    }
+
+    #[test]
+    fn evaluate_intermediate_divergence() {
+        // The infinity disappears in the output of the primitive computation, but not in the
+        // checked evaluation.
+        let y = LazyExpr::new(1.0f64) / (LazyExpr::new(f64::MAX) * 2.0);
+        assert!(y.evaluate::<IsFloat>().into_inner() == 0.0);
+        assert!(y.evaluate::<IsReal<OrError<AsExpression>>>().is_undefined());
+
+        let x = y.try_evaluate::<IsExtendedReal<OrPanic>>().unwrap();
+        assert_eq!(x, 0.0);
+        let error = y.try_evaluate::<IsReal<OrPanic>>().unwrap_err();
+        assert_eq!(
+            error.expression().operation(),
+            Operation::Binary(BinaryOperation::Mul)
+        );
+        assert_eq!(error.value(), f64::INFINITY);
+        assert_eq!(error.expression().operands().len(), 2);
+    }
+
+    #[test]
+    fn evaluate_deep() {
+        const DEPTH: usize = 1_000_000;
+
+        let mut x = LazyExpr::new(0.0f64);
+        for _ in 0..DEPTH {
+            x = x + 1.0;
+        }
+        assert_eq!(x.try_evaluate::<IsReal<OrPanic>>().unwrap(), DEPTH as f64);
+        assert_eq!(x.to_string().matches('+').count(), DEPTH);
+
+        // The undefined subexpression is at the bottom of the tree.
+        let mut y = LazyExpr::new(-1.0f64).sqrt();
+        for _ in 0..DEPTH {
+            y = -(y * 2.0);
+        }
+        let error = y.try_evaluate::<IsReal<OrPanic>>().unwrap_err();
+        assert_eq!(error.expression().to_string(), "sqrt(-1)");
+
+        drop(x);
+        drop(y);
+    }
+
+    #[test]
+    #[should_panic]
+    fn evaluate_or_panic() {
+        let _ = LazyExpr::new(-1.0f64).ln().evaluate::<IsReal<OrPanic>>();
+    }
+}
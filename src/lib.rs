@@ -133,14 +133,65 @@
 
 #[cfg(feature = "std")]
 extern crate std;
+// Allows derived code, which refers to `::decorum`, to be used in tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as decorum;
 
+#[cfg(feature = "std")]
+pub mod arch;
+#[cfg(feature = "ndarray")]
+pub mod array;
+pub mod binade;
+pub mod bits;
 pub mod cmp;
+pub mod constants;
 pub mod constraint;
+pub mod convert;
 pub mod divergence;
+#[cfg(feature = "std")]
+pub mod eval;
 pub mod expression;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fmt;
+pub mod geometry;
 pub mod hash;
+#[cfg(all(feature = "std", any(target_arch = "aarch64", target_arch = "x86_64")))]
+pub mod ieee;
+#[cfg(feature = "std")]
+pub mod inject;
+pub mod iter;
+#[cfg(feature = "std")]
+pub mod lazy;
+pub mod linalg;
+pub mod literal;
+#[cfg(feature = "std")]
+pub mod noise;
+#[cfg(feature = "oracle")]
+pub mod oracle;
+pub mod parse;
+#[cfg(feature = "experimental-posit")]
+pub mod posit;
+#[cfg(feature = "debug-precision")]
+pub mod precision;
 pub mod proxy;
+pub mod quasi;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(feature = "rand")]
+pub mod random_walk;
+pub mod range;
 pub mod real;
+#[cfg(feature = "std")]
+pub mod roots;
+pub mod saturating;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod table;
+pub mod trace;
+pub mod uncertain;
+#[cfg(feature = "std")]
+pub mod vec;
 
 use core::hash::Hash;
 use core::num::FpCategory;
@@ -161,14 +212,21 @@ mod sealed {
 
     impl Sealed for Infallible {}
 
+    // Fused multiply-add of primitive types. See the `arch` module.
+    pub trait FusedMulAdd: Sized {
+        #[cfg(feature = "std")]
+        fn fused_mul_add(self, a: Self, b: Self) -> Self;
+    }
+
     pub trait StaticDebug {
         fn fmt(formatter: &mut Formatter<'_>) -> fmt::Result;
     }
 }
-use crate::sealed::Sealed;
+use crate::sealed::{FusedMulAdd, Sealed};
 
 pub mod prelude {
     pub use crate::cmp::{CanonicalEq, CanonicalOrd};
+    pub use crate::constants::Constants;
     pub use crate::hash::CanonicalHash;
     pub use crate::real::{BinaryRealFunction, UnaryRealFunction};
 }
@@ -388,6 +446,7 @@ pub trait Primitive:
     + Copy
     + EmptyOrd<Empty = Self>
     + Endofunction
+    + FusedMulAdd
     + InfinityEncoding
     + NanEncoding<Nan = Nan<Self>>
     + PartialEq
@@ -514,6 +573,13 @@ macro_rules! impl_primitive {
             }
         }
 
+        impl FusedMulAdd for $t {
+            #[cfg(feature = "std")]
+            fn fused_mul_add(self, a: Self, b: Self) -> Self {
+                <$t>::mul_add(self, a, b)
+            }
+        }
+
         impl Primitive for $t {}
 
         impl Sealed for $t {}
@@ -0,0 +1,517 @@
+//! Closed-form solvers for small linear systems and covariance matrices over [`Constrained`] types.
+//!
+//! This module solves $2\times2$ and $3\times3$ linear systems $Ax=b$ using Cramer's rule.
+//! Rather than outputting infinities or `NaN`s (or requiring callers to check determinants),
+//! solvers output an [`Expression`] that is [`Undefined`] if the matrix is singular or
+//! ill-conditioned.
+//!
+//! A matrix is considered ill-conditioned if its determinant is small relative to the magnitudes
+//! of its rows, such that the solution is dominated by rounding error. This is an inexpensive
+//! estimate of the reciprocal of the condition number of the matrix.
+//!
+//! Before computing determinants, solvers scale each row of the system by a power of two such that
+//! the largest magnitude in the row of the matrix is in $[1, 2)$. This scaling is exact (barring
+//! subnormal elements) and does not change the solution, but prevents the determinant from
+//! overflowing or underflowing when the elements of the matrix are very large or small.
+//!
+//! Matrices are in row-major order.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::linalg;
+//! use decorum::R64;
+//!
+//! let r = |x: f64| -> R64 { R64::assert(x) };
+//!
+//! // 2x + y = 5
+//! //  x - y = 1
+//! let [x, y] = linalg::solve2([[r(2.0), r(1.0)], [r(1.0), r(-1.0)]], [r(5.0), r(1.0)]).unwrap();
+//! assert_eq!((x, y), (r(2.0), r(1.0)));
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Expression`]: crate::expression::Expression
+//! [`Undefined`]: crate::expression::Expression::Undefined
+
+use thiserror::Error;
+
+use crate::constraint::{Constraint, NotRealError};
+use crate::expression::{Defined, Expression, Undefined};
+use crate::proxy::Constrained;
+use crate::{try_expression, Primitive};
+
+/// A $2\times2$ matrix in row-major order.
+pub type Matrix2<P> = [[P; 2]; 2];
+
+/// A $3\times3$ matrix in row-major order.
+pub type Matrix3<P> = [[P; 3]; 3];
+
+pub type Vector2<P> = [P; 2];
+
+pub type Vector3<P> = [P; 3];
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum SolveError {
+    #[error("{}", "matrix is singular")]
+    Singular,
+    #[error("{}", "matrix is ill-conditioned")]
+    IllConditioned,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for SolveError {
+    fn from(error: NotRealError) -> Self {
+        SolveError::NotReal(error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum CovarianceError {
+    #[error("{}", "columns have different lengths")]
+    Length,
+    #[error("{}", "fewer than two observations")]
+    Degenerate,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for CovarianceError {
+    fn from(error: NotRealError) -> Self {
+        CovarianceError::NotReal(error)
+    }
+}
+
+/// Solves the $2\times2$ linear system $Ax=b$.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`SolveError::Singular`] if the determinant of the matrix is zero,
+/// [`SolveError::IllConditioned`] if the matrix is ill-conditioned, and [`SolveError::NotReal`] if
+/// any input or component of the solution is not a real number or if the computation overflows.
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn solve2<T, C>(
+    a: Matrix2<Constrained<T, C>>,
+    b: Vector2<Constrained<T, C>>,
+) -> Expression<Vector2<Constrained<T, C>>, SolveError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let a = try_expression!(into_primitive_matrix(a));
+    let b = try_expression!(into_primitives(b));
+    let (a, [b0, b1]) = equilibrate(a, b);
+    let [[a00, a01], [a10, a11]] = a;
+
+    let determinant = try_expression!(checked_determinant((a00 * a11) - (a01 * a10), &a));
+    into_solution([
+        ((b0 * a11) - (a01 * b1)) / determinant,
+        ((a00 * b1) - (b0 * a10)) / determinant,
+    ])
+}
+
+/// Solves the $3\times3$ linear system $Ax=b$.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`SolveError::Singular`] if the determinant of the matrix is zero,
+/// [`SolveError::IllConditioned`] if the matrix is ill-conditioned, and [`SolveError::NotReal`] if
+/// any input or component of the solution is not a real number or if the computation overflows.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::linalg::{self, SolveError};
+/// use decorum::R64;
+///
+/// let r = |x: f64| -> R64 { R64::assert(x) };
+///
+/// // The third row is the sum of the first and second rows.
+/// let a = [
+///     [r(1.0), r(2.0), r(3.0)],
+///     [r(0.0), r(1.0), r(4.0)],
+///     [r(1.0), r(3.0), r(7.0)],
+/// ];
+/// assert!(matches!(
+///     linalg::solve3(a, [r(1.0), r(2.0), r(3.0)]).undefined(),
+///     Some(SolveError::Singular),
+/// ));
+/// ```
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn solve3<T, C>(
+    a: Matrix3<Constrained<T, C>>,
+    b: Vector3<Constrained<T, C>>,
+) -> Expression<Vector3<Constrained<T, C>>, SolveError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let a = try_expression!(into_primitive_matrix(a));
+    let b = try_expression!(into_primitives(b));
+    let (a, b) = equilibrate(a, b);
+
+    let determinant = try_expression!(checked_determinant(determinant3(&a), &a));
+    // Cramer's rule: each component is the determinant of the matrix with the corresponding
+    // column replaced by `b`.
+    let mut x = [T::ZERO; 3];
+    for (j, x) in x.iter_mut().enumerate() {
+        let mut aj = a;
+        for (row, b) in aj.iter_mut().zip(b) {
+            row[j] = b;
+        }
+        *x = determinant3(&aj) / determinant;
+    }
+    into_solution(x)
+}
+
+/// Computes the sample covariance matrix of columns of observations.
+///
+/// Each column is a variable and the $k$th elements of the columns are the $k$th observation. The
+/// element $(i, j)$ of the output matrix is the covariance of columns $i$ and $j$, so the
+/// diagonal is the sample variance of each column. Covariances are computed with a corrected
+/// two-pass algorithm, which subtracts the means of the columns before accumulating products.
+/// Unlike the textbook formula $E\[XY\] - E\[X\]E\[Y\]$, this does not suffer from catastrophic
+/// cancellation when the means are large relative to the deviations.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`CovarianceError::Length`] if the columns have different lengths,
+/// [`CovarianceError::Degenerate`] if there are fewer than two observations, and
+/// [`CovarianceError::NotReal`] if any input or element of the output is not a real number or if
+/// the computation overflows.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::linalg;
+/// use decorum::R64;
+///
+/// let xs = [1.0e9 + 4.0, 1.0e9 + 7.0, 1.0e9 + 13.0, 1.0e9 + 16.0].map(<R64>::assert);
+/// let ys = [2.0, 4.0, 6.0, 8.0].map(<R64>::assert);
+/// let [[vx, cxy], [cyx, vy]] = linalg::covariance([&xs, &ys]).unwrap();
+/// assert_eq!(vx, 30.0);
+/// assert_eq!(vy, 20.0 / 3.0);
+/// assert_eq!(cxy, 14.0);
+/// assert_eq!(cxy, cyx);
+/// ```
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn covariance<T, C, const N: usize>(
+    columns: [&[Constrained<T, C>]; N],
+) -> Expression<[[Constrained<T, C>; N]; N], CovarianceError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let n = columns.first().map_or(0, |column| column.len());
+    if columns.iter().any(|column| column.len() != n) {
+        return Undefined(CovarianceError::Length);
+    }
+    if n < 2 {
+        return Undefined(CovarianceError::Degenerate);
+    }
+    if columns
+        .iter()
+        .flat_map(|column| column.iter())
+        .any(|x| !x.into_inner().is_finite())
+    {
+        return Undefined(NotRealError.into());
+    }
+    let count = (0..n).fold(T::ZERO, |count, _| count + T::ONE);
+    let means = columns.map(|column| {
+        column
+            .iter()
+            .fold(T::ZERO, |sum, x| sum + (x.into_inner() / count))
+    });
+    let mut output = [[T::ZERO; N]; N];
+    for i in 0..N {
+        for j in i..N {
+            let (mut products, mut xs, mut ys) = (T::ZERO, T::ZERO, T::ZERO);
+            for (x, y) in columns[i].iter().zip(columns[j]) {
+                let (x, y) = (x.into_inner() - means[i], y.into_inner() - means[j]);
+                products = products + (x * y);
+                xs = xs + x;
+                ys = ys + y;
+            }
+            // The correction term compensates for rounding error in the means.
+            let covariance = (products - ((xs * ys) / count)) / (count - T::ONE);
+            output[i][j] = covariance;
+            output[j][i] = covariance;
+        }
+    }
+    if output.iter().flatten().all(|x| x.is_finite()) {
+        // The elements are real numbers, which satisfy any constraint.
+        Defined(output.map(|row| row.map(Constrained::unchecked)))
+    }
+    else {
+        Undefined(NotRealError.into())
+    }
+}
+
+// Scales the rows of the system $Ax=b$ by powers of two such that the largest magnitude in each
+// row of the matrix is in $[1, 2)$. Rows of zeros are not scaled.
+fn equilibrate<T, const N: usize>(mut a: [[T; N]; N], mut b: [T; N]) -> ([[T; N]; N], [T; N])
+where
+    T: Primitive,
+{
+    for (row, b) in a.iter_mut().zip(b.iter_mut()) {
+        let magnitude = max_magnitude(row);
+        if !magnitude.is_zero() {
+            let scale = exp2::<T>(-exponent(magnitude));
+            for x in row.iter_mut() {
+                *x = *x * scale;
+            }
+            *b = *b * scale;
+        }
+    }
+    (a, b)
+}
+
+fn determinant3<T>(a: &[[T; 3]; 3]) -> T
+where
+    T: Primitive,
+{
+    let [[a00, a01, a02], [a10, a11, a12], [a20, a21, a22]] = *a;
+    (a00 * ((a11 * a22) - (a12 * a21))) - (a01 * ((a10 * a22) - (a12 * a20)))
+        + (a02 * ((a10 * a21) - (a11 * a20)))
+}
+
+fn checked_determinant<T, const N: usize>(
+    determinant: T,
+    a: &[[T; N]; N],
+) -> Expression<T, SolveError>
+where
+    T: Primitive,
+{
+    if !determinant.is_finite() {
+        return Undefined(NotRealError.into());
+    }
+    if determinant.is_zero() {
+        return Undefined(SolveError::Singular);
+    }
+    // Divide by the magnitude of each row in turn rather than by their product to avoid overflow.
+    let mut ratio = abs(determinant);
+    for row in a {
+        ratio = ratio / max_magnitude(row);
+    }
+    let n = (0..N).fold(T::ZERO, |n, _| n + T::ONE);
+    if ratio <= n * T::EPSILON {
+        Undefined(SolveError::IllConditioned)
+    }
+    else {
+        Defined(determinant)
+    }
+}
+
+fn into_primitive_matrix<T, C, const N: usize>(
+    a: [[Constrained<T, C>; N]; N],
+) -> Expression<[[T; N]; N], NotRealError>
+where
+    T: Primitive,
+{
+    let mut output = [[T::ZERO; N]; N];
+    for (output, row) in output.iter_mut().zip(a) {
+        *output = try_expression!(into_primitives(row));
+    }
+    Defined(output)
+}
+
+fn into_primitives<T, C, const N: usize>(
+    vector: [Constrained<T, C>; N],
+) -> Expression<[T; N], NotRealError>
+where
+    T: Primitive,
+{
+    let vector = vector.map(Constrained::into_inner);
+    if vector.iter().all(|x| x.is_finite()) {
+        Defined(vector)
+    }
+    else {
+        Undefined(NotRealError)
+    }
+}
+
+fn into_solution<T, C, const N: usize>(x: [T; N]) -> Expression<[Constrained<T, C>; N], SolveError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    if x.iter().all(|x| x.is_finite()) {
+        // The components are real numbers, which satisfy any constraint.
+        Defined(x.map(Constrained::unchecked))
+    }
+    else {
+        Undefined(NotRealError.into())
+    }
+}
+
+fn max_magnitude<T>(row: &[T]) -> T
+where
+    T: Primitive,
+{
+    row.iter().fold(T::ZERO, |max, x| {
+        let x = abs(*x);
+        if x > max {
+            x
+        }
+        else {
+            max
+        }
+    })
+}
+
+// Gets the exponent of the most significant bit of a finite and non-zero value.
+fn exponent<T>(x: T) -> i32
+where
+    T: Primitive,
+{
+    let (mantissa, exponent, _) = x.integer_decode();
+    i32::from(exponent) + 63 - (mantissa.leading_zeros() as i32)
+}
+
+// Computes $2^n$ by squaring, clamping $n$ to the exponents of normal values such that the output
+// is exact and no intermediate value overflows.
+fn exp2<T>(n: i32) -> T
+where
+    T: Primitive,
+{
+    let n = n.clamp(exponent(T::MIN_POSITIVE_NORMAL), exponent(T::MAX_FINITE));
+    let two = T::ONE + T::ONE;
+    let (mut base, mut n) = if n < 0 {
+        (T::ONE / two, n.unsigned_abs())
+    }
+    else {
+        (two, n.unsigned_abs())
+    };
+    let mut output = T::ONE;
+    while n > 0 {
+        if n & 1 == 1 {
+            output = output * base;
+        }
+        n >>= 1;
+        if n > 0 {
+            base = base * base;
+        }
+    }
+    output
+}
+
+fn abs<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    if x < T::ZERO {
+        -x
+    }
+    else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linalg::{self, CovarianceError, SolveError};
+    use crate::{Total, R64};
+
+    fn r(x: f64) -> R64 {
+        R64::assert(x)
+    }
+
+    #[test]
+    fn solve2() {
+        let x = linalg::solve2([[r(4.0), r(-2.0)], [r(1.0), r(1.0)]], [r(2.0), r(2.0)]).unwrap();
+        assert_eq!(x, [r(1.0), r(1.0)]);
+        assert!(matches!(
+            linalg::solve2([[r(1.0), r(2.0)], [r(2.0), r(4.0)]], [r(1.0), r(1.0)]).undefined(),
+            Some(SolveError::Singular)
+        ));
+        assert!(matches!(
+            linalg::solve2(
+                [[r(1.0), r(1.0)], [r(1.0), r(1.0 + f64::EPSILON)]],
+                [r(1.0), r(1.0)]
+            )
+            .undefined(),
+            Some(SolveError::IllConditioned)
+        ));
+    }
+
+    #[test]
+    fn solve3() {
+        let a = [
+            [r(2.0), r(0.0), r(0.0)],
+            [r(0.0), r(4.0), r(0.0)],
+            [r(1.0), r(0.0), r(1.0)],
+        ];
+        let x = linalg::solve3(a, [r(2.0), r(8.0), r(4.0)]).unwrap();
+        assert_eq!(x, [r(1.0), r(2.0), r(3.0)]);
+
+        // Scaling does not affect conditioning.
+        let a = a.map(|row| row.map(|x| x * 1.0e-100));
+        let b = [r(2.0e-100), r(8.0e-100), r(4.0e-100)];
+        assert!(linalg::solve3(a, b).is_defined());
+    }
+
+    #[test]
+    fn solve_scaled_identity() {
+        // The determinants of these matrices underflow and overflow without scaling.
+        for scale in [1.0e-110, 1.0e200] {
+            let (zero, one) = (r(0.0), r(scale));
+            let x = linalg::solve2([[one, zero], [zero, one]], [one, one]).unwrap();
+            assert_eq!(x, [r(1.0), r(1.0)]);
+            let x = linalg::solve3(
+                [[one, zero, zero], [zero, one, zero], [zero, zero, one]],
+                [one, one, one],
+            )
+            .unwrap();
+            assert_eq!(x, [r(1.0), r(1.0), r(1.0)]);
+        }
+    }
+
+    #[test]
+    fn solve_not_real() {
+        let nan = Total::from(f64::NAN);
+        let one = Total::from(1.0);
+        assert!(matches!(
+            linalg::solve2([[nan, one], [one, one]], [one, one]).undefined(),
+            Some(SolveError::NotReal(_))
+        ));
+        assert!(matches!(
+            linalg::solve2(
+                [[r(1.0e-300), r(0.0)], [r(0.0), r(1.0)]],
+                [r(1.0e300), r(1.0)]
+            )
+            .undefined(),
+            Some(SolveError::NotReal(_))
+        ));
+    }
+
+    #[test]
+    fn covariance() {
+        let xs = [r(1.0), r(2.0), r(3.0)];
+        let ys = [r(3.0), r(2.0), r(1.0)];
+        let [[vx, cxy], [_, vy]] = linalg::covariance([&xs, &ys]).unwrap();
+        assert_eq!((vx, cxy, vy), (r(1.0), r(-1.0), r(1.0)));
+        let [[v]] = linalg::covariance([&[r(5.0), r(5.0)]]).unwrap();
+        assert_eq!(v, r(0.0));
+
+        assert!(matches!(
+            linalg::covariance([&xs, &ys[..2]]).undefined(),
+            Some(CovarianceError::Length)
+        ));
+        assert!(matches!(
+            linalg::covariance([&xs[..1]]).undefined(),
+            Some(CovarianceError::Degenerate)
+        ));
+        assert!(matches!(
+            linalg::covariance([&[Total::from(f64::NAN), Total::from(1.0)]]).undefined(),
+            Some(CovarianceError::NotReal(_))
+        ));
+        assert!(matches!(
+            linalg::covariance([&[r(f64::MAX), r(-f64::MAX)]]).undefined(),
+            Some(CovarianceError::NotReal(_))
+        ));
+    }
+}
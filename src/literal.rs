@@ -0,0 +1,372 @@
+//! Exact hexadecimal floating-point literals.
+//!
+//! The [`hexf32`] and [`hexf64`] macros construct [`Constrained`] values from hexadecimal
+//! floating-point literals like `0x1.921fb54442d18p+1` at compile time. Unlike decimal literals,
+//! these literals describe the exact bit pattern of a value, which is important when reproducing
+//! reference constants and test vectors. Literals that are malformed or that cannot be represented
+//! exactly (i.e., without rounding) by the primitive floating-point type are compile-time errors.
+//!
+//! Hexadecimal literals are always real numbers, so the macros can construct any [`Constrained`]
+//! type and its constraint is inferred. Rust does not tokenize hexadecimal floating-point
+//! literals, so literals are given as strings. The syntax is the same as that of C: an optional
+//! sign, a `0x` prefix, a hexadecimal significand with an optional radix point, and a binary
+//! exponent with a `p` prefix.
+//!
+//! The same syntax is accepted at runtime by the [`FromStr`] implementation of [`Constrained`]
+//! types, which also accepts `NaN`s with a payload like `nan:0x1234`. This allows test fixtures
+//! and interchange files to specify exact values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::{hexf64, Total, R64};
+//!
+//! const PI: R64 = hexf64!("0x1.921fb54442d18p+1");
+//! assert_eq!(PI, core::f64::consts::PI);
+//!
+//! let x: Total<f64> = hexf64!("-0x1p-1074");
+//! assert_eq!(x.into_inner().to_bits(), 0x8000_0000_0000_0001);
+//! ```
+//!
+//! Inexact literals do not compile.
+//!
+//! ```rust,compile_fail
+//! use decorum::{hexf32, R32};
+//!
+//! const X: R32 = hexf32!("0x1.000001p0");
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`FromStr`]: core::str::FromStr
+
+use crate::bits::Bits;
+use crate::proxy::Constrained;
+
+/// Constructs a [`Constrained`] `f32` from an exact hexadecimal floating-point literal.
+///
+/// See the [`literal`] module.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`literal`]: crate::literal
+#[macro_export]
+macro_rules! hexf32 {
+    ($text:literal $(,)?) => {{
+        const BITS: u32 = $crate::literal::parse_f32($text);
+        $crate::literal::from_f32_bits(BITS)
+    }};
+}
+pub use hexf32;
+
+/// Constructs a [`Constrained`] `f64` from an exact hexadecimal floating-point literal.
+///
+/// See the [`literal`] module.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`literal`]: crate::literal
+#[macro_export]
+macro_rules! hexf64 {
+    ($text:literal $(,)?) => {{
+        const BITS: u64 = $crate::literal::parse_f64($text);
+        $crate::literal::from_f64_bits(BITS)
+    }};
+}
+pub use hexf64;
+
+#[allow(unknown_lints, unnecessary_transmutes)]
+#[doc(hidden)]
+pub const fn from_f32_bits<C>(bits: u32) -> Constrained<f32, C> {
+    // SAFETY: All bit patterns are valid `f32`s. `f32::from_bits` is not `const` in the minimum
+    //         supported version of Rust.
+    let inner = unsafe { core::mem::transmute::<u32, f32>(bits) };
+    // Hexadecimal literals are real numbers, which satisfy any constraint.
+    Constrained::unchecked(inner)
+}
+
+#[allow(unknown_lints, unnecessary_transmutes)]
+#[doc(hidden)]
+pub const fn from_f64_bits<C>(bits: u64) -> Constrained<f64, C> {
+    // SAFETY: All bit patterns are valid `f64`s. `f64::from_bits` is not `const` in the minimum
+    //         supported version of Rust.
+    let inner = unsafe { core::mem::transmute::<u64, f64>(bits) };
+    // Hexadecimal literals are real numbers, which satisfy any constraint.
+    Constrained::unchecked(inner)
+}
+
+#[doc(hidden)]
+pub const fn parse_f32(text: &str) -> u32 {
+    parse(text, 24, 8) as u32
+}
+
+#[doc(hidden)]
+pub const fn parse_f64(text: &str) -> u64 {
+    parse(text, 53, 11)
+}
+
+// Parses a hexadecimal floating-point literal into the bits of a binary IEEE 754 format with the
+// given precision (including the implicit bit) and exponent width.
+//
+// This function panics if the literal is malformed or inexact. When evaluated in a `const`
+// context, these panics are compile-time errors.
+const fn parse(text: &str, precision: u32, width: u32) -> u64 {
+    match try_parse(text, precision, width) {
+        Ok(bits) => bits,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+// Parses a hexadecimal floating-point literal like `parse`, but outputs an error message rather
+// than panicking if the literal is malformed or inexact.
+const fn try_parse(text: &str, precision: u32, width: u32) -> Result<u64, &'static str> {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+
+    let negative = match bytes.first() {
+        Some(&b'-') => {
+            index += 1;
+            true
+        }
+        Some(&b'+') => {
+            index += 1;
+            false
+        }
+        _ => false,
+    };
+    if !(index + 1 < bytes.len()
+        && bytes[index] == b'0'
+        && (bytes[index + 1] == b'x' || bytes[index + 1] == b'X'))
+    {
+        return Err("hexadecimal floating-point literal must have a `0x` prefix");
+    }
+    index += 2;
+
+    // Accumulate the significand into an integer and the exponent of its least significant bit.
+    let mut significand: u64 = 0;
+    let mut exponent: i64 = 0;
+    let mut digits = 0;
+    let mut fraction = false;
+    while index < bytes.len() {
+        let digit = match bytes[index] {
+            byte @ b'0'..=b'9' => byte - b'0',
+            byte @ b'a'..=b'f' => byte - b'a' + 10,
+            byte @ b'A'..=b'F' => byte - b'A' + 10,
+            b'.' if !fraction => {
+                fraction = true;
+                index += 1;
+                continue;
+            }
+            _ => break,
+        };
+        if significand >> 60 == 0 {
+            significand = (significand << 4) | digit as u64;
+            if fraction {
+                exponent -= 4;
+            }
+        }
+        else if digit != 0 {
+            return Err("hexadecimal floating-point literal is inexact");
+        }
+        else if !fraction {
+            // Discard trailing zeros of the integer part.
+            exponent += 4;
+        }
+        digits += 1;
+        index += 1;
+    }
+    if digits == 0 {
+        return Err("hexadecimal floating-point literal has no digits");
+    }
+
+    if !(index < bytes.len() && (bytes[index] == b'p' || bytes[index] == b'P')) {
+        return Err("hexadecimal floating-point literal must have a `p` exponent");
+    }
+    index += 1;
+    let negative_exponent = index < bytes.len() && bytes[index] == b'-';
+    if index < bytes.len() && (bytes[index] == b'-' || bytes[index] == b'+') {
+        index += 1;
+    }
+    if index == bytes.len() {
+        return Err("hexadecimal floating-point literal has no exponent digits");
+    }
+    let mut binary: i64 = 0;
+    while index < bytes.len() {
+        let digit = match bytes[index] {
+            byte @ b'0'..=b'9' => (byte - b'0') as i64,
+            _ => return Err("hexadecimal floating-point literal has an invalid exponent"),
+        };
+        // Saturate large exponents, which overflow or underflow regardless.
+        if binary < 1 << 32 {
+            binary = (binary * 10) + digit;
+        }
+        index += 1;
+    }
+    exponent += if negative_exponent { -binary } else { binary };
+
+    let sign = if negative {
+        1u64 << (precision + width - 1)
+    }
+    else {
+        0
+    };
+    if significand == 0 {
+        return Ok(sign);
+    }
+
+    let bias = (1i64 << (width - 1)) - 1;
+    let length = 64 - significand.leading_zeros() as i64;
+    let leading = exponent + length - 1;
+    if leading > bias {
+        return Err("hexadecimal floating-point literal overflows");
+    }
+    // The exponent of the least significant bit of the significand of the output.
+    let (biased, lsb) = if leading >= 1 - bias {
+        (leading + bias, leading - (precision as i64 - 1))
+    }
+    else {
+        (0, 2 - bias - precision as i64)
+    };
+    let shift = lsb - exponent;
+    let significand = if shift <= 0 {
+        significand << -shift
+    }
+    else if shift < 64 && significand & ((1 << shift) - 1) == 0 {
+        significand >> shift
+    }
+    else {
+        return Err("hexadecimal floating-point literal is inexact");
+    };
+    let mantissa = significand & ((1 << (precision - 1)) - 1);
+    Ok(sign | ((biased as u64) << (precision - 1)) | mantissa)
+}
+
+// Parses a hexadecimal floating-point literal or a `NaN` with a payload, such as `nan:0x1234`.
+//
+// The payload of a `NaN` is the mantissa field excluding its most significant bit, which is set
+// such that the `NaN` is quiet.
+pub(crate) fn parse_bits<T>(text: &str) -> Option<T>
+where
+    T: Bits,
+{
+    let (sign, unsigned) = match text.as_bytes().first() {
+        Some(&b'-') => (true, &text[1..]),
+        Some(&b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let quiet = 1u64 << (T::MANTISSA_WIDTH - 1);
+    if let Some(payload) = unsigned
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("nan:"))
+        .and_then(|_| {
+            let payload = &unsigned[4..];
+            payload
+                .strip_prefix("0x")
+                .or_else(|| payload.strip_prefix("0X"))
+        })
+    {
+        let payload = u64::from_str_radix(payload, 16)
+            .ok()
+            .filter(|payload| *payload < quiet)?;
+        T::from_fields(sign, T::MAX_EXPONENT_FIELD, quiet | payload).ok()
+    }
+    else {
+        let bits = try_parse(text, T::MANTISSA_WIDTH + 1, T::EXPONENT_WIDTH).ok()?;
+        T::from_fields(
+            sign,
+            ((bits >> T::MANTISSA_WIDTH) as u32) & T::MAX_EXPONENT_FIELD,
+            bits & (quiet | (quiet - 1)),
+        )
+        .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::literal;
+    use crate::{Real, Total, R32, R64};
+
+    fn r32(x: R32) -> R32 {
+        x
+    }
+
+    fn r64(x: R64) -> R64 {
+        x
+    }
+
+    #[test]
+    fn hexf64() {
+        const PI: R64 = hexf64!("0x1.921fb54442d18p+1");
+        assert_eq!(PI, core::f64::consts::PI);
+        assert_eq!(r64(hexf64!("0x1p0")), 1.0);
+        assert_eq!(r64(hexf64!("0X1.8P1")), 3.0);
+        assert_eq!(r64(hexf64!("-0x.8p0")), -0.5);
+        assert_eq!(r64(hexf64!("0x10p-4")), 1.0);
+        assert_eq!(r64(hexf64!("0x1.fffffffffffffp1023")), f64::MAX);
+        assert_eq!(r64(hexf64!("0x1p-1022")), f64::MIN_POSITIVE);
+        assert_eq!(r64(hexf64!("0x1p-1074")), 5.0e-324);
+        assert_eq!(r64(hexf64!("0x1000000000000000000p-72")), 1.0);
+
+        let zero: Total<f64> = hexf64!("-0x0p0");
+        assert_eq!(zero.into_inner().to_bits(), 0x8000_0000_0000_0000);
+    }
+
+    #[test]
+    fn hexf32() {
+        const X: Real<f32> = hexf32!("0x1.99999ap-4");
+        assert_eq!(X, 0.1f32);
+        assert_eq!(r32(hexf32!("0x1.fffffep127")), f32::MAX);
+        assert_eq!(r32(hexf32!("0x1p-149")), f32::from_bits(1));
+        assert_eq!(literal::parse_f32("-0x1p0"), (-1.0f32).to_bits());
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_inexact() {
+        literal::parse_f64("0x1.00000000000008p0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_inexact_subnormal() {
+        literal::parse_f64("0x1p-1075");
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_overflow() {
+        literal::parse_f32("0x1p128");
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_malformed() {
+        literal::parse_f64("1.0p0");
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("0x1.8p+1".parse::<R64>().unwrap(), 3.0);
+        assert_eq!("-0x1p-149".parse::<R32>().unwrap(), -f32::from_bits(1));
+        assert_eq!("1.5".parse::<R64>().unwrap(), 1.5);
+        assert!("inf"
+            .parse::<Total<f64>>()
+            .unwrap()
+            .into_inner()
+            .is_infinite());
+
+        let nan = "nan:0x1234".parse::<Total<f64>>().unwrap().into_inner();
+        assert_eq!(nan.to_bits(), 0x7ff8_0000_0000_1234);
+        let nan = "-NaN:0x1".parse::<Total<f32>>().unwrap().into_inner();
+        assert_eq!(nan.to_bits(), 0xffc0_0001);
+        assert!("nan:0x400000".parse::<Total<f32>>().is_err());
+        assert!("nan:1234".parse::<Total<f64>>().is_err());
+
+        assert!("0x1.8".parse::<R64>().is_err());
+        assert!("0x1.000001p0".parse::<R32>().is_err());
+        assert!("0x1p128".parse::<R32>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_str_not_real() {
+        let _ = "nan:0x1".parse::<R64>();
+    }
+}
@@ -0,0 +1,250 @@
+//! Deterministic gradient noise over [`Real`] coordinates.
+//!
+//! This module provides seedable [Perlin noise][perlin] in two and three dimensions. Noise is a
+//! pure function of its seed and coordinates: lattice points are hashed with [`CanonicalHash`],
+//! so the output does not depend on the platform, the process, nor the representation of zero
+//! at lattice points. The output is always a real number in the closed interval $[-1, 1]$ and
+//! is zero at every lattice point.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::noise::Perlin;
+//! use decorum::R64;
+//!
+//! let perlin = Perlin::new(42);
+//! let x: R64 = perlin.sample2([R64::assert(0.5), R64::assert(1.25)]);
+//! assert!(x >= -1.0 && x <= 1.0);
+//! assert_eq!(x, perlin.sample2([R64::assert(0.5), R64::assert(1.25)]));
+//! ```
+//!
+//! [`CanonicalHash`]: crate::hash::CanonicalHash
+//! [perlin]: https://en.wikipedia.org/wiki/Perlin_noise
+//! [`Real`]: crate::Real
+
+use core::hash::Hasher;
+use num_traits::FromPrimitive;
+
+use crate::hash::CanonicalHash;
+use crate::proxy::Constrained;
+use crate::{Primitive, Real};
+
+const GRADIENTS2: [[i8; 2]; 8] = [
+    [1, 1],
+    [-1, 1],
+    [1, -1],
+    [-1, -1],
+    [1, 0],
+    [-1, 0],
+    [0, 1],
+    [0, -1],
+];
+
+const GRADIENTS3: [[i8; 3]; 12] = [
+    [1, 1, 0],
+    [-1, 1, 0],
+    [1, -1, 0],
+    [-1, -1, 0],
+    [1, 0, 1],
+    [-1, 0, 1],
+    [1, 0, -1],
+    [-1, 0, -1],
+    [0, 1, 1],
+    [0, -1, 1],
+    [0, 1, -1],
+    [0, -1, -1],
+];
+
+/// Seedable Perlin gradient noise.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Perlin {
+    seed: u64,
+}
+
+impl Perlin {
+    pub const fn new(seed: u64) -> Self {
+        Perlin { seed }
+    }
+
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Samples two-dimensional noise at the given point.
+    pub fn sample2<T, D>(&self, point: [Real<T, D>; 2]) -> Real<T, D>
+    where
+        T: FromPrimitive + Primitive,
+    {
+        let [x, y] = point.map(Constrained::into_inner);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (x, y) = (x - x0, y - y0);
+        let (u, v) = (fade(x), fade(y));
+        let corner = |i: T, j: T| {
+            let [gx, gy] = GRADIENTS2[self.lattice_hash(&[x0 + i, y0 + j]) % GRADIENTS2.len()];
+            (gradient::<T>(gx) * (x - i)) + (gradient::<T>(gy) * (y - j))
+        };
+        let (zero, one) = (T::ZERO, T::ONE);
+        let output = lerp(
+            v,
+            lerp(u, corner(zero, zero), corner(one, zero)),
+            lerp(u, corner(zero, one), corner(one, one)),
+        );
+        into_signed_unit_interval(output)
+    }
+
+    /// Samples three-dimensional noise at the given point.
+    pub fn sample3<T, D>(&self, point: [Real<T, D>; 3]) -> Real<T, D>
+    where
+        T: FromPrimitive + Primitive,
+    {
+        let [x, y, z] = point.map(Constrained::into_inner);
+        let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+        let (x, y, z) = (x - x0, y - y0, z - z0);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let corner = |i: T, j: T, k: T| {
+            let [gx, gy, gz] =
+                GRADIENTS3[self.lattice_hash(&[x0 + i, y0 + j, z0 + k]) % GRADIENTS3.len()];
+            (gradient::<T>(gx) * (x - i))
+                + (gradient::<T>(gy) * (y - j))
+                + (gradient::<T>(gz) * (z - k))
+        };
+        let (zero, one) = (T::ZERO, T::ONE);
+        let output = lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(zero, zero, zero), corner(one, zero, zero)),
+                lerp(u, corner(zero, one, zero), corner(one, one, zero)),
+            ),
+            lerp(
+                v,
+                lerp(u, corner(zero, zero, one), corner(one, zero, one)),
+                lerp(u, corner(zero, one, one), corner(one, one, one)),
+            ),
+        );
+        into_signed_unit_interval(output)
+    }
+
+    fn lattice_hash<T>(&self, point: &[T]) -> usize
+    where
+        T: Primitive,
+    {
+        let mut state = Fnv1a::default();
+        state.write_u64(self.seed);
+        point.hash_canonical(&mut state);
+        // Fold the high bits into the low bits, because the output is reduced modulo a small
+        // number of gradients.
+        let hash = state.finish();
+        ((hash >> 32) ^ hash) as usize
+    }
+}
+
+// A fixed and portable hasher for lattice points. The hashers provided by `std` are not guaranteed
+// to be stable across releases or platforms, which would break determinism.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    // Write integers in a fixed byte order, because the default implementation uses the native
+    // byte order.
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+}
+
+fn gradient<T>(component: i8) -> T
+where
+    T: FromPrimitive + Primitive,
+{
+    T::from_i8(component).unwrap()
+}
+
+fn fade<T>(t: T) -> T
+where
+    T: FromPrimitive + Primitive,
+{
+    let constant = |n: u8| T::from_u8(n).unwrap();
+    t * t * t * ((t * ((t * constant(6)) - constant(15))) + constant(10))
+}
+
+fn lerp<T>(t: T, a: T, b: T) -> T
+where
+    T: Primitive,
+{
+    a + (t * (b - a))
+}
+
+fn into_signed_unit_interval<T, D>(x: T) -> Real<T, D>
+where
+    T: Primitive,
+{
+    Constrained::unchecked(if x > T::ONE {
+        T::ONE
+    }
+    else if x < -T::ONE {
+        -T::ONE
+    }
+    else if x.is_nan() {
+        T::ZERO
+    }
+    else {
+        x
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::noise::Perlin;
+    use crate::R64;
+
+    fn point<const N: usize>(point: [f64; N]) -> [R64; N] {
+        point.map(R64::assert)
+    }
+
+    #[test]
+    fn lattice_zero() {
+        let perlin = Perlin::new(7);
+        assert_eq!(perlin.sample2(point([3.0, -4.0])), 0.0);
+        assert_eq!(perlin.sample2(point([-0.0, 0.0])), 0.0);
+        assert_eq!(perlin.sample3(point([1.0, 2.0, -3.0])), 0.0);
+    }
+
+    #[test]
+    fn deterministic_and_bounded() {
+        let (a, b) = (Perlin::new(1), Perlin::new(2));
+        let mut differs = false;
+        for i in 0..100 {
+            let t = f64::from(i) * 0.173;
+            let p2 = point([t, -t * 0.5]);
+            let p3 = point([t, t * 0.25, -t]);
+            let x = a.sample2(p2);
+            assert_eq!(x, a.sample2(p2));
+            assert!((-1.0..=1.0).contains(&x.into_inner()));
+            assert!((-1.0..=1.0).contains(&a.sample3(p3).into_inner()));
+            differs |= x != b.sample2(p2);
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn extreme_coordinates() {
+        let perlin = Perlin::default();
+        let x = perlin.sample2(point([f64::MAX, f64::MIN]));
+        assert!((-1.0..=1.0).contains(&x.into_inner()));
+    }
+}
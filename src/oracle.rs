@@ -0,0 +1,322 @@
+//! Accuracy measurement of real functions against a reference oracle.
+//!
+//! This module measures the error of real function implementations in [ULP]s against an _oracle_
+//! that computes reference values with more precision than the function under test. It is used to
+//! check that the [`UnaryRealFunction`] implementations of [`Constrained`] types are within
+//! documented bounds and provides the infrastructure needed to validate alternative backends (such
+//! as `libm` or approximations) before accepting them.
+//!
+//! An oracle is any function from an argument to a reference value that has been rounded to the
+//! type of the argument. The `oracle` feature depends on [`rug`], which provides arbitrary
+//! precision arithmetic with correct rounding via MPFR and is a suitable oracle for both `f32` and
+//! `f64` functions.
+//!
+//! ```rust
+//! use decorum::oracle;
+//! use rug::Float;
+//!
+//! let measurement = oracle::measure(
+//!     |x: f64| x.sin(),
+//!     |x| Float::with_val(256, x).sin().to_f64(),
+//!     oracle::sweep(-1.0e3, 1.0e3, 1 << 10),
+//! );
+//! measurement.assert_within("f64::sin", 1);
+//! ```
+//!
+//! The implementations of [`UnaryRealFunction`] for [`Constrained`] `f32` and `f64` types are
+//! tested against the following bounds (in ULPs) for all constraints.
+//!
+//! | Function                                               | Bound |
+//! |--------------------------------------------------------|-------|
+//! | `sqrt`                                                 | 0     |
+//! | `cbrt`, `exp`, `exp2`, `exp_m1`, `ln`, `ln_1p`, `log2` | 1     |
+//! | `sin`, `cos`, `tan`, `asin`, `acos`, `atan`            | 1     |
+//! | `log10`, `sinh`, `cosh`, `tanh`, `asinh`               | 2     |
+//! | `acosh`                                                | 8     |
+//! | `atanh`                                                | 16    |
+//!
+//! The standard library computes `acosh` and `atanh` from other functions rather than calling
+//! `libm`, so their error is comparatively large.
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`rug`]: https://crates.io/crates/rug
+//! [`UnaryRealFunction`]: crate::real::UnaryRealFunction
+//! [ULP]: https://en.wikipedia.org/wiki/Unit_in_the_last_place
+
+use core::fmt::Debug;
+
+use crate::bits::Bits;
+
+/// Error of a function measured against an oracle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Measurement<T> {
+    max_ulps: u64,
+    argument: Option<T>,
+    count: usize,
+}
+
+impl<T> Measurement<T>
+where
+    T: Bits + Debug,
+{
+    /// Gets the greatest measured error in ULPs.
+    ///
+    /// If the function and oracle disagree on whether an output is `NaN`, then the error is
+    /// `u64::MAX`.
+    pub fn max_ulps(&self) -> u64 {
+        self.max_ulps
+    }
+
+    /// Gets the argument at which the greatest error was measured.
+    pub fn argument(&self) -> Option<T> {
+        self.argument
+    }
+
+    /// Gets the number of measured arguments.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Asserts that the greatest measured error is within the given bound.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the greatest measured error exceeds `ulps`.
+    #[track_caller]
+    pub fn assert_within(&self, name: &str, ulps: u64) {
+        assert!(
+            self.max_ulps <= ulps,
+            "`{}` has an error of {} ULPs at {:?}, which exceeds the bound of {} ULPs",
+            name,
+            self.max_ulps,
+            self.argument,
+            ulps,
+        );
+    }
+}
+
+/// Gets the distance in ULPs between two values.
+///
+/// Zeros of either sign are at the same position. The distance between `NaN`s is zero and the
+/// distance between a `NaN` and any other value is `u64::MAX`.
+pub fn ulps<T>(a: T, b: T) -> u64
+where
+    T: Bits,
+{
+    match a.ulps_between(b) {
+        Some(ulps) => ulps,
+        None if a.is_nan() && b.is_nan() => 0,
+        _ => u64::MAX,
+    }
+}
+
+/// Measures the error of `f` against `oracle` at each of the given arguments.
+///
+/// The oracle must compute its reference value with more precision than `f` and round it to `T`.
+pub fn measure<T, F, O, I>(mut f: F, mut oracle: O, arguments: I) -> Measurement<T>
+where
+    T: Bits,
+    F: FnMut(T) -> T,
+    O: FnMut(T) -> T,
+    I: IntoIterator<Item = T>,
+{
+    let mut measurement = Measurement {
+        max_ulps: 0,
+        argument: None,
+        count: 0,
+    };
+    for x in arguments {
+        let error = ulps(f(x), oracle(x));
+        if measurement.argument.is_none() || error > measurement.max_ulps {
+            measurement.max_ulps = error;
+            measurement.argument = Some(x);
+        }
+        measurement.count += 1;
+    }
+    measurement
+}
+
+/// Gets an iterator over `count` arguments from `start` to `end` (inclusive) that are evenly
+/// spaced in ULPs.
+///
+/// Unlike arguments that are evenly spaced in value, these arguments sample each binade in the
+/// interval with similar density.
+///
+/// # Panics
+///
+/// Panics if `start` or `end` is `NaN`, `start` is greater than `end`, or `count` is less than two.
+pub fn sweep<T>(start: T, end: T, count: usize) -> impl Iterator<Item = T>
+where
+    T: Bits,
+{
+    let span = match start.ulps_between(end) {
+        Some(span) if start <= end && count >= 2 => u128::from(span),
+        _ => panic!("invalid sweep"),
+    };
+    let n = (count - 1) as u128;
+    (0..count).map(move |i| {
+        // The span of finite `f64`s exceeds `i64::MAX` ULPs, so step in two halves.
+        let offset = ((span * i as u128) / n) as u64;
+        let half = offset / 2;
+        start
+            .step_ulps(half as i64)
+            .step_ulps((offset - half) as i64)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rug::Float;
+
+    use crate::bits::Bits;
+    use crate::oracle;
+    use crate::real::UnaryRealFunction;
+    use crate::{ExtendedReal, Real, Total};
+
+    const COUNT: usize = 1 << 14;
+    // The precision of reference values in bits. This far exceeds the precision of `f64`, so
+    // rounding the reference value to the primitive type is effectively correct rounding.
+    const PRECISION: u32 = 256;
+
+    trait Mpfr: Bits {
+        fn mpfr<F>(self, f: F) -> Self
+        where
+            F: FnOnce(Float) -> Float;
+    }
+
+    impl Mpfr for f32 {
+        fn mpfr<F>(self, f: F) -> Self
+        where
+            F: FnOnce(Float) -> Float,
+        {
+            f(Float::with_val(PRECISION, self)).to_f32()
+        }
+    }
+
+    impl Mpfr for f64 {
+        fn mpfr<F>(self, f: F) -> Self
+        where
+            F: FnOnce(Float) -> Float,
+        {
+            f(Float::with_val(PRECISION, self)).to_f64()
+        }
+    }
+
+    // Measures a unary real function of each constrained type against MPFR over the given domain.
+    macro_rules! assert_accuracy {
+        ($t:ty, $f:ident, $start:expr, $end:expr, $ulps:expr $(,)?) => {{
+            let oracle = |x: $t| x.mpfr(Float::$f);
+            oracle::measure(
+                |x: $t| Total::from(x).$f().into_inner(),
+                oracle,
+                oracle::sweep($start, $end, COUNT),
+            )
+            .assert_within(
+                concat!("Total<", stringify!($t), ">::", stringify!($f)),
+                $ulps,
+            );
+            oracle::measure(
+                |x: $t| <ExtendedReal<$t>>::assert(x).$f().into_inner(),
+                oracle,
+                oracle::sweep($start, $end, COUNT),
+            )
+            .assert_within(
+                concat!("ExtendedReal<", stringify!($t), ">::", stringify!($f)),
+                $ulps,
+            );
+            oracle::measure(
+                |x: $t| <Real<$t>>::assert(x).$f().into_inner(),
+                oracle,
+                oracle::sweep($start, $end, COUNT),
+            )
+            .assert_within(
+                concat!("Real<", stringify!($t), ">::", stringify!($f)),
+                $ulps,
+            );
+        }};
+    }
+
+    #[test]
+    fn ulps() {
+        assert_eq!(oracle::ulps(1.0f32, 1.0), 0);
+        assert_eq!(oracle::ulps(0.0f32, -0.0), 0);
+        assert_eq!(oracle::ulps(f32::from_bits(1), -f32::from_bits(1)), 2);
+        assert_eq!(oracle::ulps(1.0, f32::from_bits(1.0f32.to_bits() + 3)), 3);
+        assert_eq!(oracle::ulps(f32::NAN, f32::NAN), 0);
+        assert_eq!(oracle::ulps(f32::NAN, 1.0), u64::MAX);
+        assert_eq!(oracle::ulps(1.0, f64::from_bits(1.0f64.to_bits() + 3)), 3);
+        assert_eq!(oracle::ulps(f64::MIN, f64::MAX), 2 * 0x7FEF_FFFF_FFFF_FFFF);
+    }
+
+    #[test]
+    fn sweep() {
+        let xs: std::vec::Vec<_> = oracle::sweep(-1.0f32, 1.0, 3).collect();
+        assert_eq!(xs, [-1.0, 0.0, 1.0]);
+        assert!(oracle::sweep(1.0e-30f32, 1.0e30, 100).all(|x| (1.0e-30..=1.0e30).contains(&x)));
+        let xs: std::vec::Vec<_> = oracle::sweep(f64::MIN, f64::MAX, 3).collect();
+        assert_eq!(xs, [f64::MIN, 0.0, f64::MAX]);
+    }
+
+    #[test]
+    fn measure() {
+        let measurement = oracle::measure(|x| x + f32::EPSILON, |x| x, oracle::sweep(1.0, 1.5, 8));
+        assert_eq!(measurement.max_ulps(), 1);
+        assert_eq!(measurement.count(), 8);
+        let measurement = oracle::measure(
+            |x: f64| x.sqrt(),
+            |x| x.mpfr(Float::sqrt),
+            oracle::sweep(0.0, 4.0, 8),
+        );
+        assert_eq!(measurement.max_ulps(), 0);
+    }
+
+    #[test]
+    fn accuracy_of_f32_real_functions() {
+        assert_accuracy!(f32, sqrt, 0.0, 1.0e30, 0);
+        assert_accuracy!(f32, cbrt, -1.0e30, 1.0e30, 1);
+        assert_accuracy!(f32, exp, -80.0, 80.0, 1);
+        assert_accuracy!(f32, exp2, -120.0, 120.0, 1);
+        assert_accuracy!(f32, exp_m1, -80.0, 80.0, 1);
+        assert_accuracy!(f32, ln, 1.0e-30, 1.0e30, 1);
+        assert_accuracy!(f32, log2, 1.0e-30, 1.0e30, 1);
+        assert_accuracy!(f32, log10, 1.0e-30, 1.0e30, 2);
+        assert_accuracy!(f32, ln_1p, -0.5, 1.0e30, 1);
+        assert_accuracy!(f32, sin, -1.0e3, 1.0e3, 1);
+        assert_accuracy!(f32, cos, -1.0e3, 1.0e3, 1);
+        assert_accuracy!(f32, tan, -1.0e3, 1.0e3, 1);
+        assert_accuracy!(f32, asin, -1.0, 1.0, 1);
+        assert_accuracy!(f32, acos, -1.0, 1.0, 1);
+        assert_accuracy!(f32, atan, -1.0e30, 1.0e30, 1);
+        assert_accuracy!(f32, sinh, -80.0, 80.0, 2);
+        assert_accuracy!(f32, cosh, -80.0, 80.0, 2);
+        assert_accuracy!(f32, tanh, -80.0, 80.0, 2);
+        assert_accuracy!(f32, asinh, -1.0e30, 1.0e30, 2);
+        assert_accuracy!(f32, acosh, 1.0, 1.0e30, 8);
+        assert_accuracy!(f32, atanh, -0.99, 0.99, 16);
+    }
+
+    #[test]
+    fn accuracy_of_f64_real_functions() {
+        assert_accuracy!(f64, sqrt, 0.0, 1.0e300, 0);
+        assert_accuracy!(f64, cbrt, -1.0e300, 1.0e300, 1);
+        assert_accuracy!(f64, exp, -700.0, 700.0, 1);
+        assert_accuracy!(f64, exp2, -1000.0, 1000.0, 1);
+        assert_accuracy!(f64, exp_m1, -700.0, 700.0, 1);
+        assert_accuracy!(f64, ln, 1.0e-300, 1.0e300, 1);
+        assert_accuracy!(f64, log2, 1.0e-300, 1.0e300, 1);
+        assert_accuracy!(f64, log10, 1.0e-300, 1.0e300, 2);
+        assert_accuracy!(f64, ln_1p, -0.5, 1.0e300, 1);
+        assert_accuracy!(f64, sin, -1.0e3, 1.0e3, 1);
+        assert_accuracy!(f64, cos, -1.0e3, 1.0e3, 1);
+        assert_accuracy!(f64, tan, -1.0e3, 1.0e3, 1);
+        assert_accuracy!(f64, asin, -1.0, 1.0, 1);
+        assert_accuracy!(f64, acos, -1.0, 1.0, 1);
+        assert_accuracy!(f64, atan, -1.0e300, 1.0e300, 1);
+        assert_accuracy!(f64, sinh, -700.0, 700.0, 2);
+        assert_accuracy!(f64, cosh, -700.0, 700.0, 2);
+        assert_accuracy!(f64, tanh, -700.0, 700.0, 2);
+        assert_accuracy!(f64, asinh, -1.0e300, 1.0e300, 2);
+        assert_accuracy!(f64, acosh, 1.0, 1.0e300, 8);
+        assert_accuracy!(f64, atanh, -0.99, 0.99, 16);
+    }
+}
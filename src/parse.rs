@@ -0,0 +1,312 @@
+//! Parsing of [`Constrained`] values with SI prefixes and units.
+//!
+//! This module parses human-oriented text like `1.5k` and `3ms` into [`Constrained`] values. This
+//! is useful for configuring numeric parameters in command line tools, where the parsed value
+//! must also satisfy the constraints of a proxy type. The following SI prefixes are supported:
+//!
+//! | Prefix          | Name  | Factor    |
+//! |-----------------|-------|-----------|
+//! | `n`             | nano  | $10^{-9}$ |
+//! | `u`, `µ`, `μ`   | micro | $10^{-6}$ |
+//! | `m`             | milli | $10^{-3}$ |
+//! | `k`             | kilo  | $10^{3}$  |
+//! | `M`             | mega  | $10^{6}$  |
+//! | `G`             | giga  | $10^{9}$  |
+//!
+//! Prefixes are applied to the decimal representation of a number before it is parsed, so parsing
+//! is as precise as parsing the equivalent number in scientific notation (e.g., `3m` and `3e-3`
+//! are parsed into the same value).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::parse;
+//! use decorum::Real;
+//!
+//! let x = parse::parse_si::<Real<f64>>("1.5k").unwrap();
+//! assert_eq!(x, 1500.0);
+//!
+//! let t = parse::parse_si_unit::<Real<f64>>("3ms", "s").unwrap();
+//! assert_eq!(t, 0.003);
+//!
+//! assert!(parse::parse_si::<Real<f64>>("infk").is_err());
+//! ```
+//!
+//! Fractions can also be written with the suffixes `%` (percent), `‰` (per mille), and `ppm`
+//! (parts per million). [`parse_fraction`] scales such text accordingly:
+//!
+//! ```rust
+//! use decorum::parse;
+//! use decorum::Real;
+//!
+//! let x = parse::parse_fraction::<Real<f64>>("12.5%").unwrap();
+//! assert_eq!(x, 0.125);
+//! ```
+//!
+//! These functions can be used as value parsers with [`clap`]:
+//!
+//! ```rust,ignore
+//! use clap::Parser;
+//! use decorum::{parse, R64};
+//!
+//! #[derive(Parser)]
+//! struct Arguments {
+//!     #[arg(long, value_parser = parse::parse_si::<R64>)]
+//!     frequency: R64,
+//! }
+//! ```
+//!
+//! [`clap`]: https://crates.io/crates/clap
+//! [`Constrained`]: crate::proxy::Constrained
+
+use core::fmt::Write;
+use core::str::FromStr;
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::proxy::{Constrained, ConstrainedProxy, StackBuffer};
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum ParseSiError<E> {
+    #[error("{}", "invalid number")]
+    Number,
+    #[error("{0}")]
+    Constraint(E),
+}
+
+/// Parses a [`Constrained`] value with an optional SI prefix.
+///
+/// # Errors
+///
+/// Returns [`ParseSiError::Number`] if the text is not a number with an optional prefix and
+/// [`ParseSiError::Constraint`] if the parsed value does not satisfy the constraints of the proxy.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn parse_si<P>(text: &str) -> Result<P, ParseSiError<<P::Constraint as Constraint>::Error>>
+where
+    P: ConstrainedProxy,
+    P::Primitive: FromStr,
+    Constrained<P::Primitive, P::Constraint>: Into<P>,
+{
+    parse_si_unit(text, "")
+}
+
+/// Parses a [`Constrained`] value with an optional SI prefix and an optional unit.
+///
+/// The unit follows the prefix (e.g., `s` in `3ms`) and is ignored. Note that a unit may also be
+/// a prefix: if the unit is `m`, then `3m` is three (of the unit) and `3mm` is three thousandths.
+///
+/// # Errors
+///
+/// Returns [`ParseSiError::Number`] if the text is not a number with an optional prefix and unit
+/// and [`ParseSiError::Constraint`] if the parsed value does not satisfy the constraints of the
+/// proxy.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn parse_si_unit<P>(
+    text: &str,
+    unit: &str,
+) -> Result<P, ParseSiError<<P::Constraint as Constraint>::Error>>
+where
+    P: ConstrainedProxy,
+    P::Primitive: FromStr,
+    Constrained<P::Primitive, P::Constraint>: Into<P>,
+{
+    let text = text.trim();
+    let text = text.strip_suffix(unit).unwrap_or(text);
+    // Text like `nan` ends with a character that is also a prefix, so first try parsing the text
+    // as a number without a prefix.
+    let inner = match (text.parse(), split_prefix(text)) {
+        (Ok(inner), _) => inner,
+        (Err(_), Some((number, exponent))) => {
+            let mut buffer = StackBuffer::<64>::default();
+            write!(buffer, "{}e{}", number.trim_end(), exponent)
+                .map_err(|_| ParseSiError::Number)?;
+            parse(buffer.as_str())?
+        }
+        (Err(_), None) => return Err(ParseSiError::Number),
+    };
+    Constrained::try_new(inner)
+        .map(Into::into)
+        .map_err(ParseSiError::Constraint)
+}
+
+/// Parses a [`Constrained`] fraction with an optional suffix.
+///
+/// The suffixes `%`, `‰`, and `ppm` scale the number by $10^{-2}$, $10^{-3}$, and $10^{-6}$,
+/// respectively. Text without a suffix is parsed as-is. As with SI prefixes, suffixes are applied
+/// to the decimal representation of the number, so `12.5%` and `0.125` are parsed into the same
+/// value.
+///
+/// # Errors
+///
+/// Returns [`ParseSiError::Number`] if the text is not a number with an optional suffix and
+/// [`ParseSiError::Constraint`] if the parsed value does not satisfy the constraints of the proxy.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub fn parse_fraction<P>(
+    text: &str,
+) -> Result<P, ParseSiError<<P::Constraint as Constraint>::Error>>
+where
+    P: ConstrainedProxy,
+    P::Primitive: FromStr,
+    Constrained<P::Primitive, P::Constraint>: Into<P>,
+{
+    let text = text.trim();
+    let inner = match split_suffix(text) {
+        Some((number, exponent)) => {
+            let mut buffer = StackBuffer::<64>::default();
+            write!(buffer, "{}e{}", number.trim_end(), exponent)
+                .map_err(|_| ParseSiError::Number)?;
+            parse(buffer.as_str())?
+        }
+        _ => parse(text)?,
+    };
+    Constrained::try_new(inner)
+        .map(Into::into)
+        .map_err(ParseSiError::Constraint)
+}
+
+/// A [`Constrained`] fraction that is parsed from text with an optional suffix.
+///
+/// The [`FromStr`] implementation of this type uses [`parse_fraction`], so it can be used to read
+/// fields like `"12.5%"` from configuration.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Fraction<P>(pub P);
+
+impl<P> Fraction<P> {
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> FromStr for Fraction<P>
+where
+    P: ConstrainedProxy,
+    P::Primitive: FromStr,
+    Constrained<P::Primitive, P::Constraint>: Into<P>,
+{
+    type Err = ParseSiError<<P::Constraint as Constraint>::Error>;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_fraction(text).map(Fraction)
+    }
+}
+
+// Splits text into a number and the decimal exponent of its fraction suffix, if any.
+fn split_suffix(text: &str) -> Option<(&str, i32)> {
+    [("%", -2), ("‰", -3), ("ppm", -6)]
+        .into_iter()
+        .find_map(|(suffix, exponent)| text.strip_suffix(suffix).map(|number| (number, exponent)))
+}
+
+// Splits text into a number and the decimal exponent of its prefix, if any.
+fn split_prefix(text: &str) -> Option<(&str, i32)> {
+    let prefix = text.chars().next_back()?;
+    let exponent = match prefix {
+        'n' => -9,
+        'u' | 'µ' | 'μ' => -6,
+        'm' => -3,
+        'k' => 3,
+        'M' => 6,
+        'G' => 9,
+        _ => return None,
+    };
+    Some((&text[..text.len() - prefix.len_utf8()], exponent))
+}
+
+fn parse<T, E>(text: &str) -> Result<T, ParseSiError<E>>
+where
+    T: FromStr + Primitive,
+{
+    text.parse().map_err(|_| ParseSiError::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{self, Fraction, ParseSiError};
+    use crate::{Real, Total};
+
+    #[test]
+    fn parse_fraction() {
+        let parse = |text| parse::parse_fraction::<Real<f64>>(text).unwrap();
+        assert_eq!(parse("12.5%"), 0.125);
+        assert_eq!(parse(" 50 % "), 0.5);
+        assert_eq!(parse("-3‰"), -0.003);
+        assert_eq!(parse("250ppm"), 2.5e-4);
+        assert_eq!(parse("0.25"), 0.25);
+        assert_eq!(
+            "7.5%".parse::<Fraction<Real<f64>>>().unwrap().into_inner(),
+            0.075
+        );
+
+        assert!(matches!(
+            parse::parse_fraction::<Real<f64>>("%"),
+            Err(ParseSiError::Number)
+        ));
+        assert!(matches!(
+            parse::parse_fraction::<Real<f64>>("1e2%%"),
+            Err(ParseSiError::Number)
+        ));
+        assert!(matches!(
+            parse::parse_fraction::<Real<f64>>("inf%"),
+            Err(ParseSiError::Number)
+        ));
+        assert!(matches!(
+            parse::parse_fraction::<Real<f64>>("1e400"),
+            Err(ParseSiError::Constraint(_))
+        ));
+    }
+
+    #[test]
+    fn parse_si() {
+        let parse = |text| parse::parse_si::<Real<f64>>(text).unwrap();
+        assert_eq!(parse("1.5k"), 1500.0);
+        assert_eq!(parse("2M"), 2.0e6);
+        assert_eq!(parse(" 4G "), 4.0e9);
+        assert_eq!(parse("3m"), 3.0e-3);
+        assert_eq!(parse("-7u"), -7.0e-6);
+        assert_eq!(parse("7µ"), 7.0e-6);
+        assert_eq!(parse("7μ"), 7.0e-6);
+        assert_eq!(parse("1 n"), 1.0e-9);
+        assert_eq!(parse("0.1"), 0.1);
+
+        assert!(matches!(
+            parse::parse_si::<Real<f64>>("k"),
+            Err(ParseSiError::Number)
+        ));
+        assert!(matches!(
+            parse::parse_si::<Real<f64>>("1e3k"),
+            Err(ParseSiError::Number)
+        ));
+        assert!(matches!(
+            parse::parse_si::<Real<f64>>("1.5x"),
+            Err(ParseSiError::Number)
+        ));
+        assert!(matches!(
+            parse::parse_si::<Real<f64>>("inf"),
+            Err(ParseSiError::Constraint(_))
+        ));
+        assert!(matches!(
+            parse::parse_si::<Real<f32>>("1e39"),
+            Err(ParseSiError::Constraint(_))
+        ));
+        assert!(parse::parse_si::<Total<f64>>("nan").is_ok());
+    }
+
+    #[test]
+    fn parse_si_unit() {
+        let parse = |text, unit| parse::parse_si_unit::<Real<f64>>(text, unit).unwrap();
+        assert_eq!(parse("3ms", "s"), 3.0e-3);
+        assert_eq!(parse("3 ms", "s"), 3.0e-3);
+        assert_eq!(parse("3s", "s"), 3.0);
+        assert_eq!(parse("3", "s"), 3.0);
+        assert_eq!(parse("3m", "m"), 3.0);
+        assert_eq!(parse("3mm", "m"), 3.0e-3);
+        assert_eq!(parse("1.5kHz", "Hz"), 1500.0);
+    }
+}
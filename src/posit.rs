@@ -0,0 +1,601 @@
+//! Experimental software posit backend.
+//!
+//! This module provides [`Posit32`], a software implementation of the 32-bit posit type of the
+//! [posit standard][standard] with two exponent bits. [`Posit32`] implements [`Primitive`] and so
+//! can be used as the primitive type of [`Constrained`] types, which allows the constraint and
+//! divergence behavior of posits to be compared with IEEE 754 floating-point types using the same
+//! proxy API.
+//!
+//! Posits are not IEEE 754 floating-point types and the encoding traits are implemented as
+//! follows:
+//!
+//! - Posits have a single exceptional value, _not a real_ (NaR), which is the `NaN` of
+//!   [`NanEncoding`]. Unlike the posit standard, NaR is unordered and is not equal to itself, which
+//!   matches the semantics of `NaN`s and so the constraints and orderings of [`Constrained`] types.
+//! - Posits have no infinities. Rounding saturates to [`MAX_FINITE`] and [`MIN_FINITE`] and never
+//!   rounds non-zero values to zero. The [`INFINITY`] and [`NEG_INFINITY`] constants are NaR and
+//!   no posit is infinite, so the [`IsReal`] and [`IsExtendedReal`] constraints are equivalent.
+//! - Posits have no subnormals and a single zero.
+//!
+//! Arithmetic and functions are computed with `f64`, which represents all 32-bit posits exactly,
+//! and then rounded to the nearest posit with ties to even. **This double rounding is not always
+//! correctly rounded**: in rare cases, the output may differ from the correctly rounded output by
+//! one unit in the last place. This backend is intended for experimentation and is not optimized.
+//!
+//! This module requires the `experimental-posit` feature.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::posit::Posit32;
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::{Real, Total};
+//!
+//! // Posits saturate rather than overflow.
+//! let x = Total::from(Posit32::MAX);
+//! assert_eq!(x * x, x);
+//!
+//! // `NaR` does not satisfy real constraints.
+//! let nar = Posit32::ZERO / Posit32::ZERO;
+//! assert!(Real::<Posit32>::try_new(nar).is_err());
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`INFINITY`]: crate::InfinityEncoding::INFINITY
+//! [`IsExtendedReal`]: crate::constraint::IsExtendedReal
+//! [`IsReal`]: crate::constraint::IsReal
+//! [`MAX_FINITE`]: crate::BaseEncoding::MAX_FINITE
+//! [`MIN_FINITE`]: crate::BaseEncoding::MIN_FINITE
+//! [`NanEncoding`]: crate::NanEncoding
+//! [`NEG_INFINITY`]: crate::InfinityEncoding::NEG_INFINITY
+//! [`Primitive`]: crate::Primitive
+//! [standard]: https://posithub.org/docs/posit_standard-2.pdf
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::num::FpCategory;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use crate::cmp::{EmptyInhabitant, EmptyOrd};
+use crate::proxy::Nan;
+use crate::real::{BinaryRealFunction, Function, Sign, UnaryRealFunction};
+use crate::sealed::{FusedMulAdd, Sealed};
+use crate::{BaseEncoding, InfinityEncoding, NanEncoding, Primitive};
+
+const NAR_BITS: u32 = 0x8000_0000;
+const MAX_BITS: u32 = 0x7fff_ffff;
+
+/// A 32-bit posit with two exponent bits.
+///
+/// See the [module documentation][`posit`].
+///
+/// [`posit`]: crate::posit
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct Posit32(u32);
+
+impl Posit32 {
+    /// Not a real (NaR).
+    pub const NAR: Self = Posit32(NAR_BITS);
+    /// The largest posit, $2^{120}$.
+    pub const MAX: Self = Posit32(MAX_BITS);
+    /// The smallest positive posit, $2^{-120}$.
+    pub const MIN_POSITIVE: Self = Posit32(1);
+
+    pub const fn from_bits(bits: u32) -> Self {
+        Posit32(bits)
+    }
+
+    pub const fn to_bits(self) -> u32 {
+        self.0
+    }
+
+    /// Converts an `f64` into the nearest posit.
+    ///
+    /// `NaN`s and infinities are converted into NaR. Non-zero values saturate and never round to
+    /// zero.
+    pub fn from_f64(x: f64) -> Self {
+        if x.is_nan() || x.is_infinite() {
+            return Posit32::NAR;
+        }
+        if x == 0.0 {
+            return Posit32(0);
+        }
+        let bits = x.to_bits();
+        // The magnitudes of `f64` subnormals are less than the smallest positive posit, so the
+        // significand is always normal here.
+        let scale = ((bits >> 52) & 0x7ff) as i32 - 1023;
+        let fraction = u128::from(bits & ((1 << 52) - 1));
+        let pattern = if scale >= 120 {
+            MAX_BITS
+        }
+        else if scale < -120 {
+            1
+        }
+        else {
+            let k = scale.div_euclid(4);
+            let exponent = scale.rem_euclid(4) as u128;
+            // The regime is a run of `k + 1` ones or `-k` zeros followed by a terminating bit.
+            let (regime, length) = if k >= 0 {
+                (((1u128 << (k + 1)) - 1) << 1, k as u32 + 2)
+            }
+            else {
+                (1, k.unsigned_abs() + 1)
+            };
+            let unrounded = (((regime << 2) | exponent) << 52) | fraction;
+            // Round to the 31 bits that follow the sign bit. This never rounds to zero, but may
+            // carry beyond the largest posit.
+            (shift_right_even(unrounded, length + 54 - 31) as u32).min(MAX_BITS)
+        };
+        if x.is_sign_negative() {
+            Posit32(pattern.wrapping_neg())
+        }
+        else {
+            Posit32(pattern)
+        }
+    }
+
+    /// Converts the posit into an `f64`.
+    ///
+    /// This conversion is exact. NaR is converted into `NaN`.
+    pub fn to_f64(self) -> f64 {
+        match self.0 {
+            0 => 0.0,
+            NAR_BITS => f64::NAN,
+            bits => {
+                let negative = bits >> 31 == 1;
+                let body = (if negative { bits.wrapping_neg() } else { bits }) << 1;
+                let (k, length) = if body >> 31 == 1 {
+                    let length = body.leading_ones();
+                    (length as i32 - 1, length)
+                }
+                else {
+                    let length = body.leading_zeros();
+                    (-(length as i32), length)
+                };
+                let rest = body.checked_shl(length + 1).unwrap_or(0);
+                let scale = (4 * k) + (rest >> 30) as i32;
+                let significand = 1.0 + (f64::from(rest << 2) / 4_294_967_296.0);
+                // `scale` is in `[-120, 120]`, so this is a normal power of two.
+                let power = f64::from_bits(((scale + 1023) as u64) << 52);
+                let value = significand * power;
+                if negative {
+                    -value
+                }
+                else {
+                    value
+                }
+            }
+        }
+    }
+
+    fn map<F>(self, f: F) -> Self
+    where
+        F: FnOnce(f64) -> f64,
+    {
+        Posit32::from_f64(f(self.to_f64()))
+    }
+
+    fn zip_map<F>(self, other: Self, f: F) -> Self
+    where
+        F: FnOnce(f64, f64) -> f64,
+    {
+        Posit32::from_f64(f(self.to_f64(), other.to_f64()))
+    }
+}
+
+impl Add for Posit32 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.zip_map(other, Add::add)
+    }
+}
+
+impl BaseEncoding for Posit32 {
+    const MAX_FINITE: Self = Posit32::MAX;
+    const MIN_FINITE: Self = Posit32(MAX_BITS.wrapping_neg());
+    const MIN_POSITIVE_NORMAL: Self = Posit32::MIN_POSITIVE;
+    const EPSILON: Self = Posit32(0x00a0_0000);
+
+    fn classify(self) -> FpCategory {
+        match self.0 {
+            0 => FpCategory::Zero,
+            NAR_BITS => FpCategory::Nan,
+            _ => FpCategory::Normal,
+        }
+    }
+
+    fn is_normal(self) -> bool {
+        self.classify() == FpCategory::Normal
+    }
+
+    fn is_sign_positive(self) -> bool {
+        self.0 >> 31 == 0
+    }
+
+    fn is_sign_negative(self) -> bool {
+        !self.is_sign_positive()
+    }
+
+    #[cfg(feature = "std")]
+    fn signum(self) -> Self {
+        if self.is_nan() {
+            self
+        }
+        else if self.is_sign_negative() {
+            -Posit32::ONE
+        }
+        else {
+            Posit32::ONE
+        }
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        BaseEncoding::integer_decode(self.to_f64())
+    }
+}
+
+impl BinaryRealFunction for Posit32 {
+    #[cfg(feature = "std")]
+    fn div_euclid(self, n: Self) -> Self::Codomain {
+        self.zip_map(n, f64::div_euclid)
+    }
+
+    #[cfg(feature = "std")]
+    fn rem_euclid(self, n: Self) -> Self::Codomain {
+        self.zip_map(n, f64::rem_euclid)
+    }
+
+    #[cfg(feature = "std")]
+    fn pow(self, n: Self) -> Self::Codomain {
+        self.zip_map(n, f64::powf)
+    }
+
+    #[cfg(feature = "std")]
+    fn log(self, base: Self) -> Self::Codomain {
+        self.zip_map(base, f64::log)
+    }
+
+    #[cfg(feature = "std")]
+    fn hypot(self, other: Self) -> Self::Codomain {
+        self.zip_map(other, f64::hypot)
+    }
+
+    #[cfg(feature = "std")]
+    fn atan2(self, other: Self) -> Self::Codomain {
+        self.zip_map(other, f64::atan2)
+    }
+}
+
+impl Debug for Posit32 {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_tuple("Posit32")
+            .field(&format_args!("{:#010x}", self.0))
+            .finish()
+    }
+}
+
+impl Display for Posit32 {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            formatter.pad("NaR")
+        }
+        else {
+            Display::fmt(&self.to_f64(), formatter)
+        }
+    }
+}
+
+impl Div for Posit32 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.zip_map(other, Div::div)
+    }
+}
+
+impl EmptyInhabitant for Posit32 {
+    #[inline(always)]
+    fn empty() -> Self {
+        Posit32::NAR
+    }
+}
+
+impl EmptyOrd for Posit32 {
+    type Empty = Self;
+
+    #[inline(always)]
+    fn from_empty(empty: Self::Empty) -> Self {
+        empty
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_nan()
+    }
+
+    fn cmp_empty(&self, other: &Self) -> Result<Ordering, Self::Empty> {
+        self.partial_cmp(other).ok_or(Posit32::NAR)
+    }
+}
+
+impl From<f32> for Posit32 {
+    fn from(x: f32) -> Self {
+        Posit32::from_f64(f64::from(x))
+    }
+}
+
+impl From<Posit32> for f64 {
+    fn from(x: Posit32) -> Self {
+        x.to_f64()
+    }
+}
+
+impl From<Nan<Posit32>> for Posit32 {
+    fn from(nan: Nan<Posit32>) -> Self {
+        nan.into_inner()
+    }
+}
+
+impl Function for Posit32 {
+    type Codomain = Self;
+}
+
+impl InfinityEncoding for Posit32 {
+    const INFINITY: Self = Posit32::NAR;
+    const NEG_INFINITY: Self = Posit32::NAR;
+
+    fn is_infinite(self) -> bool {
+        false
+    }
+
+    fn is_finite(self) -> bool {
+        !self.is_nan()
+    }
+}
+
+impl Mul for Posit32 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.zip_map(other, Mul::mul)
+    }
+}
+
+impl NanEncoding for Posit32 {
+    type Nan = Nan<Self>;
+
+    const NAN: Self::Nan = Nan::unchecked(Posit32::NAR);
+
+    fn is_nan(self) -> bool {
+        self.0 == NAR_BITS
+    }
+}
+
+impl Neg for Posit32 {
+    type Output = Self;
+
+    // Negation is exact: the encoding of a posit is negated by its two's complement.
+    fn neg(self) -> Self::Output {
+        Posit32(self.0.wrapping_neg())
+    }
+}
+
+impl PartialEq for Posit32 {
+    fn eq(&self, other: &Self) -> bool {
+        !self.is_nan() && self.0 == other.0
+    }
+}
+
+impl PartialOrd for Posit32 {
+    // Posits are ordered like their encodings as two's complement integers.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_nan() || other.is_nan() {
+            None
+        }
+        else {
+            Some((self.0 as i32).cmp(&(other.0 as i32)))
+        }
+    }
+}
+
+impl FusedMulAdd for Posit32 {
+    #[cfg(feature = "std")]
+    fn fused_mul_add(self, a: Self, b: Self) -> Self {
+        Posit32::from_f64(self.to_f64().mul_add(a.to_f64(), b.to_f64()))
+    }
+}
+
+impl Primitive for Posit32 {}
+
+impl Rem for Posit32 {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self::Output {
+        self.zip_map(other, Rem::rem)
+    }
+}
+
+impl Sealed for Posit32 {}
+
+impl Sub for Posit32 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.zip_map(other, Sub::sub)
+    }
+}
+
+macro_rules! with_unary_functions {
+    ($f:ident) => {
+        $f!(function => abs);
+        $f!(function => floor);
+        $f!(function => ceil);
+        $f!(function => round);
+        $f!(function => trunc);
+        $f!(function => fract);
+        $f!(function => sqrt);
+        $f!(function => cbrt);
+        $f!(function => exp);
+        $f!(function => exp2);
+        $f!(function => exp_m1);
+        $f!(function => ln);
+        $f!(function => log2);
+        $f!(function => log10);
+        $f!(function => ln_1p);
+        $f!(function => to_degrees);
+        $f!(function => to_radians);
+        $f!(function => sin);
+        $f!(function => cos);
+        $f!(function => tan);
+        $f!(function => asin);
+        $f!(function => acos);
+        $f!(function => atan);
+        $f!(function => sinh);
+        $f!(function => cosh);
+        $f!(function => tanh);
+        $f!(function => asinh);
+        $f!(function => acosh);
+        $f!(function => atanh);
+    };
+}
+
+macro_rules! impl_unary_function_for_posit {
+    (function => $f:ident) => {
+        #[cfg(feature = "std")]
+        fn $f(self) -> Self {
+            self.map(f64::$f)
+        }
+    };
+}
+
+impl UnaryRealFunction for Posit32 {
+    const ZERO: Self = Posit32(0);
+    const ONE: Self = Posit32(0x4000_0000);
+    const E: Self = Posit32(0x4adf_8546);
+    const PI: Self = Posit32(0x4c90_fdaa);
+    const FRAC_1_PI: Self = Posit32(0x322f_9837);
+    const FRAC_2_PI: Self = Posit32(0x3a2f_9837);
+    const FRAC_2_SQRT_PI: Self = Posit32(0x4106_eba8);
+    const FRAC_PI_2: Self = Posit32(0x4490_fdaa);
+    const FRAC_PI_3: Self = Posit32(0x4060_a91c);
+    const FRAC_PI_4: Self = Posit32(0x3c90_fdaa);
+    const FRAC_PI_6: Self = Posit32(0x3860_a91c);
+    const FRAC_PI_8: Self = Posit32(0x3490_fdaa);
+    const SQRT_2: Self = Posit32(0x4350_4f33);
+    const FRAC_1_SQRT_2: Self = Posit32(0x3b50_4f33);
+    const LN_2: Self = Posit32(0x3b17_217f);
+    const LN_10: Self = Posit32(0x4935_d8de);
+    const LOG2_E: Self = Posit32(0x438a_a3b3);
+    const LOG10_E: Self = Posit32(0x35e5_bd8b);
+
+    fn is_zero(self) -> bool {
+        self == Self::ZERO
+    }
+
+    fn is_one(self) -> bool {
+        self == Self::ONE
+    }
+
+    fn sign(self) -> Sign {
+        if self.is_nan() || self.is_zero() {
+            Sign::Zero
+        }
+        else if self.is_sign_positive() {
+            Sign::Positive
+        }
+        else {
+            Sign::Negative
+        }
+    }
+
+    fn recip(self) -> Self::Codomain {
+        self.map(f64::recip)
+    }
+
+    #[cfg(feature = "std")]
+    fn powi(self, n: i32) -> Self::Codomain {
+        self.map(|x| x.powi(n))
+    }
+
+    #[cfg(feature = "std")]
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    with_unary_functions!(impl_unary_function_for_posit);
+}
+
+// Divides by `2^shift` with rounding to nearest, ties to even.
+fn shift_right_even(n: u128, shift: u32) -> u128 {
+    let quotient = n >> shift;
+    let remainder = n & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if remainder > half || (remainder == half && quotient & 1 == 1) {
+        quotient + 1
+    }
+    else {
+        quotient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::divergence::{AsResult, OrError};
+    use crate::posit::Posit32;
+    use crate::real::UnaryRealFunction;
+    use crate::{BaseEncoding, NanEncoding, Real, Total};
+
+    #[test]
+    fn posit_encoding() {
+        assert_eq!(Posit32::from_f64(1.0).to_bits(), 0x4000_0000);
+        assert_eq!(Posit32::from_f64(-1.0).to_bits(), 0xc000_0000);
+        assert_eq!(Posit32::from_f64(0.5).to_bits(), 0x3800_0000);
+        assert_eq!(Posit32::from_f64(16.0).to_bits(), 0x6000_0000);
+        assert_eq!(Posit32::from_f64(f64::MAX), Posit32::MAX);
+        assert_eq!(Posit32::from_f64(1.0e-300), Posit32::MIN_POSITIVE);
+        assert_eq!(Posit32::MAX.to_f64(), 2.0f64.powi(120));
+        assert_eq!(Posit32::MIN_POSITIVE.to_f64(), 2.0f64.powi(-120));
+        assert_eq!(Posit32::EPSILON.to_f64(), 2.0f64.powi(-27));
+        assert!(Posit32::from_f64(f64::INFINITY).is_nan());
+        assert!(Posit32::NAR.to_f64().is_nan());
+        assert_eq!(Posit32::PI.to_f64() as f32, core::f32::consts::PI);
+
+        for bits in (0..=u32::MAX).step_by(65_521) {
+            let x = Posit32::from_bits(bits);
+            if !x.is_nan() {
+                assert_eq!(Posit32::from_f64(x.to_f64()).to_bits(), bits);
+            }
+        }
+    }
+
+    #[test]
+    fn posit_rounding() {
+        let one = Posit32::ONE.to_f64();
+        let epsilon = Posit32::EPSILON.to_f64();
+        // Ties to even.
+        assert_eq!(Posit32::from_f64(one + (epsilon / 2.0)), Posit32::ONE);
+        assert_eq!(
+            Posit32::from_f64(one + (epsilon * 1.5)).to_bits(),
+            Posit32::ONE.to_bits() + 2
+        );
+        assert!(Posit32::ONE < Posit32::from_f64(one + epsilon));
+        assert!(-Posit32::ONE < Posit32::ZERO);
+        assert!(Posit32::NAR.partial_cmp(&Posit32::NAR).is_none());
+    }
+
+    #[test]
+    fn posit_proxy() {
+        let x = Total::from(Posit32::MAX);
+        assert_eq!(x * x, x);
+        assert!((Total::from(Posit32::ONE) / Total::from(Posit32::ZERO)).is_nan());
+
+        type Checked = Real<Posit32, OrError<AsResult>>;
+        assert!((Checked::ONE / Checked::ZERO).is_err());
+        assert_eq!(
+            (Checked::ONE + Checked::ONE).unwrap().into_inner().to_f64(),
+            2.0
+        );
+    }
+}
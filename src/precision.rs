@@ -0,0 +1,125 @@
+//! Runtime warnings for operations that lose precision.
+//!
+//! When the `debug-precision` feature is enabled, additions and subtractions of [`Constrained`]
+//! types are checked in debug builds. If an operation _absorbs_ an operand, meaning that a
+//! non-zero operand is so much smaller in magnitude than the other that it does not affect the
+//! output at all (e.g., `1.0e20 + 1.0`), then a [`PrecisionLoss`] is reported to a hook. All
+//! significant digits of the absorbed operand are lost in such an operation, which is a common
+//! source of numerical bugs in accumulations.
+//!
+//! These checks are not performed in release builds and never change the output of operations.
+//! By default, the hook writes a warning to `stderr`. The hook can be replaced via [`set_hook`].
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+
+use core::fmt::{self, Display, Formatter};
+use std::eprintln;
+use std::sync::RwLock;
+
+use crate::binade;
+use crate::trace::Operation;
+use crate::Primitive;
+
+static HOOK: RwLock<Option<fn(&PrecisionLoss)>> = RwLock::new(None);
+
+/// An operation that absorbed one of its operands.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PrecisionLoss {
+    operation: Operation,
+    binades: u32,
+}
+
+impl PrecisionLoss {
+    /// Gets the operation that absorbed an operand.
+    pub const fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Gets the difference between the [binades][`binade`] of the operands.
+    ///
+    /// [`binade`]: crate::binade
+    pub const fn binades(&self) -> u32 {
+        self.binades
+    }
+}
+
+impl Display for PrecisionLoss {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "operation `{}` absorbed an operand {} binades smaller than the other",
+            self.operation.symbol(),
+            self.binades,
+        )
+    }
+}
+
+/// Sets the hook that is called when an operation loses precision.
+///
+/// The hook replaces any previous hook, including the default hook that writes to `stderr`.
+pub fn set_hook(hook: fn(&PrecisionLoss)) {
+    *HOOK.write().unwrap_or_else(|error| error.into_inner()) = Some(hook);
+}
+
+/// Restores the default hook, which writes a warning to `stderr`.
+pub fn reset_hook() {
+    *HOOK.write().unwrap_or_else(|error| error.into_inner()) = None;
+}
+
+fn report(loss: &PrecisionLoss) {
+    match *HOOK.read().unwrap_or_else(|error| error.into_inner()) {
+        Some(hook) => hook(loss),
+        _ => eprintln!("warning: {}", loss),
+    }
+}
+
+pub(crate) fn check<T>(operation: Operation, left: T, right: T, output: T)
+where
+    T: Primitive,
+{
+    let is_absorbed = match operation {
+        Operation::Add => {
+            (output == left && right != T::ZERO) || (output == right && left != T::ZERO)
+        }
+        Operation::Sub => {
+            (output == left && right != T::ZERO) || (output == -right && left != T::ZERO)
+        }
+        _ => false,
+    };
+    if is_absorbed {
+        // Zeros, infinities, and `NaN`s have no binade and are never considered absorbed.
+        if let (Some(left), Some(right)) = (binade::binade(left), binade::binade(right)) {
+            report(&PrecisionLoss {
+                operation,
+                binades: left.abs_diff(right),
+            });
+        }
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use core::cell::Cell;
+
+    use crate::precision::{self, PrecisionLoss};
+    use crate::trace::Operation;
+    use crate::R64;
+
+    std::thread_local! {
+        // The hook is global, but is called on the thread that executes the operation.
+        static LOSS: Cell<Option<PrecisionLoss>> = const { Cell::new(None) };
+    }
+
+    #[test]
+    fn absorption() {
+        precision::set_hook(|loss| LOSS.with(|cell| cell.set(Some(*loss))));
+        let x: R64 = R64::assert(1.0e20);
+        let _ = x + R64::assert(1.0e10);
+        assert_eq!(LOSS.with(Cell::take), None);
+        let _ = x - R64::assert(1.0);
+        let loss = LOSS.with(Cell::take).unwrap();
+        assert_eq!(loss.operation(), Operation::Sub);
+        assert_eq!(loss.binades(), 66);
+        precision::reset_hook();
+    }
+}
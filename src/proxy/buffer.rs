@@ -0,0 +1,250 @@
+use bytemuck::NoUninit;
+#[cfg(feature = "std")]
+use core::mem;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::proxy::bytes::LengthError;
+#[cfg(feature = "std")]
+use crate::proxy::bytes::{DecodeError, Endianness};
+use crate::proxy::constrained::Constrained;
+use crate::with_primitives;
+#[cfg(feature = "std")]
+use crate::Primitive;
+
+#[cfg(feature = "std")]
+const MAGIC: [u8; 4] = *b"DCRM";
+#[cfg(feature = "std")]
+const VERSION: u8 = 1;
+#[cfg(feature = "std")]
+const HEADER_LEN: usize = 16;
+#[cfg(feature = "std")]
+const NAN: u8 = 1 << 0;
+#[cfg(feature = "std")]
+const INFINITY: u8 = 1 << 1;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum BufferError<E> {
+    #[error("{}", "invalid buffer header")]
+    Header,
+    #[error("{0}")]
+    Length(LengthError),
+    #[error("{}", "buffer value does not satisfy the constraint of the header")]
+    Metadata,
+    #[error("{0}")]
+    Constraint(E),
+}
+
+impl<E> From<LengthError> for BufferError<E> {
+    fn from(error: LengthError) -> Self {
+        BufferError::Length(error)
+    }
+}
+
+// Gets the flags of the values that are supported by the constraint `C`.
+#[cfg(feature = "std")]
+fn flags<T, C>() -> u8
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let mut flags = 0;
+    if C::check(T::NAN.into_inner()).is_ok() {
+        flags |= NAN;
+    }
+    if C::check(T::INFINITY).is_ok() {
+        flags |= INFINITY;
+    }
+    flags
+}
+
+// Checks a value against the flags of a header.
+#[cfg(feature = "std")]
+fn is_member<T>(inner: T, flags: u8) -> bool
+where
+    T: Primitive,
+{
+    if inner.is_nan() {
+        flags & NAN != 0
+    }
+    else if inner.is_infinite() {
+        flags & INFINITY != 0
+    }
+    else {
+        true
+    }
+}
+
+macro_rules! impl_buffer_for_proxy {
+    () => {
+        with_primitives!(impl_buffer_for_proxy);
+    };
+    (primitive => $t:ident) => {
+        impl<C> Constrained<$t, C>
+        where
+            C: Constraint,
+            Self: NoUninit,
+        {
+            /// Reinterprets a slice of proxies as a slice of their primitive floating-point values
+            /// without copying.
+            ///
+            /// This is useful for uploading buffers of proxies to GPU and compute APIs, which
+            /// typically accept slices of primitive values or bytes.
+            pub fn as_pod_slice(proxies: &[Self]) -> &[$t] {
+                bytemuck::cast_slice(proxies)
+            }
+
+            /// Encodes a slice of proxies into a byte buffer with a header that describes the
+            /// constraint of the proxy.
+            ///
+            /// The buffer begins with a 16-byte header followed by the values in little-endian
+            /// byte order. The header has the following layout:
+            ///
+            /// | Offset | Size | Description                                        |
+            /// |--------|------|----------------------------------------------------|
+            /// | 0      | 4    | Magic bytes `DCRM`                                 |
+            /// | 4      | 1    | Version (`1`)                                      |
+            /// | 5      | 1    | Size of the primitive type in bytes                |
+            /// | 6      | 1    | Flags: `NaN` (bit 0) and infinities (bit 1) allowed |
+            /// | 7      | 1    | Reserved (`0`)                                     |
+            /// | 8      | 8    | Number of values as a little-endian `u64`          |
+            ///
+            /// For example, the flags of [`Real`] buffers are zero, which asserts that all values
+            /// are finite.
+            ///
+            /// [`Real`]: crate::Real
+            #[cfg(feature = "std")]
+            pub fn into_byte_buffer(proxies: &[Self]) -> Vec<u8> {
+                let mut buffer =
+                    Vec::with_capacity(HEADER_LEN + (proxies.len() * mem::size_of::<$t>()));
+                buffer.extend_from_slice(&MAGIC);
+                buffer.extend_from_slice(&[
+                    VERSION,
+                    mem::size_of::<$t>() as u8,
+                    flags::<$t, C>(),
+                    0,
+                ]);
+                buffer.extend_from_slice(&(proxies.len() as u64).to_le_bytes());
+                if cfg!(target_endian = "little") {
+                    buffer.extend_from_slice(bytemuck::cast_slice(proxies));
+                }
+                else {
+                    for proxy in proxies {
+                        buffer.extend_from_slice(&proxy.to_le_bytes());
+                    }
+                }
+                buffer
+            }
+
+            /// Decodes proxies from a byte buffer encoded by [`into_byte_buffer`].
+            ///
+            /// Each value is validated against both the constraint described by the header of
+            /// the buffer and the constraint of the proxy.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the header is invalid or describes a different primitive type,
+            /// if the length of the buffer does not match the header, or if any value does not
+            /// satisfy the constraints of the header or proxy.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use decorum::{Total, R32};
+            ///
+            /// let xs = [1.0, f32::INFINITY].map(Total::from);
+            /// let buffer = Total::<f32>::into_byte_buffer(&xs);
+            /// assert_eq!(Total::<f32>::try_from_byte_buffer(&buffer).unwrap(), xs);
+            /// assert!(<R32>::try_from_byte_buffer(&buffer).is_err());
+            /// ```
+            ///
+            /// [`into_byte_buffer`]: crate::proxy::Constrained::into_byte_buffer
+            #[cfg(feature = "std")]
+            pub fn try_from_byte_buffer(bytes: &[u8]) -> Result<Vec<Self>, BufferError<C::Error>> {
+                if bytes.len() < HEADER_LEN
+                    || bytes[..4] != MAGIC
+                    || bytes[4] != VERSION
+                    || usize::from(bytes[5]) != mem::size_of::<$t>()
+                    || bytes[7] != 0
+                {
+                    return Err(BufferError::Header);
+                }
+                let flags = bytes[6];
+                let len = u64::from_le_bytes(bytes[8..HEADER_LEN].try_into().unwrap());
+                let payload = &bytes[HEADER_LEN..];
+                if (payload.len() / mem::size_of::<$t>()) as u64 != len {
+                    return Err(LengthError.into());
+                }
+                Self::decode(payload, Endianness::Little)
+                    .map(|proxy| match proxy {
+                        Ok(proxy) => {
+                            if is_member(proxy.into_inner(), flags) {
+                                Ok(proxy)
+                            }
+                            else {
+                                Err(BufferError::Metadata)
+                            }
+                        }
+                        Err(DecodeError::Length(error)) => Err(error.into()),
+                        Err(DecodeError::Constraint(error)) => Err(BufferError::Constraint(error)),
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+impl_buffer_for_proxy!();
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::proxy::BufferError;
+    use crate::InfinityEncoding;
+    use crate::{ExtendedReal, Total, R32, R64};
+
+    #[test]
+    fn as_pod_slice() {
+        let xs = [<R64>::assert(1.0), <R64>::assert(2.0)];
+        assert_eq!(<R64>::as_pod_slice(&xs), &[1.0, 2.0]);
+    }
+
+    #[test]
+    fn byte_buffer_round_trip() {
+        let xs = [<R32>::assert(1.0), <R32>::assert(-2.5)];
+        let buffer = <R32>::into_byte_buffer(&xs);
+        assert_eq!(&buffer[..8], b"DCRM\x01\x04\x00\x00");
+        assert_eq!(&buffer[8..16], &2u64.to_le_bytes());
+        assert_eq!(&buffer[16..20], &1.0f32.to_le_bytes());
+        assert_eq!(<R32>::try_from_byte_buffer(&buffer).unwrap(), xs);
+
+        let buffer = ExtendedReal::<f64>::into_byte_buffer(&[ExtendedReal::<f64>::INFINITY]);
+        assert_eq!(buffer[6], 0b10);
+        assert!(Total::<f64>::try_from_byte_buffer(&buffer).is_ok());
+    }
+
+    #[test]
+    fn byte_buffer_invalid() {
+        let buffer = <R32>::into_byte_buffer(&[<R32>::assert(1.0)]);
+        assert!(matches!(
+            <R64>::try_from_byte_buffer(&buffer),
+            Err(BufferError::Header)
+        ));
+        assert!(matches!(
+            <R32>::try_from_byte_buffer(&buffer[..19]),
+            Err(BufferError::Length(_))
+        ));
+
+        // The header asserts that values are finite, but the value is `NaN`.
+        let mut buffer = buffer;
+        buffer[16..].copy_from_slice(&f32::NAN.to_le_bytes());
+        assert!(matches!(
+            Total::<f32>::try_from_byte_buffer(&buffer),
+            Err(BufferError::Metadata)
+        ));
+        assert!(matches!(
+            <R32>::try_from_byte_buffer(&buffer),
+            Err(BufferError::Constraint(_))
+        ));
+    }
+}
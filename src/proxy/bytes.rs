@@ -0,0 +1,253 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::slice::ChunksExact;
+
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::proxy::constrained::{Constrained, OutputFor};
+use crate::with_primitives;
+
+/// Byte order of encoded IEEE 754 floating-point values.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endianness {
+    Big,
+    Little,
+    /// The byte order of the target platform.
+    Native,
+}
+
+#[derive(Clone, Copy, Debug, Error)]
+#[error("{}", "byte length does not match encoding")]
+pub struct LengthError;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum DecodeError<E> {
+    #[error("{0}")]
+    Length(LengthError),
+    #[error("{0}")]
+    Constraint(E),
+}
+
+impl<E> From<LengthError> for DecodeError<E> {
+    fn from(error: LengthError) -> Self {
+        DecodeError::Length(error)
+    }
+}
+
+/// Iterator over [`Constrained`] values decoded from a byte slice.
+///
+/// See [`Constrained::decode`].
+pub struct Decode<'a, T, C> {
+    chunks: ChunksExact<'a, u8>,
+    endianness: Endianness,
+    phantom: PhantomData<fn() -> Constrained<T, C>>,
+}
+
+macro_rules! impl_bytes_for_proxy {
+    () => {
+        with_primitives!(impl_bytes_for_proxy);
+    };
+    (primitive => $t:ident) => {
+        impl<C> Constrained<$t, C>
+        where
+            C: Constraint,
+        {
+            /// Constructs a proxy from its representation as a byte array in little-endian byte
+            /// order.
+            ///
+            /// The decoded value is checked against the constraints of the proxy and the output
+            /// type is determined by its [divergence][`divergence`].
+            ///
+            /// [`divergence`]: crate::divergence
+            pub fn from_le_bytes(bytes: [u8; mem::size_of::<$t>()]) -> OutputFor<Self> {
+                Self::new($t::from_le_bytes(bytes))
+            }
+
+            /// Constructs a proxy from its representation as a byte array in big-endian byte
+            /// order.
+            ///
+            /// The decoded value is checked against the constraints of the proxy and the output
+            /// type is determined by its [divergence][`divergence`].
+            ///
+            /// [`divergence`]: crate::divergence
+            pub fn from_be_bytes(bytes: [u8; mem::size_of::<$t>()]) -> OutputFor<Self> {
+                Self::new($t::from_be_bytes(bytes))
+            }
+
+            /// Constructs a proxy from its representation as a byte array in native byte order.
+            ///
+            /// The decoded value is checked against the constraints of the proxy and the output
+            /// type is determined by its [divergence][`divergence`].
+            ///
+            /// [`divergence`]: crate::divergence
+            pub fn from_ne_bytes(bytes: [u8; mem::size_of::<$t>()]) -> OutputFor<Self> {
+                Self::new($t::from_ne_bytes(bytes))
+            }
+
+            /// Fallibly constructs a proxy from its representation as a byte slice in the given
+            /// byte order.
+            ///
+            /// Like [`try_new`], this construction is independent of the divergence of the proxy.
+            ///
+            /// # Errors
+            ///
+            /// Returns an error if the length of the slice is not the size of the primitive
+            /// floating-point type or if the decoded value does not satisfy the constraints of
+            /// the proxy.
+            ///
+            /// [`try_new`]: crate::proxy::Constrained::try_new
+            pub fn try_from_bytes(
+                bytes: &[u8],
+                endianness: Endianness,
+            ) -> Result<Self, DecodeError<C::Error>> {
+                let bytes = bytes.try_into().map_err(|_| LengthError)?;
+                let inner = match endianness {
+                    Endianness::Big => $t::from_be_bytes(bytes),
+                    Endianness::Little => $t::from_le_bytes(bytes),
+                    Endianness::Native => $t::from_ne_bytes(bytes),
+                };
+                Self::try_new(inner).map_err(DecodeError::Constraint)
+            }
+
+            /// Decodes proxies from a byte slice of contiguous values in the given byte order.
+            ///
+            /// Each decoded value is checked against the constraints of the proxy. If the length
+            /// of the slice is not a multiple of the size of the primitive floating-point type,
+            /// then the final item of the iterator is a [`LengthError`].
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use decorum::proxy::Endianness;
+            /// use decorum::R32;
+            ///
+            /// let bytes = [0, 0, 128, 63, 0, 0, 0, 64];
+            /// let xs: Vec<R32> = <R32>::decode(&bytes, Endianness::Little)
+            ///     .collect::<Result<_, _>>()
+            ///     .unwrap();
+            /// assert_eq!(xs, [1.0, 2.0]);
+            /// ```
+            pub fn decode(bytes: &[u8], endianness: Endianness) -> Decode<'_, $t, C> {
+                Decode {
+                    chunks: bytes.chunks_exact(mem::size_of::<$t>()),
+                    endianness,
+                    phantom: PhantomData,
+                }
+            }
+
+            pub fn to_le_bytes(self) -> [u8; mem::size_of::<$t>()] {
+                self.into_inner().to_le_bytes()
+            }
+
+            pub fn to_be_bytes(self) -> [u8; mem::size_of::<$t>()] {
+                self.into_inner().to_be_bytes()
+            }
+
+            pub fn to_ne_bytes(self) -> [u8; mem::size_of::<$t>()] {
+                self.into_inner().to_ne_bytes()
+            }
+        }
+
+        impl<C> TryFrom<&'_ [u8]> for Constrained<$t, C>
+        where
+            C: Constraint,
+        {
+            type Error = DecodeError<C::Error>;
+
+            /// Constructs a proxy from its representation as a byte slice in native byte order.
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                Self::try_from_bytes(bytes, Endianness::Native)
+            }
+        }
+
+        impl<'a, C> Iterator for Decode<'a, $t, C>
+        where
+            C: Constraint,
+        {
+            type Item = Result<Constrained<$t, C>, DecodeError<C::Error>>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                match self.chunks.next() {
+                    Some(bytes) => {
+                        Some(Constrained::<$t, C>::try_from_bytes(bytes, self.endianness))
+                    }
+                    _ => {
+                        let remainder = self.chunks.remainder();
+                        if remainder.is_empty() {
+                            None
+                        }
+                        else {
+                            // Yield the error only once.
+                            self.chunks = remainder[remainder.len()..].chunks_exact(1);
+                            Some(Err(LengthError.into()))
+                        }
+                    }
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.chunks.len() + usize::from(!self.chunks.remainder().is_empty());
+                (n, Some(n))
+            }
+        }
+
+        impl<'a, C> ExactSizeIterator for Decode<'a, $t, C> where C: Constraint {}
+
+        impl<'a, C> FusedIterator for Decode<'a, $t, C> where C: Constraint {}
+    };
+}
+impl_bytes_for_proxy!();
+
+#[cfg(test)]
+mod tests {
+    use crate::proxy::{DecodeError, Endianness};
+    use crate::{NanEncoding, Total, R32, R64};
+
+    #[test]
+    fn bytes_round_trip() {
+        let x = <R64>::assert(-1.5);
+        assert_eq!(<R64>::from_le_bytes(x.to_le_bytes()), x);
+        assert_eq!(<R64>::from_be_bytes(x.to_be_bytes()), x);
+        assert_eq!(<R64>::from_ne_bytes(x.to_ne_bytes()), x);
+        assert_eq!(x.to_be_bytes(), (-1.5f64).to_be_bytes());
+        assert_eq!(
+            <R64>::try_from(&x.to_ne_bytes()[..]).unwrap(),
+            R64::assert(-1.5)
+        );
+    }
+
+    #[test]
+    fn bytes_invalid() {
+        let nan = f32::NAN.to_le_bytes();
+        assert!(matches!(
+            <R32>::try_from_bytes(&nan, Endianness::Little),
+            Err(DecodeError::Constraint(_))
+        ));
+        assert!(matches!(
+            <R32>::try_from_bytes(&nan[..3], Endianness::Little),
+            Err(DecodeError::Length(_))
+        ));
+        assert!(Total::<f32>::try_from_bytes(&nan, Endianness::Little)
+            .unwrap()
+            .is_nan());
+    }
+
+    #[test]
+    fn decode() {
+        let bytes = [0x3f, 0x80, 0, 0, 0x40, 0, 0, 0, 0xff];
+        let mut xs = <R32>::decode(&bytes, Endianness::Big);
+        assert_eq!(xs.len(), 3);
+        assert_eq!(xs.next().unwrap().unwrap(), 1.0);
+        assert_eq!(xs.next().unwrap().unwrap(), 2.0);
+        assert!(matches!(xs.next(), Some(Err(DecodeError::Length(_)))));
+        assert!(xs.next().is_none());
+
+        let bytes = [0, 0, 0x80, 0x7f];
+        assert!(matches!(
+            <R32>::decode(&bytes, Endianness::Little).next(),
+            Some(Err(DecodeError::Constraint(_)))
+        ));
+    }
+}
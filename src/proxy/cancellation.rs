@@ -0,0 +1,188 @@
+use core::num::FpCategory;
+
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::divergence::{self, Divergence};
+use crate::proxy::constrained::Constrained;
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum CancellationError<E> {
+    /// The output of the operation lost more significant bits than the threshold.
+    #[error("catastrophic cancellation of {bits} significant bits")]
+    Cancellation { bits: u32 },
+    #[error("{0}")]
+    Constraint(E),
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Adds proxies and detects catastrophic cancellation.
+    ///
+    /// See [`Constrained::sub_checked_cancellation`].
+    pub fn add_checked_cancellation(
+        self,
+        other: Self,
+        threshold: u32,
+    ) -> divergence::OutputFor<C::Divergence, Self, CancellationError<C::Error>> {
+        let (left, right) = (self.into_inner(), other.into_inner());
+        C::Divergence::diverge(checked_cancellation(left, right, left + right, threshold))
+    }
+
+    /// Subtracts proxies and detects catastrophic cancellation.
+    ///
+    /// Cancellation occurs when the exponent of the output is far below the exponents of the
+    /// operands, which loses significant bits of the operands. The number of lost bits
+    /// approximates the base 2 logarithm of the condition number of the operation. If the output
+    /// is zero but the operands are not, then all significant bits are lost.
+    ///
+    /// If more than `threshold` bits are lost or the output does not satisfy the constraints of
+    /// the proxy, then the operation diverges with a [`CancellationError`]. The output type is
+    /// determined by the [divergence][`divergence`] of the proxy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::proxy::CancellationError;
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::Real;
+    ///
+    /// type R64 = Real<f64, OrError>;
+    ///
+    /// let x = R64::assert(1.0 + 1.0e-12);
+    /// assert!(matches!(
+    ///     x.sub_checked_cancellation(R64::ONE, 32).undefined(),
+    ///     Some(CancellationError::Cancellation { bits: 40 }),
+    /// ));
+    /// assert!(R64::assert(3.0)
+    ///     .sub_checked_cancellation(R64::ONE, 32)
+    ///     .is_defined());
+    /// ```
+    ///
+    /// [`divergence`]: crate::divergence
+    pub fn sub_checked_cancellation(
+        self,
+        other: Self,
+        threshold: u32,
+    ) -> divergence::OutputFor<C::Divergence, Self, CancellationError<C::Error>> {
+        let (left, right) = (self.into_inner(), other.into_inner());
+        C::Divergence::diverge(checked_cancellation(left, right, left - right, threshold))
+    }
+}
+
+fn checked_cancellation<T, C>(
+    left: T,
+    right: T,
+    output: T,
+    threshold: u32,
+) -> Result<Constrained<T, C>, CancellationError<C::Error>>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let output = Constrained::try_new(output).map_err(CancellationError::Constraint)?;
+    let bits = cancelled_bits(left, right, output.into_inner());
+    if bits > threshold {
+        Err(CancellationError::Cancellation { bits })
+    }
+    else {
+        Ok(output)
+    }
+}
+
+// Gets the number of significant bits lost by an addition or subtraction of the operands.
+fn cancelled_bits<T>(left: T, right: T, output: T) -> u32
+where
+    T: Primitive,
+{
+    let operand = match (exponent(left), exponent(right)) {
+        (Some(left), Some(right)) => left.max(right),
+        // There is no cancellation if an operand is zero or is not finite.
+        _ => return 0,
+    };
+    if output.classify() == FpCategory::Zero {
+        precision::<T>()
+    }
+    else {
+        exponent(output).map_or(0, |output| (operand - output).max(0) as u32)
+    }
+}
+
+// Gets the exponent of the leading significant bit of a non-zero finite value.
+fn exponent<T>(x: T) -> Option<i32>
+where
+    T: Primitive,
+{
+    match x.classify() {
+        FpCategory::Normal | FpCategory::Subnormal => {
+            let (mantissa, exponent, _) = x.integer_decode();
+            Some(i32::from(exponent) + (63 - mantissa.leading_zeros() as i32))
+        }
+        _ => None,
+    }
+}
+
+fn precision<T>() -> u32
+where
+    T: Primitive,
+{
+    let (mantissa, _, _) = T::ONE.integer_decode();
+    64 - mantissa.leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::divergence::{AsResult, OrError};
+    use crate::proxy::CancellationError;
+    use crate::real::UnaryRealFunction;
+    use crate::{Real, Total, R64};
+
+    type R64Result = Real<f64, OrError<AsResult>>;
+
+    #[test]
+    fn sub_cancellation() {
+        let x = R64Result::assert(1.0 + f64::EPSILON);
+        assert!(matches!(
+            x.sub_checked_cancellation(R64Result::ONE, 51),
+            Err(CancellationError::Cancellation { bits: 52 })
+        ));
+        assert!(x.sub_checked_cancellation(R64Result::ONE, 52).is_ok());
+        assert!(matches!(
+            x.sub_checked_cancellation(x, 52),
+            Err(CancellationError::Cancellation { bits: 53 })
+        ));
+        assert!(R64Result::ONE
+            .sub_checked_cancellation(R64Result::ZERO, 0)
+            .is_ok());
+        assert!(matches!(
+            R64Result::assert(f64::MAX).sub_checked_cancellation(R64Result::assert(-f64::MAX), 8),
+            Err(CancellationError::Constraint(_))
+        ));
+    }
+
+    #[test]
+    fn add_cancellation() {
+        let x = <R64>::assert(1.0e8);
+        assert_eq!(
+            x.add_checked_cancellation(<R64>::assert(-1.0e8 + 1.0), 32),
+            <R64>::ONE
+        );
+        let x = Total::from(1.0f64);
+        assert!(x
+            .add_checked_cancellation(Total::from(f64::NEG_INFINITY), 0)
+            .into_inner()
+            .is_infinite());
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_cancellation_panic() {
+        let x = <R64>::assert(1.0e8);
+        let _ = x.add_checked_cancellation(<R64>::assert(-1.0e8 + 1.0), 16);
+    }
+}
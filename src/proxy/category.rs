@@ -0,0 +1,191 @@
+use core::fmt::{self, Debug, Formatter};
+use core::num::FpCategory;
+
+use crate::constraint::Constraint;
+use crate::proxy::constrained::Constrained;
+use crate::{BaseEncoding, Primitive};
+
+/// A primitive floating-point value partitioned by its IEEE 754 [category][`FpCategory`].
+///
+/// Zeros, subnormals, and normals are real numbers and so are members of every constraint, so
+/// these variants always hold a [`Constrained`] value. Infinities and `NaN`s are checked against
+/// the constraint and these variants hold either a [`Constrained`] value or the constraint's error.
+///
+/// See [`Constrained::from_category_checked`] and [`Constrained::classify_checked`].
+pub enum Categorized<T, C>
+where
+    C: Constraint,
+{
+    Zero(Constrained<T, C>),
+    Subnormal(Constrained<T, C>),
+    Normal(Constrained<T, C>),
+    Infinite(Result<Constrained<T, C>, C::Error>),
+    Nan(Result<Constrained<T, C>, C::Error>),
+}
+
+impl<T, C> Categorized<T, C>
+where
+    C: Constraint,
+{
+    /// Gets the category of the value.
+    pub fn category(&self) -> FpCategory {
+        match self {
+            Categorized::Zero(_) => FpCategory::Zero,
+            Categorized::Subnormal(_) => FpCategory::Subnormal,
+            Categorized::Normal(_) => FpCategory::Normal,
+            Categorized::Infinite(_) => FpCategory::Infinite,
+            Categorized::Nan(_) => FpCategory::Nan,
+        }
+    }
+
+    /// Converts the value into a [`Constrained`] or the constraint's error, discarding its category.
+    pub fn into_result(self) -> Result<Constrained<T, C>, C::Error> {
+        match self {
+            Categorized::Zero(proxy)
+            | Categorized::Subnormal(proxy)
+            | Categorized::Normal(proxy) => Ok(proxy),
+            Categorized::Infinite(result) | Categorized::Nan(result) => result,
+        }
+    }
+}
+
+impl<T, C> Clone for Categorized<T, C>
+where
+    T: Clone,
+    C: Constraint,
+    C::Error: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Categorized::Zero(proxy) => Categorized::Zero(proxy.clone()),
+            Categorized::Subnormal(proxy) => Categorized::Subnormal(proxy.clone()),
+            Categorized::Normal(proxy) => Categorized::Normal(proxy.clone()),
+            Categorized::Infinite(result) => Categorized::Infinite(result.clone()),
+            Categorized::Nan(result) => Categorized::Nan(result.clone()),
+        }
+    }
+}
+
+impl<T, C> Copy for Categorized<T, C>
+where
+    T: Copy,
+    C: Constraint,
+    C::Error: Copy,
+{
+}
+
+impl<T, C> Debug for Categorized<T, C>
+where
+    Constrained<T, C>: Debug,
+    C: Constraint,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Categorized::Zero(proxy) => formatter.debug_tuple("Zero").field(proxy).finish(),
+            Categorized::Subnormal(proxy) => {
+                formatter.debug_tuple("Subnormal").field(proxy).finish()
+            }
+            Categorized::Normal(proxy) => formatter.debug_tuple("Normal").field(proxy).finish(),
+            Categorized::Infinite(result) => {
+                formatter.debug_tuple("Infinite").field(result).finish()
+            }
+            Categorized::Nan(result) => formatter.debug_tuple("Nan").field(result).finish(),
+        }
+    }
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Constructs a proxy from a primitive IEEE 754 floating-point value and partitions it by its
+    /// [category][`FpCategory`].
+    ///
+    /// This function never diverges. Infinities and `NaN`s that are not members of the constraint
+    /// are reported as errors in their category, which allows untrusted values to be handled per
+    /// category with a single `match`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::proxy::Categorized;
+    /// use decorum::R64;
+    ///
+    /// fn ingest(x: f64) -> Option<R64> {
+    ///     match R64::from_category_checked(x) {
+    ///         Categorized::Zero(x) | Categorized::Normal(x) => Some(x),
+    ///         // Flush subnormals to zero.
+    ///         Categorized::Subnormal(x) => Some(R64::assert(0.0f64.copysign(x.into_inner()))),
+    ///         Categorized::Infinite(_) | Categorized::Nan(_) => None,
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(ingest(1.0e-310), Some(R64::assert(0.0)));
+    /// assert_eq!(ingest(f64::NAN), None);
+    /// ```
+    pub fn from_category_checked(inner: T) -> Categorized<T, C> {
+        match BaseEncoding::classify(inner) {
+            FpCategory::Zero => Categorized::Zero(Constrained::unchecked(inner)),
+            FpCategory::Subnormal => Categorized::Subnormal(Constrained::unchecked(inner)),
+            FpCategory::Normal => Categorized::Normal(Constrained::unchecked(inner)),
+            FpCategory::Infinite => Categorized::Infinite(Constrained::try_new(inner)),
+            FpCategory::Nan => Categorized::Nan(Constrained::try_new(inner)),
+        }
+    }
+
+    /// Partitions the proxy by its [category][`FpCategory`].
+    ///
+    /// The `Infinite` and `Nan` variants of the output are never errors, because the proxy is
+    /// already a member of its constraint.
+    pub fn classify_checked(self) -> Categorized<T, C> {
+        Self::from_category_checked(self.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::FpCategory;
+
+    use crate::proxy::Categorized;
+    use crate::{ExtendedReal, Total, R64};
+
+    #[test]
+    fn from_category_checked() {
+        assert!(matches!(
+            <R64>::from_category_checked(-0.0),
+            Categorized::Zero(_)
+        ));
+        assert!(matches!(
+            <R64>::from_category_checked(1.0e-310),
+            Categorized::Subnormal(_)
+        ));
+        assert!(matches!(
+            <R64>::from_category_checked(f64::INFINITY),
+            Categorized::Infinite(Err(_))
+        ));
+        assert!(matches!(
+            ExtendedReal::<f64>::from_category_checked(f64::INFINITY),
+            Categorized::Infinite(Ok(_))
+        ));
+        assert!(matches!(
+            Total::<f64>::from_category_checked(f64::NAN),
+            Categorized::Nan(Ok(_))
+        ));
+        assert_eq!(
+            <R64>::from_category_checked(f64::NAN).category(),
+            FpCategory::Nan
+        );
+        assert!(<R64>::from_category_checked(f64::NAN)
+            .into_result()
+            .is_err());
+        assert_eq!(
+            <R64>::from_category_checked(2.0).into_result().ok(),
+            Some(R64::assert(2.0))
+        );
+        assert!(matches!(
+            <R64>::assert(2.0).classify_checked(),
+            Categorized::Normal(_)
+        ));
+    }
+}
@@ -1,11 +1,13 @@
 #[cfg(feature = "approx")]
 use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+#[cfg(feature = "bytemuck")]
+use bytemuck::{CheckedBitPattern, NoUninit, Pod, TransparentWrapper, Zeroable};
 use core::cmp::Ordering;
-use core::fmt::{self, Debug, Display, Formatter, LowerExp, UpperExp};
+use core::fmt::{self, Debug, Display, Formatter, LowerExp, UpperExp, Write};
 use core::hash::{Hash, Hasher};
 use core::iter::{Product, Sum};
 use core::marker::PhantomData;
-use core::mem;
+use core::mem::{self, MaybeUninit};
 use core::num::FpCategory;
 use core::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
@@ -20,25 +22,43 @@ use num_traits::{
 };
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::format;
+#[cfg(feature = "std")]
+use std::string::String;
+use thiserror::Error;
 
-use crate::cmp::{CanonicalEq, CanonicalOrd, EmptyInhabitant, EmptyOrd};
+use crate::bits::Bits;
+#[cfg(feature = "std")]
+use crate::cmp::{self, UlpKey};
+use crate::cmp::{CanonicalEq, CanonicalOrd, EmptyInhabitant, EmptyOrd, PartialDecision};
 use crate::constraint::{
     Constraint, ExpectConstrained, InfinitySet, IsExtendedReal, IsFloat, IsReal, Member, NanSet,
-    SubsetOf, SupersetOf,
+    SubsetOf, SupersetOf, WithDivergence,
 };
 use crate::divergence::{self, Divergence, NonResidual};
 use crate::expression::Expression;
 use crate::hash::CanonicalHash;
+use crate::literal;
 use crate::proxy::Proxy;
 #[cfg(feature = "serde")]
 use crate::proxy::Serde;
 use crate::real::{BinaryRealFunction, Function, Sign, UnaryRealFunction};
 use crate::sealed::StaticDebug;
+use crate::trace::{self, Operand, Operation, Origin};
 use crate::{
     with_binary_operations, with_primitives, BaseEncoding, ExtendedReal, InfinityEncoding,
     NanEncoding, Primitive, Real, ToCanonical, Total,
 };
 
+#[derive(Clone, Copy, Debug, Error)]
+pub enum CanonicalStringError<E> {
+    #[error("{}", "string is not a canonical floating-point representation")]
+    NotCanonical,
+    #[error("{0}")]
+    Constraint(E),
+}
+
 pub type OutputFor<P> = divergence::OutputFor<DivergenceFor<P>, P, ErrorFor<P>>;
 pub type ConstraintFor<P> = <P as ConstrainedProxy>::Constraint;
 pub type DivergenceFor<P> = <ConstraintFor<P> as Constraint>::Divergence;
@@ -125,12 +145,32 @@ where
     pub const fn into_inner(self) -> T {
         self.inner
     }
+}
 
-    pub(crate) fn map_unchecked<F>(self, f: F) -> Self
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+{
+    // Delegated operations in implementations that are not bounded on `Constraint` use this
+    // function instead of `map_unchecked`. The constraint cannot be checked, so when the
+    // `strict-validate` feature is enabled the output is checked against the input in debug
+    // builds instead: every constraint admits the real numbers, so the output is a member of the
+    // constraint if it is real or if it is in the same class (infinite or `NaN`) as the input.
+    pub(crate) fn map_class_preserving<F>(self, f: F) -> Self
     where
         F: FnOnce(T) -> T,
     {
-        Constrained::unchecked(f(self.into_inner()))
+        #[cfg(all(debug_assertions, feature = "strict-validate"))]
+        let input = self.into_inner();
+        let inner = f(self.into_inner());
+        #[cfg(all(debug_assertions, feature = "strict-validate"))]
+        if !(inner.is_finite()
+            || (inner.is_infinite() && input.is_infinite())
+            || (inner.is_nan() && input.is_nan()))
+        {
+            panic!("delegated operation violated constraint");
+        }
+        Constrained::unchecked(inner)
     }
 }
 
@@ -388,6 +428,51 @@ where
         Ok(unsafe { mem::transmute::<&'a mut [T], &'a mut [Self]>(slice) })
     }
 
+    /// Initializes a buffer of proxies with the primitive floating-point values of a function of
+    /// the index of each element.
+    ///
+    /// Each value is checked against the constraints of the proxy as it is written, so large
+    /// buffers can be initialized without first initializing the buffer with placeholder values
+    /// nor allocating intermediate buffers of primitive floating-point values.
+    ///
+    /// # Errors
+    ///
+    /// Returns the index and error of the first value that does not satisfy the constraints of the
+    /// proxy. In this case, the elements before this index have been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::mem::MaybeUninit;
+    /// use decorum::R64;
+    ///
+    /// let mut buffer = [MaybeUninit::uninit(); 4];
+    /// let xs = <R64>::fill_uninit(&mut buffer, |i| i as f64 / 2.0).unwrap();
+    /// assert_eq!(xs, [0.0, 0.5, 1.0, 1.5]);
+    ///
+    /// let mut buffer = [MaybeUninit::uninit(); 4];
+    /// assert_eq!(
+    ///     <R64>::fill_uninit(&mut buffer, |i| 1.0 / i as f64)
+    ///         .err()
+    ///         .map(|(index, _)| index),
+    ///     Some(0),
+    /// );
+    /// ```
+    pub fn fill_uninit<F>(
+        buffer: &mut [MaybeUninit<Self>],
+        mut f: F,
+    ) -> Result<&mut [Self], (usize, C::Error)>
+    where
+        F: FnMut(usize) -> T,
+    {
+        for (index, element) in buffer.iter_mut().enumerate() {
+            element.write(Self::try_new(f(index)).map_err(|error| (index, error))?);
+        }
+        // SAFETY: Every element of `buffer` has been initialized and `MaybeUninit<Self>` has the
+        //         same binary representation as `Self`.
+        Ok(unsafe { &mut *(buffer as *mut [MaybeUninit<Self>] as *mut [Self]) })
+    }
+
     /// Converts a proxy into another proxy that is capable of representing a superset of its
     /// values per its constraint.
     ///
@@ -427,6 +512,34 @@ where
         Constrained::unchecked(self.into_inner())
     }
 
+    /// Converts a proxy into a proxy with the same constraint but a different [divergence].
+    ///
+    /// The representation and set of values of the proxy are unchanged, so this conversion is
+    /// free. It can be used to override the divergence of a proxy for a particular operation.
+    ///
+    /// # Examples
+    ///
+    /// Panicking rather than returning an [`Expression`] for a division that is known to be
+    /// defined:
+    ///
+    /// ```rust
+    /// use decorum::divergence::{OrError, OrPanic};
+    /// use decorum::real::UnaryRealFunction;
+    /// use decorum::R64;
+    ///
+    /// let x = R64::<OrError>::ONE;
+    /// let y: R64<OrError> = (x.with_divergence::<OrPanic>() / R64::ONE).with_divergence();
+    /// ```
+    ///
+    /// [divergence]: crate::divergence
+    pub fn with_divergence<D>(self) -> Constrained<T, C::Constraint>
+    where
+        C: WithDivergence<D>,
+        D: Divergence,
+    {
+        Constrained::unchecked(self.into_inner())
+    }
+
     /// Converts a proxy into its corresponding [`Expression`].
     ///
     /// The output of this function is always the [`Defined`] variant.
@@ -436,6 +549,18 @@ where
         Expression::from(self)
     }
 
+    /// Compares the proxy with the defined value of an [`Expression`].
+    ///
+    /// The output is [`PartialDecision::Undefined`] if the expression is undefined.
+    pub fn cmp_defined<E>(&self, other: &Expression<Self, E>) -> PartialDecision {
+        other
+            .as_ref()
+            .defined()
+            .map_or(PartialDecision::Undefined, |other| {
+                self.partial_cmp(other).into()
+            })
+    }
+
     pub(crate) fn map<F>(self, f: F) -> OutputFor<Self>
     where
         F: FnOnce(T) -> T,
@@ -443,6 +568,21 @@ where
         Self::new(f(self.into_inner()))
     }
 
+    // Delegated operations that cannot violate constraints use this function to bypass checks and
+    // divergence. When the `strict-validate` feature is enabled, the output is checked anyway in
+    // debug builds, which catches operations that are incorrectly assumed to preserve constraints.
+    pub(crate) fn map_unchecked<F>(self, f: F) -> Self
+    where
+        F: FnOnce(T) -> T,
+    {
+        let inner = f(self.into_inner());
+        #[cfg(all(debug_assertions, feature = "strict-validate"))]
+        if let Err(error) = C::check(inner) {
+            panic!("delegated operation violated constraint: {}", error);
+        }
+        Constrained::unchecked(inner)
+    }
+
     pub(crate) fn zip_map<C2, F>(self, other: Constrained<T, C2>, f: F) -> OutputFor<Self>
     where
         C2: Constraint,
@@ -450,6 +590,215 @@ where
     {
         Self::new(f(self.into_inner(), other.into_inner()))
     }
+
+    // Arithmetic operations use these functions to record the origin of errors in their outputs.
+    // See the `trace` module.
+    pub(crate) fn map_traced<F>(self, operation: Operation, f: F) -> OutputFor<Self>
+    where
+        F: FnOnce(T) -> T,
+    {
+        Self::new_traced(f(self.into_inner()), operation)
+    }
+
+    pub(crate) fn zip_map_traced<C2, F>(
+        self,
+        other: Constrained<T, C2>,
+        operation: Operation,
+        f: F,
+    ) -> OutputFor<Self>
+    where
+        C2: Constraint,
+        F: FnOnce(T, T) -> T,
+    {
+        let output = f(self.into_inner(), other.into_inner());
+        #[cfg(all(debug_assertions, feature = "debug-precision"))]
+        crate::precision::check(operation, self.into_inner(), other.into_inner(), output);
+        Self::new_traced(output, operation)
+    }
+
+    fn new_traced(inner: T, operation: Operation) -> OutputFor<Self> {
+        C::Divergence::diverge(
+            Self::try_new(inner)
+                .map_err(|error| trace::trace(error, Origin::new(operation, Operand::Output))),
+        )
+    }
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Display + FromStr + LowerExp + Primitive,
+    C: Constraint,
+{
+    /// Rounds the proxy to the given number of significant decimal digits.
+    ///
+    /// Rounding is correct: the output is the floating-point value nearest to the exact decimal
+    /// value of the proxy rounded to `n` significant digits with ties to even. Note that this
+    /// decimal value is often not representable, so the output is an approximation of it. Rounding
+    /// may overflow, in which case the output may not satisfy the constraints of the proxy and
+    /// the [divergence] of the proxy determines the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero **or** if the output does not satisfy the constraints of the proxy
+    /// and the [divergence] of the proxy panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::R64;
+    ///
+    /// let x: R64 = R64::assert(1234.5678);
+    /// assert_eq!(x.round_sig_figs(3), 1230.0);
+    /// assert_eq!(x.round_sig_figs(6), 1234.57);
+    /// ```
+    pub fn round_sig_figs(self, n: u32) -> OutputFor<Self> {
+        // More than 17 significant digits can be formatted, but no binary64 or smaller value is
+        // affected by rounding to more than 17 digits.
+        const MAX_SIG_FIGS: u32 = 17;
+
+        assert!(n > 0, "number of significant figures must be nonzero");
+        self.map(|inner| {
+            if inner.is_finite() {
+                let mut buffer = StackBuffer::<32>::default();
+                // The precision of exponential formatting is the number of fractional digits in
+                // the mantissa and so is one less than the number of significant digits.
+                write!(buffer, "{:.*e}", (n.min(MAX_SIG_FIGS) - 1) as usize, inner)
+                    .expect("formatted floating-point value exceeds buffer");
+                buffer
+                    .as_str()
+                    .parse()
+                    .ok()
+                    .expect("failed to parse formatted floating-point value")
+            }
+            else {
+                inner
+            }
+        })
+    }
+
+    /// Formats the proxy as a canonical string.
+    ///
+    /// The canonical string of a value is the shortest scientific notation that converts back to
+    /// the same value, such as `1.5e-3`. Canonical strings are independent of locale and platform
+    /// and follow the equivalence relation of [`CanonicalEq`]: zeroes are formatted as `0e0`
+    /// regardless of sign, all `NaN`s are formatted as `NaN`, and infinities are formatted as
+    /// `inf` and `-inf`. This makes canonical strings suitable for content hashes and reproducible
+    /// exports.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::Total;
+    ///
+    /// assert_eq!(Total::<f64>::from(-0.0).to_canonical_string(), "0e0");
+    /// assert_eq!(Total::<f64>::from(1500.0).to_canonical_string(), "1.5e3");
+    /// assert_eq!(Total::<f64>::from(f64::NAN).to_canonical_string(), "NaN");
+    /// ```
+    ///
+    /// [`CanonicalEq`]: crate::cmp::CanonicalEq
+    #[cfg(feature = "std")]
+    pub fn to_canonical_string(&self) -> String {
+        let mut buffer = StackBuffer::<32>::default();
+        self.write_canonical(&mut buffer)
+            .expect("formatted floating-point value exceeds buffer");
+        String::from(buffer.as_str())
+    }
+
+    /// Parses a proxy from a canonical string.
+    ///
+    /// This function is the inverse of `to_canonical_string` and only accepts canonical strings.
+    /// For example, `1.5e3` is accepted but `1500` and `1.50e3` are not.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a canonical string or if the value does not satisfy
+    /// the constraints of the proxy.
+    pub fn from_canonical_string(string: &str) -> Result<Self, CanonicalStringError<C::Error>> {
+        let inner: T = match string {
+            "NaN" => T::NAN.into_inner(),
+            "inf" => T::INFINITY,
+            "-inf" => T::NEG_INFINITY,
+            _ => string
+                .parse()
+                .map_err(|_| CanonicalStringError::NotCanonical)?,
+        };
+        let proxy = Constrained::try_new(inner).map_err(CanonicalStringError::Constraint)?;
+        let mut buffer = StackBuffer::<32>::default();
+        proxy
+            .write_canonical(&mut buffer)
+            .map_err(|_| CanonicalStringError::NotCanonical)?;
+        if buffer.as_str() == string {
+            Ok(proxy)
+        }
+        else {
+            Err(CanonicalStringError::NotCanonical)
+        }
+    }
+
+    fn write_canonical<W>(&self, buffer: &mut W) -> fmt::Result
+    where
+        W: Write,
+    {
+        let inner = self.into_inner();
+        if inner.is_nan() {
+            write!(buffer, "NaN")
+        }
+        else if inner.is_infinite() {
+            write!(buffer, "{}", inner)
+        }
+        else if inner.is_zero() {
+            write!(buffer, "0e0")
+        }
+        else {
+            write!(buffer, "{:e}", inner)
+        }
+    }
+
+    /// Formats the proxy in engineering notation.
+    ///
+    /// Engineering notation is scientific notation where the exponent is a multiple of three and
+    /// the mantissa is in the interval $[1, 1000)$. The mantissa uses the shortest representation
+    /// that converts back to the same value. Infinities and `NaN`s are formatted as by
+    /// [`Display`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::R64;
+    ///
+    /// let x: R64 = R64::assert(12345.0);
+    /// let y: R64 = R64::assert(-0.00047);
+    ///
+    /// assert_eq!(x.to_engineering_string(), "12.345e3");
+    /// assert_eq!(y.to_engineering_string(), "-470e-6");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_engineering_string(&self) -> String {
+        let inner = self.into_inner();
+        if !inner.is_finite() {
+            return format!("{}", inner);
+        }
+        let scientific = format!("{:e}", inner);
+        let (mantissa, exponent) = scientific.split_once('e').unwrap();
+        let exponent: i32 = exponent.parse().unwrap();
+        let (sign, mantissa) = match mantissa.strip_prefix('-') {
+            Some(mantissa) => ("-", mantissa),
+            _ => ("", mantissa),
+        };
+        let mut digits: String = mantissa.chars().filter(|digit| *digit != '.').collect();
+        let integer = 1 + exponent.rem_euclid(3) as usize;
+        while digits.len() < integer {
+            digits.push('0');
+        }
+        let (integer, fraction) = digits.split_at(integer);
+        let exponent = exponent - exponent.rem_euclid(3);
+        if fraction.is_empty() {
+            format!("{}{}e{}", sign, integer, exponent)
+        }
+        else {
+            format!("{}{}.{}e{}", sign, integer, fraction, exponent)
+        }
+    }
 }
 
 impl<T> Total<T>
@@ -510,7 +859,7 @@ where
     type Output = OutputFor<Self>;
 
     fn add(self, other: Self) -> Self::Output {
-        self.zip_map(other, Add::add)
+        self.zip_map_traced(other, Operation::Add, Add::add)
     }
 }
 
@@ -522,7 +871,7 @@ where
     type Output = OutputFor<Self>;
 
     fn add(self, other: T) -> Self::Output {
-        self.map(|inner| inner + other)
+        self.map_traced(Operation::Add, |inner| inner + other)
     }
 }
 
@@ -639,6 +988,36 @@ where
     }
 }
 
+// SAFETY: `Constrained` is `repr(transparent)` and the bit pattern is checked against the
+//         constraint.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, D> CheckedBitPattern for Constrained<T, IsExtendedReal<D>>
+where
+    T: Pod + Primitive,
+    D: 'static + Divergence,
+{
+    type Bits = T;
+
+    fn is_valid_bit_pattern(bits: &T) -> bool {
+        IsExtendedReal::<D>::check(*bits).is_ok()
+    }
+}
+
+// SAFETY: `Constrained` is `repr(transparent)` and the bit pattern is checked against the
+//         constraint.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, D> CheckedBitPattern for Constrained<T, IsReal<D>>
+where
+    T: Pod + Primitive,
+    D: 'static + Divergence,
+{
+    type Bits = T;
+
+    fn is_valid_bit_pattern(bits: &T) -> bool {
+        IsReal::<D>::check(*bits).is_ok()
+    }
+}
+
 impl<T, C> Clone for Constrained<T, C>
 where
     T: Clone,
@@ -729,7 +1108,7 @@ where
     type Output = OutputFor<Self>;
 
     fn div(self, other: Self) -> Self::Output {
-        self.zip_map(other, Div::div)
+        self.zip_map_traced(other, Operation::Div, Div::div)
     }
 }
 
@@ -741,7 +1120,7 @@ where
     type Output = OutputFor<Self>;
 
     fn div(self, other: T) -> Self::Output {
-        self.map(|inner| inner / other)
+        self.map_traced(Operation::Div, |inner| inner / other)
     }
 }
 
@@ -794,7 +1173,7 @@ where
 
     #[cfg(feature = "std")]
     fn signum(self) -> Self {
-        self.map_unchecked(|inner| inner.signum())
+        self.map_class_preserving(|inner| inner.signum())
     }
 
     fn integer_decode(self) -> (u64, i16, i8) {
@@ -1197,6 +1576,40 @@ where
     }
 }
 
+// Proxies with the same constraint but different divergence have the same representation and
+// set of values, so these conversions are free. Conversions between divergences of the same type
+// constructor (e.g., `OrPanic<AsSelf>` and `OrPanic<AsResult>`) would overlap with the reflexive
+// implementation of `From`; use `Constrained::with_divergence` instead.
+macro_rules! impl_from_for_divergence {
+    ($from:ident => $to:ident) => {
+        impl_from_for_divergence!(constraint => IsExtendedReal, $from => $to);
+        impl_from_for_divergence!(constraint => IsReal, $from => $to);
+    };
+    (constraint => $constraint:ident, $from:ident => $to:ident) => {
+        impl<T, K1, K2> From<Constrained<T, $constraint<divergence::$from<K1>>>>
+            for Constrained<T, $constraint<divergence::$to<K2>>>
+        where
+            T: Primitive,
+            divergence::$from<K1>: Divergence,
+            divergence::$to<K2>: Divergence,
+        {
+            fn from(other: Constrained<T, $constraint<divergence::$from<K1>>>) -> Self {
+                other.with_divergence()
+            }
+        }
+    };
+}
+impl_from_for_divergence!(OrError => OrPanic);
+impl_from_for_divergence!(OrPanic => OrError);
+#[cfg(feature = "trace")]
+impl_from_for_divergence!(OrError => OrTrace);
+#[cfg(feature = "trace")]
+impl_from_for_divergence!(OrPanic => OrTrace);
+#[cfg(feature = "trace")]
+impl_from_for_divergence!(OrTrace => OrError);
+#[cfg(feature = "trace")]
+impl_from_for_divergence!(OrTrace => OrPanic);
+
 impl<C> From<Constrained<f32, C>> for f32 {
     fn from(proxy: Constrained<f32, C>) -> Self {
         proxy.into_inner()
@@ -1284,16 +1697,28 @@ where
     }
 }
 
+/// Parses a proxy from a string.
+///
+/// In addition to the decimal spellings accepted by the primitive floating-point type (including
+/// `inf`, `-inf`, and `NaN`), hexadecimal floating-point literals like `0x1.8p+1` and `NaN`s with
+/// a payload like `nan:0x1234` are accepted, so that strings can specify exact values. See the
+/// [`literal`] module. The parsed value is checked against the constraint of the proxy.
+///
+/// [`literal`]: crate::literal
 impl<T, C, E> FromStr for Constrained<T, C>
 where
-    T: FromStr + Primitive,
+    T: Bits + FromStr + Primitive,
     C: Constraint<Error = E>,
     divergence::ContinueFor<C::Divergence>: NonResidual<Self, E>,
 {
     type Err = <T as FromStr>::Err;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        T::from_str(string).map(Self::new)
+        match literal::parse_bits(string) {
+            Some(inner) => Ok(Self::new(inner)),
+            // Malformed literals are also rejected by the primitive type.
+            _ => T::from_str(string).map(Self::new),
+        }
     }
 }
 
@@ -1377,7 +1802,7 @@ where
     type Output = OutputFor<Self>;
 
     fn mul(self, other: Self) -> Self::Output {
-        self.zip_map(other, Mul::mul)
+        self.zip_map_traced(other, Operation::Mul, Mul::mul)
     }
 }
 
@@ -1389,7 +1814,7 @@ where
     type Output = OutputFor<Self>;
 
     fn mul(self, other: T) -> Self::Output {
-        self.map(|a| a * other)
+        self.map_traced(Operation::Mul, |a| a * other)
     }
 }
 
@@ -1437,10 +1862,28 @@ where
 
     fn neg(self) -> Self::Output {
         // There is no constraint for which negating a value produces an invalid value.
-        Constrained::unchecked(-self.into_inner())
+        self.map_class_preserving(|inner| -inner)
     }
 }
 
+// SAFETY: `Constrained` is `repr(transparent)` and `T` has no uninitialized bytes.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, D> NoUninit for Constrained<T, IsExtendedReal<D>>
+where
+    T: NoUninit + Primitive,
+    D: 'static + Divergence,
+{
+}
+
+// SAFETY: `Constrained` is `repr(transparent)` and `T` has no uninitialized bytes.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, D> NoUninit for Constrained<T, IsReal<D>>
+where
+    T: NoUninit + Primitive,
+    D: 'static + Divergence,
+{
+}
+
 impl<T, C, E> Num for Constrained<T, C>
 where
     T: Num + Primitive,
@@ -1535,6 +1978,10 @@ where
     }
 }
 
+// SAFETY: `Constrained` is `repr(transparent)` and `IsFloat` accepts any bit pattern of `T`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T> Pod for Constrained<T, IsFloat> where T: Pod + Primitive {}
+
 impl<T, C, E> Product for Constrained<T, C>
 where
     T: Primitive,
@@ -1588,7 +2035,7 @@ where
     type Output = OutputFor<Self>;
 
     fn rem(self, other: Self) -> Self::Output {
-        self.zip_map(other, Rem::rem)
+        self.zip_map_traced(other, Operation::Rem, Rem::rem)
     }
 }
 
@@ -1600,7 +2047,7 @@ where
     type Output = OutputFor<Self>;
 
     fn rem(self, other: T) -> Self::Output {
-        self.map(|inner| inner % other)
+        self.map_traced(Operation::Rem, |inner| inner % other)
     }
 }
 
@@ -1661,7 +2108,7 @@ where
     type Output = OutputFor<Self>;
 
     fn sub(self, other: Self) -> Self::Output {
-        self.zip_map(other, Sub::sub)
+        self.zip_map_traced(other, Operation::Sub, Sub::sub)
     }
 }
 
@@ -1673,7 +2120,7 @@ where
     type Output = OutputFor<Self>;
 
     fn sub(self, other: T) -> Self::Output {
-        self.map(|inner| inner - other)
+        self.map_traced(Operation::Sub, |inner| inner - other)
     }
 }
 
@@ -1790,6 +2237,10 @@ where
     }
 }
 
+// SAFETY: `Constrained` is `repr(transparent)` and `IsFloat` accepts any value of `T`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T> TransparentWrapper<T> for Constrained<T, IsFloat> {}
+
 #[cfg(feature = "approx")]
 impl<T, C> UlpsEq for Constrained<T, C>
 where
@@ -1873,17 +2324,17 @@ where
     }
 
     fn recip(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::recip)
+        self.map_traced(Operation::Recip, UnaryRealFunction::recip)
     }
 
     #[cfg(feature = "std")]
     fn powi(self, n: i32) -> Self::Codomain {
-        self.map(|inner| UnaryRealFunction::powi(inner, n))
+        self.map_traced(Operation::Powi, |inner| UnaryRealFunction::powi(inner, n))
     }
 
     #[cfg(feature = "std")]
     fn sqrt(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::sqrt)
+        self.map_traced(Operation::Sqrt, UnaryRealFunction::sqrt)
     }
 
     #[cfg(feature = "std")]
@@ -1893,42 +2344,42 @@ where
 
     #[cfg(feature = "std")]
     fn exp(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::exp)
+        self.map_traced(Operation::Exp, UnaryRealFunction::exp)
     }
 
     #[cfg(feature = "std")]
     fn exp2(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::exp2)
+        self.map_traced(Operation::Exp2, UnaryRealFunction::exp2)
     }
 
     #[cfg(feature = "std")]
     fn exp_m1(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::exp_m1)
+        self.map_traced(Operation::ExpM1, UnaryRealFunction::exp_m1)
     }
 
     #[cfg(feature = "std")]
     fn ln(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::ln)
+        self.map_traced(Operation::Ln, UnaryRealFunction::ln)
     }
 
     #[cfg(feature = "std")]
     fn log2(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::log2)
+        self.map_traced(Operation::Log2, UnaryRealFunction::log2)
     }
 
     #[cfg(feature = "std")]
     fn log10(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::log10)
+        self.map_traced(Operation::Log10, UnaryRealFunction::log10)
     }
 
     #[cfg(feature = "std")]
     fn ln_1p(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::ln_1p)
+        self.map_traced(Operation::Ln1p, UnaryRealFunction::ln_1p)
     }
 
     #[cfg(feature = "std")]
     fn to_degrees(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::to_degrees)
+        self.map_traced(Operation::ToDegrees, UnaryRealFunction::to_degrees)
     }
 
     #[cfg(feature = "std")]
@@ -1948,17 +2399,17 @@ where
 
     #[cfg(feature = "std")]
     fn tan(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::tan)
+        self.map_traced(Operation::Tan, UnaryRealFunction::tan)
     }
 
     #[cfg(feature = "std")]
     fn asin(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::asin)
+        self.map_traced(Operation::Asin, UnaryRealFunction::asin)
     }
 
     #[cfg(feature = "std")]
     fn acos(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::acos)
+        self.map_traced(Operation::Acos, UnaryRealFunction::acos)
     }
 
     #[cfg(feature = "std")]
@@ -1969,7 +2420,7 @@ where
     #[cfg(feature = "std")]
     fn sin_cos(self) -> (Self, Self) {
         let (sin, cos) = self.into_inner().sin_cos();
-        (Constrained::unchecked(sin), Constrained::unchecked(cos))
+        (self.map_unchecked(|_| sin), self.map_unchecked(|_| cos))
     }
 
     #[cfg(feature = "std")]
@@ -1989,17 +2440,17 @@ where
 
     #[cfg(feature = "std")]
     fn asinh(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::asinh)
+        self.map_traced(Operation::Asinh, UnaryRealFunction::asinh)
     }
 
     #[cfg(feature = "std")]
     fn acosh(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::acosh)
+        self.map_traced(Operation::Acosh, UnaryRealFunction::acosh)
     }
 
     #[cfg(feature = "std")]
     fn atanh(self) -> Self::Codomain {
-        self.map(UnaryRealFunction::atanh)
+        self.map_traced(Operation::Atanh, UnaryRealFunction::atanh)
     }
 }
 
@@ -2027,13 +2478,59 @@ where
     }
 }
 
+// SAFETY: `Constrained` is `repr(transparent)` and zero satisfies all constraints.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, C> Zeroable for Constrained<T, C>
+where
+    T: Primitive + Zeroable,
+    C: Constraint,
+{
+}
+
+// Fixed capacity buffer for formatting without allocation.
+pub(crate) struct StackBuffer<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for StackBuffer<N> {
+    fn default() -> Self {
+        StackBuffer {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> StackBuffer<N> {
+    pub(crate) fn as_str(&self) -> &str {
+        // SAFETY: The buffer is only written via `fmt::Write`, which writes complete UTF-8 strings.
+        unsafe { core::str::from_utf8_unchecked(&self.bytes[..self.len]) }
+    }
+}
+
+impl<const N: usize> fmt::Write for StackBuffer<N> {
+    fn write_str(&mut self, string: &str) -> fmt::Result {
+        let bytes = string.as_bytes();
+        let end = self.len + bytes.len();
+        if end > N {
+            Err(fmt::Error)
+        }
+        else {
+            self.bytes[self.len..end].copy_from_slice(bytes);
+            self.len = end;
+            Ok(())
+        }
+    }
+}
+
 macro_rules! impl_binary_operation_for_proxy {
     () => {
         with_binary_operations!(impl_binary_operation_for_proxy);
     };
     (operation => $trait:ident :: $method:ident) => {
         impl_binary_operation_for_proxy!(operation => $trait :: $method, |left, right| {
-            right.map(|inner| $trait::$method(left, inner))
+            right.map_traced(Operation::$trait, |inner| $trait::$method(left, inner))
         });
     };
     (operation => $trait:ident :: $method:ident, |$left:ident, $right:ident| $f:block) => {
@@ -2333,8 +2830,80 @@ macro_rules! impl_try_from_for_proxy {
 }
 impl_try_from_for_proxy!();
 
+// The domain of `atanh` is an open interval, so the nearest values in its domain are adjacent to
+// its asymptotes.
+macro_rules! impl_clamped_function_for_proxy {
+    () => {
+        with_primitives!(impl_clamped_function_for_proxy);
+    };
+    (primitive => $t:ident) => {
+        impl<C> Constrained<$t, C>
+        where
+            C: Constraint,
+        {
+            /// Computes the arcsine, clamping an input that is outside of the domain $[-1,1]$ by
+            /// at most `ulps` ULPs to the nearest value in the domain.
+            ///
+            /// Inputs that are slightly outside of the domain commonly arise from rounding, such
+            /// as the dot product of unit vectors that are nearly parallel.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// use decorum::R64;
+            ///
+            /// let x = <R64>::assert(1.0000000000000002);
+            /// assert_eq!(x.asin_clamped(1), core::f64::consts::FRAC_PI_2);
+            /// ```
+            #[cfg(feature = "std")]
+            pub fn asin_clamped(self, ulps: u32) -> OutputFor<Self> {
+                self.map(|inner| clamp_to_domain(inner, 1.0, ulps).asin())
+            }
+
+            /// Computes the arccosine, clamping an input that is outside of the domain $[-1,1]$
+            /// by at most `ulps` ULPs to the nearest value in the domain.
+            #[cfg(feature = "std")]
+            pub fn acos_clamped(self, ulps: u32) -> OutputFor<Self> {
+                self.map(|inner| clamp_to_domain(inner, 1.0, ulps).acos())
+            }
+
+            /// Computes the inverse hyperbolic tangent, clamping an input that is outside of the
+            /// domain $(-1,1)$ by at most `ulps` ULPs to the nearest value in the domain.
+            ///
+            /// The nearest values in the domain are the values adjacent to $-1$ and $1$, so the
+            /// output of a clamped input is finite.
+            #[cfg(feature = "std")]
+            pub fn atanh_clamped(self, ulps: u32) -> OutputFor<Self> {
+                let limit = <$t>::from_bits((1.0 as $t).to_bits() - 1);
+                self.map(|inner| clamp_to_domain(inner, limit, ulps).atanh())
+            }
+        }
+    };
+}
+impl_clamped_function_for_proxy!();
+
+// Clamps `inner` to the interval `[-limit, limit]` if it is outside of the interval by at most
+// `ulps` ULPs.
+#[cfg(feature = "std")]
+fn clamp_to_domain<T>(inner: T, limit: T, ulps: u32) -> T
+where
+    T: Primitive + UlpKey,
+{
+    if inner > limit && cmp::eq_ulps(inner, limit, ulps) {
+        limit
+    }
+    else if inner < -limit && cmp::eq_ulps(inner, -limit, ulps) {
+        -limit
+    }
+    else {
+        inner
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use core::mem::MaybeUninit;
+
     use crate::real::RealFunction;
     use crate::{ExtendedReal, InfinityEncoding, NanEncoding, Real, Total, E32, R32};
 
@@ -2456,6 +3025,25 @@ mod tests {
         let _ = Real::<f64>::try_from_slice(&xs).unwrap();
     }
 
+    #[test]
+    fn fill_uninit() {
+        let mut buffer = [MaybeUninit::uninit(); 3];
+        let xs = Real::<f64>::fill_uninit(&mut buffer, |i| i as f64).unwrap();
+        xs[0] = Real::assert(-1.0);
+        assert_eq!(xs, [-1.0, 1.0, 2.0]);
+
+        let mut buffer = [MaybeUninit::uninit(); 3];
+        let result = ExtendedReal::<f64>::fill_uninit(&mut buffer, |i| {
+            if i == 2 {
+                f64::NAN
+            }
+            else {
+                0.0
+            }
+        });
+        assert!(matches!(result, Err((2, _))));
+    }
+
     #[test]
     #[allow(clippy::eq_op)]
     #[allow(clippy::float_cmp)]
@@ -2593,4 +3181,164 @@ mod tests {
             serde_json::to_string(&E32::<OrPanic>::INFINITY).unwrap()
         );
     }
+
+    #[cfg(all(debug_assertions, feature = "strict-validate"))]
+    #[test]
+    #[should_panic]
+    fn strict_validate_panic_on_leak() {
+        let _: R32 = R32::assert(1.0).map_unchecked(|_| f32::NAN);
+    }
+
+    #[cfg(all(debug_assertions, feature = "strict-validate"))]
+    #[test]
+    #[should_panic]
+    fn strict_validate_panic_on_class_leak() {
+        let _: R32 = R32::assert(1.0).map_class_preserving(|_| f32::INFINITY);
+    }
+
+    #[test]
+    fn round_sig_figs() {
+        use crate::divergence::OrError;
+        use crate::expression::Expression;
+        use crate::R64;
+
+        let real = |x: f64| -> R64 { R64::assert(x) };
+
+        // The decimal expansion of `0.125` is exact, so this is a tie that rounds to even.
+        assert_eq!(real(0.125).round_sig_figs(2), 0.12);
+        // The decimal expansion of `2.675` is slightly less than its literal.
+        assert_eq!(real(2.675).round_sig_figs(3), 2.67);
+        assert_eq!(real(-987.6).round_sig_figs(1), -1000.0);
+        assert_eq!(real(1.0e-310).round_sig_figs(1), 1.0e-310);
+        let x: R32 = R32::assert(0.1);
+        assert_eq!(x.round_sig_figs(20), x);
+        assert!(matches!(
+            R64::<OrError>::assert(f64::MAX).round_sig_figs(1),
+            Expression::Undefined(_)
+        ));
+        assert!(Total::<f64>::INFINITY.round_sig_figs(1).is_infinite());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn to_engineering_string() {
+        use crate::divergence::OrPanic;
+        use crate::R64;
+
+        let string = |x: f64| R64::<OrPanic>::assert(x).to_engineering_string();
+        assert_eq!(string(0.0), "0e0");
+        assert_eq!(string(1.5), "1.5e0");
+        assert_eq!(string(1000.0), "1e3");
+        assert_eq!(string(123456.0), "123.456e3");
+        assert_eq!(string(0.01), "10e-3");
+        assert_eq!(Total::<f64>::NAN.to_engineering_string(), "NaN");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn canonical_string_round_trip() {
+        use crate::divergence::OrPanic;
+
+        for x in [
+            0.0,
+            -0.0,
+            1.0,
+            -1.5e-3,
+            0.1,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+        ] {
+            let x = Total::from(x);
+            let string = x.to_canonical_string();
+            assert_eq!(Total::<f64>::from_canonical_string(&string).unwrap(), x);
+        }
+        assert_eq!(Total::from(-0.0f64).to_canonical_string(), "0e0");
+        assert_eq!(Total::from(-f64::NAN).to_canonical_string(), "NaN");
+        for string in ["1500", "1.50e3", "+1e0", "-0e0", "nan", "inf0", ""] {
+            assert!(Total::<f64>::from_canonical_string(string).is_err());
+        }
+        assert!(Real::<f64, OrPanic>::from_canonical_string("inf").is_err());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_cast() {
+        use bytemuck::checked::{self, CheckedCastError};
+        use bytemuck::TransparentWrapper;
+
+        let xs = [1.0f32, f32::NAN, 2.0];
+        let ys: &[Total<f32>] = bytemuck::cast_slice(&xs);
+        assert!(ys[1].is_nan());
+        assert_eq!(bytemuck::cast_slice::<Total<f32>, f32>(ys)[2], 2.0);
+        assert_eq!(Total::<f32>::wrap_slice(&xs)[0], 1.0);
+
+        assert!(matches!(
+            checked::try_cast_slice::<f32, R32>(&xs),
+            Err(CheckedCastError::InvalidBitPattern)
+        ));
+        let xs = [1.0f32, f32::INFINITY];
+        assert!(checked::try_cast_slice::<f32, R32>(&xs).is_err());
+        assert_eq!(
+            checked::try_cast_slice::<f32, E32>(&xs).unwrap()[1],
+            E32::INFINITY
+        );
+        let ys: &[R32] = checked::cast_slice(&xs[..1]);
+        assert_eq!(bytemuck::cast_slice::<R32, f32>(ys), &[1.0]);
+        assert_eq!(<R32 as bytemuck::Zeroable>::zeroed(), 0.0);
+    }
+
+    #[test]
+    fn with_divergence() {
+        use crate::divergence::{OrError, OrPanic};
+        use crate::real::UnaryRealFunction;
+
+        let x = Real::<f64, OrError>::ONE;
+        assert!((x / Real::ZERO).is_undefined());
+        let y: Real<f64, OrPanic> = x.with_divergence::<OrPanic>() / Real::ONE;
+        assert_eq!(y.with_divergence::<OrError>(), x);
+        let z = ExtendedReal::<f64, OrPanic>::INFINITY.with_divergence::<OrError>();
+        assert!((z - z).is_undefined());
+    }
+
+    #[test]
+    fn from_divergence() {
+        use crate::divergence::{AsResult, OrError, OrPanic};
+        use crate::real::UnaryRealFunction;
+
+        let x = Real::<f64, OrError>::ONE;
+        let y: Real<f64, OrPanic> = x.into();
+        let z: Real<f64, OrError<AsResult>> = y.into();
+        assert!((z / Real::ZERO).is_err());
+        let w: ExtendedReal<f64, OrError> = ExtendedReal::<f64, OrPanic>::INFINITY.into();
+        assert!((w - w).is_undefined());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn clamped_domain() {
+        use crate::divergence::OrError;
+        use crate::real::UnaryRealFunction;
+        use crate::R64;
+
+        let above = f64::from_bits(1.0f64.to_bits() + 2);
+        assert_eq!(
+            <R64>::assert(above).asin_clamped(2),
+            core::f64::consts::FRAC_PI_2
+        );
+        assert_eq!(<R64>::assert(-above).acos_clamped(2), core::f64::consts::PI);
+        assert!(Real::<f64, OrError>::assert(above)
+            .asin_clamped(1)
+            .is_undefined());
+        assert!(Real::<f64, OrError>::assert(1.0).atanh().is_undefined());
+        assert!(<R64>::assert(1.0).atanh_clamped(1).into_inner().is_finite());
+        assert!(<R64>::assert(-above).atanh_clamped(3).into_inner() < 0.0);
+        assert_eq!(
+            <R64>::assert(0.5).asin_clamped(0),
+            <R64>::assert(0.5).asin()
+        );
+    }
 }
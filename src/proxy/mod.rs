@@ -23,24 +23,76 @@
 //! encoding output (e.g., a `Result::Err`) or even a panic. Notably, the [`Total`] type applies no
 //! constraints and is infallible (never diverges).
 //!
+//! # Buffers and Tensors
+//!
+//! With the `bytemuck` feature, [`Constrained`] types implement traits from [`bytemuck`] that
+//! allow buffers of primitive floating-point values to be reinterpreted as buffers of
+//! [`Constrained`] values (and vice versa) without copying. [`Total`] is `Pod` and
+//! `TransparentWrapper` of its primitive type, so these casts are infallible. [`ExtendedReal`]
+//! and [`Real`] are `CheckedBitPattern` and `NoUninit`, so casting primitive values into these
+//! types checks each value against the constraint and casting out of these types is infallible.
+//!
+//! This is the recommended pattern for moving validated scalars into and out of tensor types,
+//! which typically store and expose contiguous buffers of primitive floating-point values. For
+//! example, with [`candle`]:
+//!
+//! ```rust,ignore
+//! use bytemuck::checked;
+//! use candle_core::{Device, Tensor};
+//! use decorum::R32;
+//!
+//! fn to_tensor(xs: &[R32]) -> candle_core::Result<Tensor> {
+//!     Tensor::from_slice(bytemuck::cast_slice::<R32, f32>(xs), xs.len(), &Device::Cpu)
+//! }
+//!
+//! fn from_tensor(tensor: &Tensor) -> candle_core::Result<Option<Vec<R32>>> {
+//!     let xs = tensor.flatten_all()?.to_vec1::<f32>()?;
+//!     Ok(checked::try_cast_slice::<f32, R32>(&xs).ok().map(<[R32]>::to_vec))
+//! }
+//! ```
+//!
+//! To carry these guarantees across a boundary like that of a GPU or compute API,
+//! `Constrained::into_byte_buffer` encodes proxies with a small header that describes the
+//! constraint (e.g., that all values are finite) and `Constrained::try_from_byte_buffer`
+//! re-validates each value when decoding. `Constrained::as_pod_slice` exposes proxies as primitive
+//! values without copying.
+//!
+//! [`bytemuck`]: https://crates.io/crates/bytemuck
+//! [`candle`]: https://crates.io/crates/candle-core
 //! [`constraint`]: crate::constraint
 //! [`divergence`]: crate::divergence
 //! [`ExtendedReal`]: crate::ExtendedReal
 //! [`Real`]: crate::Real
 //! [`Total`]: crate::Total
 
+#[cfg(feature = "bytemuck")]
+mod buffer;
+mod bytes;
+mod cancellation;
+mod category;
 mod constrained;
 mod nan;
+mod ratio;
+mod validation;
 
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 
 use crate::Primitive;
 
+#[cfg(feature = "bytemuck")]
+pub use crate::proxy::buffer::BufferError;
+pub use crate::proxy::bytes::{Decode, DecodeError, Endianness, LengthError};
+pub use crate::proxy::cancellation::CancellationError;
+pub use crate::proxy::category::Categorized;
 pub use crate::proxy::constrained::{
-    Constrained, ConstrainedProxy, ErrorFor, ExpressionFor, OutputFor,
+    CanonicalStringError, Constrained, ConstrainedProxy, ErrorFor, ExpressionFor, OutputFor,
 };
 pub use crate::proxy::nan::Nan;
+pub use crate::proxy::ratio::RatioError;
+pub use crate::proxy::validation::ValidationReport;
+
+pub(crate) use crate::proxy::constrained::StackBuffer;
 
 /// An IEEE 754 floating-point proxy type.
 pub trait Proxy: Sized {
@@ -0,0 +1,146 @@
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::divergence::{self, Divergence};
+use crate::proxy::constrained::Constrained;
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum RatioError<E> {
+    /// The ratio has no defined value, such as `0 / 0` or `INF / INF`.
+    #[error("{}", "indeterminate ratio")]
+    Indeterminate,
+    /// The denominator is zero but the numerator is not.
+    #[error("{}", "division by zero")]
+    DivisionByZero,
+    /// The ratio of finite operands is not finite.
+    #[error("{}", "ratio overflows")]
+    Overflow,
+    #[error("{0}")]
+    Constraint(E),
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Constructs a proxy from the ratio of primitive IEEE 754 floating-point values.
+    ///
+    /// Division is a common source of `NaN`s and infinities. Rather than constructing operands,
+    /// dividing them, and then handling divergence, this function detects the conditions that
+    /// cause an undefined or unbounded ratio and reports them explicitly as a [`RatioError`]:
+    /// indeterminate forms like `0 / 0`, division of a non-zero value by zero, and overflow of
+    /// finite operands. These conditions are errors regardless of the constraint, so even
+    /// [`Total`] ratios never divide by zero. The output type is determined by the
+    /// [divergence][`divergence`] of the proxy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::divergence::OrError;
+    /// use decorum::proxy::RatioError;
+    /// use decorum::Real;
+    ///
+    /// type R64 = Real<f64, OrError>;
+    ///
+    /// assert_eq!(R64::ratio(3.0, 4.0), R64::assert(0.75));
+    /// assert!(matches!(
+    ///     R64::ratio(0.0, 0.0).undefined(),
+    ///     Some(RatioError::Indeterminate),
+    /// ));
+    /// assert!(matches!(
+    ///     R64::ratio(1.0, -0.0).undefined(),
+    ///     Some(RatioError::DivisionByZero),
+    /// ));
+    /// assert!(matches!(
+    ///     R64::ratio(f64::MAX, 0.5).undefined(),
+    ///     Some(RatioError::Overflow),
+    /// ));
+    /// ```
+    ///
+    /// [`divergence`]: crate::divergence
+    /// [`Total`]: crate::Total
+    pub fn ratio(
+        numerator: T,
+        denominator: T,
+    ) -> divergence::OutputFor<C::Divergence, Self, RatioError<C::Error>> {
+        C::Divergence::diverge(checked_ratio(numerator, denominator))
+    }
+}
+
+fn checked_ratio<T, C>(
+    numerator: T,
+    denominator: T,
+) -> Result<Constrained<T, C>, RatioError<C::Error>>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    C::check(numerator).map_err(RatioError::Constraint)?;
+    C::check(denominator).map_err(RatioError::Constraint)?;
+    if denominator == T::ZERO {
+        return Err(if numerator == T::ZERO {
+            RatioError::Indeterminate
+        }
+        else {
+            RatioError::DivisionByZero
+        });
+    }
+    let output = numerator / denominator;
+    if output.is_nan() && !numerator.is_nan() && !denominator.is_nan() {
+        Err(RatioError::Indeterminate)
+    }
+    else if output.is_infinite() && numerator.is_finite() && denominator.is_finite() {
+        Err(RatioError::Overflow)
+    }
+    else {
+        Constrained::try_new(output).map_err(RatioError::Constraint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::divergence::{AsResult, OrError};
+    use crate::proxy::RatioError;
+    use crate::{ExtendedReal, Real, Total, R64};
+
+    type R64Result = Real<f64, OrError<AsResult>>;
+    type E64Result = ExtendedReal<f64, OrError<AsResult>>;
+
+    #[test]
+    fn ratio() {
+        assert_eq!(R64Result::ratio(1.0, 4.0).ok(), Some(R64::assert(0.25)));
+        assert!(matches!(
+            R64Result::ratio(-0.0, 0.0),
+            Err(RatioError::Indeterminate)
+        ));
+        assert!(matches!(
+            R64Result::ratio(-1.0, 0.0),
+            Err(RatioError::DivisionByZero)
+        ));
+        assert!(matches!(
+            R64Result::ratio(1.0e300, 1.0e-300),
+            Err(RatioError::Overflow)
+        ));
+        assert!(matches!(
+            R64Result::ratio(f64::NAN, 1.0),
+            Err(RatioError::Constraint(_))
+        ));
+        assert!(matches!(
+            E64Result::ratio(f64::INFINITY, f64::NEG_INFINITY),
+            Err(RatioError::Indeterminate)
+        ));
+        assert!(E64Result::ratio(f64::INFINITY, 2.0)
+            .unwrap()
+            .into_inner()
+            .is_infinite());
+        assert!(Total::<f64>::ratio(f64::NAN, 1.0).into_inner().is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn ratio_panic() {
+        let _ = <R64>::ratio(1.0, 0.0);
+    }
+}
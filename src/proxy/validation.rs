@@ -0,0 +1,178 @@
+use core::fmt::{self, Display, Formatter};
+use core::num::FpCategory;
+
+use crate::constraint::Constraint;
+use crate::proxy::constrained::Constrained;
+use crate::{BaseEncoding, Primitive};
+
+/// Summary of the validation of a slice of primitive floating-point values against a constraint.
+///
+/// See [`Constrained::validate_slice`].
+///
+/// [`Constrained::validate_slice`]: crate::proxy::Constrained::validate_slice
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValidationReport<T> {
+    /// The number of values in the slice.
+    pub len: usize,
+    /// The number of `NaN`s.
+    pub nan: usize,
+    /// The number of positive infinities.
+    pub infinity: usize,
+    /// The number of negative infinities.
+    pub neg_infinity: usize,
+    /// The number of subnormal values.
+    pub subnormal: usize,
+    /// The number of values that do not satisfy the constraint.
+    pub invalid: usize,
+    /// The index of the first value that does not satisfy the constraint.
+    pub first_invalid: Option<usize>,
+    /// The index of the last value that does not satisfy the constraint.
+    pub last_invalid: Option<usize>,
+    /// The minimum of the values that satisfy the constraint.
+    pub min: Option<T>,
+    /// The maximum of the values that satisfy the constraint.
+    pub max: Option<T>,
+}
+
+impl<T> ValidationReport<T> {
+    /// Returns `true` if all values satisfy the constraint.
+    pub fn is_valid(&self) -> bool {
+        self.invalid == 0
+    }
+}
+
+impl<T> Display for ValidationReport<T>
+where
+    T: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{} values, {} invalid (NaN: {}, +inf: {}, -inf: {}, subnormal: {})",
+            self.len, self.invalid, self.nan, self.infinity, self.neg_infinity, self.subnormal,
+        )?;
+        if let (Some(first), Some(last)) = (self.first_invalid, self.last_invalid) {
+            write!(formatter, "; invalid indices {}..={}", first, last)?;
+        }
+        if let (Some(min), Some(max)) = (self.min.as_ref(), self.max.as_ref()) {
+            write!(formatter, "; valid range [{}, {}]", min, max)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, C> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Validates a slice of primitive IEEE 754 floating-point values against the constraints of
+    /// the proxy and summarizes the result.
+    ///
+    /// Unlike [`try_from_slice`], this function examines every value and reports the number of
+    /// values in each non-real class, the range of indices of values that do not satisfy the
+    /// constraint, and the extrema of values that do. This is useful for data quality checks of
+    /// large data sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use decorum::R64;
+    ///
+    /// let xs = [1.0, f64::NAN, -3.0, f64::INFINITY, 2.0];
+    /// let report = <R64>::validate_slice(&xs);
+    /// assert!(!report.is_valid());
+    /// assert_eq!(report.invalid, 2);
+    /// assert_eq!(report.first_invalid, Some(1));
+    /// assert_eq!(report.last_invalid, Some(3));
+    /// assert_eq!(report.min, Some(-3.0));
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "5 values, 2 invalid (NaN: 1, +inf: 1, -inf: 0, subnormal: 0); \
+    ///      invalid indices 1..=3; valid range [-3, 2]",
+    /// );
+    /// ```
+    ///
+    /// [`try_from_slice`]: crate::proxy::Constrained::try_from_slice
+    pub fn validate_slice(slice: &[T]) -> ValidationReport<T> {
+        let mut report = ValidationReport {
+            len: slice.len(),
+            nan: 0,
+            infinity: 0,
+            neg_infinity: 0,
+            subnormal: 0,
+            invalid: 0,
+            first_invalid: None,
+            last_invalid: None,
+            min: None,
+            max: None,
+        };
+        for (index, inner) in slice.iter().copied().enumerate() {
+            match BaseEncoding::classify(inner) {
+                FpCategory::Nan => report.nan += 1,
+                FpCategory::Infinite if inner.is_sign_positive() => report.infinity += 1,
+                FpCategory::Infinite => report.neg_infinity += 1,
+                FpCategory::Subnormal => report.subnormal += 1,
+                _ => {}
+            }
+            if C::check(inner).is_ok() {
+                // `NaN`s may satisfy the constraint, but are not ordered.
+                if !inner.is_nan() {
+                    if report.min.map_or(true, |min| inner < min) {
+                        report.min = Some(inner);
+                    }
+                    if report.max.map_or(true, |max| inner > max) {
+                        report.max = Some(inner);
+                    }
+                }
+            }
+            else {
+                report.invalid += 1;
+                report.first_invalid.get_or_insert(index);
+                report.last_invalid = Some(index);
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExtendedReal, Total, R32};
+
+    #[test]
+    fn validate_slice() {
+        let xs = [
+            0.5f32,
+            1.0e-40,
+            f32::NEG_INFINITY,
+            f32::NAN,
+            -2.0,
+            f32::INFINITY,
+        ];
+        let report = <R32>::validate_slice(&xs);
+        assert_eq!(report.len, 6);
+        assert_eq!(report.nan, 1);
+        assert_eq!(report.infinity, 1);
+        assert_eq!(report.neg_infinity, 1);
+        assert_eq!(report.subnormal, 1);
+        assert_eq!(report.invalid, 3);
+        assert_eq!(report.first_invalid, Some(2));
+        assert_eq!(report.last_invalid, Some(5));
+        assert_eq!(report.min, Some(-2.0));
+        assert_eq!(report.max, Some(0.5));
+
+        let report = ExtendedReal::<f32>::validate_slice(&xs);
+        assert_eq!(report.invalid, 1);
+        assert_eq!(report.min, Some(f32::NEG_INFINITY));
+        assert_eq!(report.max, Some(f32::INFINITY));
+
+        let report = Total::<f32>::validate_slice(&xs);
+        assert!(report.is_valid());
+        assert_eq!(report.first_invalid, None);
+
+        let report = <R32>::validate_slice(&[]);
+        assert!(report.is_valid());
+        assert_eq!(report.min, None);
+    }
+}
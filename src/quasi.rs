@@ -0,0 +1,315 @@
+//! Quasi-random low-discrepancy sequences over [`Constrained`] types.
+//!
+//! Low-discrepancy sequences cover a domain more evenly than (pseudo-)random samples and are used
+//! in quasi-Monte Carlo integration. This module provides iterators over the [Halton] and [Sobol]
+//! sequences in the half-open unit hypercube $[0, 1)^N$. Every coordinate is a real number in the
+//! unit interval, so these sequences can emit any [`Constrained`] type without checks or
+//! divergence.
+//!
+//! # Examples
+//!
+//! Estimating $\int_0^1 \int_0^1 xy \\,dx \\,dy = \frac{1}{4}$:
+//!
+//! ```rust
+//! use decorum::quasi::Sobol;
+//! use decorum::R64;
+//!
+//! let sobol: Sobol<R64, 2> = Sobol::new();
+//! let n = 1 << 12;
+//! let sum: f64 = sobol.take(n).map(|[x, y]| (x * y).into_inner()).sum();
+//! assert!(((sum / n as f64) - 0.25).abs() < 1e-3);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [Halton]: https://en.wikipedia.org/wiki/Halton_sequence
+//! [Sobol]: https://en.wikipedia.org/wiki/Sobol_sequence
+
+use core::marker::PhantomData;
+use num_traits::FromPrimitive;
+
+use crate::constraint::Constraint;
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+const PRIMES: [u64; 32] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131,
+];
+
+// Primitive polynomials and initial direction numbers `(s, a, m)` for the Sobol sequence from Joe
+// and Kuo (`new-joe-kuo-6.21201`). The first dimension is the van der Corput sequence in base two
+// and is not listed.
+const DIRECTIONS: [(u32, u32, &[u32]); 7] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+];
+
+/// The maximum number of dimensions supported by [`Halton`].
+pub const HALTON_MAX_DIMENSIONS: usize = PRIMES.len();
+
+/// The maximum number of dimensions supported by [`Sobol`].
+pub const SOBOL_MAX_DIMENSIONS: usize = DIRECTIONS.len() + 1;
+
+/// Iterator over the Halton sequence in `N` dimensions.
+///
+/// Each dimension is the radical inverse of the index of the point in a distinct prime base. The
+/// Halton sequence is unbounded, but correlations between dimensions grow with the magnitude of
+/// the bases, so it is best suited to low dimensions.
+///
+/// # Panics
+///
+/// Construction panics if `N` is zero or greater than [`HALTON_MAX_DIMENSIONS`].
+#[derive(Clone, Debug)]
+pub struct Halton<P, const N: usize> {
+    index: u64,
+    phantom: PhantomData<fn() -> P>,
+}
+
+impl<T, C, const N: usize> Halton<Constrained<T, C>, N>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    pub fn new() -> Self {
+        Halton::from_index(0)
+    }
+
+    /// Constructs a Halton sequence that begins at the given index.
+    pub fn from_index(index: u64) -> Self {
+        assert!(
+            N > 0 && N <= HALTON_MAX_DIMENSIONS,
+            "unsupported number of Halton dimensions"
+        );
+        Halton {
+            index,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, C, const N: usize> Default for Halton<Constrained<T, C>, N>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    fn default() -> Self {
+        Halton::new()
+    }
+}
+
+impl<T, C, const N: usize> Iterator for Halton<Constrained<T, C>, N>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    type Item = [Constrained<T, C>; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        self.index = self.index.checked_add(1)?;
+        let mut bases = PRIMES.iter().copied();
+        Some([(); N].map(|_| {
+            let base = bases.next().unwrap();
+            into_unit_interval(radical_inverse(index, base))
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Iterator over the Sobol sequence in `N` dimensions.
+///
+/// The Sobol sequence is generated in Gray code order with 32-bit direction numbers and so yields
+/// exactly $2^{32}$ points.
+///
+/// # Panics
+///
+/// Construction panics if `N` is zero or greater than [`SOBOL_MAX_DIMENSIONS`].
+#[derive(Clone, Debug)]
+pub struct Sobol<P, const N: usize> {
+    index: u64,
+    point: [u32; N],
+    directions: [[u32; 32]; N],
+    phantom: PhantomData<fn() -> P>,
+}
+
+impl<T, C, const N: usize> Sobol<Constrained<T, C>, N>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    pub fn new() -> Self {
+        assert!(
+            N > 0 && N <= SOBOL_MAX_DIMENSIONS,
+            "unsupported number of Sobol dimensions"
+        );
+        let mut dimension = 0;
+        let directions = [(); N].map(|_| {
+            dimension += 1;
+            sobol_directions(dimension - 1)
+        });
+        Sobol {
+            index: 0,
+            point: [0; N],
+            directions,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, C, const N: usize> Default for Sobol<Constrained<T, C>, N>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    fn default() -> Self {
+        Sobol::new()
+    }
+}
+
+impl<T, C, const N: usize> Iterator for Sobol<Constrained<T, C>, N>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    type Item = [Constrained<T, C>; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > u64::from(u32::MAX) {
+            return None;
+        }
+        let output = self
+            .point
+            .map(|x| into_unit_interval(f64::from(x) / 4_294_967_296.0));
+        // Advance the point by the direction of the lowest zero bit of the index (Gray code).
+        let bit = (!self.index).trailing_zeros() as usize;
+        if bit < 32 {
+            for (x, directions) in self.point.iter_mut().zip(self.directions.iter()) {
+                *x ^= directions[bit];
+            }
+        }
+        self.index += 1;
+        Some(output)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (u64::from(u32::MAX) + 1).saturating_sub(self.index);
+        let remaining = usize::try_from(remaining).unwrap_or(usize::MAX);
+        (remaining, Some(remaining))
+    }
+}
+
+fn radical_inverse(mut index: u64, base: u64) -> f64 {
+    let inverse = 1.0 / base as f64;
+    let mut scale = inverse;
+    let mut output = 0.0;
+    while index > 0 {
+        output += (index % base) as f64 * scale;
+        index /= base;
+        scale *= inverse;
+    }
+    output
+}
+
+fn sobol_directions(dimension: usize) -> [u32; 32] {
+    let mut directions = [0u32; 32];
+    if dimension == 0 {
+        for (k, direction) in directions.iter_mut().enumerate() {
+            *direction = 1 << (31 - k);
+        }
+    }
+    else {
+        let (s, a, m) = DIRECTIONS[dimension - 1];
+        let s = s as usize;
+        for k in 0..32 {
+            directions[k] = if k < s {
+                m[k] << (31 - k)
+            }
+            else {
+                let mut direction = directions[k - s] ^ (directions[k - s] >> s);
+                for j in 1..s {
+                    if (a >> (s - 1 - j)) & 1 != 0 {
+                        direction ^= directions[k - j];
+                    }
+                }
+                direction
+            };
+        }
+    }
+    directions
+}
+
+// Converts a value in `[0, 1)` into a proxy. Rounding may yield one when converting to a less
+// precise type, in which case the largest representable value less than one is used instead.
+fn into_unit_interval<T, C>(x: f64) -> Constrained<T, C>
+where
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    let x = T::from_f64(x).unwrap_or(T::ZERO);
+    Constrained::unchecked(if x < T::ONE {
+        x
+    }
+    else {
+        T::ONE - (T::EPSILON / (T::ONE + T::ONE))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::quasi::{Halton, Sobol};
+    use crate::{R32, R64};
+
+    #[test]
+    fn halton_radical_inverse() {
+        let halton: Halton<R64, 2> = Halton::new();
+        let points: [[f64; 2]; 4] = {
+            let mut points = halton.map(|[x, y]| [x.into_inner(), y.into_inner()]);
+            [(); 4].map(|_| points.next().unwrap())
+        };
+        assert_eq!(
+            points,
+            [
+                [0.0, 0.0],
+                [0.5, 1.0 / 3.0],
+                [0.25, 2.0 / 3.0],
+                [0.75, 1.0 / 9.0],
+            ]
+        );
+    }
+
+    #[test]
+    fn sobol_gray_code() {
+        let sobol: Sobol<R64, 2> = Sobol::new();
+        let points: [[f64; 2]; 6] = {
+            let mut points = sobol.map(|[x, y]| [x.into_inner(), y.into_inner()]);
+            [(); 6].map(|_| points.next().unwrap())
+        };
+        assert_eq!(
+            points,
+            [
+                [0.0, 0.0],
+                [0.5, 0.5],
+                [0.75, 0.25],
+                [0.25, 0.75],
+                [0.375, 0.375],
+                [0.875, 0.875],
+            ]
+        );
+    }
+
+    #[test]
+    fn sobol_unit_interval() {
+        let sobol: Sobol<R32, 8> = Sobol::new();
+        for point in sobol.take(1 << 10) {
+            assert!(point.iter().all(|x| (0.0..1.0).contains(&x.into_inner())));
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! Random sampling of [`Constrained`] types.
+//!
+//! This module integrates with the [`rand`] crate. The [`Standard`] distribution samples
+//! [`Constrained`] types uniformly from the interval $[0, 1)$, which satisfies every constraint.
+//! Additional distributions always output real numbers, so their samples can be used with any
+//! [`Constrained`] type without checks or divergence.
+//!
+//! | Distribution      | Domain                          | Requires `std` |
+//! |-------------------|---------------------------------|----------------|
+//! | [`UnitBall<N>`]   | Interior of the unit `N`-ball   | no             |
+//! | [`UnitSphere<N>`] | Surface of the unit `N`-sphere  | yes            |
+//! | [`Normal`]        | $(-\infty, \infty)$             | yes            |
+//! | [`Exponential`]   | $(0, \infty)$                   | yes            |
+//! | [`Beta`]          | $[0, 1]$                        | yes            |
+//!
+//! The unit disk and unit circle are `UnitBall<2>` and `UnitSphere<2>`, respectively.
+//!
+//! Samples of a distribution with unbounded support are never infinite nor `NaN`: such samples
+//! are rejected and the distribution is resampled. This means that filling buffers of
+//! [`Constrained`] types with random data never requires checks, even for [`Real`]s.
+//!
+//! # Examples
+//!
+//! Sampling a direction in three dimensions:
+//!
+//! ```rust
+//! use decorum::random::UnitSphere;
+//! use decorum::R64;
+//! use rand::distributions::Distribution;
+//! use rand::rngs::SmallRng;
+//! use rand::SeedableRng;
+//!
+//! let mut rng = SmallRng::seed_from_u64(0);
+//! let direction: [R64; 3] = UnitSphere.sample(&mut rng);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`rand`]: https://crates.io/crates/rand
+//! [`Real`]: crate::Real
+//! [`UnitBall<N>`]: crate::random::UnitBall
+//! [`UnitSphere<N>`]: crate::random::UnitSphere
+//! [`Standard`]: rand::distributions::Standard
+
+#[cfg(feature = "std")]
+use num_traits::FromPrimitive;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+#[error("{}", "distribution parameter is out of range")]
+pub struct ParameterError;
+
+/// Uniform distribution over the interior of the unit ball in `N` dimensions.
+///
+/// Samples are arrays of [`Constrained`] coordinates with a Euclidean norm less than one. This
+/// distribution uses rejection sampling and is intended for low dimensions, as the acceptance rate
+/// decreases rapidly with `N`.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnitBall<const N: usize>;
+
+impl<T, C, const N: usize> Distribution<[Constrained<T, C>; N]> for UnitBall<N>
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> [Constrained<T, C>; N]
+    where
+        R: Rng + ?Sized,
+    {
+        let (point, _) = sample_unit_ball(rng, |_| true);
+        point.map(Constrained::unchecked)
+    }
+}
+
+/// Uniform distribution over the surface of the unit sphere in `N` dimensions.
+///
+/// Samples are arrays of [`Constrained`] coordinates with a Euclidean norm of one (within rounding
+/// error). This distribution normalizes samples of [`UnitBall`] and so is also intended for low
+/// dimensions.
+///
+/// # Panics
+///
+/// Sampling panics if `N` is zero.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UnitSphere<const N: usize>;
+
+#[cfg(feature = "std")]
+impl<T, C, const N: usize> Distribution<[Constrained<T, C>; N]> for UnitSphere<N>
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> [Constrained<T, C>; N]
+    where
+        R: Rng + ?Sized,
+    {
+        assert!(N > 0, "unit sphere must have at least one dimension");
+        // Reject points near the origin, because their direction is dominated by rounding error.
+        let (point, norm2) = sample_unit_ball(rng, |norm2: T| norm2 > T::EPSILON);
+        let norm = norm2.sqrt();
+        point.map(|x| Constrained::unchecked(x / norm))
+    }
+}
+
+/// Normal (Gaussian) distribution.
+///
+/// Samples are computed with the Marsaglia polar method. Samples that overflow are rejected, so
+/// samples are always real numbers.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct Normal<T> {
+    mean: T,
+    deviation: T,
+}
+
+#[cfg(feature = "std")]
+impl<T> Normal<T>
+where
+    T: Primitive,
+{
+    /// Constructs a normal distribution from its mean and standard deviation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mean or standard deviation is not a real number or if the standard
+    /// deviation is negative.
+    pub fn new(mean: T, deviation: T) -> Result<Self, ParameterError> {
+        if mean.is_finite() && deviation.is_finite() && deviation >= T::ZERO {
+            Ok(Normal { mean, deviation })
+        }
+        else {
+            Err(ParameterError)
+        }
+    }
+
+    /// Constructs the standard normal distribution, which has a mean of zero and a standard
+    /// deviation of one.
+    pub fn standard() -> Self {
+        Normal {
+            mean: T::ZERO,
+            deviation: T::ONE,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, C> Distribution<Constrained<T, C>> for Normal<T>
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> Constrained<T, C>
+    where
+        R: Rng + ?Sized,
+    {
+        loop {
+            let x = self.mean + (self.deviation * sample_standard_normal(rng));
+            if x.is_finite() {
+                return Constrained::unchecked(x);
+            }
+        }
+    }
+}
+
+/// Exponential distribution.
+///
+/// Samples are always positive real numbers: zeroes and samples that overflow are rejected.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct Exponential<T> {
+    rate: T,
+}
+
+#[cfg(feature = "std")]
+impl<T> Exponential<T>
+where
+    T: Primitive,
+{
+    /// Constructs an exponential distribution from its rate parameter ($\lambda$).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rate is not a positive real number.
+    pub fn new(rate: T) -> Result<Self, ParameterError> {
+        if rate.is_finite() && rate > T::ZERO {
+            Ok(Exponential { rate })
+        }
+        else {
+            Err(ParameterError)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, C> Distribution<Constrained<T, C>> for Exponential<T>
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> Constrained<T, C>
+    where
+        R: Rng + ?Sized,
+    {
+        loop {
+            // Sample from `(0, 1]` so that the logarithm is real.
+            let x = -(T::ONE - rng.gen::<T>()).ln() / self.rate;
+            if x.is_finite() && x > T::ZERO {
+                return Constrained::unchecked(x);
+            }
+        }
+    }
+}
+
+/// Beta distribution.
+///
+/// Samples are computed from a pair of gamma variates using the method of Marsaglia and Tsang and
+/// are always real numbers in the unit interval $[0, 1]$.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct Beta<T> {
+    alpha: T,
+    beta: T,
+}
+
+#[cfg(feature = "std")]
+impl<T> Beta<T>
+where
+    T: Primitive,
+{
+    /// Constructs a beta distribution from its shape parameters ($\alpha$ and $\beta$).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either shape parameter is not a positive real number.
+    pub fn new(alpha: T, beta: T) -> Result<Self, ParameterError> {
+        if [alpha, beta]
+            .into_iter()
+            .all(|shape| shape.is_finite() && shape > T::ZERO)
+        {
+            Ok(Beta { alpha, beta })
+        }
+        else {
+            Err(ParameterError)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, C> Distribution<Constrained<T, C>> for Beta<T>
+where
+    Standard: Distribution<T>,
+    T: FromPrimitive + Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> Constrained<T, C>
+    where
+        R: Rng + ?Sized,
+    {
+        loop {
+            let x = sample_gamma(rng, self.alpha);
+            let y = sample_gamma(rng, self.beta);
+            let z = x / (x + y);
+            // The sum of the gamma variates may be zero (`NaN`) or overflow (zero or `NaN`).
+            if z.is_finite() && (x + y).is_finite() {
+                return Constrained::unchecked(z);
+            }
+        }
+    }
+}
+
+impl<T, C> Distribution<Constrained<T, C>> for Standard
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> Constrained<T, C>
+    where
+        R: Rng + ?Sized,
+    {
+        Constrained::unchecked(rng.gen())
+    }
+}
+
+fn sample_unit_ball<T, R, F, const N: usize>(rng: &mut R, mut f: F) -> ([T; N], T)
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    R: Rng + ?Sized,
+    F: FnMut(T) -> bool,
+{
+    loop {
+        let point: [T; N] = [(); N].map(|_| (rng.gen::<T>() * (T::ONE + T::ONE)) - T::ONE);
+        let norm2 = point.iter().fold(T::ZERO, |norm2, x| norm2 + (*x * *x));
+        if norm2 < T::ONE && f(norm2) {
+            return (point, norm2);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn sample_standard_normal<T, R>(rng: &mut R) -> T
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    R: Rng + ?Sized,
+{
+    let two = T::ONE + T::ONE;
+    loop {
+        let x = (rng.gen::<T>() * two) - T::ONE;
+        let y = (rng.gen::<T>() * two) - T::ONE;
+        let r2 = (x * x) + (y * y);
+        if r2 > T::ZERO && r2 < T::ONE {
+            return x * ((-two * r2.ln()) / r2).sqrt();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn sample_gamma<T, R>(rng: &mut R, shape: T) -> T
+where
+    Standard: Distribution<T>,
+    T: FromPrimitive + Primitive,
+    R: Rng + ?Sized,
+{
+    if shape < T::ONE {
+        // Boost the shape parameter: if `X ~ Gamma(a + 1)` and `U ~ Uniform(0, 1)`, then
+        // `X * U^(1 / a) ~ Gamma(a)`.
+        let u = T::ONE - rng.gen::<T>();
+        return sample_gamma(rng, shape + T::ONE) * u.pow(T::ONE / shape);
+    }
+    let d = shape - T::from_f64(1.0 / 3.0).unwrap();
+    let c = T::ONE / (T::from_f64(9.0).unwrap() * d).sqrt();
+    loop {
+        let x = sample_standard_normal::<T, _>(rng);
+        let v = T::ONE + (c * x);
+        if v <= T::ZERO {
+            continue;
+        }
+        let v = v * v * v;
+        let u = T::ONE - rng.gen::<T>();
+        let x2 = x * x;
+        if u.ln() < ((x2 / (T::ONE + T::ONE)) + (d * (T::ONE - v + v.ln()))) {
+            return d * v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::{Distribution, Standard};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+
+    use crate::random::UnitBall;
+    use crate::R64;
+
+    #[test]
+    fn standard_in_unit_interval() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let x: R64 = Standard.sample(&mut rng);
+            assert!((0.0..1.0).contains(&x.into_inner()));
+        }
+    }
+
+    #[test]
+    fn unit_ball_interior() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let [x, y, z]: [R64; 3] = UnitBall.sample(&mut rng);
+            assert!((x * x) + (y * y) + (z * z) < 1.0);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn unit_sphere_surface() {
+        use crate::random::UnitSphere;
+        use crate::real::UnaryRealFunction;
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let [x, y]: [R64; 2] = UnitSphere.sample(&mut rng);
+            assert!((((x * x) + (y * y)) - R64::ONE).abs() < 1e-12);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn normal_moments() {
+        use crate::random::Normal;
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let normal = Normal::new(2.0, 3.0).unwrap();
+        let n = 10_000;
+        let samples: std::vec::Vec<R64> = (0..n).map(|_| normal.sample(&mut rng)).collect();
+        let mean = samples.iter().map(|x| x.into_inner()).sum::<f64>() / n as f64;
+        let variance = samples
+            .iter()
+            .map(|x| (x.into_inner() - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        assert!((mean - 2.0).abs() < 0.1);
+        assert!((variance - 9.0).abs() < 0.5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn exponential_positive() {
+        use crate::random::Exponential;
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let exponential = Exponential::new(f64::MIN_POSITIVE).unwrap();
+        for _ in 0..1000 {
+            let x: R64 = exponential.sample(&mut rng);
+            assert!(x > 0.0);
+        }
+        assert!(Exponential::new(0.0f64).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn beta_unit_interval() {
+        use crate::random::Beta;
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let n = 10_000;
+        for (alpha, beta) in [(0.5, 0.5), (2.0, 5.0), (1e-3, 1e-3)] {
+            let distribution = Beta::new(alpha, beta).unwrap();
+            let mut sum = 0.0;
+            for _ in 0..n {
+                let x: R64 = distribution.sample(&mut rng);
+                assert!((0.0..=1.0).contains(&x.into_inner()));
+                sum += x.into_inner();
+            }
+            assert!(((sum / n as f64) - (alpha / (alpha + beta))).abs() < 0.05);
+        }
+    }
+}
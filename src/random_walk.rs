@@ -0,0 +1,145 @@
+//! Reproducible jitter for retry backoff.
+//!
+//! Retrying failed operations with exponential backoff is commonly combined with _jitter_, which
+//! scales each delay by a random factor so that many clients do not retry in lockstep. This module
+//! provides [`Jitter`], a distribution of such factors, and [`jitter`], which produces a
+//! reproducible sequence of factors from a seed.
+//!
+//! Factors are [`Constrained`] types that are always real numbers in a bounded interval, so they
+//! can be multiplied into delays without checking for `NaN`s or infinities.
+//!
+//! # Examples
+//!
+//! Scaling exponential backoff delays by factors in $[0.5, 1]$ (so-called _equal jitter_):
+//!
+//! ```rust
+//! use decorum::random_walk::{self, Jitter};
+//! use decorum::R64;
+//! use rand::rngs::SmallRng;
+//!
+//! let factors = random_walk::jitter::<SmallRng, _, _>(7, Jitter::equal());
+//! let delays: Vec<f64> = factors
+//!     .zip(0..5)
+//!     .map(|(factor, attempt): (R64, i32)| (factor * 100.0 * 2.0f64.powi(attempt)).into_inner())
+//!     .collect();
+//! for (attempt, delay) in delays.into_iter().enumerate() {
+//!     let base = 100.0 * 2.0f64.powi(attempt as i32);
+//!     assert!(delay >= base / 2.0 && delay <= base);
+//! }
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+
+use rand::distributions::{DistIter, Distribution, Standard};
+use rand::{Rng, SeedableRng};
+
+use crate::constraint::Constraint;
+use crate::proxy::Constrained;
+use crate::random::ParameterError;
+use crate::Primitive;
+
+/// Uniform distribution of jitter factors in a closed interval.
+///
+/// Samples are always real numbers in the interval $[l, u]$, where $0 \le l \le u$.
+#[derive(Clone, Copy, Debug)]
+pub struct Jitter<T> {
+    lower: T,
+    upper: T,
+}
+
+impl<T> Jitter<T>
+where
+    T: Primitive,
+{
+    /// Constructs a jitter distribution over the closed interval $[l, u]$.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either bound is not a real number, if the lower bound is negative, or
+    /// if the lower bound is greater than the upper bound.
+    pub fn new(lower: T, upper: T) -> Result<Self, ParameterError> {
+        if lower.is_finite() && upper.is_finite() && T::ZERO <= lower && lower <= upper {
+            Ok(Jitter { lower, upper })
+        }
+        else {
+            Err(ParameterError)
+        }
+    }
+
+    /// Constructs a jitter distribution over $[0, 1]$ (_full jitter_).
+    pub fn full() -> Self {
+        Jitter {
+            lower: T::ZERO,
+            upper: T::ONE,
+        }
+    }
+
+    /// Constructs a jitter distribution over $[0.5, 1]$ (_equal jitter_).
+    pub fn equal() -> Self {
+        Jitter {
+            lower: T::ONE / (T::ONE + T::ONE),
+            upper: T::ONE,
+        }
+    }
+}
+
+impl<T, C> Distribution<Constrained<T, C>> for Jitter<T>
+where
+    Standard: Distribution<T>,
+    T: Primitive,
+    C: Constraint,
+{
+    fn sample<R>(&self, rng: &mut R) -> Constrained<T, C>
+    where
+        R: Rng + ?Sized,
+    {
+        let x = self.lower + ((self.upper - self.lower) * rng.gen::<T>());
+        // Rounding may exceed the upper bound.
+        Constrained::unchecked(if x > self.upper { self.upper } else { x })
+    }
+}
+
+/// Gets an unbounded sequence of jitter factors from a seeded generator.
+///
+/// The sequence depends only on the seed, the generator `R`, and the distribution.
+pub fn jitter<R, T, C>(seed: u64, jitter: Jitter<T>) -> DistIter<Jitter<T>, R, Constrained<T, C>>
+where
+    Standard: Distribution<T>,
+    R: Rng + SeedableRng,
+    T: Primitive,
+    C: Constraint,
+{
+    R::seed_from_u64(seed).sample_iter(jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::SmallRng;
+
+    use crate::random_walk::{self, Jitter};
+    use crate::R64;
+
+    #[test]
+    fn jitter_bounds() {
+        assert!(Jitter::new(1.0, 0.5).is_err());
+        assert!(Jitter::new(-1.0, 0.5).is_err());
+        assert!(Jitter::new(0.0, f64::INFINITY).is_err());
+
+        let jitter = Jitter::new(0.25, 0.75).unwrap();
+        assert!(random_walk::jitter::<SmallRng, _, _>(0, jitter)
+            .take(1000)
+            .all(|x: R64| (0.25..=0.75).contains(&x.into_inner())));
+    }
+
+    #[test]
+    fn jitter_seed() {
+        let sample = |seed| {
+            random_walk::jitter::<SmallRng, _, _>(seed, Jitter::full())
+                .take(8)
+                .map(|x: R64| x.into_inner())
+                .fold(0.0, |sum, x| sum + x)
+        };
+        assert_eq!(sample(42), sample(42));
+        assert!(sample(42) != sample(43));
+    }
+}
@@ -0,0 +1,634 @@
+//! Ranges of real numbers.
+//!
+//! [`FiniteRange`] is a closed interval with real (finite) bounds. Ranges compare values with the
+//! total ordering of [`Constrained`] types, so `NaN`s are never contained by a range and are
+//! clamped to its end. Ranges implement [`RangeBounds`] and so can be used with APIs that accept
+//! standard ranges.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::range::FiniteRange;
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::{Total, R64};
+//!
+//! let unit = FiniteRange::new(<R64>::ZERO, <R64>::ONE);
+//! assert!(unit.contains(&<R64>::assert(0.5)));
+//! assert_eq!(unit.clamp(Total::from(f64::NAN)), Total::<f64>::ONE);
+//! ```
+//!
+//! This module also provides iterators over evenly spaced real numbers: [`linspace`],
+//! `logspace` (with the `std` feature), and [`arange`]. Unlike hand-written loops that accumulate a step, these compute
+//! each value from its index, so errors do not accumulate and endpoints are exact.
+//!
+//! ```rust
+//! use decorum::range;
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::R64;
+//!
+//! let xs: Vec<_> = range::linspace(<R64>::ZERO, <R64>::ONE, 5)
+//!     .unwrap()
+//!     .collect();
+//! assert_eq!(xs, [0.0, 0.25, 0.5, 0.75, 1.0]);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`RangeBounds`]: core::ops::RangeBounds
+
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+use num_traits::FromPrimitive;
+use thiserror::Error;
+
+use crate::constraint::NotRealError;
+use crate::expression::Expression;
+use crate::expression::Expression::{Defined, Undefined};
+use crate::proxy::Constrained;
+use crate::{Primitive, Real, Total};
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum SpaceError {
+    #[error("{}", "space has no values")]
+    Empty,
+    #[error("{}", "step is zero or has the wrong sign")]
+    Step,
+    #[error("{}", "base is not positive")]
+    Base,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for SpaceError {
+    fn from(error: NotRealError) -> Self {
+        SpaceError::NotReal(error)
+    }
+}
+
+/// Closed interval with real bounds.
+///
+/// See the [module documentation][`range`].
+///
+/// [`range`]: crate::range
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct FiniteRange<T>
+where
+    T: Primitive,
+{
+    start: Total<T>,
+    end: Total<T>,
+}
+
+impl<T> FiniteRange<T>
+where
+    T: Primitive,
+{
+    /// Constructs a range from `start` to `end` inclusive.
+    ///
+    /// The range is empty if `start` is greater than `end`.
+    pub fn new<D>(start: Real<T, D>, end: Real<T, D>) -> Self {
+        FiniteRange {
+            start: Total::from(start.into_inner()),
+            end: Total::from(end.into_inner()),
+        }
+    }
+
+    /// Constructs a range from primitive floating-point bounds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `start` or `end` is not a real number.
+    pub fn try_new(start: T, end: T) -> Result<Self, NotRealError> {
+        if start.is_finite() && end.is_finite() {
+            Ok(FiniteRange {
+                start: Total::from(start),
+                end: Total::from(end),
+            })
+        }
+        else {
+            Err(NotRealError)
+        }
+    }
+
+    pub fn start(&self) -> T {
+        self.start.into_inner()
+    }
+
+    pub fn end(&self) -> T {
+        self.end.into_inner()
+    }
+
+    /// Returns `true` if the range contains `x`.
+    pub fn contains<C>(&self, x: &Constrained<T, C>) -> bool {
+        let x = x.into_inner();
+        self.start() <= x && x <= self.end()
+    }
+
+    /// Restricts `x` to the range.
+    ///
+    /// `NaN`s are greater than all other values in the total ordering of [`Constrained`] types,
+    /// so `NaN`s are clamped to the end of the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    ///
+    /// [`Constrained`]: crate::proxy::Constrained
+    pub fn clamp<C>(&self, x: Constrained<T, C>) -> Constrained<T, C> {
+        assert!(!self.is_empty(), "range is empty");
+        // The bounds are real numbers, which satisfy any constraint.
+        x.clamp(
+            Constrained::unchecked(self.start()),
+            Constrained::unchecked(self.end()),
+        )
+    }
+
+    /// Gets the intersection of the ranges, which may be empty.
+    pub fn intersect(&self, other: &Self) -> Self {
+        FiniteRange {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+}
+
+impl<T, D> From<RangeInclusive<Real<T, D>>> for FiniteRange<T>
+where
+    T: Primitive,
+{
+    fn from(range: RangeInclusive<Real<T, D>>) -> Self {
+        let (start, end) = range.into_inner();
+        FiniteRange::new(start, end)
+    }
+}
+
+impl<T> RangeBounds<T> for FiniteRange<T>
+where
+    T: Primitive,
+{
+    fn start_bound(&self) -> Bound<&T> {
+        Bound::Included(self.start.as_ref())
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        Bound::Included(self.end.as_ref())
+    }
+}
+
+/// Gets an iterator over `n` evenly spaced values from `start` to `stop` inclusive.
+///
+/// The first and last values are exactly `start` and `stop` and all values are between them. If
+/// `n` is one, then the only value is `start`.
+///
+/// # Errors
+///
+/// Returns `Undefined` if `n` is zero.
+pub fn linspace<T, D>(
+    start: Real<T, D>,
+    stop: Real<T, D>,
+    n: usize,
+) -> Expression<Linspace<T, D>, SpaceError>
+where
+    T: FromPrimitive + Primitive,
+{
+    if n == 0 {
+        Undefined(SpaceError::Empty)
+    }
+    else {
+        Defined(Linspace {
+            start: start.into_inner(),
+            stop: stop.into_inner(),
+            n,
+            front: 0,
+            back: n,
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// Gets an iterator over `n` values from `base^start` to `base^stop` inclusive that are evenly
+/// spaced on a logarithmic scale.
+///
+/// # Errors
+///
+/// Returns `Undefined` if `n` is zero, `base` is not positive, or `base^start` or `base^stop` is
+/// not a real number.
+#[cfg(feature = "std")]
+pub fn logspace<T, D>(
+    start: Real<T, D>,
+    stop: Real<T, D>,
+    n: usize,
+    base: Real<T, D>,
+) -> Expression<Logspace<T, D>, SpaceError>
+where
+    T: FromPrimitive + Primitive,
+{
+    let base = base.into_inner();
+    if base <= T::ZERO {
+        return Undefined(SpaceError::Base);
+    }
+    let (first, last) = (base.pow(start.into_inner()), base.pow(stop.into_inner()));
+    if !(first.is_finite() && last.is_finite()) {
+        return Undefined(NotRealError.into());
+    }
+    linspace(start, stop, n).map(|exponents| Logspace {
+        exponents,
+        base,
+        min: if first <= last { first } else { last },
+        max: if first <= last { last } else { first },
+    })
+}
+
+/// Gets an iterator over the values `start + (i * step)` that are less than `stop` (or greater than
+/// `stop` if `step` is negative).
+///
+/// # Errors
+///
+/// Returns `Undefined` if `step` is zero or its sign is the opposite of `stop - start`.
+pub fn arange<T, D>(
+    start: Real<T, D>,
+    stop: Real<T, D>,
+    step: Real<T, D>,
+) -> Expression<Arange<T, D>, SpaceError>
+where
+    T: FromPrimitive + Primitive,
+{
+    let (start, stop, step) = (start.into_inner(), stop.into_inner(), step.into_inner());
+    if step == T::ZERO || (start < stop && step < T::ZERO) || (start > stop && step > T::ZERO) {
+        Undefined(SpaceError::Step)
+    }
+    else {
+        Defined(Arange {
+            start,
+            stop,
+            step,
+            index: 0,
+            is_done: false,
+            phantom: PhantomData,
+        })
+    }
+}
+
+/// Iterator over evenly spaced values. See [`linspace`].
+pub struct Linspace<T, D> {
+    start: T,
+    stop: T,
+    n: usize,
+    front: usize,
+    back: usize,
+    phantom: PhantomData<fn() -> D>,
+}
+
+impl<T, D> Linspace<T, D>
+where
+    T: FromPrimitive + Primitive,
+{
+    fn get(&self, index: usize) -> Real<T, D> {
+        Constrained::unchecked(if index == 0 {
+            self.start
+        }
+        else if index + 1 == self.n {
+            self.stop
+        }
+        else {
+            let t = T::from_usize(index).unwrap() / T::from_usize(self.n - 1).unwrap();
+            // Interpolating from both ends avoids overflow in `stop - start`. Clamping guarantees
+            // that the value is real and between the endpoints despite rounding.
+            let x = (self.start * (T::ONE - t)) + (self.stop * t);
+            clamp(x, self.start, self.stop)
+        })
+    }
+}
+
+impl<T, D> Clone for Linspace<T, D>
+where
+    T: Primitive,
+{
+    fn clone(&self) -> Self {
+        Linspace {
+            phantom: PhantomData,
+            ..*self
+        }
+    }
+}
+
+impl<T, D> Debug for Linspace<T, D>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Linspace")
+            .field("start", &self.start)
+            .field("stop", &self.stop)
+            .field("n", &self.n)
+            .finish()
+    }
+}
+
+impl<T, D> DoubleEndedIterator for Linspace<T, D>
+where
+    T: FromPrimitive + Primitive,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            Some(self.get(self.back))
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl<T, D> ExactSizeIterator for Linspace<T, D> where T: FromPrimitive + Primitive {}
+
+impl<T, D> FusedIterator for Linspace<T, D> where T: FromPrimitive + Primitive {}
+
+impl<T, D> Iterator for Linspace<T, D>
+where
+    T: FromPrimitive + Primitive,
+{
+    type Item = Real<T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.front += 1;
+            Some(self.get(self.front - 1))
+        }
+        else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+/// Iterator over values that are evenly spaced on a logarithmic scale. See [`logspace`].
+#[cfg(feature = "std")]
+pub struct Logspace<T, D> {
+    exponents: Linspace<T, D>,
+    base: T,
+    min: T,
+    max: T,
+}
+
+#[cfg(feature = "std")]
+impl<T, D> Logspace<T, D>
+where
+    T: Primitive,
+{
+    fn power(&self, exponent: Real<T, D>) -> Real<T, D> {
+        // Powers of a positive base are monotonic and the powers of the endpoints are real, so
+        // clamping to them guarantees that the value is real.
+        let x = self.base.pow(exponent.into_inner());
+        Constrained::unchecked(if x.is_nan() {
+            self.min
+        }
+        else {
+            clamp(x, self.min, self.max)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, D> Clone for Logspace<T, D>
+where
+    T: Primitive,
+{
+    fn clone(&self) -> Self {
+        Logspace {
+            exponents: self.exponents.clone(),
+            ..*self
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, D> Debug for Logspace<T, D>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Logspace")
+            .field("exponents", &self.exponents)
+            .field("base", &self.base)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, D> DoubleEndedIterator for Logspace<T, D>
+where
+    T: FromPrimitive + Primitive,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.exponents
+            .next_back()
+            .map(|exponent| self.power(exponent))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, D> ExactSizeIterator for Logspace<T, D> where T: FromPrimitive + Primitive {}
+
+#[cfg(feature = "std")]
+impl<T, D> FusedIterator for Logspace<T, D> where T: FromPrimitive + Primitive {}
+
+#[cfg(feature = "std")]
+impl<T, D> Iterator for Logspace<T, D>
+where
+    T: FromPrimitive + Primitive,
+{
+    type Item = Real<T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.exponents.next().map(|exponent| self.power(exponent))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.exponents.size_hint()
+    }
+}
+
+/// Iterator over values with a fixed step. See [`arange`].
+pub struct Arange<T, D> {
+    start: T,
+    stop: T,
+    step: T,
+    index: usize,
+    is_done: bool,
+    phantom: PhantomData<fn() -> D>,
+}
+
+impl<T, D> Clone for Arange<T, D>
+where
+    T: Primitive,
+{
+    fn clone(&self) -> Self {
+        Arange {
+            phantom: PhantomData,
+            ..*self
+        }
+    }
+}
+
+impl<T, D> Debug for Arange<T, D>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Arange")
+            .field("start", &self.start)
+            .field("stop", &self.stop)
+            .field("step", &self.step)
+            .finish()
+    }
+}
+
+impl<T, D> FusedIterator for Arange<T, D> where T: FromPrimitive + Primitive {}
+
+impl<T, D> Iterator for Arange<T, D>
+where
+    T: FromPrimitive + Primitive,
+{
+    type Item = Real<T, D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+        let x = self.start + (T::from_usize(self.index)? * self.step);
+        // Values beyond `stop` (including infinities) end the iteration, so yielded values are
+        // always real.
+        if (self.step > T::ZERO && x < self.stop) || (self.step < T::ZERO && x > self.stop) {
+            self.index += 1;
+            Some(Constrained::unchecked(x))
+        }
+        else {
+            self.is_done = true;
+            None
+        }
+    }
+}
+
+// Clamps `x` to the closed interval between `a` and `b` in either order.
+fn clamp<T>(x: T, a: T, b: T) -> T
+where
+    T: Primitive,
+{
+    let (min, max) = if a <= b { (a, b) } else { (b, a) };
+    if x < min {
+        min
+    }
+    else if x > max {
+        max
+    }
+    else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ops::RangeBounds;
+
+    use crate::expression::Expression;
+    use crate::range::{self, FiniteRange, SpaceError};
+    use crate::real::UnaryRealFunction;
+    use crate::{Total, R64};
+
+    #[test]
+    fn contains() {
+        let range = FiniteRange::new(<R64>::assert(-1.0), <R64>::ONE);
+        assert!(range.contains(&<R64>::ZERO));
+        assert!(range.contains(&<R64>::ONE));
+        assert!(!range.contains(&<R64>::assert(2.0)));
+        assert!(!range.contains(&Total::from(f64::NAN)));
+        assert!(RangeBounds::contains(&range, &-0.5));
+        assert!(FiniteRange::try_new(0.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn clamp() {
+        let range = FiniteRange::from(<R64>::ZERO..=<R64>::ONE);
+        assert_eq!(range.clamp(<R64>::assert(2.0)), <R64>::ONE);
+        assert_eq!(range.clamp(<R64>::assert(-2.0)), <R64>::ZERO);
+        assert_eq!(range.clamp(Total::from(f64::NEG_INFINITY)), 0.0);
+        assert_eq!(range.clamp(Total::from(f64::NAN)), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clamp_empty() {
+        let range = FiniteRange::new(<R64>::ONE, <R64>::ZERO);
+        let _ = range.clamp(<R64>::ONE);
+    }
+
+    #[test]
+    fn intersect() {
+        let a = FiniteRange::new(<R64>::ZERO, <R64>::assert(2.0));
+        let b = FiniteRange::new(<R64>::ONE, <R64>::assert(3.0));
+        assert_eq!(
+            a.intersect(&b),
+            FiniteRange::new(<R64>::ONE, <R64>::assert(2.0))
+        );
+        let c = FiniteRange::new(<R64>::assert(4.0), <R64>::assert(5.0));
+        assert!(a.intersect(&c).is_empty());
+        assert!(!a.is_empty());
+    }
+
+    #[test]
+    fn linspace() {
+        let xs = range::linspace(<R64>::assert(-1.0), <R64>::assert(0.1), 12).unwrap();
+        assert_eq!(xs.len(), 12);
+        assert_eq!(xs.clone().next().unwrap(), -1.0);
+        assert_eq!(xs.clone().next_back().unwrap(), 0.1);
+        assert!(xs.clone().zip(xs.skip(1)).all(|(a, b)| a < b));
+        let mut xs = range::linspace(<R64>::assert(f64::MAX), <R64>::assert(f64::MIN), 3).unwrap();
+        assert_eq!(xs.nth(1).unwrap(), 0.0);
+        assert!(range::linspace(<R64>::ZERO, <R64>::ONE, 1)
+            .unwrap()
+            .eq([<R64>::ZERO]));
+        assert!(matches!(
+            range::linspace(<R64>::ZERO, <R64>::ONE, 0),
+            Expression::Undefined(SpaceError::Empty)
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn logspace() {
+        let ten = <R64>::assert(10.0);
+        let xs = range::logspace(<R64>::ZERO, <R64>::assert(2.0), 3, ten).unwrap();
+        assert!(xs.eq([1.0, 10.0, 100.0]));
+        assert!(range::logspace(<R64>::ZERO, <R64>::assert(400.0), 3, ten).is_undefined());
+        assert!(range::logspace(<R64>::ZERO, <R64>::ONE, 3, -ten).is_undefined());
+    }
+
+    #[test]
+    fn arange() {
+        let xs = range::arange(<R64>::ZERO, <R64>::ONE, <R64>::assert(0.25)).unwrap();
+        assert!(xs.eq([0.0, 0.25, 0.5, 0.75]));
+        let xs = range::arange(<R64>::ONE, <R64>::ZERO, <R64>::assert(-0.5)).unwrap();
+        assert!(xs.eq([1.0, 0.5]));
+        assert_eq!(
+            range::arange(<R64>::ZERO, <R64>::ZERO, <R64>::ONE)
+                .unwrap()
+                .count(),
+            0
+        );
+        assert!(range::arange(<R64>::ZERO, <R64>::ONE, <R64>::ZERO).is_undefined());
+        assert!(range::arange(<R64>::ZERO, <R64>::ONE, -<R64>::ONE).is_undefined());
+    }
+}
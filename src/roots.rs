@@ -0,0 +1,372 @@
+//! Real roots of quadratic and cubic polynomials over [`Constrained`] coefficients.
+//!
+//! This module provides closed-form root solvers that avoid the cancellation of the textbook
+//! formulae. The discriminant of a quadratic is computed with error-free products (the same
+//! primitives used by the robust predicates of the [`geometry`] module) and roots are computed
+//! with the stable form of the quadratic formula. The roots of a cubic are computed with the
+//! trigonometric method or Cardano's method (depending on the discriminant) and are polished with
+//! a step of Newton's method.
+//!
+//! Solvers output an [`Expression`] of the configuration of real roots, which describes their
+//! multiplicity. Roots are sorted in increasing order. Multiplicity is determined from exact
+//! comparisons of the computed discriminant, so nearly repeated roots may be reported as distinct.
+//! If the leading coefficient is zero, then the polynomial is degenerate and the output is
+//! [`Undefined`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::roots::{self, QuadraticRoots};
+//! use decorum::R64;
+//!
+//! let r = |x: f64| -> R64 { R64::assert(x) };
+//!
+//! // x^2 - 3x + 2 = (x - 1)(x - 2)
+//! let roots = roots::quadratic(r(1.0), r(-3.0), r(2.0)).unwrap();
+//! assert_eq!(roots, QuadraticRoots::Distinct(r(1.0), r(2.0)));
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`geometry`]: crate::geometry
+//! [`Undefined`]: crate::expression::Expression::Undefined
+
+use thiserror::Error;
+
+use crate::constraint::{Constraint, NotRealError};
+use crate::expression::{Defined, Expression, Undefined};
+use crate::geometry::{abs, integer, splitter, two_product};
+use crate::proxy::Constrained;
+use crate::{try_expression, Primitive};
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum RootError {
+    #[error("{}", "leading coefficient is zero")]
+    Degenerate,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for RootError {
+    fn from(error: NotRealError) -> Self {
+        RootError::NotReal(error)
+    }
+}
+
+/// Real roots of a quadratic polynomial.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum QuadraticRoots<P> {
+    /// There are no real roots: both roots are complex.
+    Complex,
+    /// There is one real root with multiplicity two.
+    Double(P),
+    /// There are two distinct real roots in increasing order.
+    Distinct(P, P),
+}
+
+/// Real roots of a cubic polynomial.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CubicRoots<P> {
+    /// There is one real root. The other roots are complex.
+    One(P),
+    /// There is a simple real root and a real root with multiplicity two.
+    SimpleAndDouble { simple: P, double: P },
+    /// There is one real root with multiplicity three.
+    Triple(P),
+    /// There are three distinct real roots in increasing order.
+    Distinct(P, P, P),
+}
+
+/// Computes the real roots of $ax^2+bx+c$.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`RootError::Degenerate`] if `a` is zero and [`RootError::NotReal`]
+/// if any coefficient or root is not a real number or if the computation overflows.
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn quadratic<T, C>(
+    a: Constrained<T, C>,
+    b: Constrained<T, C>,
+    c: Constrained<T, C>,
+) -> Expression<QuadraticRoots<Constrained<T, C>>, RootError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let [a, b, c] = try_expression!(into_primitives([a, b, c]));
+    if a.is_zero() {
+        return Undefined(RootError::Degenerate);
+    }
+    let discriminant = try_expression!(discriminant(a, b, c));
+    if discriminant < T::ZERO {
+        Defined(QuadraticRoots::Complex)
+    }
+    else if discriminant.is_zero() {
+        let root = -b / (integer::<T>(2) * a);
+        Defined(QuadraticRoots::Double(try_expression!(into_root(root))))
+    }
+    else {
+        // Avoid cancellation between `b` and the square root of the discriminant.
+        let root = discriminant.sqrt();
+        let q = if b < T::ZERO {
+            (root - b) / integer::<T>(2)
+        }
+        else {
+            -(b + root) / integer::<T>(2)
+        };
+        let [x0, x1] = sort([q / a, c / q]);
+        Defined(QuadraticRoots::Distinct(
+            try_expression!(into_root(x0)),
+            try_expression!(into_root(x1)),
+        ))
+    }
+}
+
+/// Computes the real roots of $ax^3+bx^2+cx+d$.
+///
+/// # Errors
+///
+/// Returns [`Undefined`] with [`RootError::Degenerate`] if `a` is zero and [`RootError::NotReal`]
+/// if any coefficient or root is not a real number or if the computation overflows.
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::roots::{self, CubicRoots};
+/// use decorum::R64;
+///
+/// let r = |x: f64| -> R64 { R64::assert(x) };
+///
+/// // x^3 - 3x + 2 = (x - 1)^2 (x + 2)
+/// let roots = roots::cubic(r(1.0), r(0.0), r(-3.0), r(2.0)).unwrap();
+/// assert_eq!(
+///     roots,
+///     CubicRoots::SimpleAndDouble {
+///         simple: r(-2.0),
+///         double: r(1.0),
+///     },
+/// );
+/// ```
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+pub fn cubic<T, C>(
+    a: Constrained<T, C>,
+    b: Constrained<T, C>,
+    c: Constrained<T, C>,
+    d: Constrained<T, C>,
+) -> Expression<CubicRoots<Constrained<T, C>>, RootError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    let coefficients = try_expression!(into_primitives([a, b, c, d]));
+    let [a, b, c, d] = coefficients;
+    if a.is_zero() {
+        return Undefined(RootError::Degenerate);
+    }
+    let (two, three) = (integer::<T>(2), integer::<T>(3));
+    // Normalize the polynomial to $x^3+px^2+qx+r$.
+    let (p, q, r) = (b / a, c / a, d / a);
+    let shift = p / three;
+    let s = ((p * p) - (three * q)) / integer::<T>(9);
+    let t =
+        ((two * p * p * p) - (integer::<T>(9) * p * q) + (integer::<T>(27) * r)) / integer::<T>(54);
+    let (s3, t2) = (s * s * s, t * t);
+    if !(s3.is_finite() && t2.is_finite()) {
+        return Undefined(NotRealError.into());
+    }
+
+    let polish = |x: T| polish(coefficients, x);
+    if t2 < s3 {
+        // There are three distinct real roots.
+        let theta = (t / s3.sqrt()).acos();
+        let m = -two * s.sqrt();
+        let tau = two * T::PI;
+        let [x0, x1, x2] = sort([
+            polish((m * (theta / three).cos()) - shift),
+            polish((m * ((theta + tau) / three).cos()) - shift),
+            polish((m * ((theta - tau) / three).cos()) - shift),
+        ]);
+        Defined(CubicRoots::Distinct(
+            try_expression!(into_root(x0)),
+            try_expression!(into_root(x1)),
+            try_expression!(into_root(x2)),
+        ))
+    }
+    else {
+        let u = (abs(t) + (t2 - s3).sqrt()).cbrt();
+        let u = if t < T::ZERO { u } else { -u };
+        let v = if u.is_zero() { T::ZERO } else { s / u };
+        if t2 == s3 {
+            if u.is_zero() {
+                Defined(CubicRoots::Triple(try_expression!(into_root(-shift))))
+            }
+            else {
+                Defined(CubicRoots::SimpleAndDouble {
+                    simple: try_expression!(into_root(polish((u + v) - shift))),
+                    double: try_expression!(into_root((-(u + v) / two) - shift)),
+                })
+            }
+        }
+        else {
+            Defined(CubicRoots::One(try_expression!(into_root(polish(
+                (u + v) - shift
+            )))))
+        }
+    }
+}
+
+// Computes $b^2-4ac$ with error-free products to avoid cancellation.
+fn discriminant<T>(a: T, b: T, c: T) -> Expression<T, NotRealError>
+where
+    T: Primitive,
+{
+    let splitter = splitter::<T>();
+    let (bb, bb_error) = two_product(b, b, splitter);
+    let (ac, ac_error) = two_product(integer::<T>(4) * a, c, splitter);
+    let discriminant = (bb - ac) + (bb_error - ac_error);
+    if discriminant.is_finite() {
+        Defined(discriminant)
+    }
+    else {
+        Undefined(NotRealError)
+    }
+}
+
+// Applies a step of Newton's method to a root of a cubic if it reduces the residual.
+fn polish<T>([a, b, c, d]: [T; 4], x: T) -> T
+where
+    T: Primitive,
+{
+    let f = |x: T| (((a * x) + b) * x + c) * x + d;
+    let derivative = (((integer::<T>(3) * a * x) + (integer::<T>(2) * b)) * x) + c;
+    if derivative.is_zero() {
+        return x;
+    }
+    let y = x - (f(x) / derivative);
+    if y.is_finite() && abs(f(y)) < abs(f(x)) {
+        y
+    }
+    else {
+        x
+    }
+}
+
+fn sort<T, const N: usize>(mut xs: [T; N]) -> [T; N]
+where
+    T: Primitive,
+{
+    // Insertion sort: `N` is at most three.
+    for i in 1..N {
+        let mut j = i;
+        while j > 0 && xs[j - 1] > xs[j] {
+            xs.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+    xs
+}
+
+fn into_primitives<T, C, const N: usize>(
+    coefficients: [Constrained<T, C>; N],
+) -> Expression<[T; N], NotRealError>
+where
+    T: Primitive,
+{
+    let coefficients = coefficients.map(Constrained::into_inner);
+    if coefficients.iter().all(|x| x.is_finite()) {
+        Defined(coefficients)
+    }
+    else {
+        Undefined(NotRealError)
+    }
+}
+
+fn into_root<T, C>(x: T) -> Expression<Constrained<T, C>, NotRealError>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    if x.is_finite() {
+        // Real numbers satisfy any constraint.
+        Defined(Constrained::unchecked(x))
+    }
+    else {
+        Undefined(NotRealError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::roots::{self, CubicRoots, QuadraticRoots, RootError};
+    use crate::{Total, R64};
+
+    fn r(x: f64) -> R64 {
+        R64::assert(x)
+    }
+
+    #[test]
+    fn quadratic() {
+        assert_eq!(
+            roots::quadratic(r(2.0), r(-2.0), r(-4.0)).unwrap(),
+            QuadraticRoots::Distinct(r(-1.0), r(2.0))
+        );
+        assert_eq!(
+            roots::quadratic(r(1.0), r(-2.0), r(1.0)).unwrap(),
+            QuadraticRoots::Double(r(1.0))
+        );
+        assert_eq!(
+            roots::quadratic(r(1.0), r(0.0), r(1.0)).unwrap(),
+            QuadraticRoots::Complex
+        );
+        assert!(matches!(
+            roots::quadratic(r(0.0), r(1.0), r(1.0)).undefined(),
+            Some(RootError::Degenerate)
+        ));
+        assert!(matches!(
+            roots::quadratic(Total::from(1.0), Total::from(f64::NAN), Total::from(1.0)).undefined(),
+            Some(RootError::NotReal(_))
+        ));
+    }
+
+    #[test]
+    fn quadratic_stable() {
+        // The naive formula computes the small root as zero.
+        let roots = roots::quadratic(r(1.0), r(1.0e9), r(1.0)).unwrap();
+        let QuadraticRoots::Distinct(x0, x1) = roots
+        else {
+            panic!()
+        };
+        assert_eq!(x0, r(-1.0e9));
+        assert!((x1.into_inner() + 1.0e-9).abs() < 1.0e-24);
+
+        // The naive discriminant is zero.
+        let e = f64::EPSILON;
+        assert!(matches!(
+            roots::quadratic(r(1.0), r(2.0 + (2.0 * e)), r(1.0 + (2.0 * e))).unwrap(),
+            QuadraticRoots::Distinct(_, _)
+        ));
+    }
+
+    #[test]
+    fn cubic() {
+        let CubicRoots::Distinct(x0, x1, x2) =
+            roots::cubic(r(1.0), r(-6.0), r(11.0), r(-6.0)).unwrap()
+        else {
+            panic!()
+        };
+        assert_eq!([x0, x1, x2], [r(1.0), r(2.0), r(3.0)]);
+        assert_eq!(
+            roots::cubic(r(1.0), r(0.0), r(0.0), r(-8.0)).unwrap(),
+            CubicRoots::One(r(2.0))
+        );
+        assert_eq!(
+            roots::cubic(r(1.0), r(-3.0), r(3.0), r(-1.0)).unwrap(),
+            CubicRoots::Triple(r(1.0))
+        );
+        assert!(matches!(
+            roots::cubic(r(0.0), r(1.0), r(1.0), r(1.0)).undefined(),
+            Some(RootError::Degenerate)
+        ));
+    }
+}
@@ -0,0 +1,355 @@
+//! Arithmetic with saturation semantics.
+//!
+//! The arithmetic operators of [`Constrained`] types diverge when an output does not satisfy the
+//! constraint of the proxy. The wrappers in this module instead always map outputs into a set of
+//! real numbers, regardless of the divergence of the proxy, much like [`core::num::Saturating`]
+//! does for integers.
+//!
+//! - [`Saturating`] maps infinities to the extreme finite values of the primitive type.
+//! - [`Clamping`] restricts outputs to a [`FiniteRange`].
+//!
+//! In both cases, `NaN` outputs are mapped to zero (before restricting to a range), as in
+//! saturating conversions from floating-point to integer types. Because outputs are always real
+//! numbers, they satisfy any constraint and these operations never diverge.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::saturating::Saturating;
+//! use decorum::R64;
+//!
+//! let x = Saturating(<R64>::assert(f64::MAX));
+//! assert_eq!((x + x).into_inner(), f64::MAX);
+//! assert_eq!((x / Saturating(<R64>::ZERO)).into_inner(), f64::MAX);
+//! assert_eq!((Saturating(<R64>::ZERO) / <R64>::ZERO).into_inner(), 0.0);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`FiniteRange`]: crate::range::FiniteRange
+
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+use crate::proxy::Constrained;
+use crate::range::FiniteRange;
+use crate::Primitive;
+
+/// [`Constrained`] type with saturating arithmetic.
+///
+/// Outputs that overflow saturate to the extreme finite values of the primitive type and `NaN`
+/// outputs are mapped to zero. See the [module documentation][`saturating`].
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`saturating`]: crate::saturating
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Saturating<T>(pub T);
+
+impl<T> Saturating<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, C> Saturating<Constrained<T, C>>
+where
+    T: Primitive,
+{
+    fn saturate(inner: T) -> Self {
+        // Saturated values are real numbers, which satisfy any constraint.
+        Saturating(Constrained::unchecked(saturate(inner)))
+    }
+}
+
+impl<T> Display for Saturating<T>
+where
+    T: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(formatter)
+    }
+}
+
+impl<T, C> From<Constrained<T, C>> for Saturating<Constrained<T, C>> {
+    fn from(proxy: Constrained<T, C>) -> Self {
+        Saturating(proxy)
+    }
+}
+
+impl<T, C> Neg for Saturating<Constrained<T, C>>
+where
+    T: Primitive,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Saturating::saturate(-self.0.into_inner())
+    }
+}
+
+/// [`Constrained`] type with arithmetic that clamps outputs to a [`FiniteRange`].
+///
+/// `NaN` outputs are mapped to zero before they are clamped. The range of a binary operation is
+/// the range of its left-hand operand. See the [module documentation][`saturating`].
+///
+/// # Examples
+///
+/// ```rust
+/// use decorum::range::FiniteRange;
+/// use decorum::real::UnaryRealFunction;
+/// use decorum::saturating::Clamping;
+/// use decorum::R64;
+///
+/// let x = Clamping::new(
+///     <R64>::assert(0.75),
+///     FiniteRange::new(<R64>::ZERO, <R64>::ONE),
+/// );
+/// assert_eq!((x + <R64>::assert(0.5)).into_inner(), 1.0);
+/// assert_eq!((x - <R64>::ONE).into_inner(), 0.0);
+/// ```
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`FiniteRange`]: crate::range::FiniteRange
+/// [`saturating`]: crate::saturating
+pub struct Clamping<T, C>
+where
+    T: Primitive,
+{
+    value: Constrained<T, C>,
+    range: FiniteRange<T>,
+}
+
+impl<T, C> Clamping<T, C>
+where
+    T: Primitive,
+{
+    /// Constructs a `Clamping` from a proxy and a range, clamping the proxy to the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn new(value: Constrained<T, C>, range: FiniteRange<T>) -> Self {
+        Clamping {
+            value: range.clamp(value),
+            range,
+        }
+    }
+
+    pub fn range(&self) -> FiniteRange<T> {
+        self.range
+    }
+
+    pub fn into_inner(self) -> Constrained<T, C> {
+        self.value
+    }
+
+    fn clamp(&self, inner: T) -> Self {
+        let inner = if inner.is_nan() { T::ZERO } else { inner };
+        Clamping {
+            // The bounds of the range are real numbers, which satisfy any constraint.
+            value: self.range.clamp(Constrained::unchecked(inner)),
+            range: self.range,
+        }
+    }
+}
+
+impl<T, C> Clone for Clamping<T, C>
+where
+    T: Primitive,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C> Copy for Clamping<T, C> where T: Primitive {}
+
+impl<T, C> Debug for Clamping<T, C>
+where
+    T: Debug + Primitive,
+    Constrained<T, C>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Clamping")
+            .field("value", &self.value)
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+impl<T, C> Display for Clamping<T, C>
+where
+    T: Primitive,
+    Constrained<T, C>: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        self.value.fmt(formatter)
+    }
+}
+
+impl<T, C> PartialEq for Clamping<T, C>
+where
+    T: Primitive,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.range == other.range
+    }
+}
+
+impl<T, C> Neg for Clamping<T, C>
+where
+    T: Primitive,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.clamp(-self.value.into_inner())
+    }
+}
+
+macro_rules! impl_binary_operation {
+    (operation => $trait:ident :: $method:ident, assign => $assign:ident :: $assign_method:ident) => {
+        impl<T, C> $trait for Saturating<Constrained<T, C>>
+        where
+            T: Primitive,
+        {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self::Output {
+                self.$method(other.0)
+            }
+        }
+
+        impl<T, C> $trait<Constrained<T, C>> for Saturating<Constrained<T, C>>
+        where
+            T: Primitive,
+        {
+            type Output = Self;
+
+            fn $method(self, other: Constrained<T, C>) -> Self::Output {
+                Saturating::saturate($trait::$method(self.0.into_inner(), other.into_inner()))
+            }
+        }
+
+        impl<T, C> $assign for Saturating<Constrained<T, C>>
+        where
+            T: Primitive,
+        {
+            fn $assign_method(&mut self, other: Self) {
+                *self = $trait::$method(*self, other);
+            }
+        }
+
+        impl<T, C> $assign<Constrained<T, C>> for Saturating<Constrained<T, C>>
+        where
+            T: Primitive,
+        {
+            fn $assign_method(&mut self, other: Constrained<T, C>) {
+                *self = $trait::$method(*self, other);
+            }
+        }
+
+        impl<T, C> $trait for Clamping<T, C>
+        where
+            T: Primitive,
+        {
+            type Output = Self;
+
+            fn $method(self, other: Self) -> Self::Output {
+                self.$method(other.value)
+            }
+        }
+
+        impl<T, C> $trait<Constrained<T, C>> for Clamping<T, C>
+        where
+            T: Primitive,
+        {
+            type Output = Self;
+
+            fn $method(self, other: Constrained<T, C>) -> Self::Output {
+                self.clamp($trait::$method(self.value.into_inner(), other.into_inner()))
+            }
+        }
+
+        impl<T, C> $assign for Clamping<T, C>
+        where
+            T: Primitive,
+        {
+            fn $assign_method(&mut self, other: Self) {
+                *self = $trait::$method(*self, other);
+            }
+        }
+
+        impl<T, C> $assign<Constrained<T, C>> for Clamping<T, C>
+        where
+            T: Primitive,
+        {
+            fn $assign_method(&mut self, other: Constrained<T, C>) {
+                *self = $trait::$method(*self, other);
+            }
+        }
+    };
+}
+impl_binary_operation!(operation => Add::add, assign => AddAssign::add_assign);
+impl_binary_operation!(operation => Div::div, assign => DivAssign::div_assign);
+impl_binary_operation!(operation => Mul::mul, assign => MulAssign::mul_assign);
+impl_binary_operation!(operation => Rem::rem, assign => RemAssign::rem_assign);
+impl_binary_operation!(operation => Sub::sub, assign => SubAssign::sub_assign);
+
+fn saturate<T>(inner: T) -> T
+where
+    T: Primitive,
+{
+    if inner.is_nan() {
+        T::ZERO
+    }
+    else if inner.is_finite() {
+        inner
+    }
+    else if inner.is_sign_negative() {
+        T::MIN_FINITE
+    }
+    else {
+        T::MAX_FINITE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::range::FiniteRange;
+    use crate::real::UnaryRealFunction;
+    use crate::saturating::{Clamping, Saturating};
+    use crate::{ExtendedReal, Total, R64};
+
+    #[test]
+    fn saturate() {
+        let max = Saturating(<R64>::assert(f64::MAX));
+        assert_eq!((max + max).into_inner(), f64::MAX);
+        assert_eq!((-max - max).into_inner(), f64::MIN);
+        assert_eq!((max * <R64>::assert(-2.0)).into_inner(), f64::MIN);
+        assert_eq!((max % <R64>::ZERO).into_inner(), 0.0);
+
+        // Infinities are saturated even if the constraint of the proxy admits them.
+        let x = Saturating(Total::from(1.0f64));
+        assert_eq!((x / Total::from(0.0)).into_inner(), f64::MAX);
+        let mut y = Saturating(<ExtendedReal<f64>>::assert(1.0));
+        y /= <ExtendedReal<f64>>::ZERO;
+        assert_eq!(y.into_inner(), f64::MAX);
+    }
+
+    #[test]
+    fn clamp() {
+        let range = FiniteRange::new(<R64>::assert(-1.0), <R64>::ONE);
+        let mut x = Clamping::new(<R64>::assert(0.5), range);
+        x += <R64>::ONE;
+        assert_eq!(x.into_inner(), 1.0);
+        x = -x * <R64>::assert(4.0);
+        assert_eq!(x.into_inner(), -1.0);
+        assert_eq!((x % <R64>::ZERO).into_inner(), 0.0);
+        assert_eq!(Clamping::new(<R64>::assert(8.0), range).into_inner(), 1.0);
+    }
+}
@@ -0,0 +1,697 @@
+//! Tolerant deserialization of [`Constrained`] types.
+//!
+//! By default, deserializing a value that does not satisfy the constraints of a [`Constrained`]
+//! type fails, and so does deserializing the document that contains it. The modules in this
+//! module instead substitute such values per a policy and can be used with Serde's `with`
+//! attribute to ingest messy data.
+//!
+//! | Module       | Field Type                  | Substitution                                 |
+//! |--------------|-----------------------------|----------------------------------------------|
+//! | [`or_clamp`] | `Constrained<T, C>`         | Extrema for infinities and zero for `NaN`    |
+//! | [`or_zero`]  | `Constrained<T, C>`         | Zero                                         |
+//! | [`or_none`]  | `Option<Constrained<T, C>>` | `None`                                       |
+//!
+//! Substituted values are real numbers, which satisfy any constraint. Values are serialized as
+//! usual. Serde serializes non-finite values as `null` in some human-readable formats (notably
+//! JSON), so these modules also deserialize `null` as `NaN` before substitution in such formats.
+//! Values that are not numbers still fail to deserialize.
+//!
+//! Some formats spell non-finite values as text, and each format spells them differently. The
+//! following modules deserialize [`Total`] types from numbers or any common textual spelling of
+//! non-finite values, such as `nan`, `.nan`, `inf`, `+inf`, `-.inf`, and `Infinity` (ignoring ASCII
+//! case). JSON has no representation of non-finite values, so the [`json`] module serializes them
+//! as strings. TOML and YAML have native literals for non-finite values, so the [`toml`] and
+//! [`yaml`] modules serialize them as numbers, which the serializers of these formats write with
+//! the following spellings. These modules require a self-describing format.
+//!
+//! | Module   | `NaN`  | Infinity   | Negative Infinity |
+//! |----------|--------|------------|-------------------|
+//! | [`json`] | `NaN`  | `Infinity` | `-Infinity`       |
+//! | [`toml`] | `nan`  | `inf`      | `-inf`            |
+//! | [`yaml`] | `.nan` | `.inf`     | `-.inf`           |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::R64;
+//! use serde_derive::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Sample {
+//!     #[serde(with = "decorum::serde::or_zero")]
+//!     x: R64,
+//!     #[serde(with = "decorum::serde::or_none")]
+//!     y: Option<R64>,
+//! }
+//!
+//! let sample: Sample = serde_json::from_str(r#"{ "x": null, "y": null }"#).unwrap();
+//! assert_eq!(sample.x, 0.0);
+//! assert_eq!(sample.y, None);
+//! ```
+//!
+//! Special values can be read from a document and written back per the dialect of a format.
+//!
+//! ```rust
+//! use decorum::Total;
+//! use serde_derive::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Sample {
+//!     #[serde(with = "decorum::serde::json")]
+//!     x: Total<f64>,
+//! }
+//!
+//! let sample: Sample = serde_json::from_str(r#"{ "x": ".inf" }"#).unwrap();
+//! assert_eq!(sample.x, f64::INFINITY);
+//! assert_eq!(
+//!     serde_json::to_string(&sample).unwrap(),
+//!     r#"{"x":"Infinity"}"#,
+//! );
+//! ```
+//!
+//! [`Expression`]s are serialized as externally tagged enums (e.g., `{"Defined":1.0}`) by
+//! default. The [`nullable`] module instead represents undefined expressions as `null` (like
+//! [`Option`]), which discards their errors.
+//!
+//! ```rust
+//! use decorum::divergence::OrError;
+//! use decorum::proxy::ExpressionFor;
+//! use decorum::R64;
+//! use serde_derive::{Deserialize, Serialize};
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct Sample {
+//!     #[serde(with = "decorum::serde::nullable")]
+//!     x: ExpressionFor<R64<OrError>>,
+//! }
+//!
+//! let sample: Sample = serde_json::from_str(r#"{ "x": null }"#).unwrap();
+//! assert!(sample.x.is_undefined());
+//! assert_eq!(serde_json::to_string(&sample).unwrap(), r#"{"x":null}"#);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Expression`]: crate::expression::Expression
+//! [`Total`]: crate::Total
+
+use core::fmt::{self, Formatter};
+use core::marker::PhantomData;
+
+use ::serde::de::{self, IntoDeserializer, Unexpected, Visitor};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::constraint::Constraint;
+use crate::proxy::Constrained;
+use crate::{Primitive, Total};
+
+/// Reads and writes the JSON5 (and JavaScript) spellings of non-finite values.
+///
+/// JSON has no representation of non-finite values, so they are written as strings.
+pub mod json {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::serde::Dialect;
+    use crate::{Primitive, Total};
+
+    const DIALECT: Dialect = Dialect {
+        nan: "NaN",
+        infinity: "Infinity",
+        neg_infinity: "-Infinity",
+    };
+
+    pub fn serialize<T, S>(proxy: &Total<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        S: Serializer,
+    {
+        DIALECT.serialize(proxy, serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Total<T>, D::Error>
+    where
+        T: Deserialize<'de> + Primitive,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_spelled(deserializer)
+    }
+}
+
+/// Clamps infinities to the extreme finite values and substitutes zero for `NaN`.
+///
+/// This is like a saturating conversion of a floating-point value into an integer.
+pub mod or_clamp {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::constraint::Constraint;
+    use crate::proxy::Constrained;
+    use crate::Primitive;
+
+    pub fn serialize<T, C, S>(proxy: &Constrained<T, C>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        C: Constraint,
+        S: Serializer,
+    {
+        proxy.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, C, D>(deserializer: D) -> Result<Constrained<T, C>, D::Error>
+    where
+        T: Deserialize<'de> + Primitive,
+        C: Constraint,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_or_else(deserializer, super::clamp)
+    }
+}
+
+/// Substitutes `None` for values that do not satisfy the constraint.
+pub mod or_none {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::constraint::Constraint;
+    use crate::proxy::Constrained;
+    use crate::Primitive;
+
+    pub fn serialize<T, C, S>(
+        proxy: &Option<Constrained<T, C>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        C: Constraint,
+        S: Serializer,
+    {
+        proxy.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, C, D>(deserializer: D) -> Result<Option<Constrained<T, C>>, D::Error>
+    where
+        T: Deserialize<'de> + Primitive,
+        C: Constraint,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_inner(deserializer).map(|inner| Constrained::try_new(inner).ok())
+    }
+}
+
+/// Represents [`Expression`]s as their defined values or `null`.
+///
+/// Undefined expressions are serialized as `null` (none) and `null` is deserialized as an
+/// undefined expression with the [empty inhabitant][`EmptyInhabitant`] of the error type.
+///
+/// [`EmptyInhabitant`]: crate::cmp::EmptyInhabitant
+/// [`Expression`]: crate::expression::Expression
+pub mod nullable {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::cmp::EmptyInhabitant;
+    use crate::expression::Expression;
+
+    pub fn serialize<T, E, S>(
+        expression: &Expression<T, E>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        expression.as_ref().defined().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, E, D>(deserializer: D) -> Result<Expression<T, E>, D::Error>
+    where
+        T: Deserialize<'de>,
+        E: EmptyInhabitant,
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(Expression::from)
+    }
+}
+
+/// Substitutes zero for values that do not satisfy the constraint.
+pub mod or_zero {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::constraint::Constraint;
+    use crate::proxy::Constrained;
+    use crate::Primitive;
+
+    pub fn serialize<T, C, S>(proxy: &Constrained<T, C>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        C: Constraint,
+        S: Serializer,
+    {
+        proxy.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, C, D>(deserializer: D) -> Result<Constrained<T, C>, D::Error>
+    where
+        T: Deserialize<'de> + Primitive,
+        C: Constraint,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_or_else(deserializer, |_| T::ZERO)
+    }
+}
+
+/// Reads the TOML spellings of non-finite values and writes non-finite values as TOML literals.
+pub mod toml {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Primitive, Total};
+
+    pub fn serialize<T, S>(proxy: &Total<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        S: Serializer,
+    {
+        proxy.into_inner().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Total<T>, D::Error>
+    where
+        T: Deserialize<'de> + Primitive,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_spelled(deserializer)
+    }
+}
+
+/// Reads the YAML spellings of non-finite values and writes non-finite values as YAML literals.
+pub mod yaml {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Primitive, Total};
+
+    pub fn serialize<T, S>(proxy: &Total<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        S: Serializer,
+    {
+        proxy.into_inner().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Total<T>, D::Error>
+    where
+        T: Deserialize<'de> + Primitive,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_spelled(deserializer)
+    }
+}
+
+// Spellings of non-finite values in a format that has no representation of non-finite values.
+struct Dialect {
+    nan: &'static str,
+    infinity: &'static str,
+    neg_infinity: &'static str,
+}
+
+impl Dialect {
+    fn serialize<T, S>(&self, proxy: &Total<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Primitive + Serialize,
+        S: Serializer,
+    {
+        let inner = proxy.into_inner();
+        if inner.is_nan() {
+            serializer.serialize_str(self.nan)
+        }
+        else if inner == T::INFINITY {
+            serializer.serialize_str(self.infinity)
+        }
+        else if inner == T::NEG_INFINITY {
+            serializer.serialize_str(self.neg_infinity)
+        }
+        else {
+            inner.serialize(serializer)
+        }
+    }
+}
+
+struct SpelledVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T> Visitor<'de> for SpelledVisitor<T>
+where
+    T: Deserialize<'de> + Primitive,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "a number or a spelling of a non-finite number")
+    }
+
+    fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(value.into_deserializer())
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(value.into_deserializer())
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(value.into_deserializer())
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        T::deserialize(value.into_deserializer())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        parse_spelling(value).ok_or_else(|| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+fn deserialize_spelled<'de, T, D>(deserializer: D) -> Result<Total<T>, D::Error>
+where
+    T: Deserialize<'de> + Primitive,
+    D: Deserializer<'de>,
+{
+    deserializer
+        .deserialize_any(SpelledVisitor(PhantomData))
+        .map(Total::unchecked)
+}
+
+// Parses any common spelling of a non-finite value, such as `NaN`, `.nan`, `+inf`, or `-Infinity`.
+fn parse_spelling<T>(text: &str) -> Option<T>
+where
+    T: Primitive,
+{
+    let (negative, text) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let text = text.strip_prefix('.').unwrap_or(text);
+    if text.eq_ignore_ascii_case("nan") {
+        Some(T::NAN.into_inner())
+    }
+    else if text.eq_ignore_ascii_case("inf") || text.eq_ignore_ascii_case("infinity") {
+        Some(if negative {
+            T::NEG_INFINITY
+        }
+        else {
+            T::INFINITY
+        })
+    }
+    else {
+        None
+    }
+}
+
+// Serde serializes non-finite values as `null` in human-readable formats like JSON. Other formats
+// serialize values as is and `Option` would not be compatible with their encoding.
+fn deserialize_inner<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de> + Primitive,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        Option::<T>::deserialize(deserializer).map(|inner| inner.unwrap_or(T::NAN.into_inner()))
+    }
+    else {
+        T::deserialize(deserializer)
+    }
+}
+
+fn deserialize_or_else<'de, T, C, D, F>(
+    deserializer: D,
+    f: F,
+) -> Result<Constrained<T, C>, D::Error>
+where
+    T: Deserialize<'de> + Primitive,
+    C: Constraint,
+    D: Deserializer<'de>,
+    F: FnOnce(T) -> T,
+{
+    deserialize_inner(deserializer).map(|inner| substitute(inner, f))
+}
+
+fn substitute<T, C, F>(inner: T, f: F) -> Constrained<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+    F: FnOnce(T) -> T,
+{
+    Constrained::try_new(inner).unwrap_or_else(|_| {
+        // The substitution is a real number, which satisfies any constraint.
+        Constrained::unchecked(f(inner))
+    })
+}
+
+fn clamp<T>(inner: T) -> T
+where
+    T: Primitive,
+{
+    if inner.is_nan() {
+        T::ZERO
+    }
+    else if inner > T::MAX_FINITE {
+        T::MAX_FINITE
+    }
+    else if inner < T::MIN_FINITE {
+        T::MIN_FINITE
+    }
+    else {
+        inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::{Deserialize, Serialize};
+
+    use crate::divergence::OrError;
+    use crate::expression::{Defined, Undefined};
+    use crate::proxy::ExpressionFor;
+    use crate::{ExtendedReal, Total, R64};
+
+    #[test]
+    fn expression() {
+        use crate::constraint::NotRealError;
+
+        let computed = Computed {
+            tagged: Undefined(NotRealError),
+            nullable: Defined(R64::assert(1.5)),
+        };
+        let json = serde_json::to_string(&computed).unwrap();
+        assert_eq!(json, r#"{"tagged":{"Undefined":null},"nullable":1.5}"#);
+        let computed: Computed = serde_json::from_str(&json).unwrap();
+        assert!(computed.tagged.is_undefined());
+        assert_eq!(computed.nullable, 1.5);
+
+        let computed: Computed =
+            serde_json::from_str(r#"{"tagged":{"Defined":2.0},"nullable":null}"#).unwrap();
+        assert_eq!(computed.tagged, 2.0);
+        assert!(computed.nullable.is_undefined());
+        assert!(
+            serde_json::from_str::<Computed>(r#"{"tagged":{"Defined":null},"nullable":null}"#)
+                .is_err()
+        );
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Spelled {
+        #[serde(with = "crate::serde::json")]
+        json: Total<f64>,
+        #[serde(with = "crate::serde::toml")]
+        toml: Total<f64>,
+        #[serde(with = "crate::serde::yaml")]
+        yaml: Total<f32>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Toml {
+        #[serde(with = "crate::serde::toml")]
+        nan: Total<f64>,
+        #[serde(with = "crate::serde::toml")]
+        infinity: Total<f64>,
+        #[serde(with = "crate::serde::toml")]
+        neg_infinity: Total<f64>,
+        #[serde(with = "crate::serde::toml")]
+        real: Total<f64>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Yaml {
+        #[serde(with = "crate::serde::yaml")]
+        nan: Total<f32>,
+        #[serde(with = "crate::serde::yaml")]
+        infinity: Total<f32>,
+        #[serde(with = "crate::serde::yaml")]
+        neg_infinity: Total<f32>,
+        #[serde(with = "crate::serde::yaml")]
+        real: Total<f32>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Computed {
+        tagged: ExpressionFor<R64<OrError>>,
+        #[serde(with = "crate::serde::nullable")]
+        nullable: ExpressionFor<R64<OrError>>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    struct Sample {
+        #[serde(with = "crate::serde::or_clamp")]
+        clamp: R64,
+        #[serde(with = "crate::serde::or_zero")]
+        zero: R64,
+        #[serde(with = "crate::serde::or_none")]
+        none: Option<R64>,
+    }
+
+    #[test]
+    fn clamp() {
+        let clamp = |x: f64| -> R64 { crate::serde::substitute(x, crate::serde::clamp) };
+        assert_eq!(clamp(f64::INFINITY), f64::MAX);
+        assert_eq!(clamp(f64::NEG_INFINITY), f64::MIN);
+        assert_eq!(clamp(f64::NAN), 0.0);
+        assert_eq!(clamp(1.0), 1.0);
+
+        let x: ExtendedReal<f64> = crate::serde::substitute(f64::INFINITY, crate::serde::clamp);
+        assert_eq!(x, f64::INFINITY);
+        let x: Total<f64> = crate::serde::substitute(f64::NAN, crate::serde::clamp);
+        assert!(x.into_inner().is_nan());
+    }
+
+    #[test]
+    fn document() {
+        let sample: Sample =
+            serde_json::from_str(r#"{ "clamp": null, "zero": null, "none": null }"#).unwrap();
+        assert_eq!(sample.clamp, 0.0);
+        assert_eq!(sample.zero, 0.0);
+        assert_eq!(sample.none, None);
+
+        let sample: Sample =
+            serde_json::from_str(r#"{ "clamp": 1.0, "zero": 2.0, "none": 3.0 }"#).unwrap();
+        assert_eq!(sample.clamp, 1.0);
+        assert_eq!(sample.zero, 2.0);
+        assert_eq!(sample.none, Some(R64::assert(3.0)));
+        assert_eq!(
+            serde_json::to_string(&sample).unwrap(),
+            r#"{"clamp":1.0,"zero":2.0,"none":3.0}"#
+        );
+
+        assert!(
+            serde_json::from_str::<Sample>(r#"{ "clamp": "x", "zero": 0, "none": 0 }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn spelling() {
+        for (text, expected) in [
+            ("nan", f64::NAN),
+            (".NaN", f64::NAN),
+            ("-nan", f64::NAN),
+            ("inf", f64::INFINITY),
+            ("+inf", f64::INFINITY),
+            (".inf", f64::INFINITY),
+            ("Infinity", f64::INFINITY),
+            ("-.Inf", f64::NEG_INFINITY),
+            ("-infinity", f64::NEG_INFINITY),
+        ] {
+            let x: f64 = crate::serde::parse_spelling(text).unwrap();
+            assert!(x == expected || (x.is_nan() && expected.is_nan()));
+        }
+        assert!(crate::serde::parse_spelling::<f64>("1.0").is_none());
+        assert!(crate::serde::parse_spelling::<f64>("..inf").is_none());
+        assert!(crate::serde::parse_spelling::<f64>("infinite").is_none());
+    }
+
+    #[test]
+    fn spelled_document() {
+        let sample: Spelled =
+            serde_json::from_str(r#"{ "json": "+inf", "toml": "-Infinity", "yaml": ".nan" }"#)
+                .unwrap();
+        assert_eq!(sample.json, f64::INFINITY);
+        assert_eq!(sample.toml, f64::NEG_INFINITY);
+        assert!(sample.yaml.into_inner().is_nan());
+        assert_eq!(
+            serde_json::to_string(&sample).unwrap(),
+            // JSON has no representation of non-finite numbers.
+            r#"{"json":"Infinity","toml":null,"yaml":null}"#
+        );
+
+        let sample: Spelled =
+            serde_json::from_str(r#"{ "json": 1.5, "toml": -2, "yaml": 3 }"#).unwrap();
+        assert_eq!(sample.json, 1.5);
+        assert_eq!(sample.toml, -2.0);
+        assert_eq!(sample.yaml, 3.0);
+        assert_eq!(
+            serde_json::to_string(&sample).unwrap(),
+            r#"{"json":1.5,"toml":-2.0,"yaml":3.0}"#
+        );
+
+        assert!(
+            serde_json::from_str::<Spelled>(r#"{ "json": "x", "toml": 0, "yaml": 0 }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn toml_document() {
+        let text = toml::to_string(&Toml {
+            nan: Total::from(f64::NAN),
+            infinity: Total::from(f64::INFINITY),
+            neg_infinity: Total::from(f64::NEG_INFINITY),
+            real: Total::from(1.5),
+        })
+        .unwrap();
+        assert_eq!(
+            text,
+            "nan = nan\ninfinity = inf\nneg_infinity = -inf\nreal = 1.5\n"
+        );
+        let sample: Toml = toml::from_str(&text).unwrap();
+        assert!(sample.nan.into_inner().is_nan());
+        assert_eq!(sample.infinity, f64::INFINITY);
+        assert_eq!(sample.neg_infinity, f64::NEG_INFINITY);
+        assert_eq!(sample.real, 1.5);
+
+        // Spellings of other formats are also read from strings.
+        let sample: Toml = toml::from_str(
+            "nan = \".NaN\"\ninfinity = \"Infinity\"\nneg_infinity = \"-.inf\"\nreal = 2\n",
+        )
+        .unwrap();
+        assert!(sample.nan.into_inner().is_nan());
+        assert_eq!(sample.infinity, f64::INFINITY);
+        assert_eq!(sample.neg_infinity, f64::NEG_INFINITY);
+        assert_eq!(sample.real, 2.0);
+    }
+
+    #[test]
+    fn yaml_document() {
+        let text = serde_yaml::to_string(&Yaml {
+            nan: Total::from(f32::NAN),
+            infinity: Total::from(f32::INFINITY),
+            neg_infinity: Total::from(f32::NEG_INFINITY),
+            real: Total::from(1.5f32),
+        })
+        .unwrap();
+        assert_eq!(
+            text,
+            "nan: .nan\ninfinity: .inf\nneg_infinity: -.inf\nreal: 1.5\n"
+        );
+        let sample: Yaml = serde_yaml::from_str(&text).unwrap();
+        assert!(sample.nan.into_inner().is_nan());
+        assert_eq!(sample.infinity, f32::INFINITY);
+        assert_eq!(sample.neg_infinity, f32::NEG_INFINITY);
+        assert_eq!(sample.real, 1.5);
+    }
+}
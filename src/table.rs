@@ -0,0 +1,314 @@
+//! Lookup tables that approximate real functions.
+//!
+//! A [`Table`] samples a function over a closed interval at evenly spaced points and approximates
+//! the function by interpolating between these samples. Tables are stored in arrays and do not
+//! allocate, so they can replace calls to functions like those of `libm` on targets where such
+//! calls are expensive or unavailable.
+//!
+//! Constructing a [`Table`] validates it against the sampled function: the interpolation error is
+//! measured between each pair of adjacent samples and construction fails if it exceeds a given
+//! bound. Note that this measurement is itself sampled and so cannot detect features of the
+//! function that are narrower than the validation points (a quarter of the sample spacing).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::table::{Interpolation, Table};
+//! use decorum::R64;
+//!
+//! let sin = Table::<f64, _, 256>::try_sample(
+//!     f64::sin,
+//!     <R64>::ZERO,
+//!     <R64>::FRAC_PI_2,
+//!     Interpolation::Linear,
+//!     1.0e-5,
+//! )
+//! .unwrap();
+//! let x = sin.eval(<R64>::FRAC_PI_4).unwrap();
+//! assert!((x - <R64>::FRAC_1_SQRT_2).into_inner().abs() <= 1.0e-5);
+//! assert!(sin.eval(<R64>::PI).is_undefined());
+//! ```
+
+use core::marker::PhantomData;
+use num_traits::NumCast;
+use thiserror::Error;
+
+use crate::constraint::{Constraint, NotRealError};
+use crate::expression::Expression::{self, Defined, Undefined};
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+pub enum TableError {
+    #[error("{}", "table requires at least two samples over a non-empty interval")]
+    Interval,
+    #[error("{}", "interpolation error exceeds bound")]
+    ErrorBound,
+    #[error("{}", "value is outside of the interval of the table")]
+    OutOfDomain,
+    #[error(transparent)]
+    NotReal(NotRealError),
+}
+
+impl From<NotRealError> for TableError {
+    fn from(error: NotRealError) -> Self {
+        TableError::NotReal(error)
+    }
+}
+
+/// Interpolation between the samples of a [`Table`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Interpolation {
+    /// Approximates a value by the nearest sample.
+    Nearest,
+    /// Approximates a value by linear interpolation of the adjacent samples.
+    Linear,
+}
+
+/// Lookup table of `N` evenly spaced samples of a real function.
+///
+/// See the [module documentation][`table`].
+///
+/// [`table`]: crate::table
+#[derive(Clone, Copy, Debug)]
+pub struct Table<T, C, const N: usize> {
+    start: T,
+    end: T,
+    step: T,
+    samples: [T; N],
+    interpolation: Interpolation,
+    error: T,
+    phantom: PhantomData<fn() -> C>,
+}
+
+impl<T, C, const N: usize> Table<T, C, N>
+where
+    T: NumCast + Primitive,
+    C: Constraint,
+{
+    /// Samples a function over the closed interval from `start` to `end`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TableError::Interval`] if `N` is less than two or `start` is not less than
+    /// `end`, [`TableError::NotReal`] if the interval or any sample of `f` is not a real number,
+    /// and [`TableError::ErrorBound`] if the measured interpolation error exceeds `bound`.
+    pub fn try_sample<F>(
+        mut f: F,
+        start: Constrained<T, C>,
+        end: Constrained<T, C>,
+        interpolation: Interpolation,
+        bound: T,
+    ) -> Result<Self, TableError>
+    where
+        F: FnMut(T) -> T,
+    {
+        let (start, end) = (start.into_inner(), end.into_inner());
+        if !(start.is_finite() && end.is_finite()) {
+            return Err(NotRealError.into());
+        }
+        if N < 2 || start >= end {
+            return Err(TableError::Interval);
+        }
+        let step = (end - start) / index::<T>(N - 1);
+        if !step.is_finite() {
+            return Err(NotRealError.into());
+        }
+
+        let mut samples = [T::ZERO; N];
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample = f(node(start, end, step, i));
+            if !sample.is_finite() {
+                return Err(NotRealError.into());
+            }
+        }
+        let mut table = Table {
+            start,
+            end,
+            step,
+            samples,
+            interpolation,
+            error: T::ZERO,
+            phantom: PhantomData,
+        };
+
+        // Measure the interpolation error at the quarter points between adjacent samples.
+        let quarter = step / index::<T>(4);
+        let mut error = T::ZERO;
+        for i in 0..(N - 1) {
+            let mut x = node(start, end, step, i);
+            for _ in 0..3 {
+                x = x + quarter;
+                let difference = abs(f(x) - table.interpolate(x));
+                if difference.is_nan() || difference > bound {
+                    return Err(TableError::ErrorBound);
+                }
+                if difference > error {
+                    error = difference;
+                }
+            }
+        }
+        table.error = error;
+        Ok(table)
+    }
+
+    /// Approximates the sampled function at `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TableError::OutOfDomain`] if `x` is not in the interval of the table.
+    pub fn eval(&self, x: Constrained<T, C>) -> Expression<Constrained<T, C>, TableError> {
+        let x = x.into_inner();
+        if x >= self.start && x <= self.end {
+            // Samples are real numbers and interpolation of adjacent real numbers is also a real
+            // number, which satisfies any constraint.
+            Defined(Constrained::unchecked(self.interpolate(x)))
+        }
+        else {
+            Undefined(TableError::OutOfDomain)
+        }
+    }
+
+    /// Gets the greatest interpolation error measured when the table was constructed.
+    pub fn max_error(&self) -> T {
+        self.error
+    }
+
+    pub fn samples(&self) -> &[T; N] {
+        &self.samples
+    }
+
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    fn interpolate(&self, x: T) -> T {
+        let position = (x - self.start) / self.step;
+        // `position` is non-negative, so this truncation is the floor.
+        let i = position.to_usize().unwrap_or(0).min(N - 2);
+        let fraction = position - index(i);
+        let (left, right) = (self.samples[i], self.samples[i + 1]);
+        match self.interpolation {
+            Interpolation::Nearest => {
+                if fraction < T::ONE / index(2) {
+                    left
+                }
+                else {
+                    right
+                }
+            }
+            Interpolation::Linear => left + ((right - left) * fraction),
+        }
+    }
+}
+
+fn index<T>(i: usize) -> T
+where
+    T: NumCast,
+{
+    T::from(i).expect("failed to convert index")
+}
+
+// Gets the position of the sample at index `i`. Rounding may place the position past the end of
+// the interval, in which case the end is used instead.
+fn node<T>(start: T, end: T, step: T, i: usize) -> T
+where
+    T: NumCast + Primitive,
+{
+    let x = start + (step * index(i));
+    if x > end {
+        end
+    }
+    else {
+        x
+    }
+}
+
+fn abs<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    if x < T::ZERO {
+        -x
+    }
+    else {
+        x
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::real::UnaryRealFunction;
+    use crate::table::{Interpolation, Table, TableError};
+    use crate::{Total, R64};
+
+    #[test]
+    fn table_linear() {
+        let table = Table::<f64, _, 5>::try_sample(
+            |x| 2.0 * x + 1.0,
+            <R64>::ZERO,
+            <R64>::assert(4.0),
+            Interpolation::Linear,
+            1.0e-12,
+        )
+        .unwrap();
+        assert_eq!(table.samples(), &[1.0, 3.0, 5.0, 7.0, 9.0]);
+        assert_eq!(table.eval(<R64>::assert(2.5)).unwrap(), 6.0);
+        assert_eq!(table.eval(<R64>::assert(4.0)).unwrap(), 9.0);
+        assert_eq!(table.max_error(), 0.0);
+        assert!(matches!(
+            table.eval(<R64>::assert(-1.0)).undefined(),
+            Some(TableError::OutOfDomain)
+        ));
+    }
+
+    #[test]
+    fn table_nearest() {
+        let table = Table::<f64, _, 3>::try_sample(
+            |x| x * x,
+            <R64>::ZERO,
+            <R64>::assert(2.0),
+            Interpolation::Nearest,
+            2.0,
+        )
+        .unwrap();
+        assert_eq!(table.eval(<R64>::assert(0.4)).unwrap(), 0.0);
+        assert_eq!(table.eval(<R64>::assert(0.6)).unwrap(), 1.0);
+        assert!(table.max_error() > 0.0);
+    }
+
+    #[test]
+    fn table_error_bound() {
+        assert!(matches!(
+            Table::<f64, _, 4>::try_sample(
+                f64::sin,
+                <R64>::ZERO,
+                <R64>::PI,
+                Interpolation::Linear,
+                1.0e-3,
+            ),
+            Err(TableError::ErrorBound)
+        ));
+        assert!(matches!(
+            Table::<f64, _, 4>::try_sample(
+                |x| 1.0 / x,
+                Total::from(0.0),
+                Total::from(1.0),
+                Interpolation::Linear,
+                1.0,
+            ),
+            Err(TableError::NotReal(_))
+        ));
+        assert!(matches!(
+            Table::<f64, _, 1>::try_sample(
+                f64::sin,
+                <R64>::ZERO,
+                <R64>::ONE,
+                Interpolation::Linear,
+                1.0,
+            ),
+            Err(TableError::Interval)
+        ));
+    }
+}
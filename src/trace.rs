@@ -0,0 +1,392 @@
+//! Tracing of the operations that produce [undefined][`Undefined`] expressions.
+//!
+//! When an [`Expression`] is [undefined][`Undefined`], its error does not describe where in a
+//! computation the error occurred. The `OrTrace` divergence behaves like [`OrError`], but the
+//! errors of its constraints are `Traced`, which record the [`Origin`] of an error: the
+//! operation that first encountered the error and whether it was caused by an operand of the
+//! operation or by its output. Origins are recorded by arithmetic operators (including negation)
+//! and by the functions of [`UnaryRealFunction`] that output an [`Expression`], such as `sqrt`.
+//!
+//! The origin of an error is recorded once and is not replaced as the error propagates through
+//! subsequent operations. Errors that are not produced by operations, such as constructing an
+//! undefined expression from a `NaN`, have no origin until they are first encountered by an
+//! operation.
+//!
+//! # Features
+//!
+//! `OrTrace` and `Traced` errors require the `trace` feature. Without this feature, tracing has
+//! no effect. **The `trace` feature changes the error types of constraints:** [`Divergence`] has
+//! an `Error` type constructor that wraps the errors of constraints, [`Constraint::Error`] is
+//! bounded on `Trace`, and the error types of `IsExtendedReal<D>` and `IsReal<D>` are
+//! `D::Error<NotExtendedRealError>` and `D::Error<NotRealError>`, respectively. These are the same
+//! types as without the feature for all divergences except `OrTrace`, but are opaque in code that
+//! is generic over the divergence `D`, so enabling the feature may break such code.
+//!
+//! # Examples
+#![cfg_attr(feature = "trace", doc = "```rust")]
+#![cfg_attr(not(feature = "trace"), doc = "```rust,ignore")]
+//! use decorum::constraint::IsReal;
+//! use decorum::divergence::OrTrace;
+//! use decorum::proxy::Constrained;
+//! use decorum::real::UnaryRealFunction;
+//! use decorum::trace::{Operand, Operation};
+//!
+//! type Real = Constrained<f64, IsReal<OrTrace>>;
+//!
+//! let x = (Real::ONE / Real::ZERO) + Real::ONE;
+//! let origin = x.undefined().unwrap().origin().unwrap();
+//! assert_eq!(origin.operation(), Operation::Div);
+//! assert_eq!(origin.operand(), Operand::Output);
+//!
+//! let x = (-Real::ONE).sqrt().exp();
+//! let origin = x.undefined().unwrap().origin().unwrap();
+//! assert_eq!(origin.operation(), Operation::Sqrt);
+//! assert_eq!(origin.to_string(), "output of `sqrt`");
+//! ```
+//! 
+//! [`Constraint::Error`]: crate::constraint::Constraint::Error
+//! [`Divergence`]: crate::divergence::Divergence
+//! [`Expression`]: crate::expression::Expression
+//! [`OrError`]: crate::divergence::OrError
+//! [`Undefined`]: crate::expression::Expression::Undefined
+//! [`UnaryRealFunction`]: crate::real::UnaryRealFunction
+
+#[cfg(feature = "trace")]
+use core::convert::Infallible;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "trace")]
+use crate::cmp::EmptyInhabitant;
+#[cfg(feature = "trace")]
+use crate::constraint::{NotExtendedRealError, NotRealError};
+
+/// An operation that may produce or propagate an [undefined][`Undefined`] expression.
+///
+/// [`Undefined`]: crate::expression::Expression::Undefined
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Operation {
+    Add,
+    Div,
+    Mul,
+    Rem,
+    Sub,
+    Neg,
+    Recip,
+    Powi,
+    Sqrt,
+    Exp,
+    Exp2,
+    ExpM1,
+    Ln,
+    Log2,
+    Log10,
+    Ln1p,
+    ToDegrees,
+    Tan,
+    Asin,
+    Acos,
+    Asinh,
+    Acosh,
+    Atanh,
+}
+
+impl Operation {
+    /// Gets the symbol of an operator or the name of a function.
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            Operation::Add => "+",
+            Operation::Div => "/",
+            Operation::Mul => "*",
+            Operation::Rem => "%",
+            Operation::Sub | Operation::Neg => "-",
+            Operation::Recip => "recip",
+            Operation::Powi => "powi",
+            Operation::Sqrt => "sqrt",
+            Operation::Exp => "exp",
+            Operation::Exp2 => "exp2",
+            Operation::ExpM1 => "exp_m1",
+            Operation::Ln => "ln",
+            Operation::Log2 => "log2",
+            Operation::Log10 => "log10",
+            Operation::Ln1p => "ln_1p",
+            Operation::ToDegrees => "to_degrees",
+            Operation::Tan => "tan",
+            Operation::Asin => "asin",
+            Operation::Acos => "acos",
+            Operation::Asinh => "asinh",
+            Operation::Acosh => "acosh",
+            Operation::Atanh => "atanh",
+        }
+    }
+
+    /// Returns `true` if the operation has one operand.
+    pub const fn is_unary(&self) -> bool {
+        !matches!(
+            self,
+            Operation::Add | Operation::Div | Operation::Mul | Operation::Rem | Operation::Sub
+        )
+    }
+}
+
+/// The cause of an error in an [`Operation`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Operand {
+    /// The left operand (or the only operand of a unary operation) was undefined or did not
+    /// satisfy the constraint.
+    Left,
+    /// The right operand was undefined or did not satisfy the constraint.
+    Right,
+    /// The operands were defined, but the output of the operation did not satisfy the constraint.
+    Output,
+}
+
+/// The operation and operand that first encountered an error.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Origin {
+    operation: Operation,
+    operand: Operand,
+}
+
+impl Origin {
+    pub const fn new(operation: Operation, operand: Operand) -> Self {
+        Origin { operation, operand }
+    }
+
+    pub const fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    pub const fn operand(&self) -> Operand {
+        self.operand
+    }
+}
+
+impl Display for Origin {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let operand = match self.operand {
+            Operand::Left if self.operation.is_unary() => "operand",
+            Operand::Left => "left operand",
+            Operand::Right => "right operand",
+            Operand::Output => "output",
+        };
+        write!(formatter, "{} of `{}`", operand, self.operation.symbol())
+    }
+}
+
+/// Errors that may record their [`Origin`].
+///
+/// This trait is implemented by all constraint errors, but only [`Traced`] errors record an
+/// origin. For other errors, tracing has no effect.
+#[cfg(feature = "trace")]
+pub trait Trace: Sized {
+    /// Records the origin of the error if it has not yet been recorded.
+    fn trace(self, origin: Origin) -> Self;
+
+    fn origin(&self) -> Option<Origin>;
+}
+
+#[cfg(feature = "trace")]
+macro_rules! impl_trace_for_untraced_error {
+    (error => $t:ty) => {
+        impl Trace for $t {
+            #[inline(always)]
+            fn trace(self, _: Origin) -> Self {
+                self
+            }
+
+            #[inline(always)]
+            fn origin(&self) -> Option<Origin> {
+                None
+            }
+        }
+    };
+}
+#[cfg(feature = "trace")]
+impl_trace_for_untraced_error!(error => Infallible);
+#[cfg(feature = "trace")]
+impl_trace_for_untraced_error!(error => NotExtendedRealError);
+#[cfg(feature = "trace")]
+impl_trace_for_untraced_error!(error => NotRealError);
+
+// Records the origin of an error. Without the `trace` feature, constraint errors are not bounded
+// on `Trace` and this function has no effect.
+#[cfg(feature = "trace")]
+pub(crate) fn trace<E>(error: E, origin: Origin) -> E
+where
+    E: Trace,
+{
+    error.trace(origin)
+}
+
+#[cfg(not(feature = "trace"))]
+pub(crate) fn trace<E>(error: E, _: Origin) -> E {
+    error
+}
+
+/// A constraint error and its [`Origin`].
+///
+/// This is the error type of constraints with the [`OrTrace`] divergence.
+///
+/// [`OrTrace`]: crate::divergence::OrTrace
+#[cfg(feature = "trace")]
+#[derive(Clone, Copy, Debug)]
+pub struct Traced<E> {
+    error: E,
+    origin: Option<Origin>,
+}
+
+#[cfg(feature = "trace")]
+impl<E> Traced<E> {
+    pub fn into_error(self) -> E {
+        self.error
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    /// Gets the origin of the error, if any.
+    ///
+    /// Errors have no origin if they have not been encountered by an operation, such as when a
+    /// proxy is constructed from a `NaN`.
+    pub fn origin(&self) -> Option<Origin> {
+        self.origin
+    }
+}
+
+#[cfg(feature = "trace")]
+impl<E> Display for Traced<E>
+where
+    E: Display,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self.origin {
+            Some(origin) => write!(formatter, "{} in {}", self.error, origin),
+            _ => write!(formatter, "{}", self.error),
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl<E> EmptyInhabitant for Traced<E>
+where
+    E: EmptyInhabitant,
+{
+    fn empty() -> Self {
+        Traced::from(E::empty())
+    }
+}
+
+#[cfg(all(feature = "std", feature = "trace"))]
+impl<E> std::error::Error for Traced<E>
+where
+    E: 'static + std::error::Error,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(feature = "trace")]
+impl<E> From<E> for Traced<E> {
+    fn from(error: E) -> Self {
+        Traced {
+            error,
+            origin: None,
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl<E> Trace for Traced<E> {
+    fn trace(self, origin: Origin) -> Self {
+        Traced {
+            origin: self.origin.or(Some(origin)),
+            ..self
+        }
+    }
+
+    fn origin(&self) -> Option<Origin> {
+        self.origin
+    }
+}
+
+#[cfg(all(test, feature = "trace"))]
+mod tests {
+    #[cfg(feature = "std")]
+    use std::string::ToString;
+
+    use crate::constraint::IsReal;
+    use crate::divergence::OrTrace;
+    use crate::proxy::{Constrained, ExpressionFor};
+    use crate::real::UnaryRealFunction;
+    use crate::trace::{Operand, Operation, Origin};
+
+    type Real = Constrained<f64, IsReal<OrTrace>>;
+    type RealExpression = ExpressionFor<Real>;
+
+    fn origin(expression: RealExpression) -> Option<Origin> {
+        expression.undefined().and_then(|error| error.origin())
+    }
+
+    #[test]
+    fn trace_output() {
+        let x = Real::ONE / Real::ZERO;
+        assert_eq!(
+            origin(x),
+            Some(Origin::new(Operation::Div, Operand::Output))
+        );
+    }
+
+    #[test]
+    fn trace_operand() {
+        let x = Real::new(f64::NAN);
+        assert_eq!(origin(x), None);
+        assert_eq!(
+            origin(Real::ONE - x),
+            Some(Origin::new(Operation::Sub, Operand::Right))
+        );
+        assert_eq!(
+            origin(x * 2.0),
+            Some(Origin::new(Operation::Mul, Operand::Left))
+        );
+        assert_eq!(
+            origin(Real::new(1.0) + f64::INFINITY),
+            Some(Origin::new(Operation::Add, Operand::Right))
+        );
+        assert_eq!(
+            origin(Real::ONE + f64::INFINITY),
+            Some(Origin::new(Operation::Add, Operand::Output))
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn trace_unary() {
+        assert_eq!(
+            origin((-Real::ONE).sqrt()),
+            Some(Origin::new(Operation::Sqrt, Operand::Output))
+        );
+
+        let x = Real::new(f64::NAN);
+        assert_eq!(
+            origin(x.ln()),
+            Some(Origin::new(Operation::Ln, Operand::Left))
+        );
+        assert_eq!(origin(-x), Some(Origin::new(Operation::Neg, Operand::Left)));
+        assert_eq!(
+            Origin::new(Operation::Ln, Operand::Left).to_string(),
+            "operand of `ln`"
+        );
+    }
+
+    #[test]
+    fn trace_first_origin() {
+        let x = ((Real::ZERO / Real::ZERO) + Real::ONE) * Real::ONE;
+        assert_eq!(
+            origin(x),
+            Some(Origin::new(Operation::Div, Operand::Output))
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Tracking of accumulated error bounds.
+//!
+//! [`Uncertain`] pairs a [`Constrained`] value with a bound on its absolute error. Arithmetic over
+//! [`Uncertain`]s propagates these bounds and adds the rounding error of each operation, so the
+//! bound of a result describes an interval that contains the exact result of the computation with
+//! respect to the exact inputs. The rounding error of an operation includes an absolute term of
+//! the smallest positive subnormal value, which bounds the error of outputs that underflow, and
+//! the error bounds and intervals are themselves computed with outward rounding.
+//!
+//! Comparisons of [`Uncertain`]s are [`Expression`]s: two values are only ordered if their
+//! intervals are disjoint. If the intervals overlap, then the values may not really be different
+//! and the comparison is [undefined][`Undefined`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use core::cmp::Ordering;
+//! use decorum::uncertain::Uncertain;
+//! use decorum::R64;
+//!
+//! let x = Uncertain::with_error(<R64>::assert(1.0), 0.25);
+//! let y = Uncertain::with_error(<R64>::assert(1.25), 0.25);
+//! assert!(x.compare(&y).is_undefined());
+//!
+//! let z = x + Uncertain::exact(<R64>::assert(1.0));
+//! assert_eq!(z.compare(&y).defined(), Some(Ordering::Greater));
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [`Expression`]: crate::expression::Expression
+//! [`Undefined`]: crate::expression::Expression::Undefined
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{Add, Div, Mul, Neg, Sub};
+use thiserror::Error;
+
+use crate::constraint::Constraint;
+use crate::divergence::{self, NonResidual};
+use crate::expression::Expression::{self, Defined, Undefined};
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+#[derive(Clone, Copy, Debug, Error)]
+#[error("{}", "uncertain values are indistinct")]
+pub struct IndistinctError;
+
+/// A [`Constrained`] value and a bound on its absolute error.
+///
+/// The error bound is a primitive floating-point value and is not constrained. An error bound may
+/// be infinite, such as when dividing by a value with an interval that contains zero.
+///
+/// [`Constrained`]: crate::proxy::Constrained
+pub struct Uncertain<T, C> {
+    value: Constrained<T, C>,
+    error: T,
+}
+
+impl<T, C> Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Constructs an `Uncertain` with no error.
+    pub fn exact(value: Constrained<T, C>) -> Self {
+        Uncertain {
+            value,
+            error: T::ZERO,
+        }
+    }
+
+    /// Constructs an `Uncertain` with the given bound on its absolute error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `error` is negative or `NaN`.
+    pub fn with_error(value: Constrained<T, C>, error: T) -> Self {
+        assert!(error >= T::ZERO, "error bound must be non-negative");
+        Uncertain { value, error }
+    }
+
+    pub fn value(&self) -> Constrained<T, C> {
+        self.value
+    }
+
+    /// Gets the bound on the absolute error of the value.
+    pub fn error(&self) -> T {
+        self.error
+    }
+
+    /// Gets the lower bound of the interval that contains the exact value.
+    ///
+    /// This bound is rounded down and so may be less than the difference of the value and its
+    /// error bound.
+    pub fn lower(&self) -> T {
+        round_down(self.value.into_inner() - self.error)
+    }
+
+    /// Gets the upper bound of the interval that contains the exact value.
+    ///
+    /// This bound is rounded up and so may be greater than the sum of the value and its error
+    /// bound.
+    pub fn upper(&self) -> T {
+        round_up(self.value.into_inner() + self.error)
+    }
+
+    /// Compares the intervals of `Uncertain`s.
+    ///
+    /// The output is defined if the intervals are disjoint or if both values are exact and equal.
+    /// Otherwise, the values are indistinct and the output is undefined.
+    pub fn compare(&self, other: &Self) -> Expression<Ordering, IndistinctError> {
+        if self.upper() < other.lower() {
+            Defined(Ordering::Less)
+        }
+        else if self.lower() > other.upper() {
+            Defined(Ordering::Greater)
+        }
+        else if self.error.is_zero()
+            && other.error.is_zero()
+            && self.value.into_inner() == other.value.into_inner()
+        {
+            Defined(Ordering::Equal)
+        }
+        else {
+            Undefined(IndistinctError)
+        }
+    }
+
+    /// Returns `true` if the values are distinct.
+    ///
+    /// See [`Uncertain::compare`].
+    pub fn is_distinct(&self, other: &Self) -> bool {
+        matches!(
+            self.compare(other),
+            Defined(Ordering::Less | Ordering::Greater)
+        )
+    }
+
+    // Adds the rounding error of the output of an operation to the propagated error bound. The
+    // relative term bounds the error of rounding to nearest and the absolute term bounds the
+    // error of outputs that underflow.
+    fn rounded(value: Constrained<T, C>, error: T) -> Self {
+        let rounding = mul_up(abs(value.into_inner()), T::EPSILON / (T::ONE + T::ONE));
+        Uncertain {
+            value,
+            error: add_up(error, add_up(rounding, subnormal())),
+        }
+    }
+}
+
+impl<T, C, E> Add for Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint<Error = E>,
+    divergence::ContinueFor<C::Divergence>: NonResidual<Constrained<T, C>, E>,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Uncertain::rounded(self.value + other.value, add_up(self.error, other.error))
+    }
+}
+
+impl<T, C> Clone for Uncertain<T, C>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Uncertain {
+            value: self.value.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+impl<T, C> Copy for Uncertain<T, C> where T: Copy {}
+
+impl<T, C> Debug for Uncertain<T, C>
+where
+    T: Debug,
+    Constrained<T, C>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Uncertain")
+            .field("value", &self.value)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<T, C> Display for Uncertain<T, C>
+where
+    T: Display + Primitive,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} ± {}", self.value.into_inner(), self.error)
+    }
+}
+
+impl<T, C, E> Div for Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint<Error = E>,
+    divergence::ContinueFor<C::Divergence>: NonResidual<Constrained<T, C>, E>,
+{
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        let value = self.value / other.value;
+        let divisor = round_down(abs(other.value.into_inner()) - other.error);
+        let error = if divisor > T::ZERO {
+            // The quotient is rounded, so it is rounded up to bound the exact quotient.
+            let quotient = round_up(abs(value.into_inner()));
+            div_up(add_up(self.error, mul_up(quotient, other.error)), divisor)
+        }
+        else {
+            // The interval of the divisor contains zero.
+            T::INFINITY
+        };
+        Uncertain::rounded(value, error)
+    }
+}
+
+impl<T, C> From<Constrained<T, C>> for Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    fn from(value: Constrained<T, C>) -> Self {
+        Uncertain::exact(value)
+    }
+}
+
+impl<T, C, E> Mul for Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint<Error = E>,
+    divergence::ContinueFor<C::Divergence>: NonResidual<Constrained<T, C>, E>,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        let error = add_up(
+            add_up(
+                mul_up(abs(self.value.into_inner()), other.error),
+                mul_up(abs(other.value.into_inner()), self.error),
+            ),
+            mul_up(self.error, other.error),
+        );
+        Uncertain::rounded(self.value * other.value, error)
+    }
+}
+
+impl<T, C> Neg for Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Uncertain {
+            value: -self.value,
+            error: self.error,
+        }
+    }
+}
+
+impl<T, C, E> Sub for Uncertain<T, C>
+where
+    T: Primitive,
+    C: Constraint<Error = E>,
+    divergence::ContinueFor<C::Divergence>: NonResidual<Constrained<T, C>, E>,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Uncertain::rounded(self.value - other.value, add_up(self.error, other.error))
+    }
+}
+
+fn abs<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    if x < T::ZERO {
+        -x
+    }
+    else {
+        x
+    }
+}
+
+// Gets the smallest positive subnormal value.
+fn subnormal<T>() -> T
+where
+    T: Primitive,
+{
+    T::MIN_POSITIVE_NORMAL * T::EPSILON
+}
+
+// Rounds a value computed with round-to-nearest arithmetic up (toward positive infinity). The
+// output is at least the next representable value, because the relative term is at least one unit
+// in the last place of normal values and the absolute term is one unit in the last place of
+// subnormal values.
+//
+// Error bounds are conservative: a `NaN` (e.g., from multiplying zero and infinity) is unbounded.
+fn round_up<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    let x = x + (abs(x) * T::EPSILON) + subnormal();
+    if x.is_nan() {
+        T::INFINITY
+    }
+    else {
+        x
+    }
+}
+
+// Rounds a value computed with round-to-nearest arithmetic down (toward negative infinity).
+fn round_down<T>(x: T) -> T
+where
+    T: Primitive,
+{
+    -round_up(-x)
+}
+
+fn add_up<T>(x: T, y: T) -> T
+where
+    T: Primitive,
+{
+    round_up(x + y)
+}
+
+fn mul_up<T>(x: T, y: T) -> T
+where
+    T: Primitive,
+{
+    round_up(x * y)
+}
+
+fn div_up<T>(x: T, y: T) -> T
+where
+    T: Primitive,
+{
+    round_up(x / y)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cmp::Ordering;
+
+    use crate::uncertain::Uncertain;
+    use crate::R64;
+
+    #[test]
+    fn uncertain_propagation() {
+        let x = Uncertain::with_error(<R64>::assert(2.0), 0.5);
+        let y = Uncertain::with_error(<R64>::assert(4.0), 1.0);
+        assert_eq!((x + y).value(), 6.0);
+        assert!((x + y).error() >= 1.5);
+        assert!((x - y).error() >= 1.5);
+        assert!((x * y).error() >= 2.0 * 1.0 + 4.0 * 0.5 + 0.5);
+        assert!((x * y).upper() >= 2.5 * 5.0);
+        assert!((x / y).upper() >= 2.5 / 3.0);
+        assert!((x / y).lower() <= 1.5 / 5.0);
+        assert_eq!((-x).error(), 0.5);
+        assert!((-x).lower() <= -2.5);
+    }
+
+    #[test]
+    fn uncertain_underflow() {
+        // The product underflows to zero, but is not exactly zero.
+        let x = Uncertain::exact(<R64>::assert(1.0e-200));
+        let y = x * x;
+        assert_eq!(y.value(), 0.0);
+        assert!(y.error() > 0.0);
+        assert!(y.lower() < 0.0 && y.upper() > 0.0);
+        let zero = Uncertain::exact(<R64>::assert(0.0));
+        assert!(y.compare(&zero).is_undefined());
+    }
+
+    #[test]
+    fn uncertain_rounding() {
+        let x = Uncertain::exact(<R64>::assert(0.1));
+        let y = x + x + x;
+        assert!(y.error() > 0.0);
+        let exact = Uncertain::exact(<R64>::assert(0.3));
+        assert!(y.compare(&exact).is_undefined());
+    }
+
+    #[test]
+    fn uncertain_compare() {
+        let x = Uncertain::with_error(<R64>::assert(1.0), 0.1);
+        let y = Uncertain::with_error(<R64>::assert(2.0), 0.1);
+        assert_eq!(x.compare(&y).defined(), Some(Ordering::Less));
+        assert_eq!(y.compare(&x).defined(), Some(Ordering::Greater));
+        assert!(x.is_distinct(&y));
+        assert!(!x.is_distinct(&x));
+        let z = Uncertain::exact(<R64>::assert(1.0));
+        assert_eq!(z.compare(&z).defined(), Some(Ordering::Equal));
+        assert!(x.compare(&z).is_undefined());
+    }
+
+    #[test]
+    fn uncertain_divide_by_indistinct_zero() {
+        let x = Uncertain::exact(<R64>::assert(1.0));
+        let y = Uncertain::with_error(<R64>::assert(0.5), 1.0);
+        assert!((x / y).error().is_infinite());
+        assert!((x / y).compare(&x).is_undefined());
+    }
+}
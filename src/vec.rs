@@ -0,0 +1,272 @@
+//! Vectors of [`Constrained`] values with validated appends.
+//!
+//! [`CheckedVec`] is a vector of [`Constrained`] values that accepts primitive floating-point
+//! values directly. Each appended value is checked against the constraint of the proxy and errors
+//! are handled per its [divergence], so streams of primitive values can be appended
+//! without first mapping each value into a proxy.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use decorum::vec::CheckedVec;
+//! use decorum::R64;
+//!
+//! let mut xs = CheckedVec::<f64, _>::new();
+//! xs.extend([1.0, 2.0, 3.0]);
+//! xs.extend([<R64>::assert(4.0)]);
+//! assert_eq!(xs.len(), 4);
+//! ```
+//!
+//! Appending with a divergence that outputs errors:
+//!
+//! ```rust
+//! use decorum::constraint::IsReal;
+//! use decorum::divergence::{AsResult, OrError};
+//! use decorum::vec::CheckedVec;
+//!
+//! let mut xs = CheckedVec::<f64, IsReal<OrError<AsResult>>>::new();
+//! assert!(xs.push(1.0).is_ok());
+//! assert!(xs.try_extend([2.0, f64::NAN, 3.0]).is_err());
+//! assert_eq!(xs.as_slice(), [1.0, 2.0]);
+//! ```
+//!
+//! [`Constrained`]: crate::proxy::Constrained
+//! [divergence]: crate::divergence
+
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Deref;
+use std::vec::{self, Vec};
+
+use crate::constraint::Constraint;
+use crate::divergence::{self, Divergence, NonResidual};
+use crate::proxy::Constrained;
+use crate::Primitive;
+
+/// Vector of [`Constrained`] values that validates appended primitive floating-point values.
+///
+/// See the [module documentation][`vec`].
+///
+/// [`Constrained`]: crate::proxy::Constrained
+/// [`vec`]: crate::vec
+#[derive(Clone, PartialEq)]
+pub struct CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    inner: Vec<Constrained<T, C>>,
+}
+
+impl<T, C> CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    pub fn new() -> Self {
+        CheckedVec { inner: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        CheckedVec {
+            inner: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[Constrained<T, C>] {
+        self.inner.as_slice()
+    }
+
+    pub fn into_vec(self) -> Vec<Constrained<T, C>> {
+        self.inner
+    }
+}
+
+impl<T, C> CheckedVec<T, C>
+where
+    T: Primitive,
+    C: Constraint,
+{
+    /// Appends a primitive floating-point value.
+    ///
+    /// The value is not appended if it does not satisfy the constraint of the proxy, in which case
+    /// the output diverges per the divergence of the constraint.
+    pub fn push(&mut self, inner: T) -> divergence::OutputFor<C::Divergence, (), C::Error> {
+        C::Divergence::diverge(
+            Constrained::<T, C>::try_new(inner).map(|proxy| self.inner.push(proxy)),
+        )
+    }
+
+    /// Appends primitive floating-point values until a value does not satisfy the constraint of
+    /// the proxy.
+    ///
+    /// Values before the first such value are appended and the output diverges per the divergence
+    /// of the constraint.
+    pub fn try_extend<I>(&mut self, input: I) -> divergence::OutputFor<C::Divergence, (), C::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        C::Divergence::diverge(input.into_iter().try_for_each(|inner| {
+            Constrained::<T, C>::try_new(inner).map(|proxy| self.inner.push(proxy))
+        }))
+    }
+}
+
+impl<T, C> AsRef<[Constrained<T, C>]> for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    fn as_ref(&self) -> &[Constrained<T, C>] {
+        self.as_slice()
+    }
+}
+
+impl<T, C> Debug for CheckedVec<T, C>
+where
+    T: Primitive,
+    Constrained<T, C>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.debug_list().entries(self.inner.iter()).finish()
+    }
+}
+
+impl<T, C> Default for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    fn default() -> Self {
+        CheckedVec::new()
+    }
+}
+
+impl<T, C> Deref for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    type Target = [Constrained<T, C>];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, C> Extend<Constrained<T, C>> for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    fn extend<I>(&mut self, input: I)
+    where
+        I: IntoIterator<Item = Constrained<T, C>>,
+    {
+        self.inner.extend(input)
+    }
+}
+
+// This implementation cannot output errors, so it is only provided for divergences that do not
+// output errors (i.e., that panic).
+impl<T, C, E> Extend<T> for CheckedVec<T, C>
+where
+    T: Primitive,
+    C: Constraint<Error = E>,
+    divergence::ContinueFor<C::Divergence>: NonResidual<(), E>,
+{
+    fn extend<I>(&mut self, input: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_extend(input)
+    }
+}
+
+impl<T, C> From<CheckedVec<T, C>> for Vec<Constrained<T, C>>
+where
+    T: Primitive,
+{
+    fn from(vec: CheckedVec<T, C>) -> Self {
+        vec.into_vec()
+    }
+}
+
+impl<T, C> From<Vec<Constrained<T, C>>> for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    fn from(inner: Vec<Constrained<T, C>>) -> Self {
+        CheckedVec { inner }
+    }
+}
+
+impl<T, C> FromIterator<Constrained<T, C>> for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    fn from_iter<I>(input: I) -> Self
+    where
+        I: IntoIterator<Item = Constrained<T, C>>,
+    {
+        CheckedVec {
+            inner: input.into_iter().collect(),
+        }
+    }
+}
+
+impl<T, C> IntoIterator for CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    type Item = Constrained<T, C>;
+    type IntoIter = vec::IntoIter<Constrained<T, C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a CheckedVec<T, C>
+where
+    T: Primitive,
+{
+    type Item = &'a Constrained<T, C>;
+    type IntoIter = core::slice::Iter<'a, Constrained<T, C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constraint::IsExtendedReal;
+    use crate::divergence::{AsExpression, OrError};
+    use crate::vec::CheckedVec;
+    use crate::{Total, R64};
+
+    #[test]
+    fn extend_primitive() {
+        let mut xs = CheckedVec::<f64, _>::new();
+        xs.extend([1.0, 2.0]);
+        xs.extend([<R64>::assert(3.0)]);
+        assert_eq!(xs.as_slice(), [1.0, 2.0, 3.0]);
+
+        let mut xs = CheckedVec::<f64, _>::new();
+        xs.extend([f64::NAN]);
+        xs.push(f64::INFINITY);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[1], Total::from(f64::INFINITY));
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_primitive_panic() {
+        let mut xs = CheckedVec::<f64, _>::new();
+        xs.extend([<R64>::assert(1.0)]);
+        xs.extend([f64::NAN]);
+    }
+
+    #[test]
+    fn try_extend_expression() {
+        let mut xs = CheckedVec::<f64, IsExtendedReal<OrError<AsExpression>>>::new();
+        assert!(xs.push(f64::INFINITY).is_defined());
+        assert!(xs.push(f64::NAN).is_undefined());
+        assert!(xs.try_extend([1.0, f64::NAN, 2.0]).is_undefined());
+        assert_eq!(xs.into_vec().len(), 2);
+    }
+}